@@ -5,17 +5,32 @@ use uuid::Uuid;
 
 use crate::error::AppError;
 
+/// Which of the two tokens a JWT is; each only works where it belongs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenType {
+    /// Short-lived Bearer token for API calls
+    Access,
+    /// Long-lived token that only `/auth/refresh` accepts
+    Refresh,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: Uuid,        // User ID
     pub email: String,
     pub exp: i64,         // Expiration timestamp
     pub iat: i64,         // Issued at timestamp
+    /// Session (refresh token row) the token belongs to
+    pub sid: Uuid,
+    pub typ: TokenType,
 }
 
-pub fn create_access_token(
+fn create_token(
+    typ: TokenType,
     user_id: Uuid,
     email: &str,
+    session_id: Uuid,
     secret: &str,
     expires_in_secs: i64,
 ) -> Result<String, AppError> {
@@ -27,6 +42,8 @@ pub fn create_access_token(
         email: email.to_string(),
         exp: exp.timestamp(),
         iat: now.timestamp(),
+        sid: session_id,
+        typ,
     };
 
     encode(
@@ -37,17 +54,39 @@ pub fn create_access_token(
     .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to create token: {}", e)))
 }
 
+pub fn create_access_token(
+    user_id: Uuid,
+    email: &str,
+    session_id: Uuid,
+    secret: &str,
+    expires_in_secs: i64,
+) -> Result<String, AppError> {
+    create_token(TokenType::Access, user_id, email, session_id, secret, expires_in_secs)
+}
+
 pub fn create_refresh_token(
     user_id: Uuid,
     email: &str,
+    session_id: Uuid,
     secret: &str,
     expires_in_secs: i64,
 ) -> Result<String, AppError> {
     // Refresh tokens have longer expiry
-    create_access_token(user_id, email, secret, expires_in_secs)
+    create_token(TokenType::Refresh, user_id, email, session_id, secret, expires_in_secs)
 }
 
 pub fn verify_access_token(token: &str, secret: &str) -> Result<Claims, AppError> {
+    verify_token(token, secret, TokenType::Access)
+}
+
+pub fn verify_refresh_token(token: &str, secret: &str) -> Result<Claims, AppError> {
+    verify_token(token, secret, TokenType::Refresh)
+}
+
+/// Decode a token and make sure it's the expected kind. Tokens from before
+/// sessions were tracked lack `sid` and `typ` and don't decode, so their
+/// holders log in again.
+fn verify_token(token: &str, secret: &str, typ: TokenType) -> Result<Claims, AppError> {
     let token_data = decode::<Claims>(
         token,
         &DecodingKey::from_secret(secret.as_bytes()),
@@ -58,5 +97,10 @@ pub fn verify_access_token(token: &str, secret: &str) -> Result<Claims, AppError
         AppError::Unauthorized
     })?;
 
+    if token_data.claims.typ != typ {
+        tracing::debug!("Expected {:?} token, got {:?}", typ, token_data.claims.typ);
+        return Err(AppError::Unauthorized);
+    }
+
     Ok(token_data.claims)
 }
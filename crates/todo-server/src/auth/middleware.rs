@@ -14,6 +14,8 @@ pub struct AuthUser {
     pub id: Uuid,
     #[allow(dead_code)] // Will be useful for audit logs, user display
     pub email: String,
+    /// The caller's session
+    pub session_id: Uuid,
 }
 
 pub async fn auth_middleware(
@@ -33,9 +35,23 @@ pub async fn auth_middleware(
 
     let claims = verify_access_token(token, &state.config.jwt_secret)?;
 
+    // A signed-out device loses access right away, not when its token expires
+    let live: Option<(bool,)> = sqlx::query_as(
+        "SELECT revoked_at IS NULL FROM refresh_tokens WHERE id = $1 AND user_id = $2",
+    )
+    .bind(claims.sid)
+    .bind(claims.sub)
+    .fetch_optional(&state.db)
+    .await?;
+
+    if !matches!(live, Some((true,))) {
+        return Err(AppError::Unauthorized);
+    }
+
     let auth_user = AuthUser {
         id: claims.sub,
         email: claims.email,
+        session_id: claims.sid,
     };
 
     request.extensions_mut().insert(auth_user);
@@ -0,0 +1,54 @@
+use axum::http::{header, HeaderMap, HeaderValue};
+
+use crate::error::AppError;
+
+/// Response header carrying a row's version
+pub type ETag = [(header::HeaderName, HeaderValue); 1];
+
+/// ETag for a versioned row, e.g. `"3"`
+pub fn etag(version: i32) -> ETag {
+    let value = HeaderValue::from_str(&format!("\"{}\"", version))
+        .unwrap_or_else(|_| HeaderValue::from_static("\"0\""));
+    [(header::ETAG, value)]
+}
+
+/// The version an edit was based on, from `If-Match` or the request body.
+/// `None` means the client didn't ask for a check (or sent `If-Match: *`).
+pub fn expected_version(headers: &HeaderMap, from_body: Option<i32>) -> Result<Option<i32>, AppError> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(from_body);
+    };
+
+    let value = value
+        .to_str()
+        .map_err(|_| AppError::Validation("Invalid If-Match header".to_string()))?
+        .trim();
+
+    if value == "*" {
+        return Ok(from_body);
+    }
+
+    let version: i32 = value
+        .trim_start_matches("W/")
+        .trim_matches('"')
+        .parse()
+        .map_err(|_| AppError::Validation("If-Match must be a version ETag such as \"3\"".to_string()))?;
+
+    match from_body {
+        Some(body_version) if body_version != version => Err(AppError::Validation(
+            "If-Match and expected_version disagree".to_string(),
+        )),
+        _ => Ok(Some(version)),
+    }
+}
+
+/// Reject an edit based on an older version of the row
+pub fn check_version(what: &str, current: i32, expected: Option<i32>) -> Result<(), AppError> {
+    match expected {
+        Some(expected) if expected != current => Err(AppError::Conflict(format!(
+            "{} was changed by someone else (now at version {}, your edit is based on version {})",
+            what, current, expected
+        ))),
+        _ => Ok(()),
+    }
+}
@@ -1,4 +1,5 @@
 use std::env;
+use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -7,6 +8,124 @@ pub struct Config {
     pub jwt_expires_in: i64,
     pub refresh_token_expires_in: i64,
     pub port: u16,
+    pub storage: StorageConfig,
+    pub attachment_max_bytes: usize,
+    pub workspace_storage_quota_bytes: i64,
+    pub trash_retention_days: i64,
+    pub mail: MailConfig,
+    /// Sender of outgoing email, e.g. `TODO <noreply@example.com>`
+    pub mail_from: String,
+    /// How often unread notifications are emailed; 0 turns digests off
+    pub notification_digest_hours: u64,
+}
+
+/// Where attachment contents are kept
+#[derive(Debug, Clone)]
+pub enum StorageConfig {
+    Local {
+        path: PathBuf,
+    },
+    /// Any S3-compatible service (AWS, MinIO, ...), addressed path-style
+    S3 {
+        endpoint: String,
+        bucket: String,
+        region: String,
+        access_key: String,
+        secret_key: String,
+    },
+}
+
+impl StorageConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        let backend = env::var("STORAGE_BACKEND").unwrap_or_else(|_| "local".to_string());
+
+        match backend.as_str() {
+            "local" => Ok(Self::Local {
+                path: env::var("STORAGE_PATH")
+                    .unwrap_or_else(|_| "./data/attachments".to_string())
+                    .into(),
+            }),
+            "s3" => Ok(Self::S3 {
+                endpoint: env::var("S3_ENDPOINT")
+                    .unwrap_or_else(|_| "https://s3.amazonaws.com".to_string()),
+                bucket: env::var("S3_BUCKET")
+                    .expect("S3_BUCKET must be set when STORAGE_BACKEND=s3"),
+                region: env::var("S3_REGION")
+                    .unwrap_or_else(|_| "us-east-1".to_string()),
+                access_key: env::var("S3_ACCESS_KEY")
+                    .expect("S3_ACCESS_KEY must be set when STORAGE_BACKEND=s3"),
+                secret_key: env::var("S3_SECRET_KEY")
+                    .expect("S3_SECRET_KEY must be set when STORAGE_BACKEND=s3"),
+            }),
+            other => anyhow::bail!("Unknown STORAGE_BACKEND '{}', expected local or s3", other),
+        }
+    }
+}
+
+/// How outgoing email is delivered
+#[derive(Debug, Clone)]
+pub enum MailConfig {
+    /// Written to a local maildir instead of sent; for development and tests
+    Maildir {
+        path: PathBuf,
+    },
+    Smtp {
+        host: String,
+        port: u16,
+        tls: SmtpTls,
+        username: Option<String>,
+        password: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum SmtpTls {
+    /// Plain connection upgraded with STARTTLS (usually port 587)
+    StartTls,
+    /// TLS from the first byte (usually port 465)
+    Tls,
+    /// No encryption; only for relays on a trusted network
+    None,
+}
+
+impl MailConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        let transport = env::var("MAIL_TRANSPORT").unwrap_or_else(|_| "maildir".to_string());
+
+        match transport.as_str() {
+            "maildir" => Ok(Self::Maildir {
+                path: env::var("MAIL_DIR")
+                    .unwrap_or_else(|_| "./data/mail".to_string())
+                    .into(),
+            }),
+            "smtp" => {
+                let tls = match env::var("SMTP_TLS").unwrap_or_else(|_| "starttls".to_string()).as_str() {
+                    "starttls" => SmtpTls::StartTls,
+                    "tls" => SmtpTls::Tls,
+                    "none" => SmtpTls::None,
+                    other => anyhow::bail!("Unknown SMTP_TLS '{}', expected starttls, tls or none", other),
+                };
+                let default_port = match tls {
+                    SmtpTls::StartTls => 587,
+                    SmtpTls::Tls => 465,
+                    SmtpTls::None => 25,
+                };
+
+                Ok(Self::Smtp {
+                    host: env::var("SMTP_HOST")
+                        .expect("SMTP_HOST must be set when MAIL_TRANSPORT=smtp"),
+                    port: match env::var("SMTP_PORT") {
+                        Ok(port) => port.parse()?,
+                        Err(_) => default_port,
+                    },
+                    tls,
+                    username: env::var("SMTP_USERNAME").ok(),
+                    password: env::var("SMTP_PASSWORD").ok(),
+                })
+            }
+            other => anyhow::bail!("Unknown MAIL_TRANSPORT '{}', expected maildir or smtp", other),
+        }
+    }
 }
 
 impl Config {
@@ -25,6 +144,22 @@ impl Config {
             port: env::var("PORT")
                 .unwrap_or_else(|_| "3000".to_string())
                 .parse()?,
+            storage: StorageConfig::from_env()?,
+            attachment_max_bytes: env::var("ATTACHMENT_MAX_BYTES")
+                .unwrap_or_else(|_| "26214400".to_string()) // 25 MiB
+                .parse()?,
+            workspace_storage_quota_bytes: env::var("WORKSPACE_STORAGE_QUOTA_BYTES")
+                .unwrap_or_else(|_| "1073741824".to_string()) // 1 GiB
+                .parse()?,
+            trash_retention_days: env::var("TRASH_RETENTION_DAYS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()?,
+            mail: MailConfig::from_env()?,
+            mail_from: env::var("MAIL_FROM")
+                .unwrap_or_else(|_| "TODO TUI <noreply@localhost>".to_string()),
+            notification_digest_hours: env::var("NOTIFICATION_DIGEST_HOURS")
+                .unwrap_or_else(|_| "24".to_string())
+                .parse()?,
         })
     }
 }
@@ -0,0 +1,177 @@
+//! Workspace change events
+//!
+//! Handlers publish with `pg_notify` inside their transaction, so an event
+//! goes out only if the change commits. Every server instance listens on the
+//! same channel and fans the events out to its own subscribers. Signed-out
+//! sessions and removed members go out the same way so their open streams
+//! close on every instance.
+
+use std::time::Duration;
+
+use sqlx::postgres::PgListener;
+use sqlx::PgExecutor;
+use todo_shared::{WorkspaceEvent, WorkspaceEventKind};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::DbPool;
+
+/// Postgres channel the events travel on
+const CHANNEL: &str = "workspace_events";
+
+/// Postgres channel the ids of signed-out sessions travel on
+const REVOKED_SESSIONS_CHANNEL: &str = "revoked_sessions";
+
+/// Postgres channel removed memberships travel on, as `workspace_id:user_id`
+const REMOVED_MEMBERS_CHANNEL: &str = "removed_members";
+
+/// Events buffered per subscriber before a slow one starts missing them
+const SUBSCRIBER_BUFFER: usize = 256;
+
+/// What the listener hands to this instance's subscribers
+#[derive(Debug, Clone)]
+pub enum Relayed {
+    Event(WorkspaceEvent),
+    /// The listener lost its connection and events may have been dropped;
+    /// every subscriber should reload
+    Resync,
+    /// The session was signed out; streams opened with it should end
+    SessionRevoked(Uuid),
+    /// The user left the workspace; their streams for it should end
+    MemberRemoved { workspace_id: Uuid, user_id: Uuid },
+}
+
+pub type EventSender = broadcast::Sender<Relayed>;
+
+/// Queue an event for everyone watching the workspace
+pub async fn publish<'e>(
+    executor: impl PgExecutor<'e>,
+    workspace_id: Uuid,
+    user_id: Uuid,
+    kind: WorkspaceEventKind,
+) -> Result<(), AppError> {
+    let event = WorkspaceEvent {
+        workspace_id,
+        user_id,
+        kind,
+    };
+    let payload = serde_json::to_string(&event).map_err(anyhow::Error::from)?;
+
+    sqlx::query("SELECT pg_notify($1, $2)")
+        .bind(CHANNEL)
+        .bind(payload)
+        .execute(executor)
+        .await?;
+
+    Ok(())
+}
+
+/// Close the event streams of signed-out sessions, once the transaction commits
+pub async fn publish_revoked_sessions<'e>(
+    executor: impl PgExecutor<'e>,
+    session_ids: &[Uuid],
+) -> Result<(), AppError> {
+    if session_ids.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query("SELECT pg_notify($1, id::text) FROM UNNEST($2::uuid[]) AS id")
+        .bind(REVOKED_SESSIONS_CHANNEL)
+        .bind(session_ids)
+        .execute(executor)
+        .await?;
+
+    Ok(())
+}
+
+/// Close a removed member's event streams for the workspace, once the
+/// transaction commits
+pub async fn publish_member_removed<'e>(
+    executor: impl PgExecutor<'e>,
+    workspace_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), AppError> {
+    sqlx::query("SELECT pg_notify($1, $2)")
+        .bind(REMOVED_MEMBERS_CHANNEL)
+        .bind(format!("{}:{}", workspace_id, user_id))
+        .execute(executor)
+        .await?;
+
+    Ok(())
+}
+
+/// Parse a `workspace_id:user_id` payload from the removed members channel
+fn parse_member_removed(payload: &str) -> Option<Relayed> {
+    let (workspace_id, user_id) = payload.split_once(':')?;
+    Some(Relayed::MemberRemoved {
+        workspace_id: workspace_id.parse().ok()?,
+        user_id: user_id.parse().ok()?,
+    })
+}
+
+/// Start relaying notifications from Postgres to this instance's subscribers
+pub fn spawn_listener(db: DbPool) -> EventSender {
+    let (sender, _) = broadcast::channel(SUBSCRIBER_BUFFER);
+    tokio::spawn(run_listener(db, sender.clone()));
+    sender
+}
+
+async fn run_listener(db: DbPool, sender: EventSender) {
+    let mut reconnecting = false;
+    loop {
+        if let Err(e) = relay(&db, &sender, reconnecting).await {
+            tracing::warn!("Event listener failed, reconnecting: {:?}", e);
+        }
+        reconnecting = true;
+        tokio::time::sleep(Duration::from_secs(5)).await;
+    }
+}
+
+async fn relay(db: &DbPool, sender: &EventSender, reconnecting: bool) -> Result<(), sqlx::Error> {
+    let mut listener = PgListener::connect_with(db).await?;
+    listener
+        .listen_all([CHANNEL, REVOKED_SESSIONS_CHANNEL, REMOVED_MEMBERS_CHANNEL])
+        .await?;
+
+    // Whatever was sent while disconnected is gone
+    if reconnecting {
+        let _ = sender.send(Relayed::Resync);
+    }
+
+    loop {
+        // `None` means the listener dropped its connection and reconnected on
+        // its own, losing anything sent in between
+        let Some(notification) = listener.try_recv().await? else {
+            let _ = sender.send(Relayed::Resync);
+            continue;
+        };
+        let relayed = if notification.channel() == REVOKED_SESSIONS_CHANNEL {
+            match notification.payload().parse::<Uuid>() {
+                Ok(session_id) => Relayed::SessionRevoked(session_id),
+                Err(e) => {
+                    tracing::warn!("Ignoring malformed session id: {}", e);
+                    continue;
+                }
+            }
+        } else if notification.channel() == REMOVED_MEMBERS_CHANNEL {
+            match parse_member_removed(notification.payload()) {
+                Some(relayed) => relayed,
+                None => {
+                    tracing::warn!("Ignoring malformed member removal: {}", notification.payload());
+                    continue;
+                }
+            }
+        } else {
+            match serde_json::from_str::<WorkspaceEvent>(notification.payload()) {
+                Ok(event) => Relayed::Event(event),
+                Err(e) => {
+                    tracing::warn!("Ignoring malformed workspace event: {}", e);
+                    continue;
+                }
+            }
+        };
+        // No receivers just means nobody is watching right now
+        let _ = sender.send(relayed);
+    }
+}
@@ -0,0 +1,332 @@
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use sqlx::PgConnection;
+use todo_shared::{
+    api::ActivityListResponse, ActivityAction, ActivityEntityType, ActivityEntry, WorkspaceRole,
+};
+use uuid::Uuid;
+
+use crate::auth::AuthUser;
+use crate::error::AppError;
+use crate::routes::AppState;
+
+/// Fields whose full text is too large to keep in the log; only the fact
+/// that they changed is recorded
+const SUMMARIZED_FIELDS: &[&str] = &["description", "content"];
+
+/// Helper to check workspace membership and return role
+async fn check_membership(
+    state: &AppState,
+    workspace_id: Uuid,
+    user_id: Uuid,
+) -> Result<WorkspaceRole, AppError> {
+    let role: Option<(WorkspaceRole,)> = sqlx::query_as(
+        r#"SELECT role as "role: WorkspaceRole" FROM workspace_members WHERE workspace_id = $1 AND user_id = $2"#,
+    )
+    .bind(workspace_id)
+    .bind(user_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    role.map(|(r,)| r).ok_or(AppError::NotFound)
+}
+
+/// An entry about to be written to the activity log
+pub struct NewActivity {
+    pub workspace_id: Uuid,
+    pub user_id: Uuid,
+    pub entity_type: ActivityEntityType,
+    pub entity_id: Uuid,
+    pub entity_label: String,
+    pub action: ActivityAction,
+    pub changes: Option<Value>,
+}
+
+/// Append an entry to the activity log. Pass the handler's transaction so the
+/// entry is only kept if the change itself commits.
+pub async fn record_activity(conn: &mut PgConnection, activity: NewActivity) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        INSERT INTO activity_log (workspace_id, user_id, entity_type, entity_id, entity_label, action, changes)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+    )
+    .bind(activity.workspace_id)
+    .bind(activity.user_id)
+    .bind(activity.entity_type.as_str())
+    .bind(activity.entity_id)
+    .bind(&activity.entity_label)
+    .bind(activity.action.as_str())
+    .bind(&activity.changes)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Field-level diff of two snapshots, as `{"field": {"old": .., "new": ..}}`.
+/// Returns `None` when nothing changed.
+pub fn diff(before: &Value, after: &Value) -> Option<Value> {
+    let (Value::Object(before), Value::Object(after)) = (before, after) else {
+        return None;
+    };
+
+    let mut changes = Map::new();
+    for (field, new) in after {
+        let old = before.get(field).unwrap_or(&Value::Null);
+        if old == new {
+            continue;
+        }
+
+        let change = if SUMMARIZED_FIELDS.contains(&field.as_str()) {
+            json!({ "changed": true })
+        } else {
+            json!({ "old": old, "new": new })
+        };
+        changes.insert(field.clone(), change);
+    }
+
+    if changes.is_empty() {
+        None
+    } else {
+        Some(Value::Object(changes))
+    }
+}
+
+/// Snapshot of the user-visible fields of a task, with its "KEY-1 Title" label.
+/// People, tags, status and parent are kept by name so the log stays readable.
+pub async fn task_snapshot(conn: &mut PgConnection, task_id: Uuid) -> Result<(String, Value), AppError> {
+    let row: (String, Value) = sqlx::query_as(
+        r#"
+        SELECT w.task_key_prefix || '-' || t.number || ' ' || t.title,
+               jsonb_build_object(
+                   'title', t.title,
+                   'status', s.name,
+                   'description', t.description,
+                   'priority', t.priority,
+                   'due_date', t.due_date,
+                   'time_estimate_minutes', t.time_estimate_minutes,
+                   'parent', CASE WHEN p.id IS NOT NULL
+                                  THEN w.task_key_prefix || '-' || p.number END,
+                   'recurrence', t.recurrence,
+                   'assignees', COALESCE((
+                       SELECT jsonb_agg(COALESCE(u.username, u.display_name)
+                                 ORDER BY COALESCE(u.username, u.display_name))
+                       FROM task_assignees ta INNER JOIN users u ON u.id = ta.user_id
+                       WHERE ta.task_id = t.id), '[]'::jsonb),
+                   'watchers', COALESCE((
+                       SELECT jsonb_agg(COALESCE(u.username, u.display_name)
+                                 ORDER BY COALESCE(u.username, u.display_name))
+                       FROM task_watchers tw INNER JOIN users u ON u.id = tw.user_id
+                       WHERE tw.task_id = t.id), '[]'::jsonb),
+                   'tags', COALESCE((
+                       SELECT jsonb_agg(g.name ORDER BY g.name)
+                       FROM task_tags tt INNER JOIN tags g ON g.id = tt.tag_id
+                       WHERE tt.task_id = t.id), '[]'::jsonb)
+               )
+        FROM tasks t
+        INNER JOIN workspaces w ON w.id = t.workspace_id
+        INNER JOIN task_statuses s ON s.id = t.status_id
+        LEFT JOIN tasks p ON p.id = t.parent_id
+        WHERE t.id = $1
+        "#,
+    )
+    .bind(task_id)
+    .fetch_one(conn)
+    .await?;
+
+    Ok(row)
+}
+
+/// Snapshot of a document's title, path and content, with its title as label
+pub async fn document_snapshot(conn: &mut PgConnection, doc_id: Uuid) -> Result<(String, Value), AppError> {
+    let row: (String, Value) = sqlx::query_as(
+        r#"
+        SELECT title,
+               jsonb_build_object('title', title, 'path', path::text, 'content', content)
+        FROM documents
+        WHERE id = $1
+        "#,
+    )
+    .bind(doc_id)
+    .fetch_one(conn)
+    .await?;
+
+    Ok(row)
+}
+
+/// Snapshot of a board column, with its name as label
+pub async fn status_snapshot(conn: &mut PgConnection, status_id: Uuid) -> Result<(String, Value), AppError> {
+    let row: (String, Value) = sqlx::query_as(
+        r#"
+        SELECT name, jsonb_build_object('name', name, 'color', color, 'is_done', is_done,
+                                  'wip_limit', wip_limit)
+        FROM task_statuses
+        WHERE id = $1
+        "#,
+    )
+    .bind(status_id)
+    .fetch_one(conn)
+    .await?;
+
+    Ok(row)
+}
+
+/// Snapshot of a workspace's settings, with its name as label
+pub async fn workspace_snapshot(
+    conn: &mut PgConnection,
+    workspace_id: Uuid,
+) -> Result<(String, Value), AppError> {
+    let row: (String, Value) = sqlx::query_as(
+        r#"
+        SELECT name,
+               jsonb_build_object(
+                   'name', name,
+                   'description', description,
+                   'task_key_prefix', task_key_prefix,
+                   'settings', settings
+               )
+        FROM workspaces
+        WHERE id = $1
+        "#,
+    )
+    .bind(workspace_id)
+    .fetch_one(conn)
+    .await?;
+
+    Ok(row)
+}
+
+/// Label for member entries: the username, or the display name until one is picked
+pub async fn user_label(conn: &mut PgConnection, user_id: Uuid) -> Result<String, AppError> {
+    let (label,): (String,) =
+        sqlx::query_as("SELECT COALESCE(username, display_name) FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_one(conn)
+            .await?;
+
+    Ok(label)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ActivityQuery {
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+type ActivityRow = (
+    Uuid,            // id
+    Uuid,            // workspace_id
+    Option<Uuid>,    // user_id
+    Option<String>,  // username
+    String,          // entity_type
+    Uuid,            // entity_id
+    Option<String>,  // entity_label
+    String,          // action
+    Option<Value>,   // changes
+    DateTime<Utc>,   // created_at
+);
+
+/// Rows written by a newer server may carry types this one doesn't know; skip them
+fn row_to_entry(row: ActivityRow) -> Option<ActivityEntry> {
+    Some(ActivityEntry {
+        id: row.0,
+        workspace_id: row.1,
+        user_id: row.2,
+        username: row.3,
+        entity_type: ActivityEntityType::parse(&row.4)?,
+        entity_id: row.5,
+        entity_label: row.6,
+        action: ActivityAction::parse(&row.7)?,
+        changes: row.8,
+        created_at: row.9,
+    })
+}
+
+/// Helper to load one page of the log, optionally narrowed to a single entity
+async fn load_activity(
+    state: &AppState,
+    workspace_id: Uuid,
+    entity: Option<(ActivityEntityType, Uuid)>,
+    params: ActivityQuery,
+) -> Result<ActivityListResponse, AppError> {
+    let page = params.page.unwrap_or(1).max(1);
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
+    let offset = (page - 1) * limit;
+
+    let entity_type = entity.map(|(t, _)| t.as_str());
+    let entity_id = entity.map(|(_, id)| id);
+
+    let (total,): (i64,) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*) FROM activity_log
+        WHERE workspace_id = $1
+          AND ($2::text IS NULL OR entity_type = $2)
+          AND ($3::uuid IS NULL OR entity_id = $3)
+        "#,
+    )
+    .bind(workspace_id)
+    .bind(entity_type)
+    .bind(entity_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    let rows: Vec<ActivityRow> = sqlx::query_as(
+        r#"
+        SELECT a.id, a.workspace_id, a.user_id, COALESCE(u.username, u.display_name),
+               a.entity_type, a.entity_id, a.entity_label, a.action, a.changes, a.created_at
+        FROM activity_log a
+        LEFT JOIN users u ON u.id = a.user_id
+        WHERE a.workspace_id = $1
+          AND ($2::text IS NULL OR a.entity_type = $2)
+          AND ($3::uuid IS NULL OR a.entity_id = $3)
+        ORDER BY a.created_at DESC, a.id
+        LIMIT $4 OFFSET $5
+        "#,
+    )
+    .bind(workspace_id)
+    .bind(entity_type)
+    .bind(entity_id)
+    .bind(limit as i64)
+    .bind(offset as i64)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(ActivityListResponse {
+        entries: rows.into_iter().filter_map(row_to_entry).collect(),
+        total,
+        page,
+        limit,
+    })
+}
+
+/// GET /api/v1/workspaces/:id/activity
+pub async fn list_activity(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(workspace_id): Path<Uuid>,
+    Query(params): Query<ActivityQuery>,
+) -> Result<Json<ActivityListResponse>, AppError> {
+    check_membership(&state, workspace_id, user.id).await?;
+
+    Ok(Json(load_activity(&state, workspace_id, None, params).await?))
+}
+
+/// GET /api/v1/workspaces/:id/activity/:entity_type/:entity_id
+pub async fn entity_history(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, entity_type, entity_id)): Path<(Uuid, ActivityEntityType, Uuid)>,
+    Query(params): Query<ActivityQuery>,
+) -> Result<Json<ActivityListResponse>, AppError> {
+    check_membership(&state, workspace_id, user.id).await?;
+
+    let entity = Some((entity_type, entity_id));
+
+    Ok(Json(load_activity(&state, workspace_id, entity, params).await?))
+}
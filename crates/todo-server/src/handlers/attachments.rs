@@ -0,0 +1,576 @@
+use axum::{
+    body::Bytes,
+    extract::{multipart::MultipartError, Multipart, Path, State},
+    http::header,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use chrono::Utc;
+use todo_shared::{api::StorageUsage, Attachment, WorkspaceEventKind, WorkspaceRole};
+use uuid::Uuid;
+
+use crate::auth::AuthUser;
+use crate::error::AppError;
+use crate::events::publish;
+use crate::routes::AppState;
+
+/// Helper to check workspace membership and return role
+async fn check_membership(
+    state: &AppState,
+    workspace_id: Uuid,
+    user_id: Uuid,
+) -> Result<WorkspaceRole, AppError> {
+    let role: Option<(WorkspaceRole,)> = sqlx::query_as(
+        r#"SELECT role as "role: WorkspaceRole" FROM workspace_members WHERE workspace_id = $1 AND user_id = $2"#,
+    )
+    .bind(workspace_id)
+    .bind(user_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    role.map(|(r,)| r).ok_or(AppError::NotFound)
+}
+
+/// What an attachment hangs off
+#[derive(Debug, Clone, Copy)]
+pub enum AttachmentOwner {
+    Task(Uuid),
+    Document(Uuid),
+}
+
+impl AttachmentOwner {
+    fn task_id(&self) -> Option<Uuid> {
+        match self {
+            Self::Task(id) => Some(*id),
+            Self::Document(_) => None,
+        }
+    }
+
+    fn document_id(&self) -> Option<Uuid> {
+        match self {
+            Self::Task(_) => None,
+            Self::Document(id) => Some(*id),
+        }
+    }
+
+    fn of(attachment: &Attachment) -> Option<Self> {
+        match (attachment.task_id, attachment.document_id) {
+            (Some(task_id), _) => Some(Self::Task(task_id)),
+            (None, Some(doc_id)) => Some(Self::Document(doc_id)),
+            (None, None) => None,
+        }
+    }
+
+    /// Event telling everyone else the owner's attachments changed
+    fn changed_event(&self) -> WorkspaceEventKind {
+        match self {
+            Self::Task(task_id) => WorkspaceEventKind::TaskChanged { task_id: *task_id },
+            Self::Document(document_id) => WorkspaceEventKind::DocumentChanged {
+                document_id: *document_id,
+            },
+        }
+    }
+
+    /// Helper to verify the owner belongs to the workspace
+    async fn verify(&self, state: &AppState, workspace_id: Uuid) -> Result<(), AppError> {
+        let query = match self {
+            Self::Task(_) => {
+                "SELECT id FROM tasks WHERE id = $1 AND workspace_id = $2 AND deleted_at IS NULL"
+            }
+            Self::Document(_) => {
+                "SELECT id FROM documents WHERE id = $1 AND workspace_id = $2 AND deleted_at IS NULL"
+            }
+        };
+        let id = self.task_id().or(self.document_id());
+
+        let exists: Option<(Uuid,)> = sqlx::query_as(query)
+            .bind(id)
+            .bind(workspace_id)
+            .fetch_optional(&state.db)
+            .await?;
+
+        if exists.is_none() {
+            return Err(AppError::NotFound);
+        }
+        Ok(())
+    }
+}
+
+type AttachmentRow = (
+    Uuid,                  // id
+    Uuid,                  // workspace_id
+    Option<Uuid>,          // task_id
+    Option<Uuid>,          // document_id
+    Uuid,                  // uploaded_by
+    String,                // uploader_username
+    String,                // filename
+    String,                // content_type
+    i64,                   // size_bytes
+    chrono::DateTime<Utc>, // created_at
+);
+
+fn row_to_attachment(row: AttachmentRow) -> Attachment {
+    Attachment {
+        id: row.0,
+        workspace_id: row.1,
+        task_id: row.2,
+        document_id: row.3,
+        uploaded_by: row.4,
+        uploader_username: row.5,
+        filename: row.6,
+        content_type: row.7,
+        size_bytes: row.8,
+        created_at: row.9,
+    }
+}
+
+const ATTACHMENT_COLUMNS: &str = r#"
+    a.id, a.workspace_id, a.task_id, a.document_id, a.uploaded_by, u.username,
+    a.filename, a.content_type, a.size_bytes, a.created_at
+"#;
+
+/// A file read from a multipart upload
+struct Upload {
+    filename: String,
+    content_type: String,
+    data: Bytes,
+}
+
+fn multipart_error(e: MultipartError) -> AppError {
+    AppError::Validation(e.body_text())
+}
+
+/// Keep only the base name, without control characters, at most 255 chars
+fn sanitize_filename(name: &str) -> String {
+    let base = name.rsplit(['/', '\\']).next().unwrap_or_default();
+    let cleaned: String = base
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(255)
+        .collect();
+    let cleaned = cleaned.trim();
+
+    if cleaned.is_empty() || cleaned == "." || cleaned == ".." {
+        "upload".to_string()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+/// Accept `type/subtype` media types and fall back to a generic binary type
+fn sanitize_content_type(content_type: Option<&str>) -> String {
+    match content_type {
+        Some(ct)
+            if ct.len() <= 255
+                && ct.contains('/')
+                && ct.chars().all(|c| c.is_ascii_graphic() || c == ' ') =>
+        {
+            ct.to_string()
+        }
+        _ => "application/octet-stream".to_string(),
+    }
+}
+
+/// Read the `file` field of a multipart body, enforcing the size limit as it streams
+async fn read_upload(mut multipart: Multipart, max_bytes: usize) -> Result<Upload, AppError> {
+    while let Some(mut field) = multipart.next_field().await.map_err(multipart_error)? {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let filename = sanitize_filename(field.file_name().unwrap_or_default());
+        let content_type = sanitize_content_type(field.content_type());
+
+        let mut data: Vec<u8> = Vec::new();
+        while let Some(chunk) = field.chunk().await.map_err(multipart_error)? {
+            if data.len() + chunk.len() > max_bytes {
+                return Err(AppError::Validation(format!(
+                    "File is larger than the {} byte limit",
+                    max_bytes
+                )));
+            }
+            data.extend_from_slice(&chunk);
+        }
+
+        if data.is_empty() {
+            return Err(AppError::Validation("File is empty".to_string()));
+        }
+
+        return Ok(Upload {
+            filename,
+            content_type,
+            data: Bytes::from(data),
+        });
+    }
+
+    Err(AppError::Validation("Missing 'file' field".to_string()))
+}
+
+/// Helper to load a single attachment scoped to a workspace, with its storage key
+async fn fetch_attachment(
+    state: &AppState,
+    workspace_id: Uuid,
+    attachment_id: Uuid,
+) -> Result<(Attachment, String), AppError> {
+    // Attachments of a trashed task or document stay hidden until it's restored
+    let storage_key: Option<(String,)> = sqlx::query_as(
+        r#"
+        SELECT a.storage_key
+        FROM attachments a
+        LEFT JOIN tasks t ON t.id = a.task_id
+        LEFT JOIN documents d ON d.id = a.document_id
+        WHERE a.id = $1 AND a.workspace_id = $2
+          AND t.deleted_at IS NULL AND d.deleted_at IS NULL
+        "#,
+    )
+    .bind(attachment_id)
+    .bind(workspace_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    let (storage_key,) = storage_key.ok_or(AppError::NotFound)?;
+
+    let row: AttachmentRow = sqlx::query_as(&format!(
+        r#"
+        SELECT {}
+        FROM attachments a
+        JOIN users u ON u.id = a.uploaded_by
+        WHERE a.id = $1
+        "#,
+        ATTACHMENT_COLUMNS
+    ))
+    .bind(attachment_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok((row_to_attachment(row), storage_key))
+}
+
+async fn list_for_owner(
+    state: &AppState,
+    workspace_id: Uuid,
+    user_id: Uuid,
+    owner: AttachmentOwner,
+) -> Result<Vec<Attachment>, AppError> {
+    check_membership(state, workspace_id, user_id).await?;
+    owner.verify(state, workspace_id).await?;
+
+    let rows: Vec<AttachmentRow> = sqlx::query_as(&format!(
+        r#"
+        SELECT {}
+        FROM attachments a
+        JOIN users u ON u.id = a.uploaded_by
+        WHERE a.task_id IS NOT DISTINCT FROM $1 AND a.document_id IS NOT DISTINCT FROM $2
+        ORDER BY a.created_at
+        "#,
+        ATTACHMENT_COLUMNS
+    ))
+    .bind(owner.task_id())
+    .bind(owner.document_id())
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(rows.into_iter().map(row_to_attachment).collect())
+}
+
+async fn upload_for_owner(
+    state: &AppState,
+    workspace_id: Uuid,
+    user_id: Uuid,
+    owner: AttachmentOwner,
+    multipart: Multipart,
+) -> Result<Attachment, AppError> {
+    let role = check_membership(state, workspace_id, user_id).await?;
+
+    if !role.can_edit() {
+        return Err(AppError::Forbidden);
+    }
+
+    owner.verify(state, workspace_id).await?;
+
+    let upload = read_upload(multipart, state.config.attachment_max_bytes).await?;
+    let size_bytes = upload.data.len() as i64;
+
+    let id = Uuid::new_v4();
+    let storage_key = format!("{}/{}", workspace_id, id);
+
+    // Store the contents first, the row only points at finished blobs
+    state
+        .storage
+        .put(&storage_key, upload.data, &upload.content_type)
+        .await?;
+
+    let result = insert_within_quota(
+        state,
+        workspace_id,
+        user_id,
+        owner,
+        id,
+        &upload.filename,
+        &upload.content_type,
+        size_bytes,
+        &storage_key,
+    )
+    .await;
+
+    if result.is_err() {
+        if let Err(e) = state.storage.delete(&storage_key).await {
+            tracing::warn!("Failed to remove orphaned blob {}: {:?}", storage_key, e);
+        }
+    }
+    result?;
+
+    Ok(fetch_attachment(state, workspace_id, id).await?.0)
+}
+
+/// Insert the attachment row unless it would push the workspace over its quota
+#[allow(clippy::too_many_arguments)]
+async fn insert_within_quota(
+    state: &AppState,
+    workspace_id: Uuid,
+    user_id: Uuid,
+    owner: AttachmentOwner,
+    id: Uuid,
+    filename: &str,
+    content_type: &str,
+    size_bytes: i64,
+    storage_key: &str,
+) -> Result<(), AppError> {
+    let mut tx = state.db.begin().await?;
+
+    // Serialize uploads per workspace so concurrent ones can't both squeeze under the quota
+    sqlx::query("SELECT id FROM workspaces WHERE id = $1 FOR UPDATE")
+        .bind(workspace_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let (used,): (i64,) = sqlx::query_as(
+        "SELECT COALESCE(SUM(size_bytes), 0)::BIGINT FROM attachments WHERE workspace_id = $1",
+    )
+    .bind(workspace_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let quota = state.config.workspace_storage_quota_bytes;
+    if used + size_bytes > quota {
+        return Err(AppError::Conflict(format!(
+            "Workspace storage quota exceeded ({} of {} bytes used)",
+            used, quota
+        )));
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO attachments
+            (id, workspace_id, task_id, document_id, uploaded_by, filename, content_type,
+             size_bytes, storage_key, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        "#,
+    )
+    .bind(id)
+    .bind(workspace_id)
+    .bind(owner.task_id())
+    .bind(owner.document_id())
+    .bind(user_id)
+    .bind(filename)
+    .bind(content_type)
+    .bind(size_bytes)
+    .bind(storage_key)
+    .bind(Utc::now())
+    .execute(&mut *tx)
+    .await?;
+
+    publish(&mut *tx, workspace_id, user_id, owner.changed_event()).await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Storage keys of attachments on a task or document and everything nested under it.
+/// Collect these before deleting the owner, since the rows go with it.
+pub async fn owned_storage_keys(
+    state: &AppState,
+    owner: AttachmentOwner,
+) -> Result<Vec<String>, AppError> {
+    let query = match owner {
+        AttachmentOwner::Task(_) => {
+            r#"
+            WITH RECURSIVE tree AS (
+                SELECT id FROM tasks WHERE id = $1
+                UNION ALL
+                SELECT t.id FROM tasks t JOIN tree ON t.parent_id = tree.id
+            )
+            SELECT storage_key FROM attachments WHERE task_id IN (SELECT id FROM tree)
+            "#
+        }
+        AttachmentOwner::Document(_) => {
+            r#"
+            WITH RECURSIVE tree AS (
+                SELECT id FROM documents WHERE id = $1
+                UNION ALL
+                SELECT d.id FROM documents d JOIN tree ON d.parent_id = tree.id
+            )
+            SELECT storage_key FROM attachments WHERE document_id IN (SELECT id FROM tree)
+            "#
+        }
+    };
+
+    let keys: Vec<(String,)> = sqlx::query_as(query)
+        .bind(owner.task_id().or(owner.document_id()))
+        .fetch_all(&state.db)
+        .await?;
+
+    Ok(keys.into_iter().map(|(k,)| k).collect())
+}
+
+/// Storage keys of every attachment in a workspace
+pub async fn workspace_storage_keys(
+    state: &AppState,
+    workspace_id: Uuid,
+) -> Result<Vec<String>, AppError> {
+    let keys: Vec<(String,)> =
+        sqlx::query_as("SELECT storage_key FROM attachments WHERE workspace_id = $1")
+            .bind(workspace_id)
+            .fetch_all(&state.db)
+            .await?;
+
+    Ok(keys.into_iter().map(|(k,)| k).collect())
+}
+
+/// Best-effort removal of blobs whose rows are already gone
+pub async fn purge_blobs(state: &AppState, keys: Vec<String>) {
+    for key in keys {
+        if let Err(e) = state.storage.delete(&key).await {
+            tracing::warn!("Failed to remove blob {}: {:?}", key, e);
+        }
+    }
+}
+
+/// GET /api/v1/workspaces/:id/tasks/:task_id/attachments
+pub async fn list_task_attachments(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, task_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Vec<Attachment>>, AppError> {
+    let owner = AttachmentOwner::Task(task_id);
+    Ok(Json(list_for_owner(&state, workspace_id, user.id, owner).await?))
+}
+
+/// POST /api/v1/workspaces/:id/tasks/:task_id/attachments (multipart, field `file`)
+pub async fn upload_task_attachment(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, task_id)): Path<(Uuid, Uuid)>,
+    multipart: Multipart,
+) -> Result<Json<Attachment>, AppError> {
+    let owner = AttachmentOwner::Task(task_id);
+    Ok(Json(upload_for_owner(&state, workspace_id, user.id, owner, multipart).await?))
+}
+
+/// GET /api/v1/workspaces/:id/documents/:doc_id/attachments
+pub async fn list_document_attachments(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, doc_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Vec<Attachment>>, AppError> {
+    let owner = AttachmentOwner::Document(doc_id);
+    Ok(Json(list_for_owner(&state, workspace_id, user.id, owner).await?))
+}
+
+/// POST /api/v1/workspaces/:id/documents/:doc_id/attachments (multipart, field `file`)
+pub async fn upload_document_attachment(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, doc_id)): Path<(Uuid, Uuid)>,
+    multipart: Multipart,
+) -> Result<Json<Attachment>, AppError> {
+    let owner = AttachmentOwner::Document(doc_id);
+    Ok(Json(upload_for_owner(&state, workspace_id, user.id, owner, multipart).await?))
+}
+
+/// GET /api/v1/workspaces/:id/attachments/:attachment_id/download
+pub async fn download_attachment(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, attachment_id)): Path<(Uuid, Uuid)>,
+) -> Result<Response, AppError> {
+    check_membership(&state, workspace_id, user.id).await?;
+
+    let (attachment, storage_key) = fetch_attachment(&state, workspace_id, attachment_id).await?;
+    let data = state.storage.get(&storage_key).await?;
+
+    // Filenames are user input; keep the header plain ASCII
+    let header_name: String = attachment
+        .filename
+        .chars()
+        .map(|c| {
+            if (c.is_ascii_graphic() || c == ' ') && c != '"' && c != '\\' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, attachment.content_type),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", header_name),
+            ),
+        ],
+        data,
+    )
+        .into_response())
+}
+
+/// DELETE /api/v1/workspaces/:id/attachments/:attachment_id
+pub async fn delete_attachment(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, attachment_id)): Path<(Uuid, Uuid)>,
+) -> Result<(), AppError> {
+    let role = check_membership(&state, workspace_id, user.id).await?;
+
+    let (attachment, storage_key) = fetch_attachment(&state, workspace_id, attachment_id).await?;
+
+    // Uploader or admin can delete
+    if !role.can_edit() || (attachment.uploaded_by != user.id && !role.can_admin()) {
+        return Err(AppError::Forbidden);
+    }
+
+    sqlx::query("DELETE FROM attachments WHERE id = $1")
+        .bind(attachment_id)
+        .execute(&state.db)
+        .await?;
+
+    if let Some(owner) = AttachmentOwner::of(&attachment) {
+        publish(&state.db, workspace_id, user.id, owner.changed_event()).await?;
+    }
+
+    purge_blobs(&state, vec![storage_key]).await;
+
+    Ok(())
+}
+
+/// GET /api/v1/workspaces/:id/attachments/usage
+pub async fn get_storage_usage(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(workspace_id): Path<Uuid>,
+) -> Result<Json<StorageUsage>, AppError> {
+    check_membership(&state, workspace_id, user.id).await?;
+
+    let (used_bytes,): (i64,) = sqlx::query_as(
+        "SELECT COALESCE(SUM(size_bytes), 0)::BIGINT FROM attachments WHERE workspace_id = $1",
+    )
+    .bind(workspace_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(Json(StorageUsage {
+        used_bytes,
+        quota_bytes: state.config.workspace_storage_quota_bytes,
+    }))
+}
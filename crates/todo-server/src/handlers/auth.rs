@@ -1,20 +1,153 @@
-use axum::{extract::State, Extension, Json};
+use std::net::SocketAddr;
+
+use axum::{
+    extract::{ConnectInfo, Path, State},
+    http::HeaderMap,
+    Extension, Json,
+};
 use chrono::Utc;
 use rand::Rng;
 use regex::Regex;
+use sqlx::{PgConnection, PgExecutor};
 use todo_shared::api::{
-    AuthResponse, LoginRequest, RefreshRequest, RegisterRequest, RegisterResponse,
-    ResendVerificationRequest, VerifyEmailRequest,
+    AuthResponse, ChangePasswordRequest, ForgotPasswordRequest, LoginRequest, RefreshRequest,
+    RegisterRequest, RegisterResponse, ResendVerificationRequest, ResetPasswordRequest,
+    VerifyEmailRequest,
 };
-use todo_shared::User;
+use todo_shared::{Session, User};
 use uuid::Uuid;
 
 use crate::auth::{
-    create_access_token, create_refresh_token, hash_password, verify_password, AuthUser,
+    create_access_token, create_refresh_token, hash_password, verify_password,
+    verify_refresh_token, AuthUser,
 };
+use crate::config::Config;
 use crate::error::AppError;
+use crate::events::publish_revoked_sessions;
+use crate::mail::{send_in_background, templates};
 use crate::routes::AppState;
 
+/// How long emailed verification and reset codes work
+const CODE_VALID_MINUTES: i64 = 15;
+
+/// Guesses a password reset code allows before it stops working
+const MAX_RESET_CODE_ATTEMPTS: i32 = 5;
+
+/// Reset codes a user can be sent per hour. Every code brings fresh guesses,
+/// so this is what really bounds guessing.
+const MAX_RESET_CODES_PER_HOUR: i64 = 3;
+
+/// Longest User-Agent kept for a session
+const MAX_USER_AGENT_LEN: usize = 512;
+
+/// Where a sign-in comes from: its User-Agent and IP address. Behind a reverse
+/// proxy the peer is the proxy, so the first `X-Forwarded-For` hop wins when
+/// present. Both are shown back to the user and never trusted for anything else.
+struct ClientDetails {
+    user_agent: Option<String>,
+    ip_address: Option<String>,
+}
+
+impl ClientDetails {
+    fn from_request(headers: &HeaderMap, peer: SocketAddr) -> Self {
+        let user_agent = headers
+            .get(axum::http::header::USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+            .filter(|ua| !ua.is_empty())
+            .map(|ua| ua.chars().take(MAX_USER_AGENT_LEN).collect());
+
+        let forwarded = headers
+            .get("X-Forwarded-For")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.split(',').next())
+            .map(str::trim)
+            .filter(|ip| !ip.is_empty());
+
+        Self {
+            user_agent,
+            ip_address: Some(forwarded.map_or_else(|| peer.ip().to_string(), str::to_string)),
+        }
+    }
+}
+
+/// Open a session for a user who just proved who they are and hand out its tokens
+async fn start_session<'e>(
+    executor: impl PgExecutor<'e>,
+    config: &Config,
+    user_id: Uuid,
+    email: &str,
+    client: &ClientDetails,
+) -> Result<AuthResponse, AppError> {
+    let session_id = Uuid::new_v4();
+
+    let access_token =
+        create_access_token(user_id, email, session_id, &config.jwt_secret, config.jwt_expires_in)?;
+
+    let refresh_token = create_refresh_token(
+        user_id,
+        email,
+        session_id,
+        &config.jwt_secret,
+        config.refresh_token_expires_in,
+    )?;
+
+    // Store refresh token hash
+    let token_hash = hash_password(&refresh_token)?;
+    let expires_at = Utc::now() + chrono::Duration::seconds(config.refresh_token_expires_in);
+
+    sqlx::query(
+        r#"
+        INSERT INTO refresh_tokens (id, user_id, token_hash, expires_at, user_agent, ip_address)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+    )
+    .bind(session_id)
+    .bind(user_id)
+    .bind(&token_hash)
+    .bind(expires_at)
+    .bind(&client.user_agent)
+    .bind(&client.ip_address)
+    .execute(executor)
+    .await?;
+
+    Ok(AuthResponse {
+        access_token,
+        refresh_token,
+        user_id,
+    })
+}
+
+/// Sign out the user's sessions, or only `only`, and close their event streams.
+/// Returns the sessions signed out.
+async fn revoke_sessions(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    only: Option<Uuid>,
+) -> Result<Vec<Uuid>, AppError> {
+    let revoked: Vec<(Uuid,)> = sqlx::query_as(
+        r#"
+        UPDATE refresh_tokens SET revoked_at = NOW()
+        WHERE user_id = $1 AND revoked_at IS NULL AND ($2::uuid IS NULL OR id = $2)
+        RETURNING id
+        "#,
+    )
+    .bind(user_id)
+    .bind(only)
+    .fetch_all(&mut *conn)
+    .await?;
+    let revoked: Vec<Uuid> = revoked.into_iter().map(|(id,)| id).collect();
+
+    publish_revoked_sessions(&mut *conn, &revoked).await?;
+
+    Ok(revoked)
+}
+
+/// Compare secrets without bailing out at the first differing byte
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
 /// Generate a random 6-digit verification code
 fn generate_verification_code() -> String {
     let mut rng = rand::thread_rng();
@@ -40,6 +173,16 @@ fn validate_username(username: &str) -> Result<(), AppError> {
     Ok(())
 }
 
+fn validate_password(password: &str) -> Result<(), AppError> {
+    if password.len() < 8 {
+        return Err(AppError::Validation(
+            "Password must be at least 8 characters".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
 pub async fn register(
     State(state): State<AppState>,
     Json(req): Json<RegisterRequest>,
@@ -54,12 +197,7 @@ pub async fn register(
     }
 
     validate_username(&req.username)?;
-
-    if req.password.len() < 8 {
-        return Err(AppError::Validation(
-            "Password must be at least 8 characters".to_string(),
-        ));
-    }
+    validate_password(&req.password)?;
 
     // Check if email already exists
     let existing_email: Option<(Uuid,)> =
@@ -103,7 +241,7 @@ pub async fn register(
 
     // Generate verification code
     let code = generate_verification_code();
-    let expires_at = Utc::now() + chrono::Duration::minutes(15);
+    let expires_at = Utc::now() + chrono::Duration::minutes(CODE_VALID_MINUTES);
 
     sqlx::query(
         r#"
@@ -163,13 +301,9 @@ pub async fn register(
     .execute(&state.db)
     .await?;
 
-    // Log verification code to console (development mode)
-    tracing::info!(
-        "VERIFICATION CODE for {} ({}): {}",
-        req.email,
-        req.username,
-        code
-    );
+    // If it doesn't arrive, the code can be sent again from the verification screen
+    let email = templates::verification_code(&req.email, &req.username, &code, CODE_VALID_MINUTES);
+    send_in_background(&state.mailer, email);
 
     Ok(Json(RegisterResponse {
         user_id,
@@ -181,6 +315,8 @@ pub async fn register(
 
 pub async fn verify_email(
     State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(req): Json<VerifyEmailRequest>,
 ) -> Result<Json<AuthResponse>, AppError> {
     // Find user by email
@@ -224,42 +360,10 @@ pub async fn verify_email(
         .execute(&state.db)
         .await?;
 
-    // Generate tokens
-    let access_token = create_access_token(
-        user_id,
-        &email,
-        &state.config.jwt_secret,
-        state.config.jwt_expires_in,
-    )?;
+    let client = ClientDetails::from_request(&headers, peer);
+    let auth = start_session(&state.db, &state.config, user_id, &email, &client).await?;
 
-    let refresh_token = create_refresh_token(
-        user_id,
-        &email,
-        &state.config.jwt_secret,
-        state.config.refresh_token_expires_in,
-    )?;
-
-    // Store refresh token hash
-    let token_hash = hash_password(&refresh_token)?;
-    let expires_at = Utc::now() + chrono::Duration::seconds(state.config.refresh_token_expires_in);
-
-    sqlx::query(
-        r#"
-        INSERT INTO refresh_tokens (user_id, token_hash, expires_at)
-        VALUES ($1, $2, $3)
-        "#,
-    )
-    .bind(user_id)
-    .bind(&token_hash)
-    .bind(expires_at)
-    .execute(&state.db)
-    .await?;
-
-    Ok(Json(AuthResponse {
-        access_token,
-        refresh_token,
-        user_id,
-    }))
+    Ok(Json(auth))
 }
 
 pub async fn resend_verification(
@@ -287,7 +391,7 @@ pub async fn resend_verification(
 
     // Generate new code
     let code = generate_verification_code();
-    let expires_at = Utc::now() + chrono::Duration::minutes(15);
+    let expires_at = Utc::now() + chrono::Duration::minutes(CODE_VALID_MINUTES);
 
     sqlx::query(
         r#"
@@ -301,21 +405,169 @@ pub async fn resend_verification(
     .execute(&state.db)
     .await?;
 
-    // Log verification code to console (development mode)
-    tracing::info!(
-        "VERIFICATION CODE for {} ({}): {}",
-        req.email,
-        username,
-        code
-    );
+    let email = templates::verification_code(&req.email, &username, &code, CODE_VALID_MINUTES);
+    send_in_background(&state.mailer, email);
 
     Ok(Json(serde_json::json!({
         "message": "Verification code sent"
     })))
 }
 
+/// POST /api/v1/auth/forgot-password
+pub async fn forgot_password(
+    State(state): State<AppState>,
+    Json(req): Json<ForgotPasswordRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    // Answer the same whether or not the email is registered, so the
+    // endpoint can't be used to find out who has an account
+    let response = Json(serde_json::json!({
+        "message": "If that email is registered, a reset code has been sent"
+    }));
+
+    let user_row: Option<(Uuid, String)> =
+        sqlx::query_as("SELECT id, username FROM users WHERE email = $1")
+            .bind(&req.email)
+            .fetch_optional(&state.db)
+            .await?;
+
+    let Some((user_id, username)) = user_row else {
+        return Ok(response);
+    };
+
+    let mut tx = state.db.begin().await?;
+
+    // Lock the user so parallel requests count each other's codes
+    sqlx::query("SELECT id FROM users WHERE id = $1 FOR UPDATE")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let (recent,): (i64,) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*) FROM password_reset_codes
+        WHERE user_id = $1 AND created_at > NOW() - INTERVAL '1 hour'
+        "#,
+    )
+    .bind(user_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    // Same answer as ever; the code already sent keeps working
+    if recent >= MAX_RESET_CODES_PER_HOUR {
+        tracing::warn!("Too many password reset requests for user {}", user_id);
+        return Ok(response);
+    }
+
+    // Invalidate old codes
+    sqlx::query("UPDATE password_reset_codes SET used_at = NOW() WHERE user_id = $1 AND used_at IS NULL")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let code = generate_verification_code();
+    let expires_at = Utc::now() + chrono::Duration::minutes(CODE_VALID_MINUTES);
+
+    sqlx::query(
+        r#"
+        INSERT INTO password_reset_codes (user_id, code, expires_at)
+        VALUES ($1, $2, $3)
+        "#,
+    )
+    .bind(user_id)
+    .bind(&code)
+    .bind(expires_at)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    // In the background: waiting on the mail server would tell the caller the
+    // account exists
+    let email = templates::password_reset_code(&req.email, &username, &code, CODE_VALID_MINUTES);
+    send_in_background(&state.mailer, email);
+
+    Ok(response)
+}
+
+/// POST /api/v1/auth/reset-password
+pub async fn reset_password(
+    State(state): State<AppState>,
+    Json(req): Json<ResetPasswordRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    validate_password(&req.new_password)?;
+
+    let invalid_code = || AppError::Validation("Invalid or expired reset code".to_string());
+
+    let user_row: Option<(Uuid,)> = sqlx::query_as("SELECT id FROM users WHERE email = $1")
+        .bind(&req.email)
+        .fetch_optional(&state.db)
+        .await?;
+
+    let (user_id,) = user_row.ok_or_else(invalid_code)?;
+
+    // Only the latest code is live; asking for a new one retires the rest.
+    // The guess is counted before comparing, so parallel guesses can't all
+    // slip in under the limit.
+    let code_row: Option<(Uuid, String)> = sqlx::query_as(
+        r#"
+        UPDATE password_reset_codes SET attempts = attempts + 1
+        WHERE id = (
+            SELECT id FROM password_reset_codes
+            WHERE user_id = $1 AND used_at IS NULL
+            ORDER BY created_at DESC
+            LIMIT 1
+        )
+          AND attempts < $2 AND used_at IS NULL AND expires_at > NOW()
+        RETURNING id, code
+        "#,
+    )
+    .bind(user_id)
+    .bind(MAX_RESET_CODE_ATTEMPTS)
+    .fetch_optional(&state.db)
+    .await?;
+
+    let (code_id, code) = code_row.ok_or_else(invalid_code)?;
+
+    if !constant_time_eq(&code, &req.code) {
+        return Err(invalid_code());
+    }
+
+    let password_hash = hash_password(&req.new_password)?;
+
+    let mut tx = state.db.begin().await?;
+
+    // Two resets racing with the right code: only one gets to use it
+    let consumed = sqlx::query(
+        "UPDATE password_reset_codes SET used_at = NOW() WHERE id = $1 AND used_at IS NULL",
+    )
+    .bind(code_id)
+    .execute(&mut *tx)
+    .await?;
+
+    if consumed.rows_affected() != 1 {
+        return Err(invalid_code());
+    }
+
+    sqlx::query("UPDATE users SET password_hash = $1, updated_at = NOW() WHERE id = $2")
+        .bind(&password_hash)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+    // Sign out everywhere, in case the account was taken over
+    revoke_sessions(&mut tx, user_id, None).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(serde_json::json!({
+        "message": "Password reset. Please log in with your new password."
+    })))
+}
+
 pub async fn login(
     State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(req): Json<LoginRequest>,
 ) -> Result<Json<AuthResponse>, AppError> {
     // Find user by email
@@ -344,80 +596,52 @@ pub async fn login(
         .execute(&state.db)
         .await?;
 
-    // Generate tokens
-    let access_token = create_access_token(
-        user_id,
-        &email,
-        &state.config.jwt_secret,
-        state.config.jwt_expires_in,
-    )?;
-
-    let refresh_token = create_refresh_token(
-        user_id,
-        &email,
-        &state.config.jwt_secret,
-        state.config.refresh_token_expires_in,
-    )?;
-
-    // Store refresh token
-    let token_hash = hash_password(&refresh_token)?;
-    let expires_at = Utc::now() + chrono::Duration::seconds(state.config.refresh_token_expires_in);
+    let client = ClientDetails::from_request(&headers, peer);
+    let auth = start_session(&state.db, &state.config, user_id, &email, &client).await?;
 
-    sqlx::query(
-        r#"
-        INSERT INTO refresh_tokens (user_id, token_hash, expires_at)
-        VALUES ($1, $2, $3)
-        "#,
-    )
-    .bind(user_id)
-    .bind(&token_hash)
-    .bind(expires_at)
-    .execute(&state.db)
-    .await?;
-
-    Ok(Json(AuthResponse {
-        access_token,
-        refresh_token,
-        user_id,
-    }))
+    Ok(Json(auth))
 }
 
+/// POST /api/v1/auth/refresh
+///
+/// Rotates the session's refresh token in place, so the session keeps its id
+/// and an older copy of the token stops working.
 pub async fn refresh(
     State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(req): Json<RefreshRequest>,
 ) -> Result<Json<AuthResponse>, AppError> {
     // Verify the refresh token JWT
-    let claims = crate::auth::verify_access_token(&req.refresh_token, &state.config.jwt_secret)?;
+    let claims = verify_refresh_token(&req.refresh_token, &state.config.jwt_secret)?;
 
-    // Check if token exists and not revoked
-    let row: Option<(Uuid, String)> = sqlx::query_as(
+    let row: Option<(Uuid, String, String)> = sqlx::query_as(
         r#"
-        SELECT rt.id, u.email
+        SELECT rt.id, rt.token_hash, u.email
         FROM refresh_tokens rt
         JOIN users u ON u.id = rt.user_id
-        WHERE rt.user_id = $1
+        WHERE rt.id = $1
+          AND rt.user_id = $2
           AND rt.revoked_at IS NULL
           AND rt.expires_at > NOW()
-        ORDER BY rt.created_at DESC
-        LIMIT 1
         "#,
     )
+    .bind(claims.sid)
     .bind(claims.sub)
     .fetch_optional(&state.db)
     .await?;
 
-    let (token_id, email) = row.ok_or(AppError::Unauthorized)?;
+    let (session_id, old_hash, email) = row.ok_or(AppError::Unauthorized)?;
 
-    // Revoke old refresh token
-    sqlx::query("UPDATE refresh_tokens SET revoked_at = NOW() WHERE id = $1")
-        .bind(token_id)
-        .execute(&state.db)
-        .await?;
+    if !verify_password(&req.refresh_token, &old_hash)? {
+        return Err(AppError::Unauthorized);
+    }
 
     // Generate new tokens
     let access_token = create_access_token(
         claims.sub,
         &email,
+        session_id,
         &state.config.jwt_secret,
         state.config.jwt_expires_in,
     )?;
@@ -425,26 +649,38 @@ pub async fn refresh(
     let refresh_token = create_refresh_token(
         claims.sub,
         &email,
+        session_id,
         &state.config.jwt_secret,
         state.config.refresh_token_expires_in,
     )?;
 
-    // Store new refresh token
     let token_hash = hash_password(&refresh_token)?;
     let expires_at = Utc::now() + chrono::Duration::seconds(state.config.refresh_token_expires_in);
+    let client = ClientDetails::from_request(&headers, peer);
 
-    sqlx::query(
+    // Matching the old hash makes concurrent refreshes of one token lose the race
+    // instead of both getting tokens
+    let rotated = sqlx::query(
         r#"
-        INSERT INTO refresh_tokens (user_id, token_hash, expires_at)
-        VALUES ($1, $2, $3)
+        UPDATE refresh_tokens
+        SET token_hash = $1, expires_at = $2, last_used_at = NOW(),
+            user_agent = COALESCE($3, user_agent), ip_address = COALESCE($4, ip_address)
+        WHERE id = $5 AND token_hash = $6 AND revoked_at IS NULL
         "#,
     )
-    .bind(claims.sub)
     .bind(&token_hash)
     .bind(expires_at)
+    .bind(&client.user_agent)
+    .bind(&client.ip_address)
+    .bind(session_id)
+    .bind(&old_hash)
     .execute(&state.db)
     .await?;
 
+    if rotated.rows_affected() == 0 {
+        return Err(AppError::Unauthorized);
+    }
+
     Ok(Json(AuthResponse {
         access_token,
         refresh_token,
@@ -452,21 +688,146 @@ pub async fn refresh(
     }))
 }
 
+/// POST /api/v1/auth/logout
+///
+/// Ends the caller's session.
 pub async fn logout(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
 ) -> Result<(), AppError> {
-    // Revoke all refresh tokens for user
-    sqlx::query(
-        "UPDATE refresh_tokens SET revoked_at = NOW() WHERE user_id = $1 AND revoked_at IS NULL",
+    let mut tx = state.db.begin().await?;
+    revoke_sessions(&mut tx, user.id, Some(user.session_id)).await?;
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// GET /api/v1/auth/sessions
+///
+/// Signed-in devices, most recently used first.
+pub async fn list_sessions(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+) -> Result<Json<Vec<Session>>, AppError> {
+    let rows: Vec<(
+        Uuid,
+        Option<String>,
+        Option<String>,
+        chrono::DateTime<Utc>,
+        chrono::DateTime<Utc>,
+        chrono::DateTime<Utc>,
+    )> = sqlx::query_as(
+        r#"
+        SELECT id, user_agent, ip_address, created_at, last_used_at, expires_at
+        FROM refresh_tokens
+        WHERE user_id = $1 AND revoked_at IS NULL AND expires_at > NOW()
+        ORDER BY last_used_at DESC
+        "#,
     )
     .bind(user.id)
-    .execute(&state.db)
+    .fetch_all(&state.db)
     .await?;
 
+    let sessions = rows
+        .into_iter()
+        .map(|(id, user_agent, ip_address, created_at, last_used_at, expires_at)| Session {
+            id,
+            user_agent,
+            ip_address,
+            created_at,
+            last_used_at,
+            expires_at,
+            current: user.session_id == id,
+        })
+        .collect();
+
+    Ok(Json(sessions))
+}
+
+/// DELETE /api/v1/auth/sessions/:session_id
+///
+/// Signs a device out: its tokens stop working and its event streams close.
+pub async fn revoke_session(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(session_id): Path<Uuid>,
+) -> Result<(), AppError> {
+    let mut tx = state.db.begin().await?;
+    let revoked = revoke_sessions(&mut tx, user.id, Some(session_id)).await?;
+    tx.commit().await?;
+
+    if revoked.is_empty() {
+        return Err(AppError::NotFound);
+    }
+
+    Ok(())
+}
+
+/// DELETE /api/v1/auth/sessions
+///
+/// Log out everywhere, this session included.
+pub async fn revoke_all_sessions(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+) -> Result<(), AppError> {
+    let mut tx = state.db.begin().await?;
+    revoke_sessions(&mut tx, user.id, None).await?;
+    tx.commit().await?;
+
     Ok(())
 }
 
+/// POST /api/v1/auth/change-password
+///
+/// Signs out every other session; the caller gets fresh tokens back.
+pub async fn change_password(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(req): Json<ChangePasswordRequest>,
+) -> Result<Json<AuthResponse>, AppError> {
+    let row: Option<(String, String)> =
+        sqlx::query_as("SELECT email, password_hash FROM users WHERE id = $1")
+            .bind(user.id)
+            .fetch_optional(&state.db)
+            .await?;
+
+    let (email, password_hash) = row.ok_or(AppError::NotFound)?;
+
+    // Not Unauthorized: the session is fine, the password isn't
+    if !verify_password(&req.current_password, &password_hash)? {
+        return Err(AppError::Validation("Current password is incorrect".to_string()));
+    }
+
+    validate_password(&req.new_password)?;
+
+    if req.new_password == req.current_password {
+        return Err(AppError::Validation(
+            "New password must differ from the current one".to_string(),
+        ));
+    }
+
+    let new_hash = hash_password(&req.new_password)?;
+
+    let mut tx = state.db.begin().await?;
+
+    sqlx::query("UPDATE users SET password_hash = $1, updated_at = NOW() WHERE id = $2")
+        .bind(&new_hash)
+        .bind(user.id)
+        .execute(&mut *tx)
+        .await?;
+
+    revoke_sessions(&mut tx, user.id, None).await?;
+
+    let client = ClientDetails::from_request(&headers, peer);
+    let auth = start_session(&mut *tx, &state.config, user.id, &email, &client).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(auth))
+}
+
 pub async fn me(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
@@ -0,0 +1,261 @@
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use chrono::Utc;
+use todo_shared::{
+    api::{CreateChecklistItemRequest, ReorderChecklistRequest, UpdateChecklistItemRequest},
+    ChecklistItem, WorkspaceEventKind, WorkspaceRole,
+};
+use uuid::Uuid;
+
+use crate::auth::AuthUser;
+use crate::error::AppError;
+use crate::events::publish;
+use crate::routes::AppState;
+
+/// Helper to check workspace membership and return role
+async fn check_membership(
+    state: &AppState,
+    workspace_id: Uuid,
+    user_id: Uuid,
+) -> Result<WorkspaceRole, AppError> {
+    let role: Option<(WorkspaceRole,)> = sqlx::query_as(
+        r#"SELECT role as "role: WorkspaceRole" FROM workspace_members WHERE workspace_id = $1 AND user_id = $2"#,
+    )
+    .bind(workspace_id)
+    .bind(user_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    role.map(|(r,)| r).ok_or(AppError::NotFound)
+}
+
+/// Helper to verify task belongs to workspace
+async fn verify_task(
+    state: &AppState,
+    task_id: Uuid,
+    workspace_id: Uuid,
+) -> Result<(), AppError> {
+    let exists: Option<(Uuid,)> = sqlx::query_as(
+        "SELECT id FROM tasks WHERE id = $1 AND workspace_id = $2 AND deleted_at IS NULL",
+    )
+    .bind(task_id)
+    .bind(workspace_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    if exists.is_none() {
+        return Err(AppError::NotFound);
+    }
+    Ok(())
+}
+
+type ChecklistItemRow = (
+    Uuid,                  // id
+    Uuid,                  // task_id
+    String,                // content
+    bool,                  // is_checked
+    i32,                   // position
+    chrono::DateTime<Utc>, // created_at
+    chrono::DateTime<Utc>, // updated_at
+);
+
+fn row_to_item(row: ChecklistItemRow) -> ChecklistItem {
+    ChecklistItem {
+        id: row.0,
+        task_id: row.1,
+        content: row.2,
+        is_checked: row.3,
+        position: row.4,
+        created_at: row.5,
+        updated_at: row.6,
+    }
+}
+
+/// Helper to load a task's checklist in order
+async fn load_checklist(state: &AppState, task_id: Uuid) -> Result<Vec<ChecklistItem>, AppError> {
+    let rows: Vec<ChecklistItemRow> = sqlx::query_as(
+        r#"
+        SELECT id, task_id, content, is_checked, position, created_at, updated_at
+        FROM task_checklist_items
+        WHERE task_id = $1
+        ORDER BY position, created_at
+        "#,
+    )
+    .bind(task_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(rows.into_iter().map(row_to_item).collect())
+}
+
+/// GET /api/v1/workspaces/:id/tasks/:task_id/checklist
+pub async fn list_checklist(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, task_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Vec<ChecklistItem>>, AppError> {
+    check_membership(&state, workspace_id, user.id).await?;
+    verify_task(&state, task_id, workspace_id).await?;
+
+    Ok(Json(load_checklist(&state, task_id).await?))
+}
+
+/// POST /api/v1/workspaces/:id/tasks/:task_id/checklist
+pub async fn create_checklist_item(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, task_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<CreateChecklistItemRequest>,
+) -> Result<Json<ChecklistItem>, AppError> {
+    let role = check_membership(&state, workspace_id, user.id).await?;
+
+    if !role.can_edit() {
+        return Err(AppError::Forbidden);
+    }
+
+    verify_task(&state, task_id, workspace_id).await?;
+
+    let content = req.content.trim().to_string();
+    if content.is_empty() {
+        return Err(AppError::Validation("Checklist item text is required".to_string()));
+    }
+
+    // New items go to the end
+    let (max_pos,): (i32,) = sqlx::query_as(
+        "SELECT COALESCE(MAX(position), -1) FROM task_checklist_items WHERE task_id = $1",
+    )
+    .bind(task_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    let row: ChecklistItemRow = sqlx::query_as(
+        r#"
+        INSERT INTO task_checklist_items (id, task_id, content, is_checked, position, created_at, updated_at)
+        VALUES ($1, $2, $3, FALSE, $4, $5, $5)
+        RETURNING id, task_id, content, is_checked, position, created_at, updated_at
+        "#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(task_id)
+    .bind(&content)
+    .bind(max_pos + 1)
+    .bind(Utc::now())
+    .fetch_one(&state.db)
+    .await?;
+
+    // Checklist progress shows on the task card
+    publish(&state.db, workspace_id, user.id, WorkspaceEventKind::TaskChanged { task_id }).await?;
+
+    Ok(Json(row_to_item(row)))
+}
+
+/// PATCH /api/v1/workspaces/:id/tasks/:task_id/checklist/:item_id
+pub async fn update_checklist_item(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, task_id, item_id)): Path<(Uuid, Uuid, Uuid)>,
+    Json(req): Json<UpdateChecklistItemRequest>,
+) -> Result<Json<ChecklistItem>, AppError> {
+    let role = check_membership(&state, workspace_id, user.id).await?;
+
+    if !role.can_edit() {
+        return Err(AppError::Forbidden);
+    }
+
+    verify_task(&state, task_id, workspace_id).await?;
+
+    let content = req.content.map(|c| c.trim().to_string());
+    if content.as_deref() == Some("") {
+        return Err(AppError::Validation("Checklist item text is required".to_string()));
+    }
+
+    let row: ChecklistItemRow = sqlx::query_as(
+        r#"
+        UPDATE task_checklist_items
+        SET content = COALESCE($1, content),
+            is_checked = COALESCE($2, is_checked),
+            updated_at = $3
+        WHERE id = $4 AND task_id = $5
+        RETURNING id, task_id, content, is_checked, position, created_at, updated_at
+        "#,
+    )
+    .bind(&content)
+    .bind(req.is_checked)
+    .bind(Utc::now())
+    .bind(item_id)
+    .bind(task_id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    publish(&state.db, workspace_id, user.id, WorkspaceEventKind::TaskChanged { task_id }).await?;
+
+    Ok(Json(row_to_item(row)))
+}
+
+/// DELETE /api/v1/workspaces/:id/tasks/:task_id/checklist/:item_id
+pub async fn delete_checklist_item(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, task_id, item_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<(), AppError> {
+    let role = check_membership(&state, workspace_id, user.id).await?;
+
+    if !role.can_edit() {
+        return Err(AppError::Forbidden);
+    }
+
+    verify_task(&state, task_id, workspace_id).await?;
+
+    let result = sqlx::query("DELETE FROM task_checklist_items WHERE id = $1 AND task_id = $2")
+        .bind(item_id)
+        .bind(task_id)
+        .execute(&state.db)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+
+    publish(&state.db, workspace_id, user.id, WorkspaceEventKind::TaskChanged { task_id }).await?;
+
+    Ok(())
+}
+
+/// POST /api/v1/workspaces/:id/tasks/:task_id/checklist/reorder
+pub async fn reorder_checklist(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, task_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<ReorderChecklistRequest>,
+) -> Result<Json<Vec<ChecklistItem>>, AppError> {
+    let role = check_membership(&state, workspace_id, user.id).await?;
+
+    if !role.can_edit() {
+        return Err(AppError::Forbidden);
+    }
+
+    verify_task(&state, task_id, workspace_id).await?;
+
+    // Update positions in a transaction
+    let mut tx = state.db.begin().await?;
+
+    for (position, item_id) in req.item_ids.iter().enumerate() {
+        sqlx::query(
+            "UPDATE task_checklist_items SET position = $1 WHERE id = $2 AND task_id = $3",
+        )
+        .bind(position as i32)
+        .bind(item_id)
+        .bind(task_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    publish(&mut *tx, workspace_id, user.id, WorkspaceEventKind::TaskChanged { task_id }).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(load_checklist(&state, task_id).await?))
+}
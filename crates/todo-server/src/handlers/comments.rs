@@ -1,18 +1,28 @@
+use std::collections::HashMap;
+
 use axum::{
     extract::{Path, State},
     Extension, Json,
 };
 use chrono::Utc;
+use sqlx::{PgConnection, PgExecutor};
 use todo_shared::{
-    api::{CreateCommentRequest, UpdateCommentRequest},
-    CommentWithAuthor, WorkspaceRole,
+    api::{CreateCommentRequest, ToggleReactionRequest, UpdateCommentRequest},
+    mention_spans, CommentReaction, CommentRevision, CommentWithAuthor, WorkspaceEventKind,
+    WorkspaceRole,
 };
 use uuid::Uuid;
 
 use crate::auth::AuthUser;
 use crate::error::AppError;
+use crate::events::publish;
+use crate::handlers::notifications::{notify_comment, notify_mentioned};
+use crate::mentions::{comment_mentions, sync_mentions};
 use crate::routes::AppState;
 
+/// Longest reaction, in characters
+const MAX_REACTION_CHARS: usize = 16;
+
 /// Helper to check workspace membership and return role
 async fn check_membership(
     state: &AppState,
@@ -37,7 +47,7 @@ async fn verify_task(
     workspace_id: Uuid,
 ) -> Result<(), AppError> {
     let exists: Option<(Uuid,)> = sqlx::query_as(
-        "SELECT id FROM tasks WHERE id = $1 AND workspace_id = $2",
+        "SELECT id FROM tasks WHERE id = $1 AND workspace_id = $2 AND deleted_at IS NULL",
     )
     .bind(task_id)
     .bind(workspace_id)
@@ -55,7 +65,9 @@ type CommentWithAuthorRow = (
     Uuid,                  // task_id
     Uuid,                  // user_id
     String,                // author_username
+    Option<Uuid>,          // parent_comment_id
     String,                // content
+    bool,                  // edited
     chrono::DateTime<Utc>, // created_at
     chrono::DateTime<Utc>, // updated_at
 );
@@ -66,10 +78,98 @@ fn row_to_comment(row: CommentWithAuthorRow) -> CommentWithAuthor {
         task_id: row.1,
         user_id: row.2,
         author_username: row.3,
-        content: row.4,
-        created_at: row.5,
-        updated_at: row.6,
+        parent_comment_id: row.4,
+        content: row.5,
+        mentions: Vec::new(),
+        reactions: Vec::new(),
+        replies: Vec::new(),
+        edited: row.6,
+        created_at: row.7,
+        updated_at: row.8,
+    }
+}
+
+/// Fill in the mention spans of loaded comments
+async fn attach_mentions<'e>(
+    executor: impl PgExecutor<'e>,
+    comments: &mut [CommentWithAuthor],
+) -> Result<(), AppError> {
+    let ids: Vec<Uuid> = comments.iter().map(|c| c.id).collect();
+    let mentioned = comment_mentions(executor, &ids).await?;
+
+    for comment in comments.iter_mut() {
+        if let Some(users) = mentioned.get(&comment.id) {
+            comment.mentions = mention_spans(&comment.content, users);
+        }
+    }
+
+    Ok(())
+}
+
+/// Reaction counts per comment, as `viewer_id` sees them
+async fn load_reactions<'e>(
+    executor: impl PgExecutor<'e>,
+    comment_ids: &[Uuid],
+    viewer_id: Uuid,
+) -> Result<HashMap<Uuid, Vec<CommentReaction>>, AppError> {
+    let rows: Vec<(Uuid, String, i64, bool)> = sqlx::query_as(
+        r#"
+        SELECT comment_id, emoji, COUNT(*), BOOL_OR(user_id = $2)
+        FROM comment_reactions
+        WHERE comment_id = ANY($1)
+        GROUP BY comment_id, emoji
+        ORDER BY MIN(created_at)
+        "#,
+    )
+    .bind(comment_ids)
+    .bind(viewer_id)
+    .fetch_all(executor)
+    .await?;
+
+    let mut by_comment: HashMap<Uuid, Vec<CommentReaction>> = HashMap::new();
+    for (comment_id, emoji, count, reacted) in rows {
+        by_comment.entry(comment_id).or_default().push(CommentReaction {
+            emoji,
+            count,
+            reacted,
+        });
+    }
+
+    Ok(by_comment)
+}
+
+/// Fill in the reaction counts of loaded comments
+async fn attach_reactions<'e>(
+    executor: impl PgExecutor<'e>,
+    comments: &mut [CommentWithAuthor],
+    viewer_id: Uuid,
+) -> Result<(), AppError> {
+    let ids: Vec<Uuid> = comments.iter().map(|c| c.id).collect();
+    let mut reactions = load_reactions(executor, &ids, viewer_id).await?;
+
+    for comment in comments.iter_mut() {
+        comment.reactions = reactions.remove(&comment.id).unwrap_or_default();
     }
+
+    Ok(())
+}
+
+/// Nest replies under the comments they answer; `comments` must be oldest first
+fn into_threads(comments: Vec<CommentWithAuthor>) -> Vec<CommentWithAuthor> {
+    let mut replies: HashMap<Uuid, Vec<CommentWithAuthor>> = HashMap::new();
+    let mut threads = Vec::new();
+
+    for comment in comments {
+        match comment.parent_comment_id {
+            Some(parent_id) => replies.entry(parent_id).or_default().push(comment),
+            None => threads.push(comment),
+        }
+    }
+    for thread in threads.iter_mut() {
+        thread.replies = replies.remove(&thread.id).unwrap_or_default();
+    }
+
+    threads
 }
 
 /// GET /api/v1/workspaces/:id/tasks/:task_id/comments
@@ -81,12 +181,16 @@ pub async fn list_comments(
     check_membership(&state, workspace_id, user.id).await?;
     verify_task(&state, task_id, workspace_id).await?;
 
+    // Replies to a trashed comment go with it
     let rows: Vec<CommentWithAuthorRow> = sqlx::query_as(
         r#"
-        SELECT c.id, c.task_id, c.user_id, u.username, c.content, c.created_at, c.updated_at
+        SELECT c.id, c.task_id, c.user_id, u.username, c.parent_comment_id, c.content,
+               EXISTS (SELECT 1 FROM comment_revisions r WHERE r.comment_id = c.id),
+               c.created_at, c.updated_at
         FROM task_comments c
         JOIN users u ON u.id = c.user_id
-        WHERE c.task_id = $1
+        LEFT JOIN task_comments p ON p.id = c.parent_comment_id
+        WHERE c.task_id = $1 AND c.deleted_at IS NULL AND p.deleted_at IS NULL
         ORDER BY c.created_at ASC
         "#,
     )
@@ -94,8 +198,51 @@ pub async fn list_comments(
     .fetch_all(&state.db)
     .await?;
 
-    let comments = rows.into_iter().map(row_to_comment).collect();
-    Ok(Json(comments))
+    let mut comments: Vec<CommentWithAuthor> = rows.into_iter().map(row_to_comment).collect();
+    attach_mentions(&state.db, &mut comments).await?;
+    attach_reactions(&state.db, &mut comments, user.id).await?;
+
+    Ok(Json(into_threads(comments)))
+}
+
+/// Add a comment as part of another change, e.g. a workflow move that asks for one
+pub async fn insert_comment(
+    conn: &mut PgConnection,
+    workspace_id: Uuid,
+    task_id: Uuid,
+    user_id: Uuid,
+    content: &str,
+) -> Result<(), AppError> {
+    let id = Uuid::new_v4();
+    let now = Utc::now();
+
+    sqlx::query(
+        r#"
+        INSERT INTO task_comments (id, task_id, user_id, content, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+    )
+    .bind(id)
+    .bind(task_id)
+    .bind(user_id)
+    .bind(content)
+    .bind(now)
+    .bind(now)
+    .execute(&mut *conn)
+    .await?;
+
+    let mentioned = sync_mentions(conn, workspace_id, task_id, Some(id), user_id, content).await?;
+    notify_comment(conn, workspace_id, task_id, user_id, content, &mentioned, None).await?;
+
+    publish(
+        conn,
+        workspace_id,
+        user_id,
+        WorkspaceEventKind::CommentsChanged { task_id },
+    )
+    .await?;
+
+    Ok(())
 }
 
 /// POST /api/v1/workspaces/:id/tasks/:task_id/comments
@@ -122,33 +269,88 @@ pub async fn create_comment(
 
     let (username,) = username_row.ok_or(AppError::NotFound)?;
 
+    // Threads are one level deep, so a reply to a reply joins its thread
+    let parent_comment_id = match req.parent_comment_id {
+        Some(parent_id) => {
+            let parent: Option<(Uuid, Option<Uuid>)> = sqlx::query_as(
+                r#"
+                SELECT id, parent_comment_id FROM task_comments
+                WHERE id = $1 AND task_id = $2 AND deleted_at IS NULL
+                "#,
+            )
+            .bind(parent_id)
+            .bind(task_id)
+            .fetch_optional(&state.db)
+            .await?;
+
+            let (parent_id, grandparent_id) = parent.ok_or_else(|| {
+                AppError::Validation("The comment you're replying to no longer exists".to_string())
+            })?;
+            Some(grandparent_id.unwrap_or(parent_id))
+        }
+        None => None,
+    };
+
     let id = Uuid::new_v4();
     let now = Utc::now();
 
+    let mut tx = state.db.begin().await?;
+
     sqlx::query(
         r#"
-        INSERT INTO task_comments (id, task_id, user_id, content, created_at, updated_at)
-        VALUES ($1, $2, $3, $4, $5, $6)
+        INSERT INTO task_comments (id, task_id, user_id, parent_comment_id, content, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
         "#,
     )
     .bind(id)
     .bind(task_id)
     .bind(user.id)
+    .bind(parent_comment_id)
     .bind(&req.content)
     .bind(now)
     .bind(now)
-    .execute(&state.db)
+    .execute(&mut *tx)
+    .await?;
+
+    let mentioned = sync_mentions(&mut tx, workspace_id, task_id, Some(id), user.id, &req.content).await?;
+    notify_comment(
+        &mut tx,
+        workspace_id,
+        task_id,
+        user.id,
+        &req.content,
+        &mentioned,
+        parent_comment_id,
+    )
+    .await?;
+
+    publish(
+        &mut *tx,
+        workspace_id,
+        user.id,
+        WorkspaceEventKind::CommentsChanged { task_id },
+    )
     .await?;
 
-    Ok(Json(CommentWithAuthor {
+    let mut comment = CommentWithAuthor {
         id,
         task_id,
         user_id: user.id,
         author_username: username,
+        parent_comment_id,
         content: req.content,
+        mentions: Vec::new(),
+        reactions: Vec::new(),
+        replies: Vec::new(),
+        edited: false,
         created_at: now,
         updated_at: now,
-    }))
+    };
+    attach_mentions(&mut *tx, std::slice::from_mut(&mut comment)).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(comment))
 }
 
 /// PATCH /api/v1/workspaces/:id/tasks/:task_id/comments/:comment_id
@@ -158,46 +360,90 @@ pub async fn update_comment(
     Path((workspace_id, task_id, comment_id)): Path<(Uuid, Uuid, Uuid)>,
     Json(req): Json<UpdateCommentRequest>,
 ) -> Result<Json<CommentWithAuthor>, AppError> {
-    check_membership(&state, workspace_id, user.id).await?;
+    let role = check_membership(&state, workspace_id, user.id).await?;
     verify_task(&state, task_id, workspace_id).await?;
 
     if req.content.trim().is_empty() {
         return Err(AppError::Validation("Comment content is required".to_string()));
     }
 
-    // Verify comment exists and belongs to user (author only can edit)
-    let existing: Option<(Uuid,)> = sqlx::query_as(
-        "SELECT id FROM task_comments WHERE id = $1 AND task_id = $2 AND user_id = $3",
+    let mut tx = state.db.begin().await?;
+
+    // Locked, so concurrent edits each keep the text the other replaced
+    let existing: Option<(Uuid, String, chrono::DateTime<Utc>)> = sqlx::query_as(
+        r#"
+        SELECT user_id, content, updated_at FROM task_comments
+        WHERE id = $1 AND task_id = $2 AND deleted_at IS NULL
+        FOR UPDATE
+        "#,
     )
     .bind(comment_id)
     .bind(task_id)
-    .bind(user.id)
-    .fetch_optional(&state.db)
+    .fetch_optional(&mut *tx)
     .await?;
 
-    if existing.is_none() {
+    let Some((comment_user_id, old_content, written_at)) = existing else {
+        return Err(AppError::NotFound);
+    };
+
+    // Author or admin can edit; admins moderate
+    if comment_user_id != user.id && !role.can_admin() {
         return Err(AppError::Forbidden);
     }
 
     let now = Utc::now();
 
-    sqlx::query(
-        r#"
-        UPDATE task_comments
-        SET content = $1, updated_at = $2
-        WHERE id = $3
-        "#,
+    // Saving the same text again isn't an edit
+    if old_content != req.content {
+        sqlx::query(
+            r#"
+            INSERT INTO comment_revisions (id, comment_id, content, written_at, edited_by, edited_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(comment_id)
+        .bind(&old_content)
+        .bind(written_at)
+        .bind(user.id)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE task_comments
+            SET content = $1, updated_at = $2
+            WHERE id = $3
+            "#,
+        )
+        .bind(&req.content)
+        .bind(now)
+        .bind(comment_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    // Only people the edit adds hear about it
+    let mentioned = sync_mentions(&mut tx, workspace_id, task_id, Some(comment_id), user.id, &req.content).await?;
+    notify_mentioned(&mut tx, workspace_id, task_id, user.id, &mentioned, &req.content).await?;
+
+    publish(
+        &mut *tx,
+        workspace_id,
+        user.id,
+        WorkspaceEventKind::CommentsChanged { task_id },
     )
-    .bind(&req.content)
-    .bind(now)
-    .bind(comment_id)
-    .execute(&state.db)
     .await?;
 
+    tx.commit().await?;
+
     // Fetch the updated comment with author
     let row: CommentWithAuthorRow = sqlx::query_as(
         r#"
-        SELECT c.id, c.task_id, c.user_id, u.username, c.content, c.created_at, c.updated_at
+        SELECT c.id, c.task_id, c.user_id, u.username, c.parent_comment_id, c.content,
+               EXISTS (SELECT 1 FROM comment_revisions r WHERE r.comment_id = c.id),
+               c.created_at, c.updated_at
         FROM task_comments c
         JOIN users u ON u.id = c.user_id
         WHERE c.id = $1
@@ -207,7 +453,11 @@ pub async fn update_comment(
     .fetch_one(&state.db)
     .await?;
 
-    Ok(Json(row_to_comment(row)))
+    let mut comment = row_to_comment(row);
+    attach_mentions(&state.db, std::slice::from_mut(&mut comment)).await?;
+    attach_reactions(&state.db, std::slice::from_mut(&mut comment), user.id).await?;
+
+    Ok(Json(comment))
 }
 
 /// DELETE /api/v1/workspaces/:id/tasks/:task_id/comments/:comment_id
@@ -221,7 +471,7 @@ pub async fn delete_comment(
 
     // Get comment to check ownership
     let comment: Option<(Uuid,)> = sqlx::query_as(
-        "SELECT user_id FROM task_comments WHERE id = $1 AND task_id = $2",
+        "SELECT user_id FROM task_comments WHERE id = $1 AND task_id = $2 AND deleted_at IS NULL",
     )
     .bind(comment_id)
     .bind(task_id)
@@ -237,10 +487,146 @@ pub async fn delete_comment(
         return Err(AppError::Forbidden);
     }
 
-    sqlx::query("DELETE FROM task_comments WHERE id = $1")
+    sqlx::query("UPDATE task_comments SET deleted_at = $1, deleted_by = $2 WHERE id = $3")
+        .bind(Utc::now())
+        .bind(user.id)
         .bind(comment_id)
         .execute(&state.db)
         .await?;
 
+    publish(
+        &state.db,
+        workspace_id,
+        user.id,
+        WorkspaceEventKind::CommentsChanged { task_id },
+    )
+    .await?;
+
     Ok(())
 }
+
+/// GET /api/v1/workspaces/:id/tasks/:task_id/comments/:comment_id/revisions
+pub async fn list_comment_revisions(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, task_id, comment_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<Json<Vec<CommentRevision>>, AppError> {
+    check_membership(&state, workspace_id, user.id).await?;
+    verify_task(&state, task_id, workspace_id).await?;
+
+    let exists: Option<(Uuid,)> = sqlx::query_as(
+        "SELECT id FROM task_comments WHERE id = $1 AND task_id = $2 AND deleted_at IS NULL",
+    )
+    .bind(comment_id)
+    .bind(task_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    if exists.is_none() {
+        return Err(AppError::NotFound);
+    }
+
+    // Newest first, the way the history reads back from the current text
+    let rows: Vec<(Uuid, String, chrono::DateTime<Utc>, Option<Uuid>, Option<String>, chrono::DateTime<Utc>)> =
+        sqlx::query_as(
+            r#"
+            SELECT r.id, r.content, r.written_at, r.edited_by, u.username, r.edited_at
+            FROM comment_revisions r
+            LEFT JOIN users u ON u.id = r.edited_by
+            WHERE r.comment_id = $1
+            ORDER BY r.edited_at DESC
+            "#,
+        )
+        .bind(comment_id)
+        .fetch_all(&state.db)
+        .await?;
+
+    let revisions = rows
+        .into_iter()
+        .map(|(id, content, written_at, edited_by, editor_username, edited_at)| CommentRevision {
+            id,
+            content,
+            written_at,
+            edited_by,
+            editor_username,
+            edited_at,
+        })
+        .collect();
+
+    Ok(Json(revisions))
+}
+
+/// POST /api/v1/workspaces/:id/tasks/:task_id/comments/:comment_id/reactions
+pub async fn toggle_reaction(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, task_id, comment_id)): Path<(Uuid, Uuid, Uuid)>,
+    Json(req): Json<ToggleReactionRequest>,
+) -> Result<Json<Vec<CommentReaction>>, AppError> {
+    // Any member can react
+    check_membership(&state, workspace_id, user.id).await?;
+    verify_task(&state, task_id, workspace_id).await?;
+
+    let emoji = req.emoji.trim();
+    if emoji.is_empty() || emoji.chars().any(char::is_whitespace) {
+        return Err(AppError::Validation("A reaction is a single emoji or word".to_string()));
+    }
+    if emoji.chars().count() > MAX_REACTION_CHARS {
+        return Err(AppError::Validation(format!(
+            "Reactions are at most {} characters",
+            MAX_REACTION_CHARS
+        )));
+    }
+
+    let exists: Option<(Uuid,)> = sqlx::query_as(
+        "SELECT id FROM task_comments WHERE id = $1 AND task_id = $2 AND deleted_at IS NULL",
+    )
+    .bind(comment_id)
+    .bind(task_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    if exists.is_none() {
+        return Err(AppError::NotFound);
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let removed = sqlx::query(
+        "DELETE FROM comment_reactions WHERE comment_id = $1 AND user_id = $2 AND emoji = $3",
+    )
+    .bind(comment_id)
+    .bind(user.id)
+    .bind(emoji)
+    .execute(&mut *tx)
+    .await?;
+
+    if removed.rows_affected() == 0 {
+        // A concurrent toggle may have added it since the delete; that's the same outcome
+        sqlx::query(
+            r#"
+            INSERT INTO comment_reactions (comment_id, user_id, emoji) VALUES ($1, $2, $3)
+            ON CONFLICT (comment_id, user_id, emoji) DO NOTHING
+            "#,
+        )
+        .bind(comment_id)
+        .bind(user.id)
+        .bind(emoji)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    publish(
+        &mut *tx,
+        workspace_id,
+        user.id,
+        WorkspaceEventKind::CommentsChanged { task_id },
+    )
+    .await?;
+
+    let mut reactions = load_reactions(&mut *tx, &[comment_id], user.id).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(reactions.remove(&comment_id).unwrap_or_default()))
+}
@@ -0,0 +1,399 @@
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use chrono::Utc;
+use todo_shared::{
+    api::{CreateCustomFieldRequest, SetCustomFieldValuesRequest, UpdateCustomFieldRequest},
+    CustomField, CustomFieldType, CustomFieldValue, WorkspaceEventKind, WorkspaceRole,
+};
+use uuid::Uuid;
+
+use crate::auth::AuthUser;
+use crate::error::AppError;
+use crate::events::publish;
+use crate::routes::AppState;
+
+/// Helper to check workspace membership and return role
+async fn check_membership(
+    state: &AppState,
+    workspace_id: Uuid,
+    user_id: Uuid,
+) -> Result<WorkspaceRole, AppError> {
+    let role: Option<(WorkspaceRole,)> = sqlx::query_as(
+        r#"SELECT role as "role: WorkspaceRole" FROM workspace_members WHERE workspace_id = $1 AND user_id = $2"#,
+    )
+    .bind(workspace_id)
+    .bind(user_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    role.map(|(r,)| r).ok_or(AppError::NotFound)
+}
+
+/// Helper to verify task belongs to workspace
+async fn verify_task(
+    state: &AppState,
+    task_id: Uuid,
+    workspace_id: Uuid,
+) -> Result<(), AppError> {
+    let exists: Option<(Uuid,)> = sqlx::query_as(
+        "SELECT id FROM tasks WHERE id = $1 AND workspace_id = $2 AND deleted_at IS NULL",
+    )
+    .bind(task_id)
+    .bind(workspace_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    if exists.is_none() {
+        return Err(AppError::NotFound);
+    }
+    Ok(())
+}
+
+type CustomFieldRow = (
+    Uuid,                  // id
+    Uuid,                  // workspace_id
+    String,                // name
+    CustomFieldType,       // field_type
+    serde_json::Value,     // options
+    i32,                   // position
+    chrono::DateTime<Utc>, // created_at
+);
+
+fn row_to_field(row: CustomFieldRow) -> CustomField {
+    CustomField {
+        id: row.0,
+        workspace_id: row.1,
+        name: row.2,
+        field_type: row.3,
+        options: serde_json::from_value(row.4).unwrap_or_default(),
+        position: row.5,
+        created_at: row.6,
+    }
+}
+
+/// Load all custom field definitions of a workspace in display order
+pub async fn load_workspace_fields(
+    state: &AppState,
+    workspace_id: Uuid,
+) -> Result<Vec<CustomField>, AppError> {
+    let rows: Vec<CustomFieldRow> = sqlx::query_as(
+        r#"
+        SELECT id, workspace_id, name, field_type, options, position, created_at
+        FROM custom_fields
+        WHERE workspace_id = $1
+        ORDER BY position, name
+        "#,
+    )
+    .bind(workspace_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(rows.into_iter().map(row_to_field).collect())
+}
+
+/// Helper to trim options and reject duplicates or options on non-select fields
+fn normalize_options(field_type: CustomFieldType, options: Vec<String>) -> Result<Vec<String>, AppError> {
+    let mut normalized: Vec<String> = Vec::new();
+    for option in options {
+        let option = option.trim().to_string();
+        if option.is_empty() {
+            continue;
+        }
+        if normalized.iter().any(|o| o.eq_ignore_ascii_case(&option)) {
+            return Err(AppError::Validation(format!("Duplicate option: {}", option)));
+        }
+        normalized.push(option);
+    }
+
+    if field_type.has_options() && normalized.is_empty() {
+        return Err(AppError::Validation(
+            "Select fields need at least one option".to_string(),
+        ));
+    }
+    if !field_type.has_options() && !normalized.is_empty() {
+        return Err(AppError::Validation(
+            "Only select fields can have options".to_string(),
+        ));
+    }
+    Ok(normalized)
+}
+
+/// GET /api/v1/workspaces/:id/custom-fields
+pub async fn list_custom_fields(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(workspace_id): Path<Uuid>,
+) -> Result<Json<Vec<CustomField>>, AppError> {
+    check_membership(&state, workspace_id, user.id).await?;
+
+    Ok(Json(load_workspace_fields(&state, workspace_id).await?))
+}
+
+/// POST /api/v1/workspaces/:id/custom-fields
+pub async fn create_custom_field(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(workspace_id): Path<Uuid>,
+    Json(req): Json<CreateCustomFieldRequest>,
+) -> Result<Json<CustomField>, AppError> {
+    let role = check_membership(&state, workspace_id, user.id).await?;
+
+    // Field definitions shape the whole workspace, so only admins manage them
+    if !role.can_admin() {
+        return Err(AppError::Forbidden);
+    }
+
+    let name = req.name.trim().to_string();
+    if name.is_empty() {
+        return Err(AppError::Validation("Field name is required".to_string()));
+    }
+
+    let options = normalize_options(req.field_type, req.options)?;
+
+    let existing: Option<(Uuid,)> = sqlx::query_as(
+        "SELECT id FROM custom_fields WHERE workspace_id = $1 AND name = $2",
+    )
+    .bind(workspace_id)
+    .bind(&name)
+    .fetch_optional(&state.db)
+    .await?;
+
+    if existing.is_some() {
+        return Err(AppError::Conflict(format!("Field '{}' already exists", name)));
+    }
+
+    let (max_pos,): (i32,) = sqlx::query_as(
+        "SELECT COALESCE(MAX(position), -1) FROM custom_fields WHERE workspace_id = $1",
+    )
+    .bind(workspace_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    let row: CustomFieldRow = sqlx::query_as(
+        r#"
+        INSERT INTO custom_fields (id, workspace_id, name, field_type, options, position, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id, workspace_id, name, field_type, options, position, created_at
+        "#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(workspace_id)
+    .bind(&name)
+    .bind(req.field_type)
+    .bind(serde_json::to_value(&options).unwrap_or_default())
+    .bind(max_pos + 1)
+    .bind(Utc::now())
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(Json(row_to_field(row)))
+}
+
+/// PATCH /api/v1/workspaces/:id/custom-fields/:field_id
+pub async fn update_custom_field(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, field_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<UpdateCustomFieldRequest>,
+) -> Result<Json<CustomField>, AppError> {
+    let role = check_membership(&state, workspace_id, user.id).await?;
+
+    if !role.can_admin() {
+        return Err(AppError::Forbidden);
+    }
+
+    let field = load_workspace_fields(&state, workspace_id)
+        .await?
+        .into_iter()
+        .find(|f| f.id == field_id)
+        .ok_or(AppError::NotFound)?;
+
+    if let Some(ref name) = req.name {
+        if name.trim().is_empty() {
+            return Err(AppError::Validation("Field name is required".to_string()));
+        }
+    }
+
+    let options = match req.options {
+        Some(options) => {
+            let options = normalize_options(field.field_type, options)?;
+
+            // Refuse to drop options that tasks still use
+            let removed: Vec<String> = field
+                .options
+                .iter()
+                .filter(|o| !options.contains(o))
+                .cloned()
+                .collect();
+            if !removed.is_empty() {
+                let (in_use,): (i64,) = sqlx::query_as(
+                    r#"
+                    SELECT COUNT(*) FROM task_custom_field_values
+                    WHERE field_id = $1 AND (value ?| $2 OR (value #>> '{}') = ANY($2))
+                    "#,
+                )
+                .bind(field_id)
+                .bind(&removed)
+                .fetch_one(&state.db)
+                .await?;
+
+                if in_use > 0 {
+                    return Err(AppError::Conflict(format!(
+                        "Removed options are still used by {} task(s)",
+                        in_use
+                    )));
+                }
+            }
+            Some(serde_json::to_value(&options).unwrap_or_default())
+        }
+        None => None,
+    };
+
+    let row: CustomFieldRow = sqlx::query_as(
+        r#"
+        UPDATE custom_fields
+        SET name = COALESCE($1, name),
+            options = COALESCE($2, options),
+            position = COALESCE($3, position)
+        WHERE id = $4 AND workspace_id = $5
+        RETURNING id, workspace_id, name, field_type, options, position, created_at
+        "#,
+    )
+    .bind(req.name.as_ref().map(|n| n.trim().to_string()))
+    .bind(&options)
+    .bind(req.position)
+    .bind(field_id)
+    .bind(workspace_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(Json(row_to_field(row)))
+}
+
+/// DELETE /api/v1/workspaces/:id/custom-fields/:field_id
+pub async fn delete_custom_field(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, field_id)): Path<(Uuid, Uuid)>,
+) -> Result<(), AppError> {
+    let role = check_membership(&state, workspace_id, user.id).await?;
+
+    if !role.can_admin() {
+        return Err(AppError::Forbidden);
+    }
+
+    // Values are removed by ON DELETE CASCADE
+    let result = sqlx::query("DELETE FROM custom_fields WHERE id = $1 AND workspace_id = $2")
+        .bind(field_id)
+        .bind(workspace_id)
+        .execute(&state.db)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+
+    Ok(())
+}
+
+/// PUT /api/v1/workspaces/:id/tasks/:task_id/custom-fields
+pub async fn set_task_custom_fields(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, task_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<SetCustomFieldValuesRequest>,
+) -> Result<Json<Vec<CustomFieldValue>>, AppError> {
+    let role = check_membership(&state, workspace_id, user.id).await?;
+
+    if !role.can_edit() {
+        return Err(AppError::Forbidden);
+    }
+
+    verify_task(&state, task_id, workspace_id).await?;
+
+    let fields = load_workspace_fields(&state, workspace_id).await?;
+
+    // Validate everything before writing anything
+    for entry in &req.values {
+        let field = fields
+            .iter()
+            .find(|f| f.id == entry.field_id)
+            .ok_or_else(|| AppError::Validation("Invalid field_id for this workspace".to_string()))?;
+
+        if entry.value.is_null() {
+            continue;
+        }
+        field.validate_value(&entry.value).map_err(AppError::Validation)?;
+
+        if field.field_type == CustomFieldType::User {
+            let member_id = entry.value.as_str().and_then(|s| Uuid::parse_str(s).ok());
+            let is_member: Option<(Uuid,)> = sqlx::query_as(
+                "SELECT user_id FROM workspace_members WHERE workspace_id = $1 AND user_id = $2",
+            )
+            .bind(workspace_id)
+            .bind(member_id)
+            .fetch_optional(&state.db)
+            .await?;
+
+            if is_member.is_none() {
+                return Err(AppError::Validation(format!(
+                    "{} must be a workspace member",
+                    field.name
+                )));
+            }
+        }
+    }
+
+    let mut tx = state.db.begin().await?;
+    let now = Utc::now();
+
+    for entry in &req.values {
+        if entry.value.is_null() {
+            sqlx::query("DELETE FROM task_custom_field_values WHERE task_id = $1 AND field_id = $2")
+                .bind(task_id)
+                .bind(entry.field_id)
+                .execute(&mut *tx)
+                .await?;
+        } else {
+            sqlx::query(
+                r#"
+                INSERT INTO task_custom_field_values (task_id, field_id, value, updated_at)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (task_id, field_id) DO UPDATE SET value = $3, updated_at = $4
+                "#,
+            )
+            .bind(task_id)
+            .bind(entry.field_id)
+            .bind(&entry.value)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+
+    publish(&mut *tx, workspace_id, user.id, WorkspaceEventKind::TaskChanged { task_id }).await?;
+
+    tx.commit().await?;
+
+    let values: Vec<(Uuid, serde_json::Value)> = sqlx::query_as(
+        r#"
+        SELECT v.field_id, v.value
+        FROM task_custom_field_values v
+        INNER JOIN custom_fields f ON f.id = v.field_id
+        WHERE v.task_id = $1
+        ORDER BY f.position, f.name
+        "#,
+    )
+    .bind(task_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(
+        values
+            .into_iter()
+            .map(|(field_id, value)| CustomFieldValue { field_id, value })
+            .collect(),
+    ))
+}
@@ -0,0 +1,252 @@
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use chrono::Utc;
+use todo_shared::{
+    api::{AddDependencyRequest, DependencyTask, TaskDependencies},
+    WorkspaceEventKind, WorkspaceRole,
+};
+use uuid::Uuid;
+
+use crate::auth::AuthUser;
+use crate::error::AppError;
+use crate::events::publish;
+use crate::routes::AppState;
+
+/// Helper to check workspace membership and return role
+async fn check_membership(
+    state: &AppState,
+    workspace_id: Uuid,
+    user_id: Uuid,
+) -> Result<WorkspaceRole, AppError> {
+    let role: Option<(WorkspaceRole,)> = sqlx::query_as(
+        r#"SELECT role as "role: WorkspaceRole" FROM workspace_members WHERE workspace_id = $1 AND user_id = $2"#,
+    )
+    .bind(workspace_id)
+    .bind(user_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    role.map(|(r,)| r).ok_or(AppError::NotFound)
+}
+
+/// Helper to verify task belongs to workspace
+async fn verify_task(
+    state: &AppState,
+    task_id: Uuid,
+    workspace_id: Uuid,
+) -> Result<(), AppError> {
+    let exists: Option<(Uuid,)> = sqlx::query_as(
+        "SELECT id FROM tasks WHERE id = $1 AND workspace_id = $2 AND deleted_at IS NULL",
+    )
+    .bind(task_id)
+    .bind(workspace_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    if exists.is_none() {
+        return Err(AppError::NotFound);
+    }
+    Ok(())
+}
+
+type DependencyRow = (
+    Uuid,   // task_id
+    String, // task_title
+    Uuid,   // task_status_id
+    bool,   // is_done
+);
+
+fn row_to_dependency(row: DependencyRow) -> DependencyTask {
+    DependencyTask {
+        task_id: row.0,
+        task_title: row.1,
+        task_status_id: row.2,
+        is_done: row.3,
+    }
+}
+
+/// Helper to load both directions of a task's dependencies
+async fn load_dependencies(state: &AppState, task_id: Uuid) -> Result<TaskDependencies, AppError> {
+    let blocked_by: Vec<DependencyRow> = sqlx::query_as(
+        r#"
+        SELECT t.id, t.title, t.status_id, s.is_done
+        FROM task_dependencies d
+        INNER JOIN tasks t ON t.id = d.blocking_task_id
+        INNER JOIN task_statuses s ON s.id = t.status_id
+        WHERE d.blocked_task_id = $1 AND t.deleted_at IS NULL
+        ORDER BY d.created_at ASC
+        "#,
+    )
+    .bind(task_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let blocks: Vec<DependencyRow> = sqlx::query_as(
+        r#"
+        SELECT t.id, t.title, t.status_id, s.is_done
+        FROM task_dependencies d
+        INNER JOIN tasks t ON t.id = d.blocked_task_id
+        INNER JOIN task_statuses s ON s.id = t.status_id
+        WHERE d.blocking_task_id = $1 AND t.deleted_at IS NULL
+        ORDER BY d.created_at ASC
+        "#,
+    )
+    .bind(task_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(TaskDependencies {
+        blocked_by: blocked_by.into_iter().map(row_to_dependency).collect(),
+        blocks: blocks.into_iter().map(row_to_dependency).collect(),
+    })
+}
+
+/// GET /api/v1/workspaces/:id/tasks/:task_id/dependencies
+pub async fn list_dependencies(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, task_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<TaskDependencies>, AppError> {
+    check_membership(&state, workspace_id, user.id).await?;
+    verify_task(&state, task_id, workspace_id).await?;
+
+    Ok(Json(load_dependencies(&state, task_id).await?))
+}
+
+/// POST /api/v1/workspaces/:id/tasks/:task_id/dependencies
+pub async fn add_dependency(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, task_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<AddDependencyRequest>,
+) -> Result<Json<TaskDependencies>, AppError> {
+    let role = check_membership(&state, workspace_id, user.id).await?;
+
+    if !role.can_edit() {
+        return Err(AppError::Forbidden);
+    }
+
+    verify_task(&state, task_id, workspace_id).await?;
+
+    if req.blocking_task_id == task_id {
+        return Err(AppError::Validation(
+            "A task cannot block itself".to_string(),
+        ));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    // Lock both ends in id order so two requests linking the same pair in
+    // opposite directions can't both pass the cycle check
+    let locked: Vec<(Uuid,)> = sqlx::query_as(
+        r#"
+        SELECT id FROM tasks
+        WHERE id = ANY($1) AND workspace_id = $2 AND deleted_at IS NULL
+        ORDER BY id
+        FOR UPDATE
+        "#,
+    )
+    .bind(vec![task_id, req.blocking_task_id])
+    .bind(workspace_id)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    // Blocking task must be in the same workspace
+    if !locked.iter().any(|(id,)| *id == req.blocking_task_id) {
+        return Err(AppError::Validation(
+            "Invalid blocking_task_id for this workspace".to_string(),
+        ));
+    }
+    if !locked.iter().any(|(id,)| *id == task_id) {
+        return Err(AppError::NotFound);
+    }
+
+    // Reject the edge if this task already (transitively) blocks the blocking task
+    let (creates_cycle,): (bool,) = sqlx::query_as(
+        r#"
+        WITH RECURSIVE downstream AS (
+            SELECT blocked_task_id AS id FROM task_dependencies WHERE blocking_task_id = $1
+            UNION
+            SELECT d.blocked_task_id FROM task_dependencies d
+            INNER JOIN downstream ds ON d.blocking_task_id = ds.id
+        )
+        SELECT EXISTS (SELECT 1 FROM downstream WHERE id = $2)
+        "#,
+    )
+    .bind(task_id)
+    .bind(req.blocking_task_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if creates_cycle {
+        return Err(AppError::Validation(
+            "Dependency would create a cycle".to_string(),
+        ));
+    }
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO task_dependencies (blocking_task_id, blocked_task_id, created_by, created_at)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (blocking_task_id, blocked_task_id) DO NOTHING
+        "#,
+    )
+    .bind(req.blocking_task_id)
+    .bind(task_id)
+    .bind(user.id)
+    .bind(Utc::now())
+    .execute(&mut *tx)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::Conflict(
+            "Dependency already exists".to_string(),
+        ));
+    }
+
+    // Both cards show the link: one as blocked, the other as blocking
+    for changed in [task_id, req.blocking_task_id] {
+        publish(&mut *tx, workspace_id, user.id, WorkspaceEventKind::TaskChanged { task_id: changed })
+            .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(Json(load_dependencies(&state, task_id).await?))
+}
+
+/// DELETE /api/v1/workspaces/:id/tasks/:task_id/dependencies/:blocking_task_id
+pub async fn remove_dependency(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, task_id, blocking_task_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<(), AppError> {
+    let role = check_membership(&state, workspace_id, user.id).await?;
+
+    if !role.can_edit() {
+        return Err(AppError::Forbidden);
+    }
+
+    verify_task(&state, task_id, workspace_id).await?;
+
+    let result = sqlx::query(
+        "DELETE FROM task_dependencies WHERE blocking_task_id = $1 AND blocked_task_id = $2",
+    )
+    .bind(blocking_task_id)
+    .bind(task_id)
+    .execute(&state.db)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+
+    for changed in [task_id, blocking_task_id] {
+        publish(&state.db, workspace_id, user.id, WorkspaceEventKind::TaskChanged { task_id: changed })
+            .await?;
+    }
+
+    Ok(())
+}
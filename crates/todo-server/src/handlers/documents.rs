@@ -1,5 +1,6 @@
 use axum::{
     extract::{Path, State},
+    http::HeaderMap,
     Extension, Json,
 };
 use chrono::{DateTime, Utc};
@@ -8,12 +9,15 @@ use todo_shared::{
         CreateDocumentRequest, LinkTaskRequest, LinkedDocument, LinkedTask, MoveDocumentRequest,
         UpdateDocumentRequest,
     },
-    Document, WorkspaceRole,
+    ActivityAction, ActivityEntityType, Document, WorkspaceEventKind, WorkspaceRole,
 };
 use uuid::Uuid;
 
 use crate::auth::AuthUser;
+use crate::concurrency::{check_version, etag, expected_version, ETag};
 use crate::error::AppError;
+use crate::events::publish;
+use crate::handlers::activity::{diff, document_snapshot, record_activity, NewActivity};
 use crate::routes::AppState;
 
 /// Helper to check workspace membership and return role
@@ -40,7 +44,7 @@ async fn verify_document(
     workspace_id: Uuid,
 ) -> Result<(), AppError> {
     let exists: Option<(Uuid,)> =
-        sqlx::query_as("SELECT id FROM documents WHERE id = $1 AND workspace_id = $2")
+        sqlx::query_as("SELECT id FROM documents WHERE id = $1 AND workspace_id = $2 AND deleted_at IS NULL")
             .bind(doc_id)
             .bind(workspace_id)
             .fetch_optional(&state.db)
@@ -76,6 +80,7 @@ type DocumentRow = (
     Uuid,                // created_by
     DateTime<Utc>,       // created_at
     DateTime<Utc>,       // updated_at
+    i32,                 // version
 );
 
 fn row_to_document(row: DocumentRow) -> Document {
@@ -90,6 +95,7 @@ fn row_to_document(row: DocumentRow) -> Document {
         created_by: row.7,
         created_at: row.8,
         updated_at: row.9,
+        version: row.10,
     }
 }
 
@@ -104,9 +110,9 @@ pub async fn list_documents(
     let rows: Vec<DocumentRow> = sqlx::query_as(
         r#"
         SELECT id, workspace_id, path::text, parent_id, title, slug, content,
-               created_by, created_at, updated_at
+               created_by, created_at, updated_at, version
         FROM documents
-        WHERE workspace_id = $1
+        WHERE workspace_id = $1 AND deleted_at IS NULL
         ORDER BY path
         "#,
     )
@@ -144,7 +150,7 @@ pub async fn create_document(
     let path = if let Some(parent_id) = req.parent_id {
         // Verify parent belongs to workspace
         let parent: Option<(String,)> = sqlx::query_as(
-            "SELECT path::text FROM documents WHERE id = $1 AND workspace_id = $2",
+            "SELECT path::text FROM documents WHERE id = $1 AND workspace_id = $2 AND deleted_at IS NULL",
         )
         .bind(parent_id)
         .bind(workspace_id)
@@ -164,12 +170,13 @@ pub async fn create_document(
     };
 
     // Check for duplicate path
-    let existing: Option<(Uuid,)> =
-        sqlx::query_as("SELECT id FROM documents WHERE workspace_id = $1 AND path = $2::ltree")
-            .bind(workspace_id)
-            .bind(&path)
-            .fetch_optional(&state.db)
-            .await?;
+    let existing: Option<(Uuid,)> = sqlx::query_as(
+        "SELECT id FROM documents WHERE workspace_id = $1 AND path = $2::ltree AND deleted_at IS NULL",
+    )
+    .bind(workspace_id)
+    .bind(&path)
+    .fetch_optional(&state.db)
+    .await?;
 
     if existing.is_some() {
         return Err(AppError::Conflict(
@@ -177,6 +184,8 @@ pub async fn create_document(
         ));
     }
 
+    let mut tx = state.db.begin().await?;
+
     sqlx::query(
         r#"
         INSERT INTO documents (id, workspace_id, path, parent_id, title, slug, content,
@@ -194,9 +203,33 @@ pub async fn create_document(
     .bind(user.id)
     .bind(now)
     .bind(now)
-    .execute(&state.db)
+    .execute(&mut *tx)
     .await?;
 
+    record_activity(
+        &mut tx,
+        NewActivity {
+            workspace_id,
+            user_id: user.id,
+            entity_type: ActivityEntityType::Document,
+            entity_id: id,
+            entity_label: req.title.clone(),
+            action: ActivityAction::Created,
+            changes: None,
+        },
+    )
+    .await?;
+
+    publish(
+        &mut *tx,
+        workspace_id,
+        user.id,
+        WorkspaceEventKind::DocumentChanged { document_id: id },
+    )
+    .await?;
+
+    tx.commit().await?;
+
     Ok(Json(Document {
         id,
         workspace_id,
@@ -208,6 +241,7 @@ pub async fn create_document(
         created_by: user.id,
         created_at: now,
         updated_at: now,
+        version: 1,
     }))
 }
 
@@ -216,15 +250,15 @@ pub async fn get_document(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
     Path((workspace_id, doc_id)): Path<(Uuid, Uuid)>,
-) -> Result<Json<Document>, AppError> {
+) -> Result<(ETag, Json<Document>), AppError> {
     check_membership(&state, workspace_id, user.id).await?;
 
     let row: DocumentRow = sqlx::query_as(
         r#"
         SELECT id, workspace_id, path::text, parent_id, title, slug, content,
-               created_by, created_at, updated_at
+               created_by, created_at, updated_at, version
         FROM documents
-        WHERE id = $1 AND workspace_id = $2
+        WHERE id = $1 AND workspace_id = $2 AND deleted_at IS NULL
         "#,
     )
     .bind(doc_id)
@@ -233,45 +267,101 @@ pub async fn get_document(
     .await?
     .ok_or(AppError::NotFound)?;
 
-    Ok(Json(row_to_document(row)))
+    let document = row_to_document(row);
+
+    Ok((etag(document.version), Json(document)))
 }
 
 /// PATCH /api/v1/workspaces/:id/documents/:doc_id
+/// Honors `If-Match` / `expected_version`, answering 409 if the page changed since
 pub async fn update_document(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
     Path((workspace_id, doc_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
     Json(req): Json<UpdateDocumentRequest>,
-) -> Result<Json<Document>, AppError> {
+) -> Result<(ETag, Json<Document>), AppError> {
     let role = check_membership(&state, workspace_id, user.id).await?;
 
     if !role.can_edit() {
         return Err(AppError::Forbidden);
     }
 
+    let expected = expected_version(&headers, req.expected_version)?;
+
     verify_document(&state, doc_id, workspace_id).await?;
 
+    let title = req.title.non_null("title").map_err(AppError::Validation)?;
+    if title.as_deref().is_some_and(|t| t.trim().is_empty()) {
+        return Err(AppError::Validation("Document title is required".to_string()));
+    }
+
     let now = Utc::now();
 
+    let mut tx = state.db.begin().await?;
+
+    // Lock the row so the version can't move between the check and the write
+    let (current_version,): (i32,) =
+        sqlx::query_as("SELECT version FROM documents WHERE id = $1 FOR UPDATE")
+            .bind(doc_id)
+            .fetch_one(&mut *tx)
+            .await?;
+    check_version("Document", current_version, expected)?;
+
+    let (_, before) = document_snapshot(&mut tx, doc_id).await?;
+
     let row: DocumentRow = sqlx::query_as(
         r#"
         UPDATE documents
         SET title = COALESCE($1, title),
-            content = COALESCE($2, content),
-            updated_at = $3
-        WHERE id = $4
+            content = CASE WHEN $2 THEN $3 ELSE content END,
+            updated_at = $4,
+            version = version + 1
+        WHERE id = $5
         RETURNING id, workspace_id, path::text, parent_id, title, slug, content,
-                  created_by, created_at, updated_at
+                  created_by, created_at, updated_at, version
         "#,
     )
-    .bind(&req.title)
-    .bind(&req.content)
+    .bind(&title)
+    .bind(req.content.is_set())
+    .bind(req.content.value())
     .bind(now)
     .bind(doc_id)
-    .fetch_one(&state.db)
+    .fetch_one(&mut *tx)
     .await?;
 
-    Ok(Json(row_to_document(row)))
+    let (entity_label, after) = document_snapshot(&mut tx, doc_id).await?;
+    if let Some(changes) = diff(&before, &after) {
+        record_activity(
+            &mut tx,
+            NewActivity {
+                workspace_id,
+                user_id: user.id,
+                entity_type: ActivityEntityType::Document,
+                entity_id: doc_id,
+                entity_label,
+                action: ActivityAction::Updated,
+                changes: Some(changes),
+            },
+        )
+        .await?;
+    }
+
+    publish(
+        &mut *tx,
+        workspace_id,
+        user.id,
+        WorkspaceEventKind::DocumentChanged {
+            document_id: doc_id,
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    let document = row_to_document(row);
+
+    Ok((etag(document.version), Json(document)))
 }
 
 /// DELETE /api/v1/workspaces/:id/documents/:doc_id
@@ -286,17 +376,58 @@ pub async fn delete_document(
         return Err(AppError::Forbidden);
     }
 
-    // Delete document (children cascade automatically via FK)
-    let result = sqlx::query("DELETE FROM documents WHERE id = $1 AND workspace_id = $2")
-        .bind(doc_id)
-        .bind(workspace_id)
-        .execute(&state.db)
-        .await?;
+    let mut tx = state.db.begin().await?;
+
+    // Trash the document together with its live subtree
+    let result = sqlx::query(
+        r#"
+        UPDATE documents
+        SET deleted_at = $3, deleted_by = $4
+        WHERE workspace_id = $2 AND deleted_at IS NULL
+          AND path <@ (
+              SELECT path FROM documents
+              WHERE id = $1 AND workspace_id = $2 AND deleted_at IS NULL
+          )
+        "#,
+    )
+    .bind(doc_id)
+    .bind(workspace_id)
+    .bind(Utc::now())
+    .bind(user.id)
+    .execute(&mut *tx)
+    .await?;
 
     if result.rows_affected() == 0 {
         return Err(AppError::NotFound);
     }
 
+    let (entity_label, _) = document_snapshot(&mut tx, doc_id).await?;
+    record_activity(
+        &mut tx,
+        NewActivity {
+            workspace_id,
+            user_id: user.id,
+            entity_type: ActivityEntityType::Document,
+            entity_id: doc_id,
+            entity_label,
+            action: ActivityAction::Deleted,
+            changes: None,
+        },
+    )
+    .await?;
+
+    publish(
+        &mut *tx,
+        workspace_id,
+        user.id,
+        WorkspaceEventKind::DocumentDeleted {
+            document_id: doc_id,
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
     Ok(())
 }
 
@@ -317,6 +448,8 @@ pub async fn move_document(
 
     let mut tx = state.db.begin().await?;
 
+    let (_, before) = document_snapshot(&mut tx, doc_id).await?;
+
     // Get current document info
     let (current_path, slug): (String, String) = sqlx::query_as(
         "SELECT path::text, slug FROM documents WHERE id = $1",
@@ -352,7 +485,7 @@ pub async fn move_document(
     // Calculate new path
     let new_path = if let Some(new_parent_id) = req.parent_id {
         let parent: Option<(String,)> = sqlx::query_as(
-            "SELECT path::text FROM documents WHERE id = $1 AND workspace_id = $2",
+            "SELECT path::text FROM documents WHERE id = $1 AND workspace_id = $2 AND deleted_at IS NULL",
         )
         .bind(new_parent_id)
         .bind(workspace_id)
@@ -374,7 +507,10 @@ pub async fn move_document(
 
     // Check for path conflict
     let conflict: Option<(Uuid,)> = sqlx::query_as(
-        "SELECT id FROM documents WHERE workspace_id = $1 AND path = $2::ltree AND id != $3",
+        r#"
+        SELECT id FROM documents
+        WHERE workspace_id = $1 AND path = $2::ltree AND id != $3 AND deleted_at IS NULL
+        "#,
     )
     .bind(workspace_id)
     .bind(&new_path)
@@ -391,13 +527,15 @@ pub async fn move_document(
     let now = Utc::now();
 
     // Update all descendants' paths
-    // Replace the old prefix with the new prefix
+    // Replace the old prefix with the new prefix. Trashed documents stay put:
+    // one may sit at the old path without being a descendant, and restoring
+    // rebuilds the path from the parent anyway
     sqlx::query(
         r#"
         UPDATE documents
         SET path = ($1::ltree || subpath(path, nlevel($2::ltree))),
             updated_at = $3
-        WHERE workspace_id = $4 AND path <@ $2::ltree AND id != $5
+        WHERE workspace_id = $4 AND path <@ $2::ltree AND id != $5 AND deleted_at IS NULL
         "#,
     )
     .bind(&new_path)
@@ -417,7 +555,7 @@ pub async fn move_document(
             updated_at = $3
         WHERE id = $4
         RETURNING id, workspace_id, path::text, parent_id, title, slug, content,
-                  created_by, created_at, updated_at
+                  created_by, created_at, updated_at, version
         "#,
     )
     .bind(req.parent_id)
@@ -427,6 +565,33 @@ pub async fn move_document(
     .fetch_one(&mut *tx)
     .await?;
 
+    let (entity_label, after) = document_snapshot(&mut tx, doc_id).await?;
+    if let Some(changes) = diff(&before, &after) {
+        record_activity(
+            &mut tx,
+            NewActivity {
+                workspace_id,
+                user_id: user.id,
+                entity_type: ActivityEntityType::Document,
+                entity_id: doc_id,
+                entity_label,
+                action: ActivityAction::Moved,
+                changes: Some(changes),
+            },
+        )
+        .await?;
+    }
+
+    publish(
+        &mut *tx,
+        workspace_id,
+        user.id,
+        WorkspaceEventKind::DocumentChanged {
+            document_id: doc_id,
+        },
+    )
+    .await?;
+
     tx.commit().await?;
 
     Ok(Json(row_to_document(row)))
@@ -435,7 +600,7 @@ pub async fn move_document(
 /// Helper to verify task belongs to workspace
 async fn verify_task(state: &AppState, task_id: Uuid, workspace_id: Uuid) -> Result<(), AppError> {
     let exists: Option<(Uuid,)> =
-        sqlx::query_as("SELECT id FROM tasks WHERE id = $1 AND workspace_id = $2")
+        sqlx::query_as("SELECT id FROM tasks WHERE id = $1 AND workspace_id = $2 AND deleted_at IS NULL")
             .bind(task_id)
             .bind(workspace_id)
             .fetch_optional(&state.db)
@@ -546,7 +711,7 @@ pub async fn list_linked_tasks(
         SELECT t.id, t.title, t.status_id, l.created_at
         FROM tasks t
         JOIN task_document_links l ON l.task_id = t.id
-        WHERE l.document_id = $1 AND t.workspace_id = $2
+        WHERE l.document_id = $1 AND t.workspace_id = $2 AND t.deleted_at IS NULL
         ORDER BY l.created_at DESC
         "#,
     )
@@ -583,7 +748,7 @@ pub async fn list_linked_documents(
         SELECT d.id, d.title, d.path::text, l.created_at
         FROM documents d
         JOIN task_document_links l ON l.document_id = d.id
-        WHERE l.task_id = $1 AND d.workspace_id = $2
+        WHERE l.task_id = $1 AND d.workspace_id = $2 AND d.deleted_at IS NULL
         ORDER BY l.created_at DESC
         "#,
     )
@@ -0,0 +1,78 @@
+use std::convert::Infallible;
+
+use axum::{
+    extract::{Path, State},
+    response::sse::{Event, KeepAlive, Sse},
+    Extension,
+};
+use futures_util::stream::{self, Stream};
+use todo_shared::WorkspaceRole;
+use tokio::sync::broadcast::error::RecvError;
+use uuid::Uuid;
+
+use crate::auth::AuthUser;
+use crate::error::AppError;
+use crate::events::Relayed;
+use crate::routes::AppState;
+
+/// Helper to check workspace membership and return role
+async fn check_membership(
+    state: &AppState,
+    workspace_id: Uuid,
+    user_id: Uuid,
+) -> Result<WorkspaceRole, AppError> {
+    let role: Option<(WorkspaceRole,)> = sqlx::query_as(
+        r#"SELECT role as "role: WorkspaceRole" FROM workspace_members WHERE workspace_id = $1 AND user_id = $2"#,
+    )
+    .bind(workspace_id)
+    .bind(user_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    role.map(|(r,)| r).ok_or(AppError::NotFound)
+}
+
+/// GET /api/v1/workspaces/:id/events
+///
+/// Server-sent events: a `change` event with a JSON `WorkspaceEvent` for
+/// every change, or `resync` when this subscriber fell behind or the server
+/// lost its connection to Postgres and events may have been missed.
+/// The stream ends when the caller's session is signed out or they are
+/// removed from the workspace.
+pub async fn workspace_events(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(workspace_id): Path<Uuid>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, AppError> {
+    check_membership(&state, workspace_id, user.id).await?;
+
+    let receiver = state.events.subscribe();
+    let session_id = user.session_id;
+    let user_id = user.id;
+
+    let events = stream::unfold(receiver, move |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(Relayed::Event(event)) if event.workspace_id == workspace_id => {
+                    let Ok(sse) = Event::default().event("change").json_data(&event) else {
+                        continue;
+                    };
+                    return Some((Ok(sse), receiver));
+                }
+                Ok(Relayed::SessionRevoked(revoked)) if revoked == session_id => return None,
+                Ok(Relayed::MemberRemoved { workspace_id: removed_from, user_id: removed })
+                    if removed_from == workspace_id && removed == user_id =>
+                {
+                    return None
+                }
+                Ok(Relayed::Resync) | Err(RecvError::Lagged(_)) => {
+                    return Some((Ok(Event::default().event("resync").data("")), receiver));
+                }
+                Ok(_) => {}
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
@@ -1,8 +1,18 @@
+pub mod activity;
+pub mod attachments;
 pub mod auth;
+pub mod checklists;
 pub mod comments;
+pub mod custom_fields;
+pub mod dependencies;
 pub mod documents;
+pub mod events;
+pub mod notifications;
 pub mod search;
 pub mod statuses;
 pub mod tags;
 pub mod tasks;
+pub mod trash;
+pub mod transitions;
+pub mod work_logs;
 pub mod workspaces;
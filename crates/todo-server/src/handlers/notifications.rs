@@ -0,0 +1,625 @@
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::PgConnection;
+use todo_shared::{
+    api::NotificationListResponse, Notification, NotificationKind, NotificationSettings,
+    WorkspaceRole,
+};
+use uuid::Uuid;
+
+use crate::auth::AuthUser;
+use crate::error::AppError;
+use crate::mail::templates;
+use crate::routes::AppState;
+
+/// Longest comment excerpt kept in a notification
+const EXCERPT_CHARS: usize = 80;
+
+/// Helper to check workspace membership and return role
+async fn check_membership(
+    state: &AppState,
+    workspace_id: Uuid,
+    user_id: Uuid,
+) -> Result<WorkspaceRole, AppError> {
+    let role: Option<(WorkspaceRole,)> = sqlx::query_as(
+        r#"SELECT role as "role: WorkspaceRole" FROM workspace_members WHERE workspace_id = $1 AND user_id = $2"#,
+    )
+    .bind(workspace_id)
+    .bind(user_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    role.map(|(r,)| r).ok_or(AppError::NotFound)
+}
+
+/// Column of `user_notification_settings` that turns a kind on or off
+fn setting_column(kind: NotificationKind) -> &'static str {
+    match kind {
+        NotificationKind::TaskAssigned => "notify_task_assigned",
+        NotificationKind::TaskCommented => "notify_task_commented",
+        NotificationKind::Mentioned => "notify_mention",
+        NotificationKind::StatusChanged => "notify_task_updated",
+        NotificationKind::DueSoon => "notify_due_date_approaching",
+    }
+}
+
+/// A notification about to be sent
+pub struct NewNotification {
+    pub workspace_id: Uuid,
+    pub kind: NotificationKind,
+    pub task_id: Uuid,
+    /// Who caused it; they never get notified about their own change
+    pub actor_id: Option<Uuid>,
+    pub detail: Option<String>,
+}
+
+/// Send a notification to those of `recipients` who are workspace members and
+/// haven't turned its kind off. Pass the handler's transaction so it's only
+/// sent if the change commits.
+pub async fn notify(
+    conn: &mut PgConnection,
+    recipients: &[Uuid],
+    notification: NewNotification,
+) -> Result<(), AppError> {
+    if recipients.is_empty() {
+        return Ok(());
+    }
+
+    // Members without a settings row get everything
+    sqlx::query(&format!(
+        r#"
+        INSERT INTO notifications (user_id, workspace_id, kind, task_id, task_label, actor_id, detail)
+        SELECT DISTINCT m.user_id, $2, $3, $4,
+               (SELECT w.task_key_prefix || '-' || t.number || ' ' || t.title
+                FROM tasks t INNER JOIN workspaces w ON w.id = t.workspace_id
+                WHERE t.id = $4),
+               $5, $6
+        FROM workspace_members m
+        LEFT JOIN user_notification_settings s
+               ON s.user_id = m.user_id AND s.workspace_id = m.workspace_id
+        WHERE m.workspace_id = $2 AND m.user_id = ANY($1)
+          AND m.user_id IS DISTINCT FROM $5
+          AND COALESCE(s.{}, TRUE)
+        "#,
+        setting_column(notification.kind)
+    ))
+    .bind(recipients)
+    .bind(notification.workspace_id)
+    .bind(notification.kind.as_str())
+    .bind(notification.task_id)
+    .bind(notification.actor_id)
+    .bind(&notification.detail)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Tell newly added assignees about their task
+pub async fn notify_assigned(
+    conn: &mut PgConnection,
+    workspace_id: Uuid,
+    task_id: Uuid,
+    actor_id: Uuid,
+    user_ids: &[Uuid],
+) -> Result<(), AppError> {
+    notify(
+        conn,
+        user_ids,
+        NewNotification {
+            workspace_id,
+            kind: NotificationKind::TaskAssigned,
+            task_id,
+            actor_id: Some(actor_id),
+            detail: None,
+        },
+    )
+    .await
+}
+
+/// Tell a task's watchers that it moved to another column
+pub async fn notify_status_change(
+    conn: &mut PgConnection,
+    workspace_id: Uuid,
+    task_id: Uuid,
+    actor_id: Uuid,
+    from_status_id: Uuid,
+    to_status_id: Uuid,
+) -> Result<(), AppError> {
+    if from_status_id == to_status_id {
+        return Ok(());
+    }
+
+    let (from_name, to_name): (String, String) = sqlx::query_as(
+        "SELECT f.name, t.name FROM task_statuses f, task_statuses t WHERE f.id = $1 AND t.id = $2",
+    )
+    .bind(from_status_id)
+    .bind(to_status_id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let watchers: Vec<(Uuid,)> = sqlx::query_as("SELECT user_id FROM task_watchers WHERE task_id = $1")
+        .bind(task_id)
+        .fetch_all(&mut *conn)
+        .await?;
+    let watchers: Vec<Uuid> = watchers.into_iter().map(|(id,)| id).collect();
+
+    notify(
+        conn,
+        &watchers,
+        NewNotification {
+            workspace_id,
+            kind: NotificationKind::StatusChanged,
+            task_id,
+            actor_id: Some(actor_id),
+            detail: Some(format!("{} → {}", from_name, to_name)),
+        },
+    )
+    .await
+}
+
+/// Tell people they were mentioned in `text`, a comment or a task description
+pub async fn notify_mentioned(
+    conn: &mut PgConnection,
+    workspace_id: Uuid,
+    task_id: Uuid,
+    actor_id: Uuid,
+    user_ids: &[Uuid],
+    text: &str,
+) -> Result<(), AppError> {
+    notify(
+        conn,
+        user_ids,
+        NewNotification {
+            workspace_id,
+            kind: NotificationKind::Mentioned,
+            task_id,
+            actor_id: Some(actor_id),
+            detail: Some(excerpt(text)),
+        },
+    )
+    .await
+}
+
+/// Tell the people mentioned in a new comment, then the task's assignees and
+/// watchers and, for a reply, the thread's author. Someone both mentioned and
+/// watching only hears about the mention.
+pub async fn notify_comment(
+    conn: &mut PgConnection,
+    workspace_id: Uuid,
+    task_id: Uuid,
+    actor_id: Uuid,
+    content: &str,
+    mentioned: &[Uuid],
+    parent_comment_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    notify_mentioned(conn, workspace_id, task_id, actor_id, mentioned, content).await?;
+
+    let involved: Vec<(Uuid,)> = sqlx::query_as(
+        r#"
+        SELECT user_id FROM task_assignees WHERE task_id = $1 AND user_id <> ALL($2)
+        UNION
+        SELECT user_id FROM task_watchers WHERE task_id = $1 AND user_id <> ALL($2)
+        UNION
+        SELECT user_id FROM task_comments WHERE id = $3 AND user_id <> ALL($2)
+        "#,
+    )
+    .bind(task_id)
+    .bind(mentioned)
+    .bind(parent_comment_id)
+    .fetch_all(&mut *conn)
+    .await?;
+    let involved: Vec<Uuid> = involved.into_iter().map(|(id,)| id).collect();
+
+    notify(
+        conn,
+        &involved,
+        NewNotification {
+            workspace_id,
+            kind: NotificationKind::TaskCommented,
+            task_id,
+            actor_id: Some(actor_id),
+            detail: Some(excerpt(content)),
+        },
+    )
+    .await
+}
+
+/// First line of a comment or description, shortened for the inbox
+fn excerpt(content: &str) -> String {
+    let line = content.lines().find(|l| !l.trim().is_empty()).unwrap_or("").trim();
+    if line.chars().count() > EXCERPT_CHARS {
+        let cut: String = line.chars().take(EXCERPT_CHARS).collect();
+        format!("{}...", cut)
+    } else {
+        line.to_string()
+    }
+}
+
+/// Remind assignees of open tasks due today or tomorrow, once per due date
+async fn send_due_reminders(state: &AppState) -> Result<u64, AppError> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO notifications (user_id, workspace_id, kind, task_id, task_label, detail)
+        SELECT a.user_id, t.workspace_id, 'due_soon', t.id,
+               w.task_key_prefix || '-' || t.number || ' ' || t.title,
+               'Due ' || to_char(t.due_date, 'YYYY-MM-DD')
+        FROM tasks t
+        INNER JOIN workspaces w ON w.id = t.workspace_id
+        INNER JOIN task_statuses st ON st.id = t.status_id
+        INNER JOIN task_assignees a ON a.task_id = t.id
+        LEFT JOIN user_notification_settings s
+               ON s.user_id = a.user_id AND s.workspace_id = t.workspace_id
+        WHERE t.deleted_at IS NULL AND NOT st.is_done
+          AND t.due_date BETWEEN CURRENT_DATE AND CURRENT_DATE + 1
+          AND COALESCE(s.notify_due_date_approaching, TRUE)
+          AND NOT EXISTS (
+              SELECT 1 FROM notifications n
+              WHERE n.user_id = a.user_id AND n.task_id = t.id AND n.kind = 'due_soon'
+                AND n.created_at >= t.due_date - 2
+          )
+        "#,
+    )
+    .execute(&state.db)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Background job: send due-date reminders every hour
+pub async fn run_due_reminders(state: AppState) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(60 * 60));
+
+    loop {
+        interval.tick().await;
+
+        match send_due_reminders(&state).await {
+            Ok(0) => {}
+            Ok(sent) => tracing::info!("Sent {} due date reminder(s)", sent),
+            Err(e) => tracing::warn!("Failed to send due date reminders: {:?}", e),
+        }
+    }
+}
+
+type DigestRow = (
+    Uuid,                  // user_id
+    String,                // email
+    String,                // username
+    String,                // workspace name
+    Uuid,                  // id
+    Uuid,                  // workspace_id
+    String,                // kind
+    Uuid,                  // task_id
+    String,                // task_label
+    Option<Uuid>,          // actor_id
+    Option<String>,        // actor_name
+    Option<String>,        // detail
+    Option<DateTime<Utc>>, // read_at
+    DateTime<Utc>,         // created_at
+);
+
+/// Everything one user gets in a digest
+struct Digest {
+    user_id: Uuid,
+    email: String,
+    username: String,
+    items: Vec<(String, Notification)>,
+    notification_ids: Vec<Uuid>,
+}
+
+/// Email verified users the unread notifications they haven't been mailed yet,
+/// from workspaces where they haven't turned digests off
+async fn send_notification_digests(state: &AppState) -> Result<usize, AppError> {
+    let rows: Vec<DigestRow> = sqlx::query_as(
+        r#"
+        SELECT u.id, u.email, u.username, w.name,
+               n.id, n.workspace_id, n.kind, n.task_id, n.task_label, n.actor_id,
+               COALESCE(a.username, a.display_name), n.detail, n.read_at, n.created_at
+        FROM notifications n
+        INNER JOIN users u ON u.id = n.user_id
+        INNER JOIN workspaces w ON w.id = n.workspace_id
+        LEFT JOIN users a ON a.id = n.actor_id
+        LEFT JOIN user_notification_settings s
+            ON s.user_id = n.user_id AND s.workspace_id = n.workspace_id
+        WHERE n.emailed_at IS NULL AND n.read_at IS NULL AND u.email_verified
+          AND COALESCE(s.email_digest, TRUE)
+        ORDER BY u.id, w.name, n.created_at
+        "#,
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut digests: Vec<Digest> = Vec::new();
+    for row in rows {
+        let (user_id, email, username, workspace_name) = (row.0, row.1, row.2, row.3);
+        let notification_id = row.4;
+        let notification = row_to_notification((
+            row.4, row.5, row.6, row.7, row.8, row.9, row.10, row.11, row.12, row.13,
+        ));
+
+        if digests.last().map(|d| d.user_id) != Some(user_id) {
+            digests.push(Digest {
+                user_id,
+                email,
+                username,
+                items: Vec::new(),
+                notification_ids: Vec::new(),
+            });
+        }
+        if let Some(digest) = digests.last_mut() {
+            // Kinds this server doesn't know are marked as mailed all the same
+            digest.notification_ids.push(notification_id);
+            if let Some(notification) = notification {
+                digest.items.push((workspace_name, notification));
+            }
+        }
+    }
+
+    let mut sent = 0;
+    for digest in digests {
+        if !digest.items.is_empty() {
+            let email = templates::notification_digest(&digest.email, &digest.username, &digest.items);
+            if let Err(e) = state.mailer.send(&email).await {
+                // Left unmarked, so they go out with the next digest
+                tracing::warn!("Failed to send notification digest to {}: {:?}", digest.email, e);
+                continue;
+            }
+            sent += 1;
+        }
+
+        sqlx::query("UPDATE notifications SET emailed_at = NOW() WHERE id = ANY($1)")
+            .bind(&digest.notification_ids)
+            .execute(&state.db)
+            .await?;
+    }
+
+    Ok(sent)
+}
+
+/// Background job: email notification digests every `NOTIFICATION_DIGEST_HOURS`
+pub async fn run_notification_digests(state: AppState) {
+    let hours = state.config.notification_digest_hours;
+    if hours == 0 {
+        return;
+    }
+
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(hours * 60 * 60));
+    // The first tick is immediate; wait a full period so restarts don't send extra digests
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+
+        match send_notification_digests(&state).await {
+            Ok(0) => {}
+            Ok(sent) => tracing::info!("Sent {} notification digest(s)", sent),
+            Err(e) => tracing::warn!("Failed to send notification digests: {:?}", e),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationQuery {
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+    #[serde(default)]
+    pub unread_only: bool,
+}
+
+type NotificationRow = (
+    Uuid,                  // id
+    Uuid,                  // workspace_id
+    String,                // kind
+    Uuid,                  // task_id
+    String,                // task_label
+    Option<Uuid>,          // actor_id
+    Option<String>,        // actor_name
+    Option<String>,        // detail
+    Option<DateTime<Utc>>, // read_at
+    DateTime<Utc>,         // created_at
+);
+
+/// Rows written by a newer server may carry kinds this one doesn't know; skip them
+fn row_to_notification(row: NotificationRow) -> Option<Notification> {
+    Some(Notification {
+        id: row.0,
+        workspace_id: row.1,
+        kind: NotificationKind::parse(&row.2)?,
+        task_id: row.3,
+        task_label: row.4,
+        actor_id: row.5,
+        actor_name: row.6,
+        detail: row.7,
+        read_at: row.8,
+        created_at: row.9,
+    })
+}
+
+/// GET /api/v1/workspaces/:id/notifications
+pub async fn list_notifications(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(workspace_id): Path<Uuid>,
+    Query(params): Query<NotificationQuery>,
+) -> Result<Json<NotificationListResponse>, AppError> {
+    check_membership(&state, workspace_id, user.id).await?;
+
+    let page = params.page.unwrap_or(1).max(1);
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
+    let offset = (page - 1) * limit;
+
+    let (total, unread): (i64, i64) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*) FILTER (WHERE NOT $3 OR read_at IS NULL),
+               COUNT(*) FILTER (WHERE read_at IS NULL)
+        FROM notifications
+        WHERE user_id = $1 AND workspace_id = $2
+        "#,
+    )
+    .bind(user.id)
+    .bind(workspace_id)
+    .bind(params.unread_only)
+    .fetch_one(&state.db)
+    .await?;
+
+    let rows: Vec<NotificationRow> = sqlx::query_as(
+        r#"
+        SELECT n.id, n.workspace_id, n.kind, n.task_id, n.task_label,
+               n.actor_id, COALESCE(u.username, u.display_name), n.detail, n.read_at, n.created_at
+        FROM notifications n
+        LEFT JOIN users u ON u.id = n.actor_id
+        WHERE n.user_id = $1 AND n.workspace_id = $2
+          AND (NOT $3 OR n.read_at IS NULL)
+        ORDER BY n.created_at DESC, n.id
+        LIMIT $4 OFFSET $5
+        "#,
+    )
+    .bind(user.id)
+    .bind(workspace_id)
+    .bind(params.unread_only)
+    .bind(limit as i64)
+    .bind(offset as i64)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(NotificationListResponse {
+        notifications: rows.into_iter().filter_map(row_to_notification).collect(),
+        total,
+        unread,
+        page,
+        limit,
+    }))
+}
+
+/// POST /api/v1/workspaces/:id/notifications/:notification_id/read
+pub async fn mark_read(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, notification_id)): Path<(Uuid, Uuid)>,
+) -> Result<(), AppError> {
+    check_membership(&state, workspace_id, user.id).await?;
+
+    let result = sqlx::query(
+        r#"
+        UPDATE notifications SET read_at = COALESCE(read_at, NOW())
+        WHERE id = $1 AND user_id = $2 AND workspace_id = $3
+        "#,
+    )
+    .bind(notification_id)
+    .bind(user.id)
+    .bind(workspace_id)
+    .execute(&state.db)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+
+    Ok(())
+}
+
+/// POST /api/v1/workspaces/:id/notifications/read-all
+pub async fn mark_all_read(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(workspace_id): Path<Uuid>,
+) -> Result<(), AppError> {
+    check_membership(&state, workspace_id, user.id).await?;
+
+    sqlx::query(
+        "UPDATE notifications SET read_at = NOW() WHERE user_id = $1 AND workspace_id = $2 AND read_at IS NULL",
+    )
+    .bind(user.id)
+    .bind(workspace_id)
+    .execute(&state.db)
+    .await?;
+
+    Ok(())
+}
+
+/// GET /api/v1/workspaces/:id/notifications/settings
+pub async fn get_settings(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(workspace_id): Path<Uuid>,
+) -> Result<Json<NotificationSettings>, AppError> {
+    check_membership(&state, workspace_id, user.id).await?;
+
+    let row: Option<(
+        Option<bool>,
+        Option<bool>,
+        Option<bool>,
+        Option<bool>,
+        Option<bool>,
+        Option<bool>,
+    )> = sqlx::query_as(
+        r#"
+        SELECT notify_task_assigned, notify_task_updated, notify_task_commented,
+               notify_due_date_approaching, notify_mention, email_digest
+        FROM user_notification_settings
+        WHERE user_id = $1 AND workspace_id = $2
+        "#,
+    )
+    .bind(user.id)
+    .bind(workspace_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    // The flags are nullable; unset ones keep their default of on
+    let settings = match row {
+        Some((assigned, updated, commented, due, mention, digest)) => NotificationSettings {
+            notify_task_assigned: assigned.unwrap_or(true),
+            notify_task_updated: updated.unwrap_or(true),
+            notify_task_commented: commented.unwrap_or(true),
+            notify_due_date_approaching: due.unwrap_or(true),
+            notify_mention: mention.unwrap_or(true),
+            email_digest: digest.unwrap_or(true),
+        },
+        None => NotificationSettings::default(),
+    };
+
+    Ok(Json(settings))
+}
+
+/// PUT /api/v1/workspaces/:id/notifications/settings
+pub async fn update_settings(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(workspace_id): Path<Uuid>,
+    Json(req): Json<NotificationSettings>,
+) -> Result<Json<NotificationSettings>, AppError> {
+    check_membership(&state, workspace_id, user.id).await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO user_notification_settings
+            (user_id, workspace_id, notify_task_assigned, notify_task_updated,
+             notify_task_commented, notify_due_date_approaching, notify_mention, email_digest)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (user_id, workspace_id) DO UPDATE
+        SET notify_task_assigned = EXCLUDED.notify_task_assigned,
+            notify_task_updated = EXCLUDED.notify_task_updated,
+            notify_task_commented = EXCLUDED.notify_task_commented,
+            notify_due_date_approaching = EXCLUDED.notify_due_date_approaching,
+            notify_mention = EXCLUDED.notify_mention,
+            email_digest = EXCLUDED.email_digest,
+            updated_at = NOW()
+        "#,
+    )
+    .bind(user.id)
+    .bind(workspace_id)
+    .bind(req.notify_task_assigned)
+    .bind(req.notify_task_updated)
+    .bind(req.notify_task_commented)
+    .bind(req.notify_due_date_approaching)
+    .bind(req.notify_mention)
+    .bind(req.email_digest)
+    .execute(&state.db)
+    .await?;
+
+    Ok(Json(req))
+}
@@ -37,6 +37,8 @@ async fn check_membership(
 struct SearchTaskRow {
     id: Uuid,
     workspace_id: Uuid,
+    number: i32,
+    task_key: String,
     status_id: Uuid,
     title: String,
     description: Option<String>,
@@ -46,10 +48,13 @@ struct SearchTaskRow {
     time_estimate_minutes: Option<i32>,
     position: i32,
     created_by: Uuid,
-    assigned_to: Option<Uuid>,
+    assignees: Vec<Uuid>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    version: i32,
     completed_at: Option<DateTime<Utc>>,
+    parent_id: Option<Uuid>,
+    recurrence: Option<serde_json::Value>,
     rank: f32,
     title_highlight: Option<String>,
     desc_highlight: Option<String>,
@@ -57,23 +62,35 @@ struct SearchTaskRow {
 
 fn row_to_search_result(row: SearchTaskRow) -> SearchResultItem {
     SearchResultItem::Task(SearchTaskResult {
-        task: Task {
+        task: Box::new(Task {
             id: row.id,
             workspace_id: row.workspace_id,
+            number: row.number,
+            key: row.task_key,
             status_id: row.status_id,
             title: row.title,
             description: row.description,
             priority: row.priority,
             due_date: row.due_date,
             time_estimate_minutes: row.time_estimate_minutes,
+            logged_minutes: 0,
             position: row.position,
             created_by: row.created_by,
-            assigned_to: row.assigned_to,
+            assignees: row.assignees,
+            watchers: Vec::new(),
             created_at: row.created_at,
             updated_at: row.updated_at,
+            version: row.version,
             completed_at: row.completed_at,
+            parent_id: row.parent_id,
+            recurrence: row.recurrence.and_then(|v| serde_json::from_value(v).ok()),
             tags: Vec::new(),
-        },
+            custom_fields: Vec::new(),
+            blocked_by: Vec::new(),
+            subtask_progress: None,
+            checklist_progress: None,
+            subtasks: Vec::new(),
+        }),
         rank: row.rank,
         title_highlights: row.title_highlight,
         description_highlights: row.desc_highlight,
@@ -93,6 +110,7 @@ struct SearchDocumentRow {
     created_by: Uuid,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    version: i32,
     rank: f32,
     title_highlight: Option<String>,
     content_highlight: Option<String>,
@@ -111,6 +129,7 @@ fn row_to_document_result(row: SearchDocumentRow) -> SearchResultItem {
             created_by: row.created_by,
             created_at: row.created_at,
             updated_at: row.updated_at,
+            version: row.version,
         },
         rank: row.rank,
         title_highlights: row.title_highlight,
@@ -236,8 +255,11 @@ async fn search_tasks_impl(
         r#"
         SELECT COUNT(*)
         FROM tasks t
+        INNER JOIN workspaces w ON w.id = t.workspace_id
         WHERE t.workspace_id = $1
-          AND ($2 <% t.title OR $2 <% COALESCE(t.description, ''))
+          AND t.deleted_at IS NULL
+          AND ($2 <% t.title OR $2 <% COALESCE(t.description, '')
+               OR UPPER($2) = w.task_key_prefix || '-' || t.number)
         "#,
     )
     .bind(workspace_id)
@@ -248,18 +270,27 @@ async fn search_tasks_impl(
     // Get results with word_similarity ranking
     let rows: Vec<SearchTaskRow> = sqlx::query_as(
         r#"
-        SELECT t.id, t.workspace_id, t.status_id, t.title, t.description,
+        SELECT t.id, t.workspace_id, t.number, w.task_key_prefix || '-' || t.number as task_key,
+               t.status_id, t.title, t.description,
                t.priority as "priority: Priority", t.due_date, t.time_estimate_minutes,
-               t.position, t.created_by, t.assigned_to, t.created_at, t.updated_at, t.completed_at,
-               GREATEST(
-                   word_similarity($2, t.title),
-                   COALESCE(word_similarity($2, t.description), 0)
-               )::real as rank,
+               t.position, t.created_by,
+               ARRAY(SELECT user_id FROM task_assignees WHERE task_id = t.id ORDER BY created_at) as assignees,
+               t.created_at, t.updated_at, t.version, t.completed_at,
+               t.parent_id, t.recurrence,
+               CASE WHEN UPPER($2) = w.task_key_prefix || '-' || t.number THEN 1.0
+                    ELSE GREATEST(
+                        word_similarity($2, t.title),
+                        COALESCE(word_similarity($2, t.description), 0)
+                    )
+               END::real as rank,
                NULL::text as title_highlight,
                NULL::text as desc_highlight
         FROM tasks t
+        INNER JOIN workspaces w ON w.id = t.workspace_id
         WHERE t.workspace_id = $1
-          AND ($2 <% t.title OR $2 <% COALESCE(t.description, ''))
+          AND t.deleted_at IS NULL
+          AND ($2 <% t.title OR $2 <% COALESCE(t.description, '')
+               OR UPPER($2) = w.task_key_prefix || '-' || t.number)
         ORDER BY rank DESC
         LIMIT $3 OFFSET $4
         "#,
@@ -302,6 +333,7 @@ async fn search_documents_impl(
         SELECT COUNT(*)
         FROM documents d
         WHERE d.workspace_id = $1
+          AND d.deleted_at IS NULL
           AND ($2 <% d.title OR $2 <% COALESCE(d.content, ''))
         "#,
     )
@@ -314,7 +346,7 @@ async fn search_documents_impl(
     let rows: Vec<SearchDocumentRow> = sqlx::query_as(
         r#"
         SELECT d.id, d.workspace_id, d.path::text, d.parent_id, d.title, d.slug,
-               d.content, d.created_by, d.created_at, d.updated_at,
+               d.content, d.created_by, d.created_at, d.updated_at, d.version,
                GREATEST(
                    word_similarity($2, d.title),
                    COALESCE(word_similarity($2, d.content), 0)
@@ -323,6 +355,7 @@ async fn search_documents_impl(
                NULL::text as content_highlight
         FROM documents d
         WHERE d.workspace_id = $1
+          AND d.deleted_at IS NULL
           AND ($2 <% d.title OR $2 <% COALESCE(d.content, ''))
         ORDER BY rank DESC
         LIMIT $3 OFFSET $4
@@ -1,17 +1,24 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     Extension, Json,
 };
 use chrono::Utc;
 use serde::Deserialize;
+use serde_json::{json, Value};
+use sqlx::PgConnection;
 use todo_shared::{
     api::{CreateStatusRequest, UpdateStatusRequest},
-    TaskStatus, WorkspaceRole,
+    ActivityAction, ActivityEntityType, TaskStatus, WorkspaceEventKind, WorkspaceRole,
 };
 use uuid::Uuid;
 
 use crate::auth::AuthUser;
 use crate::error::AppError;
+use crate::events::publish;
+use crate::handlers::activity::{
+    diff, record_activity, status_snapshot, workspace_snapshot, NewActivity,
+};
+use crate::handlers::tasks::move_column_tasks;
 use crate::routes::AppState;
 
 /// Helper to check workspace membership and return role
@@ -31,6 +38,50 @@ async fn check_membership(
     role.map(|(r,)| r).ok_or(AppError::NotFound)
 }
 
+type StatusRow = (
+    Uuid,           // id
+    Uuid,           // workspace_id
+    String,         // name
+    String,         // slug
+    Option<String>, // color
+    i32,            // position
+    bool,           // is_done
+    Option<i32>,    // wip_limit
+);
+
+fn row_to_status(row: StatusRow) -> TaskStatus {
+    TaskStatus {
+        id: row.0,
+        workspace_id: row.1,
+        name: row.2,
+        slug: row.3,
+        color: row.4,
+        position: row.5,
+        is_done: row.6,
+        wip_limit: row.7,
+    }
+}
+
+/// Helper to reject WIP limits that could never be met
+fn validate_wip_limit(wip_limit: Option<i32>) -> Result<(), AppError> {
+    if wip_limit.is_some_and(|limit| limit < 1) {
+        return Err(AppError::Validation("WIP limit must be at least 1".to_string()));
+    }
+    Ok(())
+}
+
+/// Helper to list the board's column names in display order
+async fn column_order(conn: &mut PgConnection, workspace_id: Uuid) -> Result<Value, AppError> {
+    let (names,): (Value,) = sqlx::query_as(
+        "SELECT COALESCE(jsonb_agg(name ORDER BY position), '[]'::jsonb) FROM task_statuses WHERE workspace_id = $1",
+    )
+    .bind(workspace_id)
+    .fetch_one(conn)
+    .await?;
+
+    Ok(names)
+}
+
 /// GET /api/v1/workspaces/:id/statuses
 pub async fn list_statuses(
     State(state): State<AppState>,
@@ -40,9 +91,9 @@ pub async fn list_statuses(
     // Verify membership
     check_membership(&state, workspace_id, user.id).await?;
 
-    let rows: Vec<(Uuid, Uuid, String, String, Option<String>, i32, bool)> = sqlx::query_as(
+    let rows: Vec<StatusRow> = sqlx::query_as(
         r#"
-        SELECT id, workspace_id, name, slug, color, position, is_done
+        SELECT id, workspace_id, name, slug, color, position, is_done, wip_limit
         FROM task_statuses
         WHERE workspace_id = $1
         ORDER BY position
@@ -52,18 +103,7 @@ pub async fn list_statuses(
     .fetch_all(&state.db)
     .await?;
 
-    let statuses = rows
-        .into_iter()
-        .map(|(id, workspace_id, name, slug, color, position, is_done)| TaskStatus {
-            id,
-            workspace_id,
-            name,
-            slug,
-            color,
-            position,
-            is_done,
-        })
-        .collect();
+    let statuses = rows.into_iter().map(row_to_status).collect();
 
     Ok(Json(statuses))
 }
@@ -84,6 +124,7 @@ pub async fn create_status(
     if req.name.trim().is_empty() {
         return Err(AppError::Validation("Status name is required".to_string()));
     }
+    validate_wip_limit(req.wip_limit)?;
 
     let id = Uuid::new_v4();
     let slug = req
@@ -108,10 +149,12 @@ pub async fn create_status(
     let position = max_pos + 1;
     let now = Utc::now();
 
+    let mut tx = state.db.begin().await?;
+
     sqlx::query(
         r#"
-        INSERT INTO task_statuses (id, workspace_id, name, slug, color, position, is_done, created_at)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        INSERT INTO task_statuses (id, workspace_id, name, slug, color, position, is_done, wip_limit, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
         "#,
     )
     .bind(id)
@@ -121,10 +164,35 @@ pub async fn create_status(
     .bind(&req.color)
     .bind(position)
     .bind(req.is_done)
+    .bind(req.wip_limit)
     .bind(now)
-    .execute(&state.db)
+    .execute(&mut *tx)
     .await?;
 
+    record_activity(
+        &mut tx,
+        NewActivity {
+            workspace_id,
+            user_id: user.id,
+            entity_type: ActivityEntityType::Status,
+            entity_id: id,
+            entity_label: req.name.clone(),
+            action: ActivityAction::Created,
+            changes: None,
+        },
+    )
+    .await?;
+
+    publish(
+        &mut *tx,
+        workspace_id,
+        user.id,
+        WorkspaceEventKind::StatusesChanged,
+    )
+    .await?;
+
+    tx.commit().await?;
+
     Ok(Json(TaskStatus {
         id,
         workspace_id,
@@ -133,6 +201,7 @@ pub async fn create_status(
         color: req.color,
         position,
         is_done: req.is_done,
+        wip_limit: req.wip_limit,
     }))
 }
 
@@ -162,32 +231,69 @@ pub async fn update_status(
         return Err(AppError::NotFound);
     }
 
-    let row: (Uuid, Uuid, String, String, Option<String>, i32, bool) = sqlx::query_as(
+    let name = req.name.non_null("name").map_err(AppError::Validation)?;
+    let is_done = req.is_done.non_null("is_done").map_err(AppError::Validation)?;
+    validate_wip_limit(req.wip_limit.value().copied())?;
+
+    let mut tx = state.db.begin().await?;
+
+    let (_, before) = status_snapshot(&mut tx, status_id).await?;
+
+    let row: StatusRow = sqlx::query_as(
         r#"
         UPDATE task_statuses
         SET name = COALESCE($1, name),
-            color = COALESCE($2, color),
-            is_done = COALESCE($3, is_done)
-        WHERE id = $4
-        RETURNING id, workspace_id, name, slug, color, position, is_done
+            color = CASE WHEN $2 THEN $3 ELSE color END,
+            is_done = COALESCE($4, is_done),
+            wip_limit = CASE WHEN $5 THEN $6 ELSE wip_limit END
+        WHERE id = $7
+        RETURNING id, workspace_id, name, slug, color, position, is_done, wip_limit
         "#,
     )
-    .bind(&req.name)
-    .bind(&req.color)
-    .bind(req.is_done)
+    .bind(&name)
+    .bind(req.color.is_set())
+    .bind(req.color.value())
+    .bind(is_done)
+    .bind(req.wip_limit.is_set())
+    .bind(req.wip_limit.value())
     .bind(status_id)
-    .fetch_one(&state.db)
+    .fetch_one(&mut *tx)
     .await?;
 
-    Ok(Json(TaskStatus {
-        id: row.0,
-        workspace_id: row.1,
-        name: row.2,
-        slug: row.3,
-        color: row.4,
-        position: row.5,
-        is_done: row.6,
-    }))
+    let (entity_label, after) = status_snapshot(&mut tx, status_id).await?;
+    if let Some(changes) = diff(&before, &after) {
+        record_activity(
+            &mut tx,
+            NewActivity {
+                workspace_id,
+                user_id: user.id,
+                entity_type: ActivityEntityType::Status,
+                entity_id: status_id,
+                entity_label,
+                action: ActivityAction::Updated,
+                changes: Some(changes),
+            },
+        )
+        .await?;
+    }
+
+    publish(
+        &mut *tx,
+        workspace_id,
+        user.id,
+        WorkspaceEventKind::StatusesChanged,
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(row_to_status(row)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeleteStatusQuery {
+    /// Column that takes over the deleted column's tasks
+    pub move_to: Option<Uuid>,
 }
 
 /// DELETE /api/v1/workspaces/:id/statuses/:status_id
@@ -195,6 +301,7 @@ pub async fn delete_status(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
     Path((workspace_id, status_id)): Path<(Uuid, Uuid)>,
+    Query(params): Query<DeleteStatusQuery>,
 ) -> Result<(), AppError> {
     let role = check_membership(&state, workspace_id, user.id).await?;
 
@@ -202,33 +309,100 @@ pub async fn delete_status(
         return Err(AppError::Forbidden);
     }
 
-    // Check if there are tasks in this status
-    let task_count: (i64,) = sqlx::query_as(
-        "SELECT COUNT(*) FROM tasks WHERE status_id = $1",
-    )
-    .bind(status_id)
-    .fetch_one(&state.db)
-    .await?;
+    if let Some(move_to) = params.move_to {
+        if move_to == status_id {
+            return Err(AppError::Validation(
+                "Tasks can't move to the column being deleted".to_string(),
+            ));
+        }
+
+        let target: Option<(Uuid,)> =
+            sqlx::query_as("SELECT id FROM task_statuses WHERE id = $1 AND workspace_id = $2")
+                .bind(move_to)
+                .bind(workspace_id)
+                .fetch_optional(&state.db)
+                .await?;
+
+        if target.is_none() {
+            return Err(AppError::Validation(
+                "Invalid status for this workspace".to_string(),
+            ));
+        }
+    } else {
+        // Check if there are tasks in this status, trashed ones still reference it
+        let (live_count, trashed_count): (i64, i64) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*) FILTER (WHERE deleted_at IS NULL),
+                   COUNT(*) FILTER (WHERE deleted_at IS NOT NULL)
+            FROM tasks WHERE status_id = $1
+            "#,
+        )
+        .bind(status_id)
+        .fetch_one(&state.db)
+        .await?;
 
-    if task_count.0 > 0 {
-        return Err(AppError::Conflict(
-            "Cannot delete status with existing tasks. Move or delete tasks first.".to_string(),
-        ));
+        if live_count > 0 {
+            return Err(AppError::Conflict(
+                "Cannot delete status with existing tasks. Move them first or pass move_to.".to_string(),
+            ));
+        }
+        if trashed_count > 0 {
+            return Err(AppError::Conflict(
+                "Cannot delete status while trashed tasks still use it. Restore or purge them first, or pass move_to."
+                    .to_string(),
+            ));
+        }
     }
 
-    // Verify status belongs to workspace and delete
-    let result = sqlx::query(
-        "DELETE FROM task_statuses WHERE id = $1 AND workspace_id = $2",
+    let mut tx = state.db.begin().await?;
+
+    // Locking the column holds off tasks being created in or moved into it
+    let existing: Option<(Uuid,)> = sqlx::query_as(
+        "SELECT id FROM task_statuses WHERE id = $1 AND workspace_id = $2 FOR UPDATE",
     )
     .bind(status_id)
     .bind(workspace_id)
-    .execute(&state.db)
+    .fetch_optional(&mut *tx)
     .await?;
 
-    if result.rows_affected() == 0 {
+    if existing.is_none() {
         return Err(AppError::NotFound);
     }
 
+    if let Some(move_to) = params.move_to {
+        move_column_tasks(&mut tx, workspace_id, status_id, move_to, user.id).await?;
+    }
+
+    let (entity_label,): (String,) =
+        sqlx::query_as("DELETE FROM task_statuses WHERE id = $1 RETURNING name")
+            .bind(status_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+    record_activity(
+        &mut tx,
+        NewActivity {
+            workspace_id,
+            user_id: user.id,
+            entity_type: ActivityEntityType::Status,
+            entity_id: status_id,
+            entity_label,
+            action: ActivityAction::Deleted,
+            changes: None,
+        },
+    )
+    .await?;
+
+    publish(
+        &mut *tx,
+        workspace_id,
+        user.id,
+        WorkspaceEventKind::StatusesChanged,
+    )
+    .await?;
+
+    tx.commit().await?;
+
     Ok(())
 }
 
@@ -253,6 +427,8 @@ pub async fn reorder_statuses(
     // Update positions in a transaction
     let mut tx = state.db.begin().await?;
 
+    let before = column_order(&mut tx, workspace_id).await?;
+
     for (position, status_id) in req.status_ids.iter().enumerate() {
         sqlx::query(
             "UPDATE task_statuses SET position = $1 WHERE id = $2 AND workspace_id = $3",
@@ -264,12 +440,39 @@ pub async fn reorder_statuses(
         .await?;
     }
 
+    let after = column_order(&mut tx, workspace_id).await?;
+    if before != after {
+        let (entity_label, _) = workspace_snapshot(&mut tx, workspace_id).await?;
+        let changes = json!({ "columns": { "old": before, "new": after } });
+        record_activity(
+            &mut tx,
+            NewActivity {
+                workspace_id,
+                user_id: user.id,
+                entity_type: ActivityEntityType::Workspace,
+                entity_id: workspace_id,
+                entity_label,
+                action: ActivityAction::Reordered,
+                changes: Some(changes),
+            },
+        )
+        .await?;
+    }
+
+    publish(
+        &mut *tx,
+        workspace_id,
+        user.id,
+        WorkspaceEventKind::StatusesChanged,
+    )
+    .await?;
+
     tx.commit().await?;
 
     // Return updated list
-    let rows: Vec<(Uuid, Uuid, String, String, Option<String>, i32, bool)> = sqlx::query_as(
+    let rows: Vec<StatusRow> = sqlx::query_as(
         r#"
-        SELECT id, workspace_id, name, slug, color, position, is_done
+        SELECT id, workspace_id, name, slug, color, position, is_done, wip_limit
         FROM task_statuses
         WHERE workspace_id = $1
         ORDER BY position
@@ -279,18 +482,7 @@ pub async fn reorder_statuses(
     .fetch_all(&state.db)
     .await?;
 
-    let statuses = rows
-        .into_iter()
-        .map(|(id, workspace_id, name, slug, color, position, is_done)| TaskStatus {
-            id,
-            workspace_id,
-            name,
-            slug,
-            color,
-            position,
-            is_done,
-        })
-        .collect();
+    let statuses = rows.into_iter().map(row_to_status).collect();
 
     Ok(Json(statuses))
 }
@@ -4,12 +4,14 @@ use axum::{
 };
 use todo_shared::{
     api::{CreateTagRequest, SetTaskTagsRequest, UpdateTagRequest},
-    Tag, WorkspaceRole,
+    ActivityAction, ActivityEntityType, Tag, WorkspaceEventKind, WorkspaceRole,
 };
 use uuid::Uuid;
 
 use crate::auth::AuthUser;
 use crate::error::AppError;
+use crate::events::publish;
+use crate::handlers::activity::{diff, record_activity, task_snapshot, NewActivity};
 use crate::routes::AppState;
 
 async fn check_membership(
@@ -90,6 +92,14 @@ pub async fn create_tag(
     .execute(&state.db)
     .await?;
 
+    publish(
+        &state.db,
+        workspace_id,
+        user.id,
+        WorkspaceEventKind::TagsChanged,
+    )
+    .await?;
+
     Ok(Json(Tag {
         id,
         workspace_id,
@@ -111,15 +121,18 @@ pub async fn update_tag(
         return Err(AppError::Forbidden);
     }
 
+    let name = req.name.non_null("name").map_err(AppError::Validation)?;
+
     // Build dynamic update query
     let mut updates = Vec::new();
     let mut param_idx = 3;
 
-    if req.name.is_some() {
+    if name.is_some() {
         updates.push(format!("name = ${}", param_idx));
         param_idx += 1;
     }
-    if req.color.is_some() {
+    // Sending `null` for the color clears it
+    if req.color.is_set() {
         updates.push(format!("color = ${}", param_idx));
     }
 
@@ -151,11 +164,11 @@ pub async fn update_tag(
         .bind(tag_id)
         .bind(workspace_id);
 
-    if let Some(ref name) = req.name {
+    if let Some(ref name) = name {
         q = q.bind(name);
     }
-    if let Some(ref color) = req.color {
-        q = q.bind(color);
+    if req.color.is_set() {
+        q = q.bind(req.color.value());
     }
 
     let tag = q
@@ -163,6 +176,14 @@ pub async fn update_tag(
         .await?
         .ok_or(AppError::NotFound)?;
 
+    publish(
+        &state.db,
+        workspace_id,
+        user.id,
+        WorkspaceEventKind::TagsChanged,
+    )
+    .await?;
+
     Ok(Json(Tag {
         id: tag.0,
         workspace_id: tag.1,
@@ -193,6 +214,14 @@ pub async fn delete_tag(
         return Err(AppError::NotFound);
     }
 
+    publish(
+        &state.db,
+        workspace_id,
+        user.id,
+        WorkspaceEventKind::TagsChanged,
+    )
+    .await?;
+
     Ok(())
 }
 
@@ -211,7 +240,7 @@ pub async fn set_task_tags(
 
     // Verify task exists
     let exists: Option<(Uuid,)> =
-        sqlx::query_as("SELECT id FROM tasks WHERE id = $1 AND workspace_id = $2")
+        sqlx::query_as("SELECT id FROM tasks WHERE id = $1 AND workspace_id = $2 AND deleted_at IS NULL")
             .bind(task_id)
             .bind(workspace_id)
             .fetch_optional(&state.db)
@@ -221,10 +250,14 @@ pub async fn set_task_tags(
         return Err(AppError::NotFound);
     }
 
+    let mut tx = state.db.begin().await?;
+
+    let (_, before) = task_snapshot(&mut tx, task_id).await?;
+
     // Delete existing task tags
     sqlx::query("DELETE FROM task_tags WHERE task_id = $1")
         .bind(task_id)
-        .execute(&state.db)
+        .execute(&mut *tx)
         .await?;
 
     // Insert new task tags
@@ -232,10 +265,37 @@ pub async fn set_task_tags(
         sqlx::query("INSERT INTO task_tags (task_id, tag_id) VALUES ($1, $2) ON CONFLICT DO NOTHING")
             .bind(task_id)
             .bind(tag_id)
-            .execute(&state.db)
+            .execute(&mut *tx)
             .await?;
     }
 
+    let (entity_label, after) = task_snapshot(&mut tx, task_id).await?;
+    if let Some(changes) = diff(&before, &after) {
+        record_activity(
+            &mut tx,
+            NewActivity {
+                workspace_id,
+                user_id: user.id,
+                entity_type: ActivityEntityType::Task,
+                entity_id: task_id,
+                entity_label,
+                action: ActivityAction::Updated,
+                changes: Some(changes),
+            },
+        )
+        .await?;
+    }
+
+    publish(
+        &mut *tx,
+        workspace_id,
+        user.id,
+        WorkspaceEventKind::TaskChanged { task_id },
+    )
+    .await?;
+
+    tx.commit().await?;
+
     // Return the updated tags
     let tags: Vec<(Uuid, Uuid, String, Option<String>)> = sqlx::query_as(
         r#"
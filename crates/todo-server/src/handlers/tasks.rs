@@ -1,19 +1,40 @@
 use axum::{
     extract::{Path, Query, State},
+    http::{HeaderMap, HeaderName, HeaderValue},
     Extension, Json,
 };
 use chrono::{NaiveDate, Utc};
 use serde::Deserialize;
+use serde_json::Value;
+use sqlx::PgConnection;
 use todo_shared::{
-    api::{CreateTaskRequest, MoveTaskRequest, UpdateTaskRequest},
-    Priority, Tag, Task, WorkspaceRole,
+    api::{
+        BulkTaskOperation, BulkTaskRequest, BulkTaskResponse, CreateTaskRequest, MoveTaskRequest, Patch,
+        UpdateTaskRequest,
+    },
+    ActivityAction, ActivityEntityType, ChecklistProgress, CustomFieldType, CustomFieldValue, Priority, RecurrenceRule, SubtaskProgress, Tag, Task, WorkspaceEventKind, WorkspaceRole,
+    WorkspaceSettings,
 };
 use uuid::Uuid;
 
 use crate::auth::AuthUser;
+use crate::concurrency::{check_version, etag, expected_version, ETag};
 use crate::error::AppError;
+use crate::events::publish;
+use crate::handlers::activity::{diff, record_activity, task_snapshot, NewActivity};
+use crate::handlers::comments::insert_comment;
+use crate::handlers::custom_fields::load_workspace_fields;
+use crate::handlers::notifications::{notify_assigned, notify_mentioned, notify_status_change};
+use crate::handlers::transitions::check_transition;
+use crate::mentions::sync_mentions;
 use crate::routes::AppState;
 
+/// Response header set when a move takes a column over its WIP limit, e.g. `5/4`
+const WIP_EXCEEDED_HEADER: HeaderName = HeaderName::from_static("x-wip-limit-exceeded");
+
+/// Optional WIP warning header on responses that can move tasks between columns
+type WipWarning = Option<[(HeaderName, HeaderValue); 1]>;
+
 /// Helper to check workspace membership and return role
 async fn check_membership(
     state: &AppState,
@@ -38,7 +59,7 @@ async fn verify_task(
     workspace_id: Uuid,
 ) -> Result<(), AppError> {
     let exists: Option<(Uuid,)> = sqlx::query_as(
-        "SELECT id FROM tasks WHERE id = $1 AND workspace_id = $2",
+        "SELECT id FROM tasks WHERE id = $1 AND workspace_id = $2 AND deleted_at IS NULL",
     )
     .bind(task_id)
     .bind(workspace_id)
@@ -73,40 +94,632 @@ async fn verify_status(
     Ok(())
 }
 
-type TaskRow = (
-    Uuid,                          // id
-    Uuid,                          // workspace_id
-    Uuid,                          // status_id
-    String,                        // title
-    Option<String>,                // description
-    Option<Priority>,              // priority
-    Option<NaiveDate>,             // due_date
-    Option<i32>,                   // time_estimate_minutes
-    i32,                           // position
-    Uuid,                          // created_by
-    Option<Uuid>,                  // assigned_to
-    chrono::DateTime<Utc>,         // created_at
-    chrono::DateTime<Utc>,         // updated_at
-    Option<chrono::DateTime<Utc>>, // completed_at
-);
+/// Helper to verify a parent task belongs to workspace and would not create a cycle.
+/// `task_id` is the task being re-parented, if it already exists.
+async fn verify_parent(
+    state: &AppState,
+    parent_id: Uuid,
+    workspace_id: Uuid,
+    task_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    let exists: Option<(Uuid,)> = sqlx::query_as(
+        "SELECT id FROM tasks WHERE id = $1 AND workspace_id = $2 AND deleted_at IS NULL",
+    )
+    .bind(parent_id)
+    .bind(workspace_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    if exists.is_none() {
+        return Err(AppError::Validation(
+            "Invalid parent_id for this workspace".to_string(),
+        ));
+    }
+
+    if let Some(task_id) = task_id {
+        if task_id == parent_id {
+            return Err(AppError::Validation(
+                "A task cannot be its own parent".to_string(),
+            ));
+        }
+
+        // The new parent must not be one of the task's own descendants
+        let (is_descendant,): (bool,) = sqlx::query_as(
+            r#"
+            WITH RECURSIVE descendants AS (
+                SELECT id FROM tasks WHERE parent_id = $1
+                UNION
+                SELECT t.id FROM tasks t INNER JOIN descendants d ON t.parent_id = d.id
+            )
+            SELECT EXISTS (SELECT 1 FROM descendants WHERE id = $2)
+            "#,
+        )
+        .bind(task_id)
+        .bind(parent_id)
+        .fetch_one(&state.db)
+        .await?;
+
+        if is_descendant {
+            return Err(AppError::Validation(
+                "A task cannot be nested under its own subtask".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Helper to issue the next task number in a workspace, returning it with the key prefix.
+/// Locks the workspace row until the surrounding transaction ends, so numbers never repeat.
+async fn next_task_number(conn: &mut PgConnection, workspace_id: Uuid) -> Result<(i32, String), AppError> {
+    let row: (i32, String) = sqlx::query_as(
+        "UPDATE workspaces SET task_seq = task_seq + 1 WHERE id = $1 RETURNING task_seq, task_key_prefix",
+    )
+    .bind(workspace_id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(row)
+}
+
+/// Helper to fill in human-readable keys (e.g. "TODO-123") for a batch of tasks
+async fn populate_task_keys(state: &AppState, tasks: &mut [Task]) -> Result<(), AppError> {
+    if tasks.is_empty() {
+        return Ok(());
+    }
+
+    let workspace_ids: Vec<Uuid> = tasks.iter().map(|t| t.workspace_id).collect();
+    let rows: Vec<(Uuid, String)> = sqlx::query_as(
+        "SELECT id, task_key_prefix FROM workspaces WHERE id = ANY($1)",
+    )
+    .bind(&workspace_ids)
+    .fetch_all(&state.db)
+    .await?;
+
+    let prefixes: std::collections::HashMap<Uuid, String> = rows.into_iter().collect();
+
+    for task in tasks {
+        if let Some(prefix) = prefixes.get(&task.workspace_id) {
+            task.key = format!("{}-{}", prefix, task.number);
+        }
+    }
+
+    Ok(())
+}
+
+/// Helper to fill in custom field values for a batch of tasks
+async fn populate_custom_fields(state: &AppState, tasks: &mut [Task]) -> Result<(), AppError> {
+    if tasks.is_empty() {
+        return Ok(());
+    }
+
+    let task_ids: Vec<Uuid> = tasks.iter().map(|t| t.id).collect();
+    let rows: Vec<(Uuid, Uuid, serde_json::Value)> = sqlx::query_as(
+        r#"
+        SELECT v.task_id, v.field_id, v.value
+        FROM task_custom_field_values v
+        INNER JOIN custom_fields f ON f.id = v.field_id
+        WHERE v.task_id = ANY($1)
+        ORDER BY f.position, f.name
+        "#,
+    )
+    .bind(&task_ids)
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut values_by_task: std::collections::HashMap<Uuid, Vec<CustomFieldValue>> =
+        std::collections::HashMap::new();
+    for (task_id, field_id, value) in rows {
+        values_by_task
+            .entry(task_id)
+            .or_default()
+            .push(CustomFieldValue { field_id, value });
+    }
+
+    for task in tasks {
+        task.custom_fields = values_by_task.remove(&task.id).unwrap_or_default();
+    }
+
+    Ok(())
+}
+
+/// Per-task user lists stored in join tables
+#[derive(Debug, Clone, Copy)]
+enum TaskPeople {
+    Assignees,
+    Watchers,
+}
+
+impl TaskPeople {
+    fn table(self) -> &'static str {
+        match self {
+            Self::Assignees => "task_assignees",
+            Self::Watchers => "task_watchers",
+        }
+    }
+}
+
+/// Helper to drop duplicate user ids and verify they all belong to the workspace
+async fn verify_members(
+    state: &AppState,
+    workspace_id: Uuid,
+    user_ids: Vec<Uuid>,
+) -> Result<Vec<Uuid>, AppError> {
+    let mut unique: Vec<Uuid> = Vec::with_capacity(user_ids.len());
+    for id in user_ids {
+        if !unique.contains(&id) {
+            unique.push(id);
+        }
+    }
+
+    if unique.is_empty() {
+        return Ok(unique);
+    }
+
+    let (members,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM workspace_members WHERE workspace_id = $1 AND user_id = ANY($2)",
+    )
+    .bind(workspace_id)
+    .bind(&unique)
+    .fetch_one(&state.db)
+    .await?;
+
+    if members != unique.len() as i64 {
+        return Err(AppError::Validation(
+            "Assignees and watchers must be workspace members".to_string(),
+        ));
+    }
+    Ok(unique)
+}
+
+/// Helper to replace the assignees or watchers of a task, keeping existing entries.
+/// Returns the users who weren't on the list before.
+async fn set_task_people(
+    conn: &mut PgConnection,
+    people: TaskPeople,
+    task_id: Uuid,
+    user_ids: &[Uuid],
+) -> Result<Vec<Uuid>, AppError> {
+    let table = people.table();
+
+    sqlx::query(&format!("DELETE FROM {table} WHERE task_id = $1 AND user_id <> ALL($2)"))
+        .bind(task_id)
+        .bind(user_ids)
+        .execute(&mut *conn)
+        .await?;
+
+    let added: Vec<(Uuid,)> = sqlx::query_as(&format!(
+        "INSERT INTO {table} (task_id, user_id, created_at) SELECT $1, UNNEST($2::uuid[]), $3 ON CONFLICT DO NOTHING RETURNING user_id"
+    ))
+    .bind(task_id)
+    .bind(user_ids)
+    .bind(Utc::now())
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(added.into_iter().map(|(id,)| id).collect())
+}
+
+/// Helper to fill in tags for a batch of tasks
+async fn populate_tags(state: &AppState, tasks: &mut [Task]) -> Result<(), AppError> {
+    if tasks.is_empty() {
+        return Ok(());
+    }
+
+    let task_ids: Vec<Uuid> = tasks.iter().map(|t| t.id).collect();
+    let tags: Vec<(Uuid, Uuid, Uuid, String, Option<String>)> = sqlx::query_as(
+        r#"
+        SELECT tt.task_id, t.id, t.workspace_id, t.name, t.color
+        FROM tags t
+        INNER JOIN task_tags tt ON t.id = tt.tag_id
+        WHERE tt.task_id = ANY($1)
+        ORDER BY t.name
+        "#,
+    )
+    .bind(&task_ids)
+    .fetch_all(&state.db)
+    .await?;
+
+    // Group tags by task_id
+    let mut tags_by_task: std::collections::HashMap<Uuid, Vec<Tag>> =
+        std::collections::HashMap::new();
+    for (task_id, id, workspace_id, name, color) in tags {
+        tags_by_task
+            .entry(task_id)
+            .or_default()
+            .push(Tag { id, workspace_id, name, color });
+    }
+
+    for task in tasks {
+        if let Some(task_tags) = tags_by_task.remove(&task.id) {
+            task.tags = task_tags;
+        }
+    }
+
+    Ok(())
+}
+
+/// Helper to fill in assignees and watchers for a batch of tasks
+async fn populate_people(state: &AppState, tasks: &mut [Task]) -> Result<(), AppError> {
+    if tasks.is_empty() {
+        return Ok(());
+    }
+
+    let task_ids: Vec<Uuid> = tasks.iter().map(|t| t.id).collect();
+    let rows: Vec<(Uuid, Uuid, bool)> = sqlx::query_as(
+        r#"
+        SELECT task_id, user_id, is_assignee FROM (
+            SELECT task_id, user_id, created_at, TRUE AS is_assignee
+            FROM task_assignees WHERE task_id = ANY($1)
+            UNION ALL
+            SELECT task_id, user_id, created_at, FALSE AS is_assignee
+            FROM task_watchers WHERE task_id = ANY($1)
+        ) people
+        ORDER BY created_at, user_id
+        "#,
+    )
+    .bind(&task_ids)
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut people_by_task: std::collections::HashMap<Uuid, (Vec<Uuid>, Vec<Uuid>)> =
+        std::collections::HashMap::new();
+    for (task_id, user_id, is_assignee) in rows {
+        let (assignees, watchers) = people_by_task.entry(task_id).or_default();
+        if is_assignee {
+            assignees.push(user_id);
+        } else {
+            watchers.push(user_id);
+        }
+    }
+
+    for task in tasks {
+        let (assignees, watchers) = people_by_task.remove(&task.id).unwrap_or_default();
+        task.assignees = assignees;
+        task.watchers = watchers;
+    }
+
+    Ok(())
+}
+
+/// Helper to fill in total logged minutes for a batch of tasks
+async fn populate_logged_minutes(state: &AppState, tasks: &mut [Task]) -> Result<(), AppError> {
+    if tasks.is_empty() {
+        return Ok(());
+    }
+
+    let task_ids: Vec<Uuid> = tasks.iter().map(|t| t.id).collect();
+    let rows: Vec<(Uuid, i64)> = sqlx::query_as(
+        "SELECT task_id, SUM(minutes)::bigint FROM work_logs WHERE task_id = ANY($1) GROUP BY task_id",
+    )
+    .bind(&task_ids)
+    .fetch_all(&state.db)
+    .await?;
+
+    let minutes_by_task: std::collections::HashMap<Uuid, i64> = rows.into_iter().collect();
+
+    for task in tasks {
+        task.logged_minutes = minutes_by_task.get(&task.id).copied().unwrap_or(0);
+    }
+
+    Ok(())
+}
+
+/// Helper to fill in subtask completion rollups for a batch of tasks
+async fn populate_subtask_progress(state: &AppState, tasks: &mut [Task]) -> Result<(), AppError> {
+    if tasks.is_empty() {
+        return Ok(());
+    }
+
+    let task_ids: Vec<Uuid> = tasks.iter().map(|t| t.id).collect();
+    let rows: Vec<(Uuid, i64, i64)> = sqlx::query_as(
+        r#"
+        SELECT t.parent_id, COUNT(*), COUNT(*) FILTER (WHERE s.is_done)
+        FROM tasks t
+        INNER JOIN task_statuses s ON s.id = t.status_id
+        WHERE t.parent_id = ANY($1) AND t.deleted_at IS NULL
+        GROUP BY t.parent_id
+        "#,
+    )
+    .bind(&task_ids)
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut progress_by_task: std::collections::HashMap<Uuid, SubtaskProgress> = rows
+        .into_iter()
+        .map(|(parent_id, total, done)| (parent_id, SubtaskProgress { done, total }))
+        .collect();
+
+    for task in tasks {
+        task.subtask_progress = progress_by_task.remove(&task.id);
+    }
+
+    Ok(())
+}
+
+/// Helper to fill in checklist completion for a batch of tasks
+async fn populate_checklist_progress(state: &AppState, tasks: &mut [Task]) -> Result<(), AppError> {
+    if tasks.is_empty() {
+        return Ok(());
+    }
+
+    let task_ids: Vec<Uuid> = tasks.iter().map(|t| t.id).collect();
+    let rows: Vec<(Uuid, i64, i64)> = sqlx::query_as(
+        r#"
+        SELECT task_id, COUNT(*), COUNT(*) FILTER (WHERE is_checked)
+        FROM task_checklist_items
+        WHERE task_id = ANY($1)
+        GROUP BY task_id
+        "#,
+    )
+    .bind(&task_ids)
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut progress_by_task: std::collections::HashMap<Uuid, ChecklistProgress> = rows
+        .into_iter()
+        .map(|(task_id, total, checked)| (task_id, ChecklistProgress { checked, total }))
+        .collect();
+
+    for task in tasks {
+        task.checklist_progress = progress_by_task.remove(&task.id);
+    }
+
+    Ok(())
+}
+
+/// Helper to fill in unfinished blockers for a batch of tasks
+async fn populate_blockers(state: &AppState, tasks: &mut [Task]) -> Result<(), AppError> {
+    if tasks.is_empty() {
+        return Ok(());
+    }
+
+    let task_ids: Vec<Uuid> = tasks.iter().map(|t| t.id).collect();
+    let rows: Vec<(Uuid, Uuid)> = sqlx::query_as(
+        r#"
+        SELECT d.blocked_task_id, d.blocking_task_id
+        FROM task_dependencies d
+        INNER JOIN tasks t ON t.id = d.blocking_task_id
+        INNER JOIN task_statuses s ON s.id = t.status_id
+        WHERE d.blocked_task_id = ANY($1) AND NOT s.is_done AND t.deleted_at IS NULL
+        "#,
+    )
+    .bind(&task_ids)
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut blockers_by_task: std::collections::HashMap<Uuid, Vec<Uuid>> =
+        std::collections::HashMap::new();
+    for (blocked_task_id, blocking_task_id) in rows {
+        blockers_by_task
+            .entry(blocked_task_id)
+            .or_default()
+            .push(blocking_task_id);
+    }
+
+    for task in tasks {
+        task.blocked_by = blockers_by_task.remove(&task.id).unwrap_or_default();
+    }
+
+    Ok(())
+}
+
+/// Helper to refuse completing a task that still has unfinished blockers
+async fn ensure_not_blocked(state: &AppState, task_id: Uuid) -> Result<(), AppError> {
+    let (blockers,): (i64,) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*)
+        FROM task_dependencies d
+        INNER JOIN tasks t ON t.id = d.blocking_task_id
+        INNER JOIN task_statuses s ON s.id = t.status_id
+        WHERE d.blocked_task_id = $1 AND NOT s.is_done AND t.deleted_at IS NULL
+        "#,
+    )
+    .bind(task_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    if blockers > 0 {
+        return Err(AppError::Conflict(format!(
+            "Task is blocked by {} unfinished task(s)",
+            blockers
+        )));
+    }
+    Ok(())
+}
+
+/// Helper to check whether a task currently sits in a "done" status
+async fn is_task_done(state: &AppState, task_id: Uuid) -> Result<bool, AppError> {
+    let (is_done,): (bool,) = sqlx::query_as(
+        r#"
+        SELECT s.is_done
+        FROM tasks t
+        INNER JOIN task_statuses s ON s.id = t.status_id
+        WHERE t.id = $1
+        "#,
+    )
+    .bind(task_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(is_done)
+}
+
+/// Helper to validate a recurrence rule from a request
+fn validate_recurrence(rule: Option<&RecurrenceRule>) -> Result<(), AppError> {
+    match rule {
+        Some(rule) => rule.validate().map_err(AppError::Validation),
+        None => Ok(()),
+    }
+}
+
+/// Helper to create the next occurrence of a recurring task that was just completed.
+/// The rule moves to the new task, so reopening and re-completing the old one
+/// doesn't spawn duplicates.
+async fn spawn_next_occurrence(
+    conn: &mut PgConnection,
+    task: &mut Task,
+    user_id: Uuid,
+) -> Result<(), AppError> {
+    // Pin monthly rules to the original day before the first clamped date
+    let rule = match task.recurrence {
+        Some(ref rule) => rule.anchored(task.due_date),
+        None => return Ok(()),
+    };
+
+    // Series has ended
+    let due_date = match rule.next_occurrence(task.due_date, Utc::now().date_naive()) {
+        Some(date) => date,
+        None => return Ok(()),
+    };
+
+    // New occurrence starts in the first open column
+    let status: Option<(Uuid,)> = sqlx::query_as(
+        "SELECT id FROM task_statuses WHERE workspace_id = $1 AND NOT is_done ORDER BY position LIMIT 1",
+    )
+    .bind(task.workspace_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    let status_id = match status {
+        Some((id,)) => id,
+        None => return Ok(()),
+    };
+
+    let (max_pos,): (i32,) = sqlx::query_as(
+        "SELECT COALESCE(MAX(position), -1) FROM tasks WHERE status_id = $1 AND deleted_at IS NULL",
+    )
+    .bind(status_id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let id = Uuid::new_v4();
+    let now = Utc::now();
+    let (number, _) = next_task_number(&mut *conn, task.workspace_id).await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO tasks (id, workspace_id, number, status_id, title, description, priority,
+                          due_date, time_estimate_minutes, position, created_by,
+                          created_at, updated_at, parent_id, recurrence)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+        "#,
+    )
+    .bind(id)
+    .bind(task.workspace_id)
+    .bind(number)
+    .bind(status_id)
+    .bind(&task.title)
+    .bind(&task.description)
+    .bind(&task.priority)
+    .bind(due_date)
+    .bind(task.time_estimate_minutes)
+    .bind(max_pos + 1)
+    .bind(task.created_by)
+    .bind(now)
+    .bind(now)
+    .bind(task.parent_id)
+    .bind(serde_json::to_value(&rule).unwrap_or_default())
+    .execute(&mut *conn)
+    .await?;
+
+    // Carry tags, people and custom field values over to the new occurrence
+    sqlx::query("INSERT INTO task_tags (task_id, tag_id) SELECT $1, tag_id FROM task_tags WHERE task_id = $2")
+        .bind(id)
+        .bind(task.id)
+        .execute(&mut *conn)
+        .await?;
+
+    for table in [TaskPeople::Assignees.table(), TaskPeople::Watchers.table()] {
+        sqlx::query(&format!(
+            "INSERT INTO {table} (task_id, user_id, created_at) SELECT $1, user_id, created_at FROM {table} WHERE task_id = $2"
+        ))
+        .bind(id)
+        .bind(task.id)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO task_custom_field_values (task_id, field_id, value, updated_at)
+        SELECT $1, field_id, value, $3 FROM task_custom_field_values WHERE task_id = $2
+        "#,
+    )
+    .bind(id)
+    .bind(task.id)
+    .bind(now)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query("UPDATE tasks SET recurrence = NULL, version = version + 1 WHERE id = $1")
+        .bind(task.id)
+        .execute(&mut *conn)
+        .await?;
+    task.recurrence = None;
+    task.version += 1;
+
+    publish(
+        &mut *conn,
+        task.workspace_id,
+        user_id,
+        WorkspaceEventKind::TaskChanged { task_id: id },
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[derive(sqlx::FromRow)]
+struct TaskRow {
+    id: Uuid,
+    workspace_id: Uuid,
+    number: i32,
+    status_id: Uuid,
+    title: String,
+    description: Option<String>,
+    priority: Option<Priority>,
+    due_date: Option<NaiveDate>,
+    time_estimate_minutes: Option<i32>,
+    position: i32,
+    created_by: Uuid,
+    created_at: chrono::DateTime<Utc>,
+    updated_at: chrono::DateTime<Utc>,
+    version: i32,
+    completed_at: Option<chrono::DateTime<Utc>>,
+    parent_id: Option<Uuid>,
+    recurrence: Option<serde_json::Value>,
+}
 
 fn row_to_task(row: TaskRow) -> Task {
     Task {
-        id: row.0,
-        workspace_id: row.1,
-        status_id: row.2,
-        title: row.3,
-        description: row.4,
-        priority: row.5,
-        due_date: row.6,
-        time_estimate_minutes: row.7,
-        position: row.8,
-        created_by: row.9,
-        assigned_to: row.10,
-        created_at: row.11,
-        updated_at: row.12,
-        completed_at: row.13,
+        id: row.id,
+        workspace_id: row.workspace_id,
+        number: row.number,
+        key: String::new(), // Key will be populated separately
+        status_id: row.status_id,
+        title: row.title,
+        description: row.description,
+        priority: row.priority,
+        due_date: row.due_date,
+        time_estimate_minutes: row.time_estimate_minutes,
+        logged_minutes: 0, // Logged time will be populated separately
+        position: row.position,
+        created_by: row.created_by,
+        assignees: Vec::new(),
+        watchers: Vec::new(),
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+        version: row.version,
+        completed_at: row.completed_at,
+        parent_id: row.parent_id,
+        recurrence: row.recurrence.and_then(|v| serde_json::from_value(v).ok()),
         tags: Vec::new(), // Tags will be populated separately
+        custom_fields: Vec::new(),
+        blocked_by: Vec::new(),
+        subtask_progress: None,
+        checklist_progress: None,
+        subtasks: Vec::new(),
     }
 }
 
@@ -123,6 +736,79 @@ pub struct TaskListQuery {
     pub page: Option<u32>,
     pub limit: Option<u32>,
     pub tag_ids: Option<String>,  // comma-separated UUIDs
+    pub parent_id: Option<Uuid>,
+    pub top_level: Option<bool>,
+    pub custom_fields: Option<String>, // JSON object: field id -> value
+}
+
+/// Argument bound for a custom field filter, depending on the field type
+enum CustomFieldFilterArg {
+    /// ILIKE pattern against the text value
+    Pattern(String),
+    /// Exact JSON value, or for multi-select the options that must all be present
+    Json(serde_json::Value),
+}
+
+struct CustomFieldFilter {
+    field_id: Uuid,
+    field_type: CustomFieldType,
+    arg: CustomFieldFilterArg,
+}
+
+/// Helper to turn the `custom_fields` query parameter into typed filters
+async fn parse_custom_field_filters(
+    state: &AppState,
+    workspace_id: Uuid,
+    raw: Option<&str>,
+) -> Result<Vec<CustomFieldFilter>, AppError> {
+    let raw = match raw {
+        Some(raw) if !raw.trim().is_empty() => raw,
+        _ => return Ok(Vec::new()),
+    };
+
+    let requested: std::collections::BTreeMap<Uuid, String> = serde_json::from_str(raw)
+        .map_err(|_| AppError::Validation("custom_fields must be a JSON object of field id to value".to_string()))?;
+
+    let fields = load_workspace_fields(state, workspace_id).await?;
+    let mut filters = Vec::new();
+
+    for (field_id, input) in requested {
+        let field = fields
+            .iter()
+            .find(|f| f.id == field_id)
+            .ok_or_else(|| AppError::Validation("Invalid custom field for this workspace".to_string()))?;
+
+        let value = field.parse_input(&input).map_err(AppError::Validation)?;
+        if value.is_null() {
+            continue;
+        }
+
+        let arg = match field.field_type {
+            CustomFieldType::Text => {
+                CustomFieldFilterArg::Pattern(format!("%{}%", escape_like(input.trim())))
+            }
+            _ => CustomFieldFilterArg::Json(value),
+        };
+        filters.push(CustomFieldFilter {
+            field_id,
+            field_type: field.field_type,
+            arg,
+        });
+    }
+
+    Ok(filters)
+}
+
+/// Escape LIKE wildcards so the input matches literally (pair with `ESCAPE '\'`)
+fn escape_like(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for c in input.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -156,8 +842,14 @@ pub async fn list_tasks(
         if ids.is_empty() { None } else { Some(ids) }
     });
 
+    let custom_field_filters =
+        parse_custom_field_filters(&state, workspace_id, params.custom_fields.as_deref()).await?;
+
     // Build dynamic query
-    let mut conditions = vec!["workspace_id = $1".to_string()];
+    let mut conditions = vec![
+        "workspace_id = $1".to_string(),
+        "deleted_at IS NULL".to_string(),
+    ];
     let mut param_idx = 2;
 
     if params.status_id.is_some() {
@@ -169,7 +861,10 @@ pub async fn list_tasks(
         param_idx += 1;
     }
     if params.assigned_to.is_some() {
-        conditions.push(format!("assigned_to = ${}", param_idx));
+        conditions.push(format!(
+            "EXISTS (SELECT 1 FROM task_assignees WHERE task_id = tasks.id AND user_id = ${})",
+            param_idx
+        ));
         param_idx += 1;
     }
     if params.due_before.is_some() {
@@ -195,7 +890,28 @@ pub async fn list_tasks(
         ));
         param_idx += 1;
     }
-    let _ = param_idx; // Suppress unused warning
+    if params.parent_id.is_some() {
+        conditions.push(format!("parent_id = ${}", param_idx));
+        param_idx += 1;
+    }
+    if params.top_level == Some(true) {
+        conditions.push("parent_id IS NULL".to_string());
+    }
+    for filter in &custom_field_filters {
+        let (matcher, escape) = match filter.field_type {
+            CustomFieldType::Text => ("value #>> '{}' ILIKE", r" ESCAPE '\'"),
+            CustomFieldType::MultiSelect => ("value @>", ""),
+            _ => ("value =", ""),
+        };
+        conditions.push(format!(
+            "EXISTS (SELECT 1 FROM task_custom_field_values WHERE task_id = tasks.id AND field_id = ${} AND {} ${}{})",
+            param_idx,
+            matcher,
+            param_idx + 1,
+            escape
+        ));
+        param_idx += 2;
+    }
 
     let where_clause = conditions.join(" AND ");
 
@@ -239,15 +955,26 @@ pub async fn list_tasks(
     if let Some(ref tag_ids) = parsed_tag_ids {
         count_builder = count_builder.bind(tag_ids);
     }
+    if let Some(ref parent_id) = params.parent_id {
+        count_builder = count_builder.bind(parent_id);
+    }
+    for filter in &custom_field_filters {
+        count_builder = count_builder.bind(filter.field_id);
+        count_builder = match filter.arg {
+            CustomFieldFilterArg::Pattern(ref pattern) => count_builder.bind(pattern.clone()),
+            CustomFieldFilterArg::Json(ref value) => count_builder.bind(value.clone()),
+        };
+    }
 
     let (total,): (i64,) = count_builder.fetch_one(&state.db).await?;
 
     // Fetch tasks
     let select_query = format!(
         r#"
-        SELECT id, workspace_id, status_id, title, description,
-               priority as "priority: Priority", due_date, time_estimate_minutes,
-               position, created_by, assigned_to, created_at, updated_at, completed_at
+        SELECT id, workspace_id, number, status_id, title, description,
+               priority, due_date, time_estimate_minutes,
+               position, created_by, created_at, updated_at, version, completed_at,
+               parent_id, recurrence
         FROM tasks
         WHERE {}
         ORDER BY {} {}
@@ -280,45 +1007,30 @@ pub async fn list_tasks(
     if let Some(ref tag_ids) = parsed_tag_ids {
         select_builder = select_builder.bind(tag_ids);
     }
+    if let Some(ref parent_id) = params.parent_id {
+        select_builder = select_builder.bind(parent_id);
+    }
+    for filter in &custom_field_filters {
+        select_builder = select_builder.bind(filter.field_id);
+        select_builder = match filter.arg {
+            CustomFieldFilterArg::Pattern(ref pattern) => select_builder.bind(pattern.clone()),
+            CustomFieldFilterArg::Json(ref value) => select_builder.bind(value.clone()),
+        };
+    }
 
     select_builder = select_builder.bind(limit as i64).bind(offset as i64);
 
     let rows = select_builder.fetch_all(&state.db).await?;
     let mut tasks: Vec<Task> = rows.into_iter().map(row_to_task).collect();
 
-    // Fetch tags for all tasks in bulk
-    if !tasks.is_empty() {
-        let task_ids: Vec<Uuid> = tasks.iter().map(|t| t.id).collect();
-        let tags: Vec<(Uuid, Uuid, Uuid, String, Option<String>)> = sqlx::query_as(
-            r#"
-            SELECT tt.task_id, t.id, t.workspace_id, t.name, t.color
-            FROM tags t
-            INNER JOIN task_tags tt ON t.id = tt.tag_id
-            WHERE tt.task_id = ANY($1)
-            ORDER BY t.name
-            "#,
-        )
-        .bind(&task_ids)
-        .fetch_all(&state.db)
-        .await?;
-
-        // Group tags by task_id
-        let mut tags_by_task: std::collections::HashMap<Uuid, Vec<Tag>> =
-            std::collections::HashMap::new();
-        for (task_id, id, workspace_id, name, color) in tags {
-            tags_by_task
-                .entry(task_id)
-                .or_default()
-                .push(Tag { id, workspace_id, name, color });
-        }
-
-        // Assign tags to tasks
-        for task in &mut tasks {
-            if let Some(task_tags) = tags_by_task.remove(&task.id) {
-                task.tags = task_tags;
-            }
-        }
-    }
+    populate_tags(&state, &mut tasks).await?;
+    populate_task_keys(&state, &mut tasks).await?;
+    populate_people(&state, &mut tasks).await?;
+    populate_logged_minutes(&state, &mut tasks).await?;
+    populate_custom_fields(&state, &mut tasks).await?;
+    populate_subtask_progress(&state, &mut tasks).await?;
+    populate_checklist_progress(&state, &mut tasks).await?;
+    populate_blockers(&state, &mut tasks).await?;
 
     Ok(Json(TaskListResponse {
         tasks,
@@ -348,12 +1060,22 @@ pub async fn create_task(
     // Verify status belongs to workspace
     verify_status(&state, req.status_id, workspace_id).await?;
 
+    if let Some(parent_id) = req.parent_id {
+        verify_parent(&state, parent_id, workspace_id, None).await?;
+    }
+
+    validate_recurrence(req.recurrence.as_ref())?;
+    let recurrence_json = req.recurrence.as_ref().map(|r| serde_json::to_value(r).unwrap_or_default());
+
+    let assignees = verify_members(&state, workspace_id, req.assignees).await?;
+    let watchers = verify_members(&state, workspace_id, req.watchers).await?;
+
     let id = Uuid::new_v4();
     let now = Utc::now();
 
     // Get max position in status
     let (max_pos,): (i32,) = sqlx::query_as(
-        "SELECT COALESCE(MAX(position), -1) FROM tasks WHERE status_id = $1",
+        "SELECT COALESCE(MAX(position), -1) FROM tasks WHERE status_id = $1 AND deleted_at IS NULL",
     )
     .bind(req.status_id)
     .fetch_one(&state.db)
@@ -361,16 +1083,20 @@ pub async fn create_task(
 
     let position = max_pos + 1;
 
+    let mut tx = state.db.begin().await?;
+    let (number, key_prefix) = next_task_number(&mut tx, workspace_id).await?;
+
     sqlx::query(
         r#"
-        INSERT INTO tasks (id, workspace_id, status_id, title, description, priority,
+        INSERT INTO tasks (id, workspace_id, number, status_id, title, description, priority,
                           due_date, time_estimate_minutes, position, created_by,
-                          assigned_to, created_at, updated_at)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+                          created_at, updated_at, parent_id, recurrence)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
         "#,
     )
     .bind(id)
     .bind(workspace_id)
+    .bind(number)
     .bind(req.status_id)
     .bind(&req.title)
     .bind(&req.description)
@@ -379,28 +1105,74 @@ pub async fn create_task(
     .bind(req.time_estimate_minutes)
     .bind(position)
     .bind(user.id)
-    .bind(req.assigned_to)
     .bind(now)
     .bind(now)
-    .execute(&state.db)
+    .bind(req.parent_id)
+    .bind(&recurrence_json)
+    .execute(&mut *tx)
     .await?;
 
+    let assigned = set_task_people(&mut tx, TaskPeople::Assignees, id, &assignees).await?;
+    set_task_people(&mut tx, TaskPeople::Watchers, id, &watchers).await?;
+    notify_assigned(&mut tx, workspace_id, id, user.id, &assigned).await?;
+
+    if let Some(ref description) = req.description {
+        let mentioned = sync_mentions(&mut tx, workspace_id, id, None, user.id, description).await?;
+        notify_mentioned(&mut tx, workspace_id, id, user.id, &mentioned, description).await?;
+    }
+
+    record_activity(
+        &mut tx,
+        NewActivity {
+            workspace_id,
+            user_id: user.id,
+            entity_type: ActivityEntityType::Task,
+            entity_id: id,
+            entity_label: format!("{}-{} {}", key_prefix, number, req.title),
+            action: ActivityAction::Created,
+            changes: None,
+        },
+    )
+    .await?;
+
+    publish(
+        &mut *tx,
+        workspace_id,
+        user.id,
+        WorkspaceEventKind::TaskChanged { task_id: id },
+    )
+    .await?;
+
+    tx.commit().await?;
+
     Ok(Json(Task {
         id,
         workspace_id,
+        number,
+        key: format!("{}-{}", key_prefix, number),
         status_id: req.status_id,
         title: req.title,
         description: req.description,
         priority: req.priority,
         due_date: req.due_date,
         time_estimate_minutes: req.time_estimate_minutes,
+        logged_minutes: 0,
         position,
         created_by: user.id,
-        assigned_to: req.assigned_to,
+        assignees,
+        watchers,
         created_at: now,
         updated_at: now,
+        version: 1,
         completed_at: None,
+        parent_id: req.parent_id,
+        recurrence: req.recurrence,
         tags: Vec::new(),
+        custom_fields: Vec::new(),
+        blocked_by: Vec::new(),
+        subtask_progress: None,
+        checklist_progress: None,
+        subtasks: Vec::new(),
     }))
 }
 
@@ -409,16 +1181,17 @@ pub async fn get_task(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
     Path((workspace_id, task_id)): Path<(Uuid, Uuid)>,
-) -> Result<Json<Task>, AppError> {
+) -> Result<(ETag, Json<Task>), AppError> {
     check_membership(&state, workspace_id, user.id).await?;
 
     let row: TaskRow = sqlx::query_as(
         r#"
-        SELECT id, workspace_id, status_id, title, description,
-               priority as "priority: Priority", due_date, time_estimate_minutes,
-               position, created_by, assigned_to, created_at, updated_at, completed_at
+        SELECT id, workspace_id, number, status_id, title, description,
+               priority, due_date, time_estimate_minutes,
+               position, created_by, created_at, updated_at, version, completed_at,
+               parent_id, recurrence
         FROM tasks
-        WHERE id = $1 AND workspace_id = $2
+        WHERE id = $1 AND workspace_id = $2 AND deleted_at IS NULL
         "#,
     )
     .bind(task_id)
@@ -448,33 +1221,136 @@ pub async fn get_task(
         .map(|(id, workspace_id, name, color)| Tag { id, workspace_id, name, color })
         .collect();
 
-    Ok(Json(task))
+    // Fetch direct subtasks
+    let subtask_rows: Vec<TaskRow> = sqlx::query_as(
+        r#"
+        SELECT id, workspace_id, number, status_id, title, description,
+               priority, due_date, time_estimate_minutes,
+               position, created_by, created_at, updated_at, version, completed_at,
+               parent_id, recurrence
+        FROM tasks
+        WHERE parent_id = $1 AND deleted_at IS NULL
+        ORDER BY created_at
+        "#,
+    )
+    .bind(task_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut subtasks: Vec<Task> = subtask_rows.into_iter().map(row_to_task).collect();
+    populate_task_keys(&state, &mut subtasks).await?;
+    populate_people(&state, &mut subtasks).await?;
+    populate_logged_minutes(&state, &mut subtasks).await?;
+    populate_custom_fields(&state, &mut subtasks).await?;
+    populate_subtask_progress(&state, &mut subtasks).await?;
+    populate_checklist_progress(&state, &mut subtasks).await?;
+    populate_blockers(&state, &mut subtasks).await?;
+    task.subtasks = subtasks;
+
+    populate_task_keys(&state, std::slice::from_mut(&mut task)).await?;
+    populate_people(&state, std::slice::from_mut(&mut task)).await?;
+    populate_logged_minutes(&state, std::slice::from_mut(&mut task)).await?;
+    populate_custom_fields(&state, std::slice::from_mut(&mut task)).await?;
+    populate_subtask_progress(&state, std::slice::from_mut(&mut task)).await?;
+    populate_checklist_progress(&state, std::slice::from_mut(&mut task)).await?;
+    populate_blockers(&state, std::slice::from_mut(&mut task)).await?;
+
+    Ok((etag(task.version), Json(task)))
+}
+
+/// GET /api/v1/workspaces/:id/tasks/by-key/:key
+pub async fn get_task_by_key(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, key)): Path<(Uuid, String)>,
+) -> Result<(ETag, Json<Task>), AppError> {
+    check_membership(&state, workspace_id, user.id).await?;
+
+    // Keys look like "TODO-123"; the prefix itself may not contain dashes.
+    // Prefixes the workspace used before a rename still match.
+    let (prefix, number) = key.rsplit_once('-').ok_or(AppError::NotFound)?;
+    let number: i32 = number.parse().map_err(|_| AppError::NotFound)?;
+
+    let task_id: Option<(Uuid,)> = sqlx::query_as(
+        r#"
+        SELECT t.id
+        FROM tasks t
+        INNER JOIN workspaces w ON w.id = t.workspace_id
+        WHERE t.workspace_id = $1 AND t.number = $2
+          AND (w.task_key_prefix = UPPER($3)
+               OR EXISTS (SELECT 1 FROM workspace_previous_key_prefixes p
+                          WHERE p.workspace_id = w.id AND p.prefix = UPPER($3)))
+          AND t.deleted_at IS NULL
+        "#,
+    )
+    .bind(workspace_id)
+    .bind(number)
+    .bind(prefix)
+    .fetch_optional(&state.db)
+    .await?;
+
+    let (task_id,) = task_id.ok_or(AppError::NotFound)?;
+
+    get_task(State(state), Extension(user), Path((workspace_id, task_id))).await
 }
 
 /// PATCH /api/v1/workspaces/:id/tasks/:task_id
+/// Honors `If-Match` / `expected_version`, answering 409 if the task changed since
 pub async fn update_task(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
     Path((workspace_id, task_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
     Json(req): Json<UpdateTaskRequest>,
-) -> Result<Json<Task>, AppError> {
+) -> Result<(ETag, WipWarning, Json<Task>), AppError> {
     let role = check_membership(&state, workspace_id, user.id).await?;
 
     if !role.can_edit() {
         return Err(AppError::Forbidden);
     }
 
+    let expected = expected_version(&headers, req.expected_version)?;
+
     verify_task(&state, task_id, workspace_id).await?;
 
+    // Title and status can be changed but not cleared
+    let title = req.title.non_null("title").map_err(AppError::Validation)?;
+    let status_id = req.status_id.non_null("status_id").map_err(AppError::Validation)?;
+
+    if title.as_deref().is_some_and(|t| t.trim().is_empty()) {
+        return Err(AppError::Validation("Task title is required".to_string()));
+    }
+
     // If status_id is being changed, verify the new status
-    if let Some(ref status_id) = req.status_id {
-        verify_status(&state, *status_id, workspace_id).await?;
+    if let Some(status_id) = status_id {
+        verify_status(&state, status_id, workspace_id).await?;
+    }
+
+    if let Patch::Value(parent_id) = req.parent_id {
+        verify_parent(&state, parent_id, workspace_id, Some(task_id)).await?;
     }
 
+    validate_recurrence(req.recurrence.value())?;
+    let recurrence_json = req.recurrence.value().map(|r| serde_json::to_value(r).unwrap_or_default());
+
+    // A cleared list is the same as an empty one
+    let assignees = match req.assignees {
+        Patch::Absent => None,
+        Patch::Null => Some(Vec::new()),
+        Patch::Value(ids) => Some(verify_members(&state, workspace_id, ids).await?),
+    };
+    let watchers = match req.watchers {
+        Patch::Absent => None,
+        Patch::Null => Some(Vec::new()),
+        Patch::Value(ids) => Some(verify_members(&state, workspace_id, ids).await?),
+    };
+
     let now = Utc::now();
+    let was_done = is_task_done(&state, task_id).await?;
+    let mut becomes_done = false;
 
     // Check if moving to a "done" status
-    let completed_at = if let Some(ref status_id) = req.status_id {
+    let completed_at = if let Some(status_id) = status_id {
         let (is_done,): (bool,) = sqlx::query_as(
             "SELECT is_done FROM task_statuses WHERE id = $1",
         )
@@ -483,6 +1359,8 @@ pub async fn update_task(
         .await?;
 
         if is_done {
+            ensure_not_blocked(&state, task_id).await?;
+            becomes_done = !was_done;
             Some(now)
         } else {
             None
@@ -498,38 +1376,180 @@ pub async fn update_task(
         existing
     };
 
+    let mut tx = state.db.begin().await?;
+
+    let wip_warning = match status_id {
+        Some(status_id) => check_wip_limit(&mut tx, workspace_id, status_id, &[task_id]).await?,
+        None => None,
+    };
+
+    // Lock the row so the version can't move between the check and the write
+    let (current_version,): (i32,) =
+        sqlx::query_as("SELECT version FROM tasks WHERE id = $1 FOR UPDATE")
+            .bind(task_id)
+            .fetch_one(&mut *tx)
+            .await?;
+    check_version("Task", current_version, expected)?;
+
+    let (from_status_id,): (Uuid,) = sqlx::query_as("SELECT status_id FROM tasks WHERE id = $1")
+        .bind(task_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    let (_, before) = task_snapshot(&mut tx, task_id).await?;
+
     let row: TaskRow = sqlx::query_as(
         r#"
         UPDATE tasks
         SET title = COALESCE($1, title),
             status_id = COALESCE($2, status_id),
-            description = COALESCE($3, description),
-            priority = COALESCE($4, priority),
-            due_date = COALESCE($5, due_date),
-            time_estimate_minutes = COALESCE($6, time_estimate_minutes),
-            assigned_to = COALESCE($7, assigned_to),
-            updated_at = $8,
-            completed_at = $9
-        WHERE id = $10
-        RETURNING id, workspace_id, status_id, title, description,
-                  priority as "priority: Priority", due_date, time_estimate_minutes,
-                  position, created_by, assigned_to, created_at, updated_at, completed_at
+            description = CASE WHEN $3 THEN $4 ELSE description END,
+            priority = CASE WHEN $5 THEN $6 ELSE priority END,
+            due_date = CASE WHEN $7 THEN $8 ELSE due_date END,
+            time_estimate_minutes = CASE WHEN $9 THEN $10 ELSE time_estimate_minutes END,
+            parent_id = CASE WHEN $11 THEN $12 ELSE parent_id END,
+            recurrence = CASE WHEN $13 THEN $14 ELSE recurrence END,
+            updated_at = $15,
+            completed_at = $16,
+            version = version + 1
+        WHERE id = $17
+        RETURNING id, workspace_id, number, status_id, title, description,
+                  priority, due_date, time_estimate_minutes,
+                  position, created_by, created_at, updated_at, version, completed_at,
+                  parent_id, recurrence
         "#,
     )
-    .bind(&req.title)
-    .bind(req.status_id)
-    .bind(&req.description)
-    .bind(&req.priority)
-    .bind(req.due_date)
-    .bind(req.time_estimate_minutes)
-    .bind(req.assigned_to)
+    .bind(&title)
+    .bind(status_id)
+    .bind(req.description.is_set())
+    .bind(req.description.value())
+    .bind(req.priority.is_set())
+    .bind(req.priority.value())
+    .bind(req.due_date.is_set())
+    .bind(req.due_date.value())
+    .bind(req.time_estimate_minutes.is_set())
+    .bind(req.time_estimate_minutes.value())
+    .bind(req.parent_id.is_set())
+    .bind(req.parent_id.value())
+    .bind(req.recurrence.is_set())
+    .bind(&recurrence_json)
     .bind(now)
     .bind(completed_at)
     .bind(task_id)
-    .fetch_one(&state.db)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let mut task = row_to_task(row);
+
+    if let Some(ref assignees) = assignees {
+        let assigned = set_task_people(&mut tx, TaskPeople::Assignees, task_id, assignees).await?;
+        notify_assigned(&mut tx, workspace_id, task_id, user.id, &assigned).await?;
+    }
+    if let Some(ref watchers) = watchers {
+        set_task_people(&mut tx, TaskPeople::Watchers, task_id, watchers).await?;
+    }
+    if req.description.is_set() {
+        let description = task.description.as_deref().unwrap_or("");
+        let mentioned = sync_mentions(&mut tx, workspace_id, task_id, None, user.id, description).await?;
+        notify_mentioned(&mut tx, workspace_id, task_id, user.id, &mentioned, description).await?;
+    }
+
+    // Checked after the people changes so an update can assign and move at once
+    if let Some(status_id) = status_id.filter(|id| *id != from_status_id) {
+        let comment = req.comment.as_deref();
+        check_transition(&mut tx, role, task_id, from_status_id, status_id, comment).await?;
+
+        if let Some(comment) = comment.filter(|c| !c.trim().is_empty()) {
+            insert_comment(&mut tx, workspace_id, task_id, user.id, comment).await?;
+        }
+
+        notify_status_change(&mut tx, workspace_id, task_id, user.id, from_status_id, status_id)
+            .await?;
+    }
+
+    let (entity_label, after) = task_snapshot(&mut tx, task_id).await?;
+    if let Some(changes) = diff(&before, &after) {
+        record_activity(
+            &mut tx,
+            NewActivity {
+                workspace_id,
+                user_id: user.id,
+                entity_type: ActivityEntityType::Task,
+                entity_id: task_id,
+                entity_label,
+                action: ActivityAction::Updated,
+                changes: Some(changes),
+            },
+        )
+        .await?;
+    }
+
+    publish(
+        &mut *tx,
+        workspace_id,
+        user.id,
+        WorkspaceEventKind::TaskChanged { task_id },
+    )
+    .await?;
+
+    if becomes_done {
+        spawn_next_occurrence(&mut tx, &mut task, user.id).await?;
+    }
+
+    tx.commit().await?;
+
+    populate_task_keys(&state, std::slice::from_mut(&mut task)).await?;
+    populate_people(&state, std::slice::from_mut(&mut task)).await?;
+    populate_logged_minutes(&state, std::slice::from_mut(&mut task)).await?;
+    populate_custom_fields(&state, std::slice::from_mut(&mut task)).await?;
+    populate_subtask_progress(&state, std::slice::from_mut(&mut task)).await?;
+    populate_checklist_progress(&state, std::slice::from_mut(&mut task)).await?;
+    populate_blockers(&state, std::slice::from_mut(&mut task)).await?;
+
+    Ok((etag(task.version), wip_warning, Json(task)))
+}
+
+/// Helper to move a task and its live subtasks to the trash together, sharing a
+/// timestamp so a restore brings the whole branch back. Returns the number of
+/// tasks trashed; 0 if the task was already gone.
+async fn trash_task_tree(
+    conn: &mut PgConnection,
+    workspace_id: Uuid,
+    task_id: Uuid,
+    user_id: Uuid,
+) -> Result<u64, AppError> {
+    let trashed: Vec<(Uuid,)> = sqlx::query_as(
+        r#"
+        WITH RECURSIVE tree AS (
+            SELECT id FROM tasks WHERE id = $1 AND workspace_id = $2 AND deleted_at IS NULL
+            UNION ALL
+            SELECT t.id FROM tasks t
+            INNER JOIN tree ON t.parent_id = tree.id
+            WHERE t.deleted_at IS NULL
+        )
+        UPDATE tasks SET deleted_at = $3, deleted_by = $4
+        WHERE id IN (SELECT id FROM tree)
+        RETURNING id
+        "#,
+    )
+    .bind(task_id)
+    .bind(workspace_id)
+    .bind(Utc::now())
+    .bind(user_id)
+    .fetch_all(&mut *conn)
     .await?;
 
-    Ok(Json(row_to_task(row)))
+    for (id,) in &trashed {
+        publish(
+            &mut *conn,
+            workspace_id,
+            user_id,
+            WorkspaceEventKind::TaskDeleted { task_id: *id },
+        )
+        .await?;
+    }
+
+    Ok(trashed.len() as u64)
 }
 
 /// DELETE /api/v1/workspaces/:id/tasks/:task_id
@@ -544,26 +1564,100 @@ pub async fn delete_task(
         return Err(AppError::Forbidden);
     }
 
-    let result = sqlx::query("DELETE FROM tasks WHERE id = $1 AND workspace_id = $2")
-        .bind(task_id)
-        .bind(workspace_id)
-        .execute(&state.db)
-        .await?;
+    let mut tx = state.db.begin().await?;
 
-    if result.rows_affected() == 0 {
+    if trash_task_tree(&mut tx, workspace_id, task_id, user.id).await? == 0 {
         return Err(AppError::NotFound);
     }
 
+    let (entity_label, _) = task_snapshot(&mut tx, task_id).await?;
+    record_activity(
+        &mut tx,
+        NewActivity {
+            workspace_id,
+            user_id: user.id,
+            entity_type: ActivityEntityType::Task,
+            entity_id: task_id,
+            entity_label,
+            action: ActivityAction::Deleted,
+            changes: None,
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
     Ok(())
 }
 
+/// Helper to check a column's WIP limit before `task_ids` move into it; tasks
+/// already there don't count. Going over the limit is a 409 in workspaces with
+/// strict WIP limits and a warning header otherwise. Call it before locking any
+/// task rows, since it locks the column to keep concurrent moves from both
+/// taking the last slot.
+async fn check_wip_limit(
+    conn: &mut PgConnection,
+    workspace_id: Uuid,
+    status_id: Uuid,
+    task_ids: &[Uuid],
+) -> Result<WipWarning, AppError> {
+    let (name, wip_limit, settings): (String, Option<i32>, Value) = sqlx::query_as(
+        r#"
+        SELECT s.name, s.wip_limit, w.settings
+        FROM task_statuses s
+        INNER JOIN workspaces w ON w.id = s.workspace_id
+        WHERE s.id = $1 AND s.workspace_id = $2
+        FOR UPDATE OF s
+        "#,
+    )
+    .bind(status_id)
+    .bind(workspace_id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let Some(limit) = wip_limit else {
+        return Ok(None);
+    };
+
+    let (current, incoming): (i64, i64) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*) FILTER (WHERE status_id = $1),
+               COUNT(*) FILTER (WHERE status_id <> $1 AND id = ANY($2))
+        FROM tasks
+        WHERE workspace_id = $3 AND deleted_at IS NULL
+        "#,
+    )
+    .bind(status_id)
+    .bind(task_ids)
+    .bind(workspace_id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let total = current + incoming;
+    if incoming == 0 || total <= limit as i64 {
+        return Ok(None);
+    }
+
+    let settings: WorkspaceSettings = serde_json::from_value(settings).unwrap_or_default();
+    if settings.strict_wip_limits {
+        return Err(AppError::Conflict(format!(
+            "\"{}\" would go over its WIP limit ({}/{})",
+            name, total, limit
+        )));
+    }
+
+    Ok(HeaderValue::from_str(&format!("{}/{}", total, limit))
+        .ok()
+        .map(|value| [(WIP_EXCEEDED_HEADER, value)]))
+}
+
 /// POST /api/v1/workspaces/:id/tasks/:task_id/move
 pub async fn move_task(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
     Path((workspace_id, task_id)): Path<(Uuid, Uuid)>,
     Json(req): Json<MoveTaskRequest>,
-) -> Result<Json<Task>, AppError> {
+) -> Result<(WipWarning, Json<Task>), AppError> {
     let role = check_membership(&state, workspace_id, user.id).await?;
 
     if !role.can_edit() {
@@ -573,8 +1667,22 @@ pub async fn move_task(
     verify_task(&state, task_id, workspace_id).await?;
     verify_status(&state, req.status_id, workspace_id).await?;
 
+    let was_done = is_task_done(&state, task_id).await?;
+
     let mut tx = state.db.begin().await?;
 
+    let wip_warning = check_wip_limit(&mut tx, workspace_id, req.status_id, &[task_id]).await?;
+
+    let (from_status_id,): (Uuid,) = sqlx::query_as("SELECT status_id FROM tasks WHERE id = $1")
+        .bind(task_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    let comment = req.comment.as_deref().filter(|c| !c.trim().is_empty());
+    check_transition(&mut tx, role, task_id, from_status_id, req.status_id, comment).await?;
+
+    let (_, before) = task_snapshot(&mut tx, task_id).await?;
+
     // Check if the target status is a "done" status
     let (is_done,): (bool,) = sqlx::query_as(
         "SELECT is_done FROM task_statuses WHERE id = $1",
@@ -583,6 +1691,10 @@ pub async fn move_task(
     .fetch_one(&mut *tx)
     .await?;
 
+    if is_done {
+        ensure_not_blocked(&state, task_id).await?;
+    }
+
     let now = Utc::now();
     let completed_at = if is_done { Some(now) } else { None };
 
@@ -600,7 +1712,7 @@ pub async fn move_task(
     } else {
         // Append to end
         let (max_pos,): (i32,) = sqlx::query_as(
-            "SELECT COALESCE(MAX(position), -1) FROM tasks WHERE status_id = $1",
+            "SELECT COALESCE(MAX(position), -1) FROM tasks WHERE status_id = $1 AND deleted_at IS NULL",
         )
         .bind(req.status_id)
         .fetch_one(&mut *tx)
@@ -611,11 +1723,13 @@ pub async fn move_task(
     let row: TaskRow = sqlx::query_as(
         r#"
         UPDATE tasks
-        SET status_id = $1, position = $2, updated_at = $3, completed_at = $4
+        SET status_id = $1, position = $2, updated_at = $3, completed_at = $4,
+            version = version + 1
         WHERE id = $5
-        RETURNING id, workspace_id, status_id, title, description,
-                  priority as "priority: Priority", due_date, time_estimate_minutes,
-                  position, created_by, assigned_to, created_at, updated_at, completed_at
+        RETURNING id, workspace_id, number, status_id, title, description,
+                  priority, due_date, time_estimate_minutes,
+                  position, created_by, created_at, updated_at, version, completed_at,
+                  parent_id, recurrence
         "#,
     )
     .bind(req.status_id)
@@ -626,7 +1740,515 @@ pub async fn move_task(
     .fetch_one(&mut *tx)
     .await?;
 
+    let mut task = row_to_task(row);
+
+    if let Some(comment) = comment.filter(|_| from_status_id != req.status_id) {
+        insert_comment(&mut tx, workspace_id, task_id, user.id, comment).await?;
+    }
+    notify_status_change(&mut tx, workspace_id, task_id, user.id, from_status_id, req.status_id)
+        .await?;
+
+    // Reordering within a column leaves nothing worth logging
+    let (entity_label, after) = task_snapshot(&mut tx, task_id).await?;
+    if let Some(changes) = diff(&before, &after) {
+        record_activity(
+            &mut tx,
+            NewActivity {
+                workspace_id,
+                user_id: user.id,
+                entity_type: ActivityEntityType::Task,
+                entity_id: task_id,
+                entity_label,
+                action: ActivityAction::Moved,
+                changes: Some(changes),
+            },
+        )
+        .await?;
+    }
+
+    publish(
+        &mut *tx,
+        workspace_id,
+        user.id,
+        WorkspaceEventKind::TaskChanged { task_id },
+    )
+    .await?;
+
+    if is_done && !was_done {
+        spawn_next_occurrence(&mut tx, &mut task, user.id).await?;
+    }
+
+    tx.commit().await?;
+
+    populate_task_keys(&state, std::slice::from_mut(&mut task)).await?;
+    populate_people(&state, std::slice::from_mut(&mut task)).await?;
+    populate_logged_minutes(&state, std::slice::from_mut(&mut task)).await?;
+    populate_custom_fields(&state, std::slice::from_mut(&mut task)).await?;
+    populate_subtask_progress(&state, std::slice::from_mut(&mut task)).await?;
+    populate_checklist_progress(&state, std::slice::from_mut(&mut task)).await?;
+    populate_blockers(&state, std::slice::from_mut(&mut task)).await?;
+
+    Ok((wip_warning, Json(task)))
+}
+
+/// Most tasks a single bulk request may change
+const MAX_BULK_TASKS: usize = 200;
+
+/// Helper to check that tags exist in the workspace, dropping duplicates
+async fn verify_tags(state: &AppState, workspace_id: Uuid, tag_ids: Vec<Uuid>) -> Result<Vec<Uuid>, AppError> {
+    let mut unique: Vec<Uuid> = Vec::with_capacity(tag_ids.len());
+    for id in tag_ids {
+        if !unique.contains(&id) {
+            unique.push(id);
+        }
+    }
+
+    let (found,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM tags WHERE workspace_id = $1 AND id = ANY($2)")
+            .bind(workspace_id)
+            .bind(&unique)
+            .fetch_one(&state.db)
+            .await?;
+
+    if found != unique.len() as i64 {
+        return Err(AppError::Validation("Tags must belong to the workspace".to_string()));
+    }
+    Ok(unique)
+}
+
+/// Helper to apply one bulk operation to a single task inside the bulk transaction.
+/// Returns the task row when the operation rewrote it.
+async fn apply_bulk_operation(
+    conn: &mut PgConnection,
+    workspace_id: Uuid,
+    task_id: Uuid,
+    user_id: Uuid,
+    operation: &BulkTaskOperation,
+    target_is_done: bool,
+) -> Result<Option<TaskRow>, AppError> {
+    let now = Utc::now();
+
+    let row = match operation {
+        BulkTaskOperation::Move { status_id } => {
+            let (from_status_id,): (Uuid,) = sqlx::query_as("SELECT status_id FROM tasks WHERE id = $1")
+                .bind(task_id)
+                .fetch_one(&mut *conn)
+                .await?;
+            notify_status_change(conn, workspace_id, task_id, user_id, from_status_id, *status_id)
+                .await?;
+
+            let (position,): (i32,) = sqlx::query_as(
+                "SELECT COALESCE(MAX(position), -1) + 1 FROM tasks WHERE status_id = $1 AND deleted_at IS NULL",
+            )
+            .bind(status_id)
+            .fetch_one(&mut *conn)
+            .await?;
+
+            // Tasks already in the column stay where they are
+            sqlx::query_as(
+                r#"
+                UPDATE tasks
+                SET status_id = $1, position = $2, updated_at = $3,
+                    completed_at = CASE WHEN $4 THEN COALESCE(completed_at, $3) END,
+                    version = version + 1
+                WHERE id = $5 AND status_id <> $1
+                RETURNING id, workspace_id, number, status_id, title, description,
+                          priority, due_date, time_estimate_minutes,
+                          position, created_by, created_at, updated_at, version, completed_at,
+                          parent_id, recurrence
+                "#,
+            )
+            .bind(status_id)
+            .bind(position)
+            .bind(now)
+            .bind(target_is_done)
+            .bind(task_id)
+            .fetch_optional(&mut *conn)
+            .await?
+        }
+        BulkTaskOperation::SetPriority { priority } => {
+            sqlx::query_as(
+                r#"
+                UPDATE tasks
+                SET priority = $1, updated_at = $2, version = version + 1
+                WHERE id = $3 AND priority IS DISTINCT FROM $1
+                RETURNING id, workspace_id, number, status_id, title, description,
+                          priority, due_date, time_estimate_minutes,
+                          position, created_by, created_at, updated_at, version, completed_at,
+                          parent_id, recurrence
+                "#,
+            )
+            .bind(priority)
+            .bind(now)
+            .bind(task_id)
+            .fetch_optional(&mut *conn)
+            .await?
+        }
+        BulkTaskOperation::SetDueDate { due_date } => {
+            sqlx::query_as(
+                r#"
+                UPDATE tasks
+                SET due_date = $1, updated_at = $2, version = version + 1
+                WHERE id = $3 AND due_date IS DISTINCT FROM $1
+                RETURNING id, workspace_id, number, status_id, title, description,
+                          priority, due_date, time_estimate_minutes,
+                          position, created_by, created_at, updated_at, version, completed_at,
+                          parent_id, recurrence
+                "#,
+            )
+            .bind(due_date)
+            .bind(now)
+            .bind(task_id)
+            .fetch_optional(&mut *conn)
+            .await?
+        }
+        BulkTaskOperation::Assign { user_ids } => {
+            let added: Vec<(Uuid,)> = sqlx::query_as(
+                "INSERT INTO task_assignees (task_id, user_id, created_at) SELECT $1, UNNEST($2::uuid[]), $3 ON CONFLICT DO NOTHING RETURNING user_id",
+            )
+            .bind(task_id)
+            .bind(user_ids)
+            .bind(now)
+            .fetch_all(&mut *conn)
+            .await?;
+
+            if !added.is_empty() {
+                sqlx::query("UPDATE tasks SET updated_at = $1, version = version + 1 WHERE id = $2")
+                    .bind(now)
+                    .bind(task_id)
+                    .execute(&mut *conn)
+                    .await?;
+
+                let added: Vec<Uuid> = added.into_iter().map(|(id,)| id).collect();
+                notify_assigned(conn, workspace_id, task_id, user_id, &added).await?;
+            }
+            None
+        }
+        BulkTaskOperation::AddTags { tag_ids } => {
+            sqlx::query("INSERT INTO task_tags (task_id, tag_id) SELECT $1, UNNEST($2::uuid[]) ON CONFLICT DO NOTHING")
+                .bind(task_id)
+                .bind(tag_ids)
+                .execute(&mut *conn)
+                .await?;
+            None
+        }
+        BulkTaskOperation::RemoveTags { tag_ids } => {
+            sqlx::query("DELETE FROM task_tags WHERE task_id = $1 AND tag_id = ANY($2)")
+                .bind(task_id)
+                .bind(tag_ids)
+                .execute(&mut *conn)
+                .await?;
+            None
+        }
+        // Deletes are handled by the caller, subtree by subtree
+        BulkTaskOperation::Delete => None,
+    };
+
+    Ok(row)
+}
+
+/// POST /api/v1/workspaces/:id/tasks/bulk
+/// Applies one operation to a list of tasks in a single transaction; if any
+/// task can't take it, none of them change
+pub async fn bulk_tasks(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(workspace_id): Path<Uuid>,
+    Json(req): Json<BulkTaskRequest>,
+) -> Result<(WipWarning, Json<BulkTaskResponse>), AppError> {
+    let role = check_membership(&state, workspace_id, user.id).await?;
+
+    if !role.can_edit() {
+        return Err(AppError::Forbidden);
+    }
+
+    let mut task_ids: Vec<Uuid> = Vec::with_capacity(req.task_ids.len());
+    for id in req.task_ids {
+        if !task_ids.contains(&id) {
+            task_ids.push(id);
+        }
+    }
+
+    if task_ids.is_empty() {
+        return Err(AppError::Validation("No tasks given".to_string()));
+    }
+    if task_ids.len() > MAX_BULK_TASKS {
+        return Err(AppError::Validation(format!(
+            "At most {} tasks can be changed at once",
+            MAX_BULK_TASKS
+        )));
+    }
+
+    let (found,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM tasks WHERE id = ANY($1) AND workspace_id = $2 AND deleted_at IS NULL",
+    )
+    .bind(&task_ids)
+    .bind(workspace_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    if found != task_ids.len() as i64 {
+        return Err(AppError::NotFound);
+    }
+
+    // Check the operation's arguments once, up front
+    let mut target_is_done = false;
+    let operation = match req.operation {
+        BulkTaskOperation::Move { status_id } => {
+            verify_status(&state, status_id, workspace_id).await?;
+
+            let (is_done,): (bool,) = sqlx::query_as("SELECT is_done FROM task_statuses WHERE id = $1")
+                .bind(status_id)
+                .fetch_one(&state.db)
+                .await?;
+            target_is_done = is_done;
+
+            // Blockers inside the selection are finished by this same move
+            if is_done {
+                let (blocked,): (i64,) = sqlx::query_as(
+                    r#"
+                    SELECT COUNT(DISTINCT d.blocked_task_id)
+                    FROM task_dependencies d
+                    INNER JOIN tasks t ON t.id = d.blocking_task_id
+                    INNER JOIN task_statuses s ON s.id = t.status_id
+                    WHERE d.blocked_task_id = ANY($1)
+                      AND d.blocking_task_id <> ALL($1)
+                      AND NOT s.is_done AND t.deleted_at IS NULL
+                    "#,
+                )
+                .bind(&task_ids)
+                .fetch_one(&state.db)
+                .await?;
+
+                if blocked > 0 {
+                    return Err(AppError::Conflict(format!(
+                        "{} of the tasks are blocked by unfinished tasks",
+                        blocked
+                    )));
+                }
+            }
+
+            BulkTaskOperation::Move { status_id }
+        }
+        BulkTaskOperation::Assign { user_ids } => BulkTaskOperation::Assign {
+            user_ids: verify_members(&state, workspace_id, user_ids).await?,
+        },
+        BulkTaskOperation::AddTags { tag_ids } => BulkTaskOperation::AddTags {
+            tag_ids: verify_tags(&state, workspace_id, tag_ids).await?,
+        },
+        BulkTaskOperation::RemoveTags { tag_ids } => BulkTaskOperation::RemoveTags {
+            tag_ids: verify_tags(&state, workspace_id, tag_ids).await?,
+        },
+        other => other,
+    };
+
+    let action = match operation {
+        BulkTaskOperation::Move { .. } => ActivityAction::Moved,
+        BulkTaskOperation::Delete => ActivityAction::Deleted,
+        _ => ActivityAction::Updated,
+    };
+
+    let mut tx = state.db.begin().await?;
+    let mut affected = 0;
+
+    let wip_warning = match operation {
+        BulkTaskOperation::Move { status_id } => {
+            check_wip_limit(&mut tx, workspace_id, status_id, &task_ids).await?
+        }
+        _ => None,
+    };
+
+    for &task_id in &task_ids {
+        if let BulkTaskOperation::Delete = operation {
+            // Already trashed along with a selected parent
+            if trash_task_tree(&mut tx, workspace_id, task_id, user.id).await? == 0 {
+                continue;
+            }
+
+            let (entity_label, _) = task_snapshot(&mut tx, task_id).await?;
+            record_activity(
+                &mut tx,
+                NewActivity {
+                    workspace_id,
+                    user_id: user.id,
+                    entity_type: ActivityEntityType::Task,
+                    entity_id: task_id,
+                    entity_label,
+                    action,
+                    changes: None,
+                },
+            )
+            .await?;
+            affected += 1;
+            continue;
+        }
+
+        let (from_status_id, was_done): (Uuid, bool) = sqlx::query_as(
+            "SELECT t.status_id, s.is_done FROM tasks t INNER JOIN task_statuses s ON s.id = t.status_id WHERE t.id = $1",
+        )
+        .bind(task_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        // Bulk moves carry no comment, so transitions that need one refuse them
+        if let BulkTaskOperation::Move { status_id } = operation {
+            check_transition(&mut tx, role, task_id, from_status_id, status_id, None).await?;
+        }
+
+        let (_, before) = task_snapshot(&mut tx, task_id).await?;
+        let row =
+            apply_bulk_operation(&mut tx, workspace_id, task_id, user.id, &operation, target_is_done)
+                .await?;
+        let (entity_label, after) = task_snapshot(&mut tx, task_id).await?;
+
+        let Some(changes) = diff(&before, &after) else {
+            continue;
+        };
+        record_activity(
+            &mut tx,
+            NewActivity {
+                workspace_id,
+                user_id: user.id,
+                entity_type: ActivityEntityType::Task,
+                entity_id: task_id,
+                entity_label,
+                action,
+                changes: Some(changes),
+            },
+        )
+        .await?;
+        publish(
+            &mut *tx,
+            workspace_id,
+            user.id,
+            WorkspaceEventKind::TaskChanged { task_id },
+        )
+        .await?;
+        affected += 1;
+
+        if let Some(row) = row {
+            if target_is_done && !was_done {
+                spawn_next_occurrence(&mut tx, &mut row_to_task(row), user.id).await?;
+            }
+        }
+    }
+
     tx.commit().await?;
 
-    Ok(Json(row_to_task(row)))
+    let tasks = if let BulkTaskOperation::Delete = operation {
+        Vec::new()
+    } else {
+        let rows: Vec<TaskRow> = sqlx::query_as(
+            r#"
+            SELECT id, workspace_id, number, status_id, title, description,
+                   priority, due_date, time_estimate_minutes,
+                   position, created_by, created_at, updated_at, version, completed_at,
+                   parent_id, recurrence
+            FROM tasks
+            WHERE id = ANY($1) AND deleted_at IS NULL
+            "#,
+        )
+        .bind(&task_ids)
+        .fetch_all(&state.db)
+        .await?;
+
+        let mut tasks: Vec<Task> = rows.into_iter().map(row_to_task).collect();
+        // Keep the order the tasks were given in
+        tasks.sort_by_key(|t| task_ids.iter().position(|id| *id == t.id));
+
+        populate_tags(&state, &mut tasks).await?;
+        populate_task_keys(&state, &mut tasks).await?;
+        populate_people(&state, &mut tasks).await?;
+        populate_logged_minutes(&state, &mut tasks).await?;
+        populate_custom_fields(&state, &mut tasks).await?;
+        populate_subtask_progress(&state, &mut tasks).await?;
+        populate_checklist_progress(&state, &mut tasks).await?;
+        populate_blockers(&state, &mut tasks).await?;
+        tasks
+    };
+
+    Ok((wip_warning, Json(BulkTaskResponse { tasks, affected })))
+}
+
+/// Helper to empty a column that's about to be deleted. Live tasks keep their
+/// order at the end of the target column and get a history entry each;
+/// trashed ones follow along so they can still be restored. Returns the number
+/// of live tasks moved.
+pub async fn move_column_tasks(
+    conn: &mut PgConnection,
+    workspace_id: Uuid,
+    from_status_id: Uuid,
+    to_status_id: Uuid,
+    user_id: Uuid,
+) -> Result<u64, AppError> {
+    let (from_is_done, to_is_done): (bool, bool) = sqlx::query_as(
+        "SELECT f.is_done, t.is_done FROM task_statuses f, task_statuses t WHERE f.id = $1 AND t.id = $2",
+    )
+    .bind(from_status_id)
+    .bind(to_status_id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let task_ids: Vec<(Uuid,)> = sqlx::query_as(
+        "SELECT id FROM tasks WHERE status_id = $1 AND deleted_at IS NULL ORDER BY position FOR UPDATE",
+    )
+    .bind(from_status_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let operation = BulkTaskOperation::Move { status_id: to_status_id };
+    let mut moved = 0;
+
+    for (task_id,) in task_ids {
+        let (_, before) = task_snapshot(&mut *conn, task_id).await?;
+        let row =
+            apply_bulk_operation(&mut *conn, workspace_id, task_id, user_id, &operation, to_is_done)
+                .await?;
+        let (entity_label, after) = task_snapshot(&mut *conn, task_id).await?;
+
+        record_activity(
+            &mut *conn,
+            NewActivity {
+                workspace_id,
+                user_id,
+                entity_type: ActivityEntityType::Task,
+                entity_id: task_id,
+                entity_label,
+                action: ActivityAction::Moved,
+                changes: diff(&before, &after),
+            },
+        )
+        .await?;
+        publish(
+            &mut *conn,
+            workspace_id,
+            user_id,
+            WorkspaceEventKind::TaskChanged { task_id },
+        )
+        .await?;
+        moved += 1;
+
+        if let Some(row) = row {
+            if to_is_done && !from_is_done {
+                spawn_next_occurrence(&mut *conn, &mut row_to_task(row), user_id).await?;
+            }
+        }
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE tasks
+        SET status_id = $1,
+            completed_at = CASE WHEN $2 THEN COALESCE(completed_at, $3) END
+        WHERE status_id = $4 AND deleted_at IS NOT NULL
+        "#,
+    )
+    .bind(to_status_id)
+    .bind(to_is_done)
+    .bind(Utc::now())
+    .bind(from_status_id)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(moved)
 }
@@ -0,0 +1,342 @@
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use chrono::Utc;
+use sqlx::PgConnection;
+use todo_shared::{
+    api::{CreateTransitionRequest, UpdateTransitionRequest},
+    StatusTransition, WorkspaceEventKind, WorkspaceRole,
+};
+use uuid::Uuid;
+
+use crate::auth::AuthUser;
+use crate::error::AppError;
+use crate::events::publish;
+use crate::routes::AppState;
+
+/// Helper to check workspace membership and return role
+async fn check_membership(
+    state: &AppState,
+    workspace_id: Uuid,
+    user_id: Uuid,
+) -> Result<WorkspaceRole, AppError> {
+    let role: Option<(WorkspaceRole,)> = sqlx::query_as(
+        r#"SELECT role as "role: WorkspaceRole" FROM workspace_members WHERE workspace_id = $1 AND user_id = $2"#,
+    )
+    .bind(workspace_id)
+    .bind(user_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    role.map(|(r,)| r).ok_or(AppError::NotFound)
+}
+
+type TransitionRow = (
+    Uuid,                  // id
+    Uuid,                  // workspace_id
+    Uuid,                  // from_status_id
+    Uuid,                  // to_status_id
+    bool,                  // require_assignee
+    bool,                  // require_comment
+    Option<WorkspaceRole>, // required_role
+);
+
+fn row_to_transition(row: TransitionRow) -> StatusTransition {
+    StatusTransition {
+        id: row.0,
+        workspace_id: row.1,
+        from_status_id: row.2,
+        to_status_id: row.3,
+        require_assignee: row.4,
+        require_comment: row.5,
+        required_role: row.6,
+    }
+}
+
+/// Check that a task may move from one column to another. A column without
+/// outgoing transitions allows any move; otherwise the move needs a transition
+/// and has to meet its rules. Run it inside the moving transaction, after any
+/// assignee changes made by the same request.
+pub async fn check_transition(
+    conn: &mut PgConnection,
+    role: WorkspaceRole,
+    task_id: Uuid,
+    from_status_id: Uuid,
+    to_status_id: Uuid,
+    comment: Option<&str>,
+) -> Result<(), AppError> {
+    if from_status_id == to_status_id {
+        return Ok(());
+    }
+
+    let (from_name, to_name, restricted): (String, String, bool) = sqlx::query_as(
+        r#"
+        SELECT f.name, t.name,
+               EXISTS(SELECT 1 FROM status_transitions WHERE from_status_id = f.id)
+        FROM task_statuses f, task_statuses t
+        WHERE f.id = $1 AND t.id = $2
+        "#,
+    )
+    .bind(from_status_id)
+    .bind(to_status_id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    if !restricted {
+        return Ok(());
+    }
+
+    let rule: Option<(bool, bool, Option<WorkspaceRole>)> = sqlx::query_as(
+        r#"
+        SELECT require_assignee, require_comment, required_role
+        FROM status_transitions
+        WHERE from_status_id = $1 AND to_status_id = $2
+        "#,
+    )
+    .bind(from_status_id)
+    .bind(to_status_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    let Some((require_assignee, require_comment, required_role)) = rule else {
+        return Err(AppError::Validation(format!(
+            "Tasks can't move from \"{}\" to \"{}\" in this workspace's workflow",
+            from_name, to_name
+        )));
+    };
+
+    if let Some(required) = required_role {
+        if !role.at_least(required) {
+            return Err(AppError::Validation(format!(
+                "Moving tasks from \"{}\" to \"{}\" needs the {} role or higher",
+                from_name,
+                to_name,
+                format!("{:?}", required).to_lowercase()
+            )));
+        }
+    }
+
+    if require_assignee {
+        let (assigned,): (bool,) =
+            sqlx::query_as("SELECT EXISTS(SELECT 1 FROM task_assignees WHERE task_id = $1)")
+                .bind(task_id)
+                .fetch_one(&mut *conn)
+                .await?;
+
+        if !assigned {
+            return Err(AppError::Validation(format!(
+                "Assign the task before moving it from \"{}\" to \"{}\"",
+                from_name, to_name
+            )));
+        }
+    }
+
+    let has_comment = comment.is_some_and(|c| !c.trim().is_empty());
+    if require_comment && !has_comment {
+        return Err(AppError::Validation(format!(
+            "Moving a task from \"{}\" to \"{}\" needs a comment",
+            from_name, to_name
+        )));
+    }
+
+    Ok(())
+}
+
+/// Load the workspace's workflow in board order
+pub async fn load_transitions(
+    state: &AppState,
+    workspace_id: Uuid,
+) -> Result<Vec<StatusTransition>, AppError> {
+    let rows: Vec<TransitionRow> = sqlx::query_as(
+        r#"
+        SELECT tr.id, tr.workspace_id, tr.from_status_id, tr.to_status_id,
+               tr.require_assignee, tr.require_comment, tr.required_role
+        FROM status_transitions tr
+        INNER JOIN task_statuses f ON f.id = tr.from_status_id
+        INNER JOIN task_statuses t ON t.id = tr.to_status_id
+        WHERE tr.workspace_id = $1
+        ORDER BY f.position, t.position
+        "#,
+    )
+    .bind(workspace_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(rows.into_iter().map(row_to_transition).collect())
+}
+
+/// GET /api/v1/workspaces/:id/transitions
+pub async fn list_transitions(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(workspace_id): Path<Uuid>,
+) -> Result<Json<Vec<StatusTransition>>, AppError> {
+    check_membership(&state, workspace_id, user.id).await?;
+
+    Ok(Json(load_transitions(&state, workspace_id).await?))
+}
+
+/// POST /api/v1/workspaces/:id/transitions
+pub async fn create_transition(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(workspace_id): Path<Uuid>,
+    Json(req): Json<CreateTransitionRequest>,
+) -> Result<Json<StatusTransition>, AppError> {
+    let role = check_membership(&state, workspace_id, user.id).await?;
+
+    // The workflow binds everyone in the workspace, so only admins shape it
+    if !role.can_admin() {
+        return Err(AppError::Forbidden);
+    }
+
+    if req.from_status_id == req.to_status_id {
+        return Err(AppError::Validation(
+            "A transition needs two different statuses".to_string(),
+        ));
+    }
+
+    let (found,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM task_statuses WHERE workspace_id = $1 AND id IN ($2, $3)",
+    )
+    .bind(workspace_id)
+    .bind(req.from_status_id)
+    .bind(req.to_status_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    if found != 2 {
+        return Err(AppError::Validation(
+            "Invalid status for this workspace".to_string(),
+        ));
+    }
+
+    let existing: Option<(Uuid,)> = sqlx::query_as(
+        "SELECT id FROM status_transitions WHERE from_status_id = $1 AND to_status_id = $2",
+    )
+    .bind(req.from_status_id)
+    .bind(req.to_status_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    if existing.is_some() {
+        return Err(AppError::Conflict("That transition already exists".to_string()));
+    }
+
+    let row: TransitionRow = sqlx::query_as(
+        r#"
+        INSERT INTO status_transitions
+            (id, workspace_id, from_status_id, to_status_id, require_assignee, require_comment, required_role, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING id, workspace_id, from_status_id, to_status_id, require_assignee, require_comment, required_role
+        "#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(workspace_id)
+    .bind(req.from_status_id)
+    .bind(req.to_status_id)
+    .bind(req.require_assignee)
+    .bind(req.require_comment)
+    .bind(req.required_role)
+    .bind(Utc::now())
+    .fetch_one(&state.db)
+    .await?;
+
+    publish(
+        &state.db,
+        workspace_id,
+        user.id,
+        WorkspaceEventKind::StatusesChanged,
+    )
+    .await?;
+
+    Ok(Json(row_to_transition(row)))
+}
+
+/// PATCH /api/v1/workspaces/:id/transitions/:transition_id
+pub async fn update_transition(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, transition_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<UpdateTransitionRequest>,
+) -> Result<Json<StatusTransition>, AppError> {
+    let role = check_membership(&state, workspace_id, user.id).await?;
+
+    if !role.can_admin() {
+        return Err(AppError::Forbidden);
+    }
+
+    let require_assignee = req
+        .require_assignee
+        .non_null("require_assignee")
+        .map_err(AppError::Validation)?;
+    let require_comment = req
+        .require_comment
+        .non_null("require_comment")
+        .map_err(AppError::Validation)?;
+
+    let row: Option<TransitionRow> = sqlx::query_as(
+        r#"
+        UPDATE status_transitions
+        SET require_assignee = COALESCE($1, require_assignee),
+            require_comment = COALESCE($2, require_comment),
+            required_role = CASE WHEN $3 THEN $4 ELSE required_role END
+        WHERE id = $5 AND workspace_id = $6
+        RETURNING id, workspace_id, from_status_id, to_status_id, require_assignee, require_comment, required_role
+        "#,
+    )
+    .bind(require_assignee)
+    .bind(require_comment)
+    .bind(req.required_role.is_set())
+    .bind(req.required_role.value())
+    .bind(transition_id)
+    .bind(workspace_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    let row = row.ok_or(AppError::NotFound)?;
+
+    publish(
+        &state.db,
+        workspace_id,
+        user.id,
+        WorkspaceEventKind::StatusesChanged,
+    )
+    .await?;
+
+    Ok(Json(row_to_transition(row)))
+}
+
+/// DELETE /api/v1/workspaces/:id/transitions/:transition_id
+pub async fn delete_transition(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, transition_id)): Path<(Uuid, Uuid)>,
+) -> Result<(), AppError> {
+    let role = check_membership(&state, workspace_id, user.id).await?;
+
+    if !role.can_admin() {
+        return Err(AppError::Forbidden);
+    }
+
+    let result = sqlx::query("DELETE FROM status_transitions WHERE id = $1 AND workspace_id = $2")
+        .bind(transition_id)
+        .bind(workspace_id)
+        .execute(&state.db)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+
+    publish(
+        &state.db,
+        workspace_id,
+        user.id,
+        WorkspaceEventKind::StatusesChanged,
+    )
+    .await?;
+
+    Ok(())
+}
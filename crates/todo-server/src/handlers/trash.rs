@@ -0,0 +1,606 @@
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use todo_shared::{
+    ActivityAction, ActivityEntityType, TrashItem, TrashItemKind, WorkspaceEventKind, WorkspaceRole,
+};
+use uuid::Uuid;
+
+use crate::auth::AuthUser;
+use crate::error::AppError;
+use crate::events::publish;
+use crate::handlers::activity::{document_snapshot, record_activity, task_snapshot, NewActivity};
+use crate::handlers::attachments::{owned_storage_keys, purge_blobs, AttachmentOwner};
+use crate::routes::AppState;
+
+/// Helper to check workspace membership and return role
+async fn check_membership(
+    state: &AppState,
+    workspace_id: Uuid,
+    user_id: Uuid,
+) -> Result<WorkspaceRole, AppError> {
+    let role: Option<(WorkspaceRole,)> = sqlx::query_as(
+        r#"SELECT role as "role: WorkspaceRole" FROM workspace_members WHERE workspace_id = $1 AND user_id = $2"#,
+    )
+    .bind(workspace_id)
+    .bind(user_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    role.map(|(r,)| r).ok_or(AppError::NotFound)
+}
+
+type TrashRow = (
+    Uuid,           // id
+    String,         // title
+    Option<String>, // context
+    i64,            // nested_count
+    DateTime<Utc>,  // deleted_at
+    Option<Uuid>,   // deleted_by
+    Option<String>, // deleted_by username
+);
+
+fn row_to_trash_item(kind: TrashItemKind, row: TrashRow, retention_days: i64) -> TrashItem {
+    TrashItem {
+        kind,
+        id: row.0,
+        title: row.1,
+        context: row.2,
+        nested_count: row.3,
+        deleted_at: row.4,
+        deleted_by: row.5,
+        deleted_by_username: row.6,
+        purge_at: row.4 + Duration::days(retention_days),
+    }
+}
+
+/// Helper to shorten a comment to its first line for the trash listing
+fn comment_excerpt(content: &str) -> String {
+    const MAX_CHARS: usize = 80;
+
+    let line = content.lines().find(|l| !l.trim().is_empty()).unwrap_or("").trim();
+    if line.chars().count() > MAX_CHARS {
+        let cut: String = line.chars().take(MAX_CHARS).collect();
+        format!("{}...", cut)
+    } else {
+        line.to_string()
+    }
+}
+
+/// GET /api/v1/workspaces/:id/trash
+pub async fn list_trash(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(workspace_id): Path<Uuid>,
+) -> Result<Json<Vec<TrashItem>>, AppError> {
+    check_membership(&state, workspace_id, user.id).await?;
+
+    let retention_days = state.config.trash_retention_days;
+
+    // Only list the roots of each delete; whatever was trashed along with
+    // them shares their deleted_at and is counted instead
+    let task_rows: Vec<TrashRow> = sqlx::query_as(
+        r#"
+        SELECT t.id, w.task_key_prefix || '-' || t.number || ' ' || t.title,
+               CASE WHEN p.id IS NOT NULL
+                    THEN w.task_key_prefix || '-' || p.number || ' ' || p.title
+               END,
+               (SELECT COUNT(*) FROM tasks n
+                WHERE n.workspace_id = t.workspace_id AND n.deleted_at = t.deleted_at
+                  AND n.id <> t.id),
+               t.deleted_at, t.deleted_by, u.username
+        FROM tasks t
+        INNER JOIN workspaces w ON w.id = t.workspace_id
+        LEFT JOIN tasks p ON p.id = t.parent_id
+        LEFT JOIN users u ON u.id = t.deleted_by
+        WHERE t.workspace_id = $1 AND t.deleted_at IS NOT NULL
+          AND (p.id IS NULL OR p.deleted_at IS DISTINCT FROM t.deleted_at)
+        "#,
+    )
+    .bind(workspace_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let document_rows: Vec<TrashRow> = sqlx::query_as(
+        r#"
+        SELECT d.id, d.title, d.path::text,
+               (SELECT COUNT(*) FROM documents n
+                WHERE n.workspace_id = d.workspace_id AND n.deleted_at = d.deleted_at
+                  AND n.id <> d.id),
+               d.deleted_at, d.deleted_by, u.username
+        FROM documents d
+        LEFT JOIN documents p ON p.id = d.parent_id
+        LEFT JOIN users u ON u.id = d.deleted_by
+        WHERE d.workspace_id = $1 AND d.deleted_at IS NOT NULL
+          AND (p.id IS NULL OR p.deleted_at IS DISTINCT FROM d.deleted_at)
+        "#,
+    )
+    .bind(workspace_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let comment_rows: Vec<TrashRow> = sqlx::query_as(
+        r#"
+        SELECT c.id, c.content, w.task_key_prefix || '-' || t.number || ' ' || t.title,
+               (SELECT COUNT(*) FROM task_comments r
+                WHERE r.parent_comment_id = c.id AND r.deleted_at IS NULL),
+               c.deleted_at, c.deleted_by, u.username
+        FROM task_comments c
+        INNER JOIN tasks t ON t.id = c.task_id
+        INNER JOIN workspaces w ON w.id = t.workspace_id
+        LEFT JOIN users u ON u.id = c.deleted_by
+        WHERE t.workspace_id = $1 AND c.deleted_at IS NOT NULL
+        "#,
+    )
+    .bind(workspace_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut items: Vec<TrashItem> = task_rows
+        .into_iter()
+        .map(|row| row_to_trash_item(TrashItemKind::Task, row, retention_days))
+        .chain(
+            document_rows
+                .into_iter()
+                .map(|row| row_to_trash_item(TrashItemKind::Document, row, retention_days)),
+        )
+        .chain(comment_rows.into_iter().map(|mut row| {
+            row.1 = comment_excerpt(&row.1);
+            row_to_trash_item(TrashItemKind::Comment, row, retention_days)
+        }))
+        .collect();
+
+    items.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+
+    Ok(Json(items))
+}
+
+/// POST /api/v1/workspaces/:id/trash/:kind/:item_id/restore
+pub async fn restore_item(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, kind, item_id)): Path<(Uuid, TrashItemKind, Uuid)>,
+) -> Result<(), AppError> {
+    let role = check_membership(&state, workspace_id, user.id).await?;
+
+    if !role.can_edit() {
+        return Err(AppError::Forbidden);
+    }
+
+    match kind {
+        TrashItemKind::Task => restore_task(&state, workspace_id, user.id, item_id).await,
+        TrashItemKind::Document => restore_document(&state, workspace_id, user.id, item_id).await,
+        TrashItemKind::Comment => restore_comment(&state, workspace_id, user.id, item_id).await,
+    }
+}
+
+/// Helper to restore a task and the subtasks trashed along with it
+async fn restore_task(
+    state: &AppState,
+    workspace_id: Uuid,
+    user_id: Uuid,
+    task_id: Uuid,
+) -> Result<(), AppError> {
+    let mut tx = state.db.begin().await?;
+
+    let task: Option<(Option<Uuid>, DateTime<Utc>)> = sqlx::query_as(
+        r#"
+        SELECT parent_id, deleted_at FROM tasks
+        WHERE id = $1 AND workspace_id = $2 AND deleted_at IS NOT NULL
+        FOR UPDATE
+        "#,
+    )
+    .bind(task_id)
+    .bind(workspace_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let (parent_id, deleted_at) = task.ok_or(AppError::NotFound)?;
+
+    if let Some(parent_id) = parent_id {
+        let (parent_trashed,): (bool,) =
+            sqlx::query_as("SELECT deleted_at IS NOT NULL FROM tasks WHERE id = $1")
+                .bind(parent_id)
+                .fetch_one(&mut *tx)
+                .await?;
+
+        if parent_trashed {
+            return Err(AppError::Conflict(
+                "The parent task is in the trash. Restore it first.".to_string(),
+            ));
+        }
+    }
+
+    let restored: Vec<(Uuid,)> = sqlx::query_as(
+        r#"
+        WITH RECURSIVE tree AS (
+            SELECT id FROM tasks WHERE id = $1
+            UNION ALL
+            SELECT t.id FROM tasks t
+            INNER JOIN tree ON t.parent_id = tree.id
+            WHERE t.deleted_at = $2
+        )
+        UPDATE tasks SET deleted_at = NULL, deleted_by = NULL, updated_at = NOW()
+        WHERE id IN (SELECT id FROM tree)
+        RETURNING id
+        "#,
+    )
+    .bind(task_id)
+    .bind(deleted_at)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    for (restored_id,) in restored {
+        publish(
+            &mut *tx,
+            workspace_id,
+            user_id,
+            WorkspaceEventKind::TaskChanged {
+                task_id: restored_id,
+            },
+        )
+        .await?;
+    }
+
+    let (entity_label, _) = task_snapshot(&mut tx, task_id).await?;
+    record_activity(
+        &mut tx,
+        NewActivity {
+            workspace_id,
+            user_id,
+            entity_type: ActivityEntityType::Task,
+            entity_id: task_id,
+            entity_label,
+            action: ActivityAction::Restored,
+            changes: None,
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Helper to restore a document and the subtree trashed along with it.
+/// The path is rebuilt from the parent, which may have moved in the meantime.
+async fn restore_document(
+    state: &AppState,
+    workspace_id: Uuid,
+    user_id: Uuid,
+    doc_id: Uuid,
+) -> Result<(), AppError> {
+    let mut tx = state.db.begin().await?;
+
+    let document: Option<(Option<Uuid>, String, String, DateTime<Utc>)> = sqlx::query_as(
+        r#"
+        SELECT parent_id, path::text, slug, deleted_at FROM documents
+        WHERE id = $1 AND workspace_id = $2 AND deleted_at IS NOT NULL
+        FOR UPDATE
+        "#,
+    )
+    .bind(doc_id)
+    .bind(workspace_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let (parent_id, old_path, slug, deleted_at) = document.ok_or(AppError::NotFound)?;
+
+    let new_path = if let Some(parent_id) = parent_id {
+        let (parent_path, parent_trashed): (String, bool) = sqlx::query_as(
+            "SELECT path::text, deleted_at IS NOT NULL FROM documents WHERE id = $1",
+        )
+        .bind(parent_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if parent_trashed {
+            return Err(AppError::Conflict(
+                "The parent document is in the trash. Restore it first.".to_string(),
+            ));
+        }
+        format!("{}.{}", parent_path, slug)
+    } else {
+        slug
+    };
+
+    let conflict: Option<(Uuid,)> = sqlx::query_as(
+        "SELECT id FROM documents WHERE workspace_id = $1 AND path = $2::ltree AND deleted_at IS NULL",
+    )
+    .bind(workspace_id)
+    .bind(&new_path)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if conflict.is_some() {
+        return Err(AppError::Conflict(
+            "A document with this path already exists".to_string(),
+        ));
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE documents
+        SET path = ($1::ltree || subpath(path, nlevel($2::ltree))),
+            deleted_at = NULL,
+            deleted_by = NULL,
+            updated_at = NOW()
+        WHERE workspace_id = $3 AND path <@ $2::ltree AND deleted_at = $4
+        "#,
+    )
+    .bind(&new_path)
+    .bind(&old_path)
+    .bind(workspace_id)
+    .bind(deleted_at)
+    .execute(&mut *tx)
+    .await?;
+
+    let (entity_label, _) = document_snapshot(&mut tx, doc_id).await?;
+    record_activity(
+        &mut tx,
+        NewActivity {
+            workspace_id,
+            user_id,
+            entity_type: ActivityEntityType::Document,
+            entity_id: doc_id,
+            entity_label,
+            action: ActivityAction::Restored,
+            changes: None,
+        },
+    )
+    .await?;
+
+    publish(
+        &mut *tx,
+        workspace_id,
+        user_id,
+        WorkspaceEventKind::DocumentChanged {
+            document_id: doc_id,
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Helper to restore a comment onto its task
+async fn restore_comment(
+    state: &AppState,
+    workspace_id: Uuid,
+    user_id: Uuid,
+    comment_id: Uuid,
+) -> Result<(), AppError> {
+    let comment: Option<(Uuid, bool, bool)> = sqlx::query_as(
+        r#"
+        SELECT t.id, t.deleted_at IS NOT NULL, p.deleted_at IS NOT NULL
+        FROM task_comments c
+        INNER JOIN tasks t ON t.id = c.task_id
+        LEFT JOIN task_comments p ON p.id = c.parent_comment_id
+        WHERE c.id = $1 AND t.workspace_id = $2 AND c.deleted_at IS NOT NULL
+        "#,
+    )
+    .bind(comment_id)
+    .bind(workspace_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    let (task_id, task_trashed, parent_trashed) = comment.ok_or(AppError::NotFound)?;
+
+    if task_trashed {
+        return Err(AppError::Conflict(
+            "The comment's task is in the trash. Restore it first.".to_string(),
+        ));
+    }
+    if parent_trashed {
+        return Err(AppError::Conflict(
+            "The comment this replies to is in the trash. Restore it first.".to_string(),
+        ));
+    }
+
+    sqlx::query("UPDATE task_comments SET deleted_at = NULL, deleted_by = NULL WHERE id = $1")
+        .bind(comment_id)
+        .execute(&state.db)
+        .await?;
+
+    publish(
+        &state.db,
+        workspace_id,
+        user_id,
+        WorkspaceEventKind::CommentsChanged { task_id },
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// DELETE /api/v1/workspaces/:id/trash/:kind/:item_id
+/// Permanently delete a trashed item (admin only)
+pub async fn purge_item(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, kind, item_id)): Path<(Uuid, TrashItemKind, Uuid)>,
+) -> Result<(), AppError> {
+    let role = check_membership(&state, workspace_id, user.id).await?;
+
+    if !role.can_admin() {
+        return Err(AppError::Forbidden);
+    }
+
+    let query = match kind {
+        TrashItemKind::Task => {
+            "SELECT id FROM tasks WHERE id = $1 AND workspace_id = $2 AND deleted_at IS NOT NULL"
+        }
+        TrashItemKind::Document => {
+            "SELECT id FROM documents WHERE id = $1 AND workspace_id = $2 AND deleted_at IS NOT NULL"
+        }
+        TrashItemKind::Comment => {
+            r#"
+            SELECT c.id FROM task_comments c
+            INNER JOIN tasks t ON t.id = c.task_id
+            WHERE c.id = $1 AND t.workspace_id = $2 AND c.deleted_at IS NOT NULL
+            "#
+        }
+    };
+
+    let exists: Option<(Uuid,)> = sqlx::query_as(query)
+        .bind(item_id)
+        .bind(workspace_id)
+        .fetch_optional(&state.db)
+        .await?;
+
+    if exists.is_none() {
+        return Err(AppError::NotFound);
+    }
+
+    // Subtasks, child documents, comments and attachment rows cascade; blobs don't
+    let blob_keys = match kind {
+        TrashItemKind::Task => owned_storage_keys(&state, AttachmentOwner::Task(item_id)).await?,
+        TrashItemKind::Document => {
+            owned_storage_keys(&state, AttachmentOwner::Document(item_id)).await?
+        }
+        TrashItemKind::Comment => Vec::new(),
+    };
+
+    let mut tx = state.db.begin().await?;
+
+    // Label the entry while the row still exists; comments aren't tracked
+    let purged = match kind {
+        TrashItemKind::Task => {
+            Some((ActivityEntityType::Task, task_snapshot(&mut tx, item_id).await?.0))
+        }
+        TrashItemKind::Document => {
+            Some((ActivityEntityType::Document, document_snapshot(&mut tx, item_id).await?.0))
+        }
+        TrashItemKind::Comment => None,
+    };
+
+    let query = match kind {
+        TrashItemKind::Task => "DELETE FROM tasks WHERE id = $1",
+        TrashItemKind::Document => "DELETE FROM documents WHERE id = $1",
+        TrashItemKind::Comment => "DELETE FROM task_comments WHERE id = $1",
+    };
+
+    sqlx::query(query).bind(item_id).execute(&mut *tx).await?;
+
+    if let Some((entity_type, entity_label)) = purged {
+        record_activity(
+            &mut tx,
+            NewActivity {
+                workspace_id,
+                user_id: user.id,
+                entity_type,
+                entity_id: item_id,
+                entity_label,
+                action: ActivityAction::Purged,
+                changes: None,
+            },
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    purge_blobs(&state, blob_keys).await;
+
+    Ok(())
+}
+
+/// DELETE /api/v1/workspaces/:id/trash
+/// Empty the workspace trash (admin only)
+pub async fn empty_trash(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path(workspace_id): Path<Uuid>,
+) -> Result<(), AppError> {
+    let role = check_membership(&state, workspace_id, user.id).await?;
+
+    if !role.can_admin() {
+        return Err(AppError::Forbidden);
+    }
+
+    purge_trashed(&state, Some(workspace_id), Utc::now()).await?;
+
+    Ok(())
+}
+
+/// Permanently delete everything trashed at or before `cutoff`, in one
+/// workspace or across all of them. Returns the number of rows removed.
+pub async fn purge_trashed(
+    state: &AppState,
+    workspace_id: Option<Uuid>,
+    cutoff: DateTime<Utc>,
+) -> Result<u64, AppError> {
+    // Anything nested under an expired task or document was trashed no later
+    // than it, so matching attachments on the owner's own deleted_at is enough
+    let blob_keys: Vec<(String,)> = sqlx::query_as(
+        r#"
+        SELECT a.storage_key
+        FROM attachments a
+        LEFT JOIN tasks t ON t.id = a.task_id
+        LEFT JOIN documents d ON d.id = a.document_id
+        WHERE ($1::uuid IS NULL OR a.workspace_id = $1)
+          AND (t.deleted_at <= $2 OR d.deleted_at <= $2)
+        "#,
+    )
+    .bind(workspace_id)
+    .bind(cutoff)
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut tx = state.db.begin().await?;
+
+    let comments = sqlx::query(
+        r#"
+        DELETE FROM task_comments c
+        USING tasks t
+        WHERE t.id = c.task_id AND ($1::uuid IS NULL OR t.workspace_id = $1)
+          AND c.deleted_at <= $2
+        "#,
+    )
+    .bind(workspace_id)
+    .bind(cutoff)
+    .execute(&mut *tx)
+    .await?;
+
+    let tasks = sqlx::query(
+        "DELETE FROM tasks WHERE ($1::uuid IS NULL OR workspace_id = $1) AND deleted_at <= $2",
+    )
+    .bind(workspace_id)
+    .bind(cutoff)
+    .execute(&mut *tx)
+    .await?;
+
+    let documents = sqlx::query(
+        "DELETE FROM documents WHERE ($1::uuid IS NULL OR workspace_id = $1) AND deleted_at <= $2",
+    )
+    .bind(workspace_id)
+    .bind(cutoff)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    purge_blobs(state, blob_keys.into_iter().map(|(k,)| k).collect()).await;
+
+    Ok(comments.rows_affected() + tasks.rows_affected() + documents.rows_affected())
+}
+
+/// Background job that purges trash older than the retention period
+pub async fn run_retention(state: AppState) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(60 * 60));
+
+    loop {
+        interval.tick().await;
+
+        let cutoff = Utc::now() - Duration::days(state.config.trash_retention_days);
+        match purge_trashed(&state, None, cutoff).await {
+            Ok(0) => {}
+            Ok(purged) => tracing::info!("Purged {} expired item(s) from the trash", purged),
+            Err(e) => tracing::warn!("Failed to purge expired trash: {:?}", e),
+        }
+    }
+}
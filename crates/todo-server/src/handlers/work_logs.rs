@@ -0,0 +1,288 @@
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use chrono::{Days, NaiveDate, Utc};
+use todo_shared::{
+    api::{CreateWorkLogRequest, UpdateWorkLogRequest},
+    WorkLog, WorkspaceEventKind, WorkspaceRole,
+};
+use uuid::Uuid;
+
+use crate::auth::AuthUser;
+use crate::error::AppError;
+use crate::events::publish;
+use crate::routes::AppState;
+
+/// Longest single entry: one full day
+const MAX_WORK_LOG_MINUTES: i32 = 24 * 60;
+
+/// Helper to check workspace membership and return role
+async fn check_membership(
+    state: &AppState,
+    workspace_id: Uuid,
+    user_id: Uuid,
+) -> Result<WorkspaceRole, AppError> {
+    let role: Option<(WorkspaceRole,)> = sqlx::query_as(
+        r#"SELECT role as "role: WorkspaceRole" FROM workspace_members WHERE workspace_id = $1 AND user_id = $2"#,
+    )
+    .bind(workspace_id)
+    .bind(user_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    role.map(|(r,)| r).ok_or(AppError::NotFound)
+}
+
+/// Helper to verify task belongs to workspace
+async fn verify_task(
+    state: &AppState,
+    task_id: Uuid,
+    workspace_id: Uuid,
+) -> Result<(), AppError> {
+    let exists: Option<(Uuid,)> = sqlx::query_as(
+        "SELECT id FROM tasks WHERE id = $1 AND workspace_id = $2 AND deleted_at IS NULL",
+    )
+    .bind(task_id)
+    .bind(workspace_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    if exists.is_none() {
+        return Err(AppError::NotFound);
+    }
+    Ok(())
+}
+
+/// Helper to validate minutes and date of an entry
+fn validate_entry(minutes: i32, work_date: NaiveDate) -> Result<(), AppError> {
+    if minutes <= 0 || minutes > MAX_WORK_LOG_MINUTES {
+        return Err(AppError::Validation(format!(
+            "Minutes must be between 1 and {}",
+            MAX_WORK_LOG_MINUTES
+        )));
+    }
+
+    // Allow one day of slack for clients ahead of UTC
+    let latest = Utc::now().date_naive() + Days::new(1);
+    if work_date > latest {
+        return Err(AppError::Validation(
+            "Work date cannot be in the future".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+type WorkLogRow = (
+    Uuid,                  // id
+    Uuid,                  // task_id
+    Uuid,                  // user_id
+    String,                // author_username
+    i32,                   // minutes
+    NaiveDate,             // work_date
+    Option<String>,        // note
+    chrono::DateTime<Utc>, // created_at
+    chrono::DateTime<Utc>, // updated_at
+);
+
+fn row_to_work_log(row: WorkLogRow) -> WorkLog {
+    WorkLog {
+        id: row.0,
+        task_id: row.1,
+        user_id: row.2,
+        author_username: row.3,
+        minutes: row.4,
+        work_date: row.5,
+        note: row.6,
+        created_at: row.7,
+        updated_at: row.8,
+    }
+}
+
+/// Helper to load a single work log with its author
+async fn fetch_work_log(state: &AppState, work_log_id: Uuid) -> Result<WorkLog, AppError> {
+    let row: WorkLogRow = sqlx::query_as(
+        r#"
+        SELECT w.id, w.task_id, w.user_id, u.username, w.minutes, w.work_date, w.note,
+               w.created_at, w.updated_at
+        FROM work_logs w
+        JOIN users u ON u.id = w.user_id
+        WHERE w.id = $1
+        "#,
+    )
+    .bind(work_log_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(row_to_work_log(row))
+}
+
+/// GET /api/v1/workspaces/:id/tasks/:task_id/work-logs
+pub async fn list_work_logs(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, task_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Vec<WorkLog>>, AppError> {
+    check_membership(&state, workspace_id, user.id).await?;
+    verify_task(&state, task_id, workspace_id).await?;
+
+    let rows: Vec<WorkLogRow> = sqlx::query_as(
+        r#"
+        SELECT w.id, w.task_id, w.user_id, u.username, w.minutes, w.work_date, w.note,
+               w.created_at, w.updated_at
+        FROM work_logs w
+        JOIN users u ON u.id = w.user_id
+        WHERE w.task_id = $1
+        ORDER BY w.work_date DESC, w.created_at DESC
+        "#,
+    )
+    .bind(task_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(rows.into_iter().map(row_to_work_log).collect()))
+}
+
+/// POST /api/v1/workspaces/:id/tasks/:task_id/work-logs
+pub async fn create_work_log(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, task_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<CreateWorkLogRequest>,
+) -> Result<Json<WorkLog>, AppError> {
+    let role = check_membership(&state, workspace_id, user.id).await?;
+
+    if !role.can_edit() {
+        return Err(AppError::Forbidden);
+    }
+
+    verify_task(&state, task_id, workspace_id).await?;
+
+    let work_date = req.work_date.unwrap_or_else(|| Utc::now().date_naive());
+    validate_entry(req.minutes, work_date)?;
+
+    let note = req.note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    let id = Uuid::new_v4();
+    let now = Utc::now();
+
+    sqlx::query(
+        r#"
+        INSERT INTO work_logs (id, task_id, user_id, minutes, work_date, note, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#,
+    )
+    .bind(id)
+    .bind(task_id)
+    .bind(user.id)
+    .bind(req.minutes)
+    .bind(work_date)
+    .bind(&note)
+    .bind(now)
+    .bind(now)
+    .execute(&state.db)
+    .await?;
+
+    // Logged time shows on the task card
+    publish(&state.db, workspace_id, user.id, WorkspaceEventKind::TaskChanged { task_id }).await?;
+
+    Ok(Json(fetch_work_log(&state, id).await?))
+}
+
+/// PATCH /api/v1/workspaces/:id/tasks/:task_id/work-logs/:work_log_id
+pub async fn update_work_log(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, task_id, work_log_id)): Path<(Uuid, Uuid, Uuid)>,
+    Json(req): Json<UpdateWorkLogRequest>,
+) -> Result<Json<WorkLog>, AppError> {
+    let role = check_membership(&state, workspace_id, user.id).await?;
+
+    if !role.can_edit() {
+        return Err(AppError::Forbidden);
+    }
+
+    verify_task(&state, task_id, workspace_id).await?;
+
+    let existing: Option<(Uuid, i32, NaiveDate)> = sqlx::query_as(
+        "SELECT user_id, minutes, work_date FROM work_logs WHERE id = $1 AND task_id = $2",
+    )
+    .bind(work_log_id)
+    .bind(task_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    let Some((author_id, minutes, work_date)) = existing else {
+        return Err(AppError::NotFound);
+    };
+
+    // Logged time is billed, so only the author may change it
+    if author_id != user.id {
+        return Err(AppError::Forbidden);
+    }
+
+    validate_entry(
+        req.minutes.unwrap_or(minutes),
+        req.work_date.unwrap_or(work_date),
+    )?;
+
+    // An empty note clears it
+    let note = req.note.map(|n| n.trim().to_string());
+
+    sqlx::query(
+        r#"
+        UPDATE work_logs
+        SET minutes = COALESCE($1, minutes),
+            work_date = COALESCE($2, work_date),
+            note = CASE WHEN $3::text IS NULL THEN note ELSE NULLIF($3, '') END,
+            updated_at = $4
+        WHERE id = $5
+        "#,
+    )
+    .bind(req.minutes)
+    .bind(req.work_date)
+    .bind(&note)
+    .bind(Utc::now())
+    .bind(work_log_id)
+    .execute(&state.db)
+    .await?;
+
+    publish(&state.db, workspace_id, user.id, WorkspaceEventKind::TaskChanged { task_id }).await?;
+
+    Ok(Json(fetch_work_log(&state, work_log_id).await?))
+}
+
+/// DELETE /api/v1/workspaces/:id/tasks/:task_id/work-logs/:work_log_id
+pub async fn delete_work_log(
+    State(state): State<AppState>,
+    Extension(user): Extension<AuthUser>,
+    Path((workspace_id, task_id, work_log_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<(), AppError> {
+    let role = check_membership(&state, workspace_id, user.id).await?;
+    verify_task(&state, task_id, workspace_id).await?;
+
+    let work_log: Option<(Uuid,)> = sqlx::query_as(
+        "SELECT user_id FROM work_logs WHERE id = $1 AND task_id = $2",
+    )
+    .bind(work_log_id)
+    .bind(task_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    let Some((author_id,)) = work_log else {
+        return Err(AppError::NotFound);
+    };
+
+    // Author (while they can still edit) or admin can delete
+    if !role.can_admin() && (author_id != user.id || !role.can_edit()) {
+        return Err(AppError::Forbidden);
+    }
+
+    sqlx::query("DELETE FROM work_logs WHERE id = $1")
+        .bind(work_log_id)
+        .execute(&state.db)
+        .await?;
+
+    publish(&state.db, workspace_id, user.id, WorkspaceEventKind::TaskChanged { task_id }).await?;
+
+    Ok(())
+}
@@ -4,17 +4,23 @@ use axum::{
     Extension, Json,
 };
 use chrono::{Duration, Utc};
+use serde_json::json;
 use todo_shared::{
     api::{
         CreateWorkspaceRequest, InviteMemberRequest, InviteDetails, UpdateMemberRoleRequest,
         UpdateWorkspaceRequest, WorkspaceInvite, WorkspaceMemberWithUser,
     },
-    Workspace, WorkspaceRole, WorkspaceSettings, WorkspaceWithRole,
+    ActivityAction, ActivityEntityType, Workspace, WorkspaceRole, WorkspaceSettings,
+    WorkspaceWithRole,
 };
 use uuid::Uuid;
 
 use crate::auth::AuthUser;
 use crate::error::AppError;
+use crate::events::publish_member_removed;
+use crate::handlers::activity::{diff, record_activity, user_label, workspace_snapshot, NewActivity};
+use crate::handlers::attachments::{purge_blobs, workspace_storage_keys};
+use crate::mail::{send_in_background, templates};
 use crate::routes::AppState;
 
 /// Generate URL-friendly slug from name
@@ -29,6 +35,37 @@ fn slugify(name: &str) -> String {
         .join("-")
 }
 
+/// Derive a task key prefix from the workspace name, e.g. "Backend Team" -> "BACK"
+fn default_key_prefix(name: &str) -> String {
+    let prefix: String = name
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .take(4)
+        .collect::<String>()
+        .to_ascii_uppercase();
+
+    match prefix.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() && prefix.len() >= 2 => prefix,
+        _ => "TODO".to_string(),
+    }
+}
+
+/// Validate a user-supplied task key prefix and normalize it to uppercase
+fn normalize_key_prefix(prefix: &str) -> Result<String, AppError> {
+    let prefix = prefix.trim().to_ascii_uppercase();
+
+    let valid = (2..=10).contains(&prefix.len())
+        && prefix.starts_with(|c: char| c.is_ascii_alphabetic())
+        && prefix.chars().all(|c| c.is_ascii_alphanumeric());
+
+    if !valid {
+        return Err(AppError::Validation(
+            "Task key prefix must be 2-10 letters or digits, starting with a letter".to_string(),
+        ));
+    }
+    Ok(prefix)
+}
+
 /// POST /api/v1/workspaces
 pub async fn create_workspace(
     State(state): State<AppState>,
@@ -39,6 +76,11 @@ pub async fn create_workspace(
         return Err(AppError::Validation("Workspace name is required".to_string()));
     }
 
+    let task_key_prefix = match req.task_key_prefix {
+        Some(ref prefix) => normalize_key_prefix(prefix)?,
+        None => default_key_prefix(&req.name),
+    };
+
     let workspace_id = Uuid::new_v4();
     let base_slug = slugify(&req.name);
 
@@ -47,11 +89,13 @@ pub async fn create_workspace(
 
     let now = Utc::now();
 
+    let mut tx = state.db.begin().await?;
+
     // Create workspace
     sqlx::query(
         r#"
-        INSERT INTO workspaces (id, name, slug, description, owner_id, created_at, updated_at)
-        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        INSERT INTO workspaces (id, name, slug, description, owner_id, task_key_prefix, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
         "#,
     )
     .bind(workspace_id)
@@ -59,9 +103,10 @@ pub async fn create_workspace(
     .bind(&slug)
     .bind(&req.description)
     .bind(user.id)
+    .bind(&task_key_prefix)
     .bind(now)
     .bind(now)
-    .execute(&state.db)
+    .execute(&mut *tx)
     .await?;
 
     // Add owner as member
@@ -74,7 +119,7 @@ pub async fn create_workspace(
     .bind(workspace_id)
     .bind(user.id)
     .bind(now)
-    .execute(&state.db)
+    .execute(&mut *tx)
     .await?;
 
     // Create default statuses
@@ -99,16 +144,33 @@ pub async fn create_workspace(
         .bind(is_done)
         .bind(position)
         .bind(now)
-        .execute(&state.db)
+        .execute(&mut *tx)
         .await?;
     }
 
+    record_activity(
+        &mut tx,
+        NewActivity {
+            workspace_id,
+            user_id: user.id,
+            entity_type: ActivityEntityType::Workspace,
+            entity_id: workspace_id,
+            entity_label: req.name.clone(),
+            action: ActivityAction::Created,
+            changes: None,
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
     Ok(Json(Workspace {
         id: workspace_id,
         name: req.name,
         slug,
         description: req.description,
         owner_id: user.id,
+        task_key_prefix,
         settings: WorkspaceSettings::default(),
         created_at: now,
         updated_at: now,
@@ -120,9 +182,9 @@ pub async fn list_workspaces(
     State(state): State<AppState>,
     Extension(user): Extension<AuthUser>,
 ) -> Result<Json<Vec<WorkspaceWithRole>>, AppError> {
-    let rows: Vec<(Uuid, String, String, Option<String>, Uuid, serde_json::Value, chrono::DateTime<Utc>, chrono::DateTime<Utc>, WorkspaceRole)> = sqlx::query_as(
+    let rows: Vec<(Uuid, String, String, Option<String>, Uuid, String, serde_json::Value, chrono::DateTime<Utc>, chrono::DateTime<Utc>, WorkspaceRole)> = sqlx::query_as(
         r#"
-        SELECT w.id, w.name, w.slug, w.description, w.owner_id, w.settings, w.created_at, w.updated_at, wm.role as "role: WorkspaceRole"
+        SELECT w.id, w.name, w.slug, w.description, w.owner_id, w.task_key_prefix, w.settings, w.created_at, w.updated_at, wm.role as "role: WorkspaceRole"
         FROM workspaces w
         JOIN workspace_members wm ON wm.workspace_id = w.id
         WHERE wm.user_id = $1
@@ -135,7 +197,7 @@ pub async fn list_workspaces(
 
     let workspaces = rows
         .into_iter()
-        .map(|(id, name, slug, description, owner_id, settings_json, created_at, updated_at, role)| {
+        .map(|(id, name, slug, description, owner_id, task_key_prefix, settings_json, created_at, updated_at, role)| {
             let settings: WorkspaceSettings = serde_json::from_value(settings_json).unwrap_or_default();
             WorkspaceWithRole {
                 workspace: Workspace {
@@ -144,6 +206,7 @@ pub async fn list_workspaces(
                     slug,
                     description,
                     owner_id,
+                    task_key_prefix,
                     settings,
                     created_at,
                     updated_at,
@@ -162,9 +225,9 @@ pub async fn get_workspace(
     Extension(user): Extension<AuthUser>,
     Path(workspace_id): Path<Uuid>,
 ) -> Result<Json<WorkspaceWithRole>, AppError> {
-    let row: Option<(Uuid, String, String, Option<String>, Uuid, serde_json::Value, chrono::DateTime<Utc>, chrono::DateTime<Utc>, WorkspaceRole)> = sqlx::query_as(
+    let row: Option<(Uuid, String, String, Option<String>, Uuid, String, serde_json::Value, chrono::DateTime<Utc>, chrono::DateTime<Utc>, WorkspaceRole)> = sqlx::query_as(
         r#"
-        SELECT w.id, w.name, w.slug, w.description, w.owner_id, w.settings, w.created_at, w.updated_at, wm.role as "role: WorkspaceRole"
+        SELECT w.id, w.name, w.slug, w.description, w.owner_id, w.task_key_prefix, w.settings, w.created_at, w.updated_at, wm.role as "role: WorkspaceRole"
         FROM workspaces w
         JOIN workspace_members wm ON wm.workspace_id = w.id
         WHERE w.id = $1 AND wm.user_id = $2
@@ -175,7 +238,7 @@ pub async fn get_workspace(
     .fetch_optional(&state.db)
     .await?;
 
-    let (id, name, slug, description, owner_id, settings_json, created_at, updated_at, role) =
+    let (id, name, slug, description, owner_id, task_key_prefix, settings_json, created_at, updated_at, role) =
         row.ok_or(AppError::NotFound)?;
 
     let settings: WorkspaceSettings = serde_json::from_value(settings_json).unwrap_or_default();
@@ -187,6 +250,7 @@ pub async fn get_workspace(
             slug,
             description,
             owner_id,
+            task_key_prefix,
             settings,
             created_at,
             updated_at,
@@ -217,30 +281,87 @@ pub async fn update_workspace(
         return Err(AppError::Forbidden);
     }
 
+    let name = req.name.non_null("name").map_err(AppError::Validation)?;
+    if name.as_deref().is_some_and(|n| n.trim().is_empty()) {
+        return Err(AppError::Validation("Workspace name is required".to_string()));
+    }
+
+    let task_key_prefix = match req.task_key_prefix.non_null("task_key_prefix").map_err(AppError::Validation)? {
+        Some(ref prefix) => Some(normalize_key_prefix(prefix)?),
+        None => None,
+    };
+
     let now = Utc::now();
-    let settings_json = req.settings.as_ref().map(|s| serde_json::to_value(s).unwrap_or_default());
+    let settings_json = req
+        .settings
+        .non_null("settings")
+        .map_err(AppError::Validation)?
+        .map(|s| serde_json::to_value(s).unwrap_or_default());
+
+    let mut tx = state.db.begin().await?;
+
+    let (_, before) = workspace_snapshot(&mut tx, workspace_id).await?;
+
+    // Keys already written down with the old prefix keep resolving
+    if let Some(ref prefix) = task_key_prefix {
+        sqlx::query(
+            r#"
+            INSERT INTO workspace_previous_key_prefixes (workspace_id, prefix, retired_at)
+            SELECT id, task_key_prefix, $3 FROM workspaces
+            WHERE id = $1 AND task_key_prefix != $2
+            ON CONFLICT (workspace_id, prefix) DO UPDATE SET retired_at = EXCLUDED.retired_at
+            "#,
+        )
+        .bind(workspace_id)
+        .bind(prefix)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+    }
 
     // Build dynamic update query
-    let row: (Uuid, String, String, Option<String>, Uuid, serde_json::Value, chrono::DateTime<Utc>, chrono::DateTime<Utc>) = sqlx::query_as(
+    let row: (Uuid, String, String, Option<String>, Uuid, String, serde_json::Value, chrono::DateTime<Utc>, chrono::DateTime<Utc>) = sqlx::query_as(
         r#"
         UPDATE workspaces
         SET name = COALESCE($1, name),
-            description = COALESCE($2, description),
-            settings = COALESCE($3, settings),
-            updated_at = $4
-        WHERE id = $5
-        RETURNING id, name, slug, description, owner_id, settings, created_at, updated_at
+            description = CASE WHEN $2 THEN $3 ELSE description END,
+            settings = COALESCE($4, settings),
+            task_key_prefix = COALESCE($5, task_key_prefix),
+            updated_at = $6
+        WHERE id = $7
+        RETURNING id, name, slug, description, owner_id, task_key_prefix, settings, created_at, updated_at
         "#,
     )
-    .bind(&req.name)
-    .bind(&req.description)
+    .bind(&name)
+    .bind(req.description.is_set())
+    .bind(req.description.value())
     .bind(&settings_json)
+    .bind(&task_key_prefix)
     .bind(now)
     .bind(workspace_id)
-    .fetch_one(&state.db)
+    .fetch_one(&mut *tx)
     .await?;
 
-    let settings: WorkspaceSettings = serde_json::from_value(row.5).unwrap_or_default();
+    let (entity_label, after) = workspace_snapshot(&mut tx, workspace_id).await?;
+    if let Some(changes) = diff(&before, &after) {
+        record_activity(
+            &mut tx,
+            NewActivity {
+                workspace_id,
+                user_id: user.id,
+                entity_type: ActivityEntityType::Workspace,
+                entity_id: workspace_id,
+                entity_label,
+                action: ActivityAction::Updated,
+                changes: Some(changes),
+            },
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    let settings: WorkspaceSettings = serde_json::from_value(row.6).unwrap_or_default();
 
     Ok(Json(Workspace {
         id: row.0,
@@ -248,9 +369,10 @@ pub async fn update_workspace(
         slug: row.2,
         description: row.3,
         owner_id: row.4,
+        task_key_prefix: row.5,
         settings,
-        created_at: row.6,
-        updated_at: row.7,
+        created_at: row.7,
+        updated_at: row.8,
     }))
 }
 
@@ -275,12 +397,16 @@ pub async fn delete_workspace(
         return Err(AppError::Forbidden);
     }
 
+    let blob_keys = workspace_storage_keys(&state, workspace_id).await?;
+
     // Delete workspace (cascades to members, statuses, tasks, etc.)
     sqlx::query("DELETE FROM workspaces WHERE id = $1")
         .bind(workspace_id)
         .execute(&state.db)
         .await?;
 
+    purge_blobs(&state, blob_keys).await;
+
     Ok(())
 }
 
@@ -303,9 +429,9 @@ pub async fn list_members(
         return Err(AppError::NotFound);
     }
 
-    let rows: Vec<(Uuid, String, String, WorkspaceRole)> = sqlx::query_as(
+    let rows: Vec<(Uuid, String, String, String, WorkspaceRole)> = sqlx::query_as(
         r#"
-        SELECT u.id, u.display_name, u.email, wm.role as "role: WorkspaceRole"
+        SELECT u.id, u.username, u.display_name, u.email, wm.role as "role: WorkspaceRole"
         FROM workspace_members wm
         JOIN users u ON u.id = wm.user_id
         WHERE wm.workspace_id = $1
@@ -325,8 +451,9 @@ pub async fn list_members(
 
     let members = rows
         .into_iter()
-        .map(|(user_id, display_name, email, role)| WorkspaceMemberWithUser {
+        .map(|(user_id, username, display_name, email, role)| WorkspaceMemberWithUser {
             user_id,
+            username,
             display_name,
             email,
             role,
@@ -392,6 +519,8 @@ pub async fn create_invite(
     let now = Utc::now();
     let expires_at = now + Duration::days(7);
 
+    let mut tx = state.db.begin().await?;
+
     sqlx::query(
         r#"
         INSERT INTO workspace_invites (id, workspace_id, email, role, token, invited_by, expires_at, created_at)
@@ -406,9 +535,49 @@ pub async fn create_invite(
     .bind(user.id)
     .bind(expires_at)
     .bind(now)
-    .execute(&state.db)
+    .execute(&mut *tx)
     .await?;
 
+    let changes = json!({ "role": { "old": null, "new": req.role } });
+    record_activity(
+        &mut tx,
+        NewActivity {
+            workspace_id,
+            user_id: user.id,
+            entity_type: ActivityEntityType::Member,
+            entity_id: invite_id,
+            entity_label: req.email.clone(),
+            action: ActivityAction::Invited,
+            changes: Some(changes),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    // The token in the response still works if the email doesn't arrive
+    let (workspace_name, inviter_name): (String, String) = sqlx::query_as(
+        r#"
+        SELECT w.name, u.display_name
+        FROM workspaces w, users u
+        WHERE w.id = $1 AND u.id = $2
+        "#,
+    )
+    .bind(workspace_id)
+    .bind(user.id)
+    .fetch_one(&state.db)
+    .await?;
+
+    let email = templates::workspace_invite(
+        &req.email,
+        &workspace_name,
+        &inviter_name,
+        req.role,
+        &token,
+        expires_at,
+    );
+    send_in_background(&state.mailer, email);
+
     Ok(Json(WorkspaceInvite {
         id: invite_id,
         workspace_id,
@@ -507,6 +676,8 @@ pub async fn accept_invite(
 
     let now = Utc::now();
 
+    let mut tx = state.db.begin().await?;
+
     // Add user as member
     sqlx::query(
         r#"
@@ -519,20 +690,38 @@ pub async fn accept_invite(
     .bind(&role)
     .bind(now)
     .bind(invite_id)
-    .execute(&state.db)
+    .execute(&mut *tx)
     .await?;
 
     // Mark invite as accepted
     sqlx::query("UPDATE workspace_invites SET accepted_at = $1 WHERE id = $2")
         .bind(now)
         .bind(invite_id)
-        .execute(&state.db)
+        .execute(&mut *tx)
         .await?;
 
+    let entity_label = user_label(&mut tx, user.id).await?;
+    let changes = json!({ "role": { "old": null, "new": role } });
+    record_activity(
+        &mut tx,
+        NewActivity {
+            workspace_id,
+            user_id: user.id,
+            entity_type: ActivityEntityType::Member,
+            entity_id: user.id,
+            entity_label,
+            action: ActivityAction::Joined,
+            changes: Some(changes),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
     // Return workspace with role
-    let workspace_row: (Uuid, String, String, Option<String>, Uuid, serde_json::Value, chrono::DateTime<Utc>, chrono::DateTime<Utc>) = sqlx::query_as(
+    let workspace_row: (Uuid, String, String, Option<String>, Uuid, String, serde_json::Value, chrono::DateTime<Utc>, chrono::DateTime<Utc>) = sqlx::query_as(
         r#"
-        SELECT id, name, slug, description, owner_id, settings, created_at, updated_at
+        SELECT id, name, slug, description, owner_id, task_key_prefix, settings, created_at, updated_at
         FROM workspaces WHERE id = $1
         "#,
     )
@@ -540,7 +729,7 @@ pub async fn accept_invite(
     .fetch_one(&state.db)
     .await?;
 
-    let settings: WorkspaceSettings = serde_json::from_value(workspace_row.5).unwrap_or_default();
+    let settings: WorkspaceSettings = serde_json::from_value(workspace_row.6).unwrap_or_default();
 
     Ok(Json(WorkspaceWithRole {
         workspace: Workspace {
@@ -549,9 +738,10 @@ pub async fn accept_invite(
             slug: workspace_row.2,
             description: workspace_row.3,
             owner_id: workspace_row.4,
+            task_key_prefix: workspace_row.5,
             settings,
-            created_at: workspace_row.6,
-            updated_at: workspace_row.7,
+            created_at: workspace_row.7,
+            updated_at: workspace_row.8,
         },
         role,
     }))
@@ -605,18 +795,40 @@ pub async fn update_member_role(
         return Err(AppError::Validation("Cannot change your own role".to_string()));
     }
 
+    let mut tx = state.db.begin().await?;
+
     // Update role
     sqlx::query("UPDATE workspace_members SET role = $1 WHERE workspace_id = $2 AND user_id = $3")
         .bind(&req.role)
         .bind(workspace_id)
         .bind(member_id)
-        .execute(&state.db)
+        .execute(&mut *tx)
         .await?;
 
+    if target_role != req.role {
+        let entity_label = user_label(&mut tx, member_id).await?;
+        let changes = json!({ "role": { "old": target_role, "new": req.role } });
+        record_activity(
+            &mut tx,
+            NewActivity {
+                workspace_id,
+                user_id: user.id,
+                entity_type: ActivityEntityType::Member,
+                entity_id: member_id,
+                entity_label,
+                action: ActivityAction::RoleChanged,
+                changes: Some(changes),
+            },
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+
     // Return updated member
-    let row: (Uuid, String, String, WorkspaceRole) = sqlx::query_as(
+    let row: (Uuid, String, String, String, WorkspaceRole) = sqlx::query_as(
         r#"
-        SELECT u.id, u.display_name, u.email, wm.role as "role: WorkspaceRole"
+        SELECT u.id, u.username, u.display_name, u.email, wm.role as "role: WorkspaceRole"
         FROM workspace_members wm
         JOIN users u ON u.id = wm.user_id
         WHERE wm.workspace_id = $1 AND wm.user_id = $2
@@ -629,9 +841,10 @@ pub async fn update_member_role(
 
     Ok(Json(WorkspaceMemberWithUser {
         user_id: row.0,
-        display_name: row.1,
-        email: row.2,
-        role: row.3,
+        username: row.1,
+        display_name: row.2,
+        email: row.3,
+        role: row.4,
     }))
 }
 
@@ -679,13 +892,35 @@ pub async fn remove_member(
         ));
     }
 
+    let mut tx = state.db.begin().await?;
+
     // Remove member
     sqlx::query("DELETE FROM workspace_members WHERE workspace_id = $1 AND user_id = $2")
         .bind(workspace_id)
         .bind(member_id)
-        .execute(&state.db)
+        .execute(&mut *tx)
         .await?;
 
+    let entity_label = user_label(&mut tx, member_id).await?;
+    let changes = json!({ "role": { "old": target_role, "new": null } });
+    record_activity(
+        &mut tx,
+        NewActivity {
+            workspace_id,
+            user_id: user.id,
+            entity_type: ActivityEntityType::Member,
+            entity_id: member_id,
+            entity_label,
+            action: ActivityAction::Removed,
+            changes: Some(changes),
+        },
+    )
+    .await?;
+
+    publish_member_removed(&mut *tx, workspace_id, member_id).await?;
+
+    tx.commit().await?;
+
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -718,6 +953,7 @@ pub async fn get_workspace_stats(
         SELECT COUNT(*) FROM tasks t
         JOIN task_statuses s ON t.status_id = s.id
         WHERE t.workspace_id = $1 AND t.due_date = $2 AND s.is_done = FALSE
+          AND t.deleted_at IS NULL
         "#,
     )
     .bind(workspace_id)
@@ -731,6 +967,7 @@ pub async fn get_workspace_stats(
         SELECT COUNT(*) FROM tasks t
         JOIN task_statuses s ON t.status_id = s.id
         WHERE t.workspace_id = $1 AND t.due_date < $2 AND s.is_done = FALSE
+          AND t.deleted_at IS NULL
         "#,
     )
     .bind(workspace_id)
@@ -743,7 +980,7 @@ pub async fn get_workspace_stats(
         r#"
         SELECT COUNT(*) FROM tasks t
         JOIN task_statuses s ON t.status_id = s.id
-        WHERE t.workspace_id = $1 AND s.is_done = FALSE
+        WHERE t.workspace_id = $1 AND s.is_done = FALSE AND t.deleted_at IS NULL
         "#,
     )
     .bind(workspace_id)
@@ -754,7 +991,7 @@ pub async fn get_workspace_stats(
     let (completed_this_week,): (i64,) = sqlx::query_as(
         r#"
         SELECT COUNT(*) FROM tasks
-        WHERE workspace_id = $1 AND completed_at >= $2
+        WHERE workspace_id = $1 AND completed_at >= $2 AND deleted_at IS NULL
         "#,
     )
     .bind(workspace_id)
@@ -767,7 +1004,9 @@ pub async fn get_workspace_stats(
         r#"
         SELECT COUNT(*) FROM tasks t
         JOIN task_statuses s ON t.status_id = s.id
-        WHERE t.workspace_id = $1 AND t.assigned_to = $2 AND s.is_done = FALSE
+        JOIN task_assignees a ON a.task_id = t.id
+        WHERE t.workspace_id = $1 AND a.user_id = $2 AND s.is_done = FALSE
+          AND t.deleted_at IS NULL
         "#,
     )
     .bind(workspace_id)
@@ -777,7 +1016,7 @@ pub async fn get_workspace_stats(
 
     // Documents count
     let (documents_count,): (i64,) = sqlx::query_as(
-        "SELECT COUNT(*) FROM documents WHERE workspace_id = $1",
+        "SELECT COUNT(*) FROM documents WHERE workspace_id = $1 AND deleted_at IS NULL",
     )
     .bind(workspace_id)
     .fetch_one(&state.db)
@@ -0,0 +1,42 @@
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use lettre::message::Mailbox;
+use uuid::Uuid;
+
+use super::{Email, Mailer};
+
+/// Delivers into a local maildir (`tmp/`, `new/`, `cur/`), so development
+/// setups and tests can read what would have been sent. Any mail client that
+/// speaks maildir, e.g. `mutt -f`, can open it.
+pub struct MaildirMailer {
+    root: PathBuf,
+    from: Mailbox,
+}
+
+impl MaildirMailer {
+    pub fn new(root: PathBuf, from: Mailbox) -> anyhow::Result<Self> {
+        for dir in ["tmp", "new", "cur"] {
+            std::fs::create_dir_all(root.join(dir))?;
+        }
+        Ok(Self { root, from })
+    }
+}
+
+#[async_trait]
+impl Mailer for MaildirMailer {
+    async fn send(&self, email: &Email) -> anyhow::Result<()> {
+        let message = email.to_message(&self.from)?;
+        let name = format!("{}.{}.todo-server", Utc::now().timestamp(), Uuid::new_v4().simple());
+
+        // Maildir delivery: write under tmp/, then move into new/ in one step
+        let tmp = self.root.join("tmp").join(&name);
+        let new = self.root.join("new").join(&name);
+        tokio::fs::write(&tmp, message.formatted()).await?;
+        tokio::fs::rename(&tmp, &new).await?;
+
+        tracing::debug!("Delivered \"{}\" for {} to {}", email.subject, email.to, new.display());
+        Ok(())
+    }
+}
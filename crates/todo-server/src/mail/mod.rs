@@ -0,0 +1,76 @@
+mod maildir;
+mod smtp;
+pub mod templates;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use lettre::message::{header::ContentType, Mailbox};
+use lettre::Message;
+
+use crate::config::MailConfig;
+
+pub use maildir::MaildirMailer;
+pub use smtp::SmtpMailer;
+
+/// A plain-text email, addressed and ready to send
+#[derive(Debug, Clone)]
+pub struct Email {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+impl Email {
+    fn to_message(&self, from: &Mailbox) -> anyhow::Result<Message> {
+        Ok(Message::builder()
+            .from(from.clone())
+            .to(self.to.parse()?)
+            .subject(&self.subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(self.body.clone())?)
+    }
+}
+
+/// Outbound email delivery
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, email: &Email) -> anyhow::Result<()>;
+}
+
+pub type DynMailer = Arc<dyn Mailer>;
+
+/// Send from a spawned task, so the request neither waits on the mail server
+/// nor shows through its timing whether anything was sent. Failures are logged.
+pub fn send_in_background(mailer: &DynMailer, email: Email) {
+    let mailer = mailer.clone();
+    tokio::spawn(async move {
+        if let Err(e) = mailer.send(&email).await {
+            tracing::warn!("Failed to send \"{}\" to {}: {:?}", email.subject, email.to, e);
+        }
+    });
+}
+
+pub fn create_mailer(config: &MailConfig, from: &str) -> anyhow::Result<DynMailer> {
+    let from: Mailbox = from
+        .parse()
+        .map_err(|e| anyhow::anyhow!("Invalid MAIL_FROM '{}': {}", from, e))?;
+
+    match config {
+        MailConfig::Maildir { path } => Ok(Arc::new(MaildirMailer::new(path.clone(), from)?)),
+        MailConfig::Smtp {
+            host,
+            port,
+            tls,
+            username,
+            password,
+        } => Ok(Arc::new(SmtpMailer::new(
+            host,
+            *port,
+            *tls,
+            username.as_deref(),
+            password.as_deref(),
+            from,
+        )?)),
+    }
+}
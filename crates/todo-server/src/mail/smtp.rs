@@ -0,0 +1,52 @@
+use async_trait::async_trait;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+
+use super::{Email, Mailer};
+use crate::config::SmtpTls;
+
+/// Sends through an SMTP relay, pooling connections between messages
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    pub fn new(
+        host: &str,
+        port: u16,
+        tls: SmtpTls,
+        username: Option<&str>,
+        password: Option<&str>,
+        from: Mailbox,
+    ) -> anyhow::Result<Self> {
+        let mut builder = match tls {
+            SmtpTls::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)?,
+            SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(host)?,
+            SmtpTls::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host),
+        }
+        .port(port);
+
+        if let Some(username) = username {
+            builder = builder.credentials(Credentials::new(
+                username.to_string(),
+                password.unwrap_or_default().to_string(),
+            ));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+        })
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, email: &Email) -> anyhow::Result<()> {
+        let message = email.to_message(&self.from)?;
+        self.transport.send(message).await?;
+        Ok(())
+    }
+}
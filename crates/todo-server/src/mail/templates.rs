@@ -0,0 +1,126 @@
+//! The emails the server sends, as plain text
+
+use chrono::{DateTime, Utc};
+use todo_shared::{Notification, WorkspaceRole};
+
+use super::Email;
+
+/// Most notifications listed in one digest; the rest are only counted
+const MAX_DIGEST_ITEMS: usize = 20;
+
+/// Greeting, paragraphs and the common sign-off
+fn render(name: &str, paragraphs: &[String]) -> String {
+    let mut body = format!("Hi {},\n\n", name);
+    for paragraph in paragraphs {
+        body.push_str(paragraph);
+        body.push_str("\n\n");
+    }
+    body.push_str("-- \nTODO TUI\n");
+    body
+}
+
+pub fn verification_code(to: &str, username: &str, code: &str, valid_minutes: i64) -> Email {
+    Email {
+        to: to.to_string(),
+        subject: format!("{} is your verification code", code),
+        body: render(
+            username,
+            &[
+                format!("Your verification code is:\n\n    {}", code),
+                format!(
+                    "It expires in {} minutes. Enter it in the app to finish setting up your account.",
+                    valid_minutes
+                ),
+                "If you didn't sign up, you can ignore this email.".to_string(),
+            ],
+        ),
+    }
+}
+
+pub fn password_reset_code(to: &str, username: &str, code: &str, valid_minutes: i64) -> Email {
+    Email {
+        to: to.to_string(),
+        subject: format!("{} is your password reset code", code),
+        body: render(
+            username,
+            &[
+                format!("Someone asked to reset your password. Your reset code is:\n\n    {}", code),
+                format!(
+                    "It expires in {} minutes. Resetting signs you out everywhere else.",
+                    valid_minutes
+                ),
+                "If it wasn't you, ignore this email; your password stays as it is.".to_string(),
+            ],
+        ),
+    }
+}
+
+pub fn workspace_invite(
+    to: &str,
+    workspace_name: &str,
+    inviter_name: &str,
+    role: WorkspaceRole,
+    token: &str,
+    expires_at: DateTime<Utc>,
+) -> Email {
+    let role = format!("{:?}", role).to_lowercase();
+    Email {
+        to: to.to_string(),
+        subject: format!("{} invited you to {}", inviter_name, workspace_name),
+        body: render(
+            to,
+            &[
+                format!(
+                    "{} invited you to join the workspace \"{}\" as {}.",
+                    inviter_name, workspace_name, role
+                ),
+                format!(
+                    "To accept, sign in with this email address and run:\n\n    todo-tui --accept-invite {}\n\nor paste the token into \"Accept invite\" on the workspace screen.",
+                    token
+                ),
+                format!("The invitation expires on {}.", expires_at.format("%Y-%m-%d %H:%M UTC")),
+            ],
+        ),
+    }
+}
+
+/// Unread notifications since the last digest, as (workspace name, notification)
+pub fn notification_digest(to: &str, username: &str, items: &[(String, Notification)]) -> Email {
+    let mut list = String::new();
+    let mut workspace: Option<&str> = None;
+    for (workspace_name, notification) in items.iter().take(MAX_DIGEST_ITEMS) {
+        if workspace != Some(workspace_name.as_str()) {
+            if workspace.is_some() {
+                list.push('\n');
+            }
+            list.push_str(&format!("{}\n", workspace_name));
+            workspace = Some(workspace_name);
+        }
+        list.push_str(&format!("  - {}", notification.summary()));
+        if let Some(ref detail) = notification.detail {
+            list.push_str(&format!(": {}", detail));
+        }
+        list.push('\n');
+    }
+    if items.len() > MAX_DIGEST_ITEMS {
+        list.push_str(&format!("\n...and {} more.\n", items.len() - MAX_DIGEST_ITEMS));
+    }
+
+    let subject = match items.len() {
+        1 => "1 new notification".to_string(),
+        n => format!("{} new notifications", n),
+    };
+
+    Email {
+        to: to.to_string(),
+        subject,
+        body: render(
+            username,
+            &[
+                "Here's what happened while you were away:".to_string(),
+                list.trim_end().to_string(),
+                "Open the inbox with `I` in the app to read them there.".to_string(),
+            ],
+        ),
+    }
+}
@@ -4,11 +4,16 @@ use tokio::net::TcpListener;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 mod auth;
+mod concurrency;
 mod config;
 mod db;
 mod error;
+mod events;
 mod handlers;
+mod mail;
+mod mentions;
 mod routes;
+mod storage;
 
 pub use config::Config;
 pub use db::DbPool;
@@ -31,13 +36,37 @@ async fn main() -> anyhow::Result<()> {
 
     tracing::info!("Connected to database");
 
-    let app = routes::create_router(db_pool, config.clone());
+    let storage = storage::create_storage(&config.storage)?;
+    let mailer = mail::create_mailer(&config.mail, &config.mail_from)?;
+
+    // Relay change notifications from Postgres to connected clients
+    let events = events::spawn_listener(db_pool.clone());
+
+    let state = routes::AppState {
+        db: db_pool,
+        config: config.clone(),
+        storage,
+        mailer,
+        events,
+    };
+
+    // Purge trash past its retention period in the background
+    tokio::spawn(handlers::trash::run_retention(state.clone()));
+
+    // Remind assignees of tasks coming due
+    tokio::spawn(handlers::notifications::run_due_reminders(state.clone()));
+
+    // Email unread notifications to people who haven't seen them
+    tokio::spawn(handlers::notifications::run_notification_digests(state.clone()));
+
+    let app = routes::create_router(state);
 
     let addr = SocketAddr::from(([0, 0, 0, 0], config.port));
     tracing::info!("Server listening on {}", addr);
 
     let listener = TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+    // Peer addresses are recorded on login sessions
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await?;
 
     Ok(())
 }
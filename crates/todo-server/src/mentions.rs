@@ -0,0 +1,105 @@
+//! @mentions in task descriptions and comments
+//!
+//! Mentions are parsed when the text is saved and stored per person, so an
+//! edit only reports the people it newly mentions. Readers get the stored
+//! mentions back as spans over the text.
+
+use std::collections::HashMap;
+
+use sqlx::{PgConnection, PgExecutor};
+use todo_shared::find_mentions;
+use uuid::Uuid;
+
+use crate::error::AppError;
+
+/// Store who `text` mentions, replacing what was stored for the same
+/// description (`comment_id` of `None`) or comment. Only workspace members
+/// count. Returns the members who weren't mentioned there before.
+pub async fn sync_mentions(
+    conn: &mut PgConnection,
+    workspace_id: Uuid,
+    task_id: Uuid,
+    comment_id: Option<Uuid>,
+    author_id: Uuid,
+    text: &str,
+) -> Result<Vec<Uuid>, AppError> {
+    let mut usernames: Vec<String> = Vec::new();
+    for (_, name) in find_mentions(text) {
+        let name = name.to_lowercase();
+        if !usernames.contains(&name) {
+            usernames.push(name);
+        }
+    }
+
+    let mentioned: Vec<(Uuid,)> = if usernames.is_empty() {
+        Vec::new()
+    } else {
+        sqlx::query_as(
+            r#"
+            SELECT u.id FROM users u
+            INNER JOIN workspace_members m ON m.user_id = u.id AND m.workspace_id = $1
+            WHERE LOWER(u.username) = ANY($2)
+            "#,
+        )
+        .bind(workspace_id)
+        .bind(&usernames)
+        .fetch_all(&mut *conn)
+        .await?
+    };
+    let mentioned: Vec<Uuid> = mentioned.into_iter().map(|(id,)| id).collect();
+
+    sqlx::query(
+        r#"
+        DELETE FROM mentions
+        WHERE task_id = $1 AND comment_id IS NOT DISTINCT FROM $2 AND user_id <> ALL($3)
+        "#,
+    )
+    .bind(task_id)
+    .bind(comment_id)
+    .bind(&mentioned)
+    .execute(&mut *conn)
+    .await?;
+
+    let added: Vec<(Uuid,)> = sqlx::query_as(
+        r#"
+        INSERT INTO mentions (workspace_id, task_id, comment_id, user_id, mentioned_by)
+        SELECT $1, $2, $3, user_id, $5 FROM UNNEST($4::uuid[]) AS user_id
+        ON CONFLICT DO NOTHING
+        RETURNING user_id
+        "#,
+    )
+    .bind(workspace_id)
+    .bind(task_id)
+    .bind(comment_id)
+    .bind(&mentioned)
+    .bind(author_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(added.into_iter().map(|(id,)| id).collect())
+}
+
+/// The members each of `comment_ids` mentions, as (user id, username)
+pub async fn comment_mentions<'e>(
+    executor: impl PgExecutor<'e>,
+    comment_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<(Uuid, String)>>, AppError> {
+    let rows: Vec<(Uuid, Uuid, String)> = sqlx::query_as(
+        r#"
+        SELECT m.comment_id, u.id, u.username
+        FROM mentions m
+        INNER JOIN users u ON u.id = m.user_id
+        WHERE m.comment_id = ANY($1)
+        "#,
+    )
+    .bind(comment_ids)
+    .fetch_all(executor)
+    .await?;
+
+    let mut by_comment: HashMap<Uuid, Vec<(Uuid, String)>> = HashMap::new();
+    for (comment_id, user_id, username) in rows {
+        by_comment.entry(comment_id).or_default().push((user_id, username));
+    }
+
+    Ok(by_comment)
+}
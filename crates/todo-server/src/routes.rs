@@ -1,4 +1,5 @@
 use axum::{
+    extract::DefaultBodyLimit,
     middleware,
     routing::{delete, get, patch, post, put},
     Router,
@@ -6,21 +7,30 @@ use axum::{
 use tower_http::{compression::CompressionLayer, cors::CorsLayer, trace::TraceLayer};
 
 use crate::auth::auth_middleware;
+use crate::events::EventSender;
 use crate::handlers::{
-    auth as auth_handlers, comments as comment_handlers, documents as document_handlers,
-    search as search_handlers, statuses as status_handlers, tags as tag_handlers,
-    tasks as task_handlers, workspaces as workspace_handlers,
+    activity as activity_handlers, attachments as attachment_handlers, auth as auth_handlers, checklists as checklist_handlers, comments as comment_handlers, custom_fields as custom_field_handlers,
+    dependencies as dependency_handlers,
+    documents as document_handlers, events as event_handlers, notifications as notification_handlers, search as search_handlers, statuses as status_handlers, tags as tag_handlers,
+    tasks as task_handlers, transitions as transition_handlers, trash as trash_handlers, work_logs as work_log_handlers,
+    workspaces as workspace_handlers,
 };
+use crate::mail::DynMailer;
+use crate::storage::DynStorage;
 use crate::{Config, DbPool};
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: DbPool,
     pub config: Config,
+    pub storage: DynStorage,
+    pub mailer: DynMailer,
+    pub events: EventSender,
 }
 
-pub fn create_router(db: DbPool, config: Config) -> Router {
-    let state = AppState { db, config };
+pub fn create_router(state: AppState) -> Router {
+    // Leave room for the multipart framing around the file itself
+    let upload_limit = DefaultBodyLimit::max(state.config.attachment_max_bytes + 64 * 1024);
 
     // Public auth routes (no middleware)
     let public_auth_routes = Router::new()
@@ -28,12 +38,20 @@ pub fn create_router(db: DbPool, config: Config) -> Router {
         .route("/login", post(auth_handlers::login))
         .route("/refresh", post(auth_handlers::refresh))
         .route("/verify-email", post(auth_handlers::verify_email))
-        .route("/resend-verification", post(auth_handlers::resend_verification));
+        .route("/resend-verification", post(auth_handlers::resend_verification))
+        .route("/forgot-password", post(auth_handlers::forgot_password))
+        .route("/reset-password", post(auth_handlers::reset_password));
 
     // Protected auth routes (need auth)
     let protected_auth_routes = Router::new()
         .route("/logout", post(auth_handlers::logout))
         .route("/me", get(auth_handlers::me))
+        .route("/change-password", post(auth_handlers::change_password))
+        .route(
+            "/sessions",
+            get(auth_handlers::list_sessions).delete(auth_handlers::revoke_all_sessions),
+        )
+        .route("/sessions/:session_id", delete(auth_handlers::revoke_session))
         .layer(middleware::from_fn_with_state(
             state.clone(),
             auth_middleware,
@@ -71,6 +89,8 @@ pub fn create_router(db: DbPool, config: Config) -> Router {
     let task_routes = Router::new()
         .route("/", get(task_handlers::list_tasks))
         .route("/", post(task_handlers::create_task))
+        .route("/bulk", post(task_handlers::bulk_tasks))
+        .route("/by-key/:key", get(task_handlers::get_task_by_key))
         .route("/:task_id", get(task_handlers::get_task))
         .route("/:task_id", patch(task_handlers::update_task))
         .route("/:task_id", delete(task_handlers::delete_task))
@@ -81,7 +101,69 @@ pub fn create_router(db: DbPool, config: Config) -> Router {
         .route("/", get(comment_handlers::list_comments))
         .route("/", post(comment_handlers::create_comment))
         .route("/:comment_id", patch(comment_handlers::update_comment))
-        .route("/:comment_id", delete(comment_handlers::delete_comment));
+        .route("/:comment_id", delete(comment_handlers::delete_comment))
+        .route("/:comment_id/revisions", get(comment_handlers::list_comment_revisions))
+        .route("/:comment_id/reactions", post(comment_handlers::toggle_reaction));
+
+    // Checklist routes (nested under tasks)
+    let checklist_routes = Router::new()
+        .route("/", get(checklist_handlers::list_checklist))
+        .route("/", post(checklist_handlers::create_checklist_item))
+        .route("/reorder", post(checklist_handlers::reorder_checklist))
+        .route("/:item_id", patch(checklist_handlers::update_checklist_item))
+        .route("/:item_id", delete(checklist_handlers::delete_checklist_item));
+
+    // Work log routes (nested under tasks)
+    let work_log_routes = Router::new()
+        .route("/", get(work_log_handlers::list_work_logs))
+        .route("/", post(work_log_handlers::create_work_log))
+        .route("/:work_log_id", patch(work_log_handlers::update_work_log))
+        .route("/:work_log_id", delete(work_log_handlers::delete_work_log));
+
+    // Attachment upload routes (nested under tasks and documents)
+    let task_attachment_routes = Router::new()
+        .route("/", get(attachment_handlers::list_task_attachments))
+        .route("/", post(attachment_handlers::upload_task_attachment))
+        .layer(upload_limit.clone());
+    let document_attachment_routes = Router::new()
+        .route("/", get(attachment_handlers::list_document_attachments))
+        .route("/", post(attachment_handlers::upload_document_attachment))
+        .layer(upload_limit);
+
+    // Attachment routes (nested under workspaces)
+    let attachment_routes = Router::new()
+        .route("/usage", get(attachment_handlers::get_storage_usage))
+        .route("/:attachment_id", delete(attachment_handlers::delete_attachment))
+        .route("/:attachment_id/download", get(attachment_handlers::download_attachment));
+
+    // Dependency routes (nested under tasks)
+    let dependency_routes = Router::new()
+        .route("/", get(dependency_handlers::list_dependencies))
+        .route("/", post(dependency_handlers::add_dependency))
+        .route("/:blocking_task_id", delete(dependency_handlers::remove_dependency));
+
+    // Trash routes (nested under workspaces)
+    let trash_routes = Router::new()
+        .route("/", get(trash_handlers::list_trash))
+        .route("/", delete(trash_handlers::empty_trash))
+        .route("/:kind/:item_id", delete(trash_handlers::purge_item))
+        .route("/:kind/:item_id/restore", post(trash_handlers::restore_item));
+
+    // Activity routes (nested under workspaces)
+    let activity_routes = Router::new()
+        .route("/", get(activity_handlers::list_activity))
+        .route("/:entity_type/:entity_id", get(activity_handlers::entity_history));
+
+    // Live change events (nested under workspaces)
+    let event_routes = Router::new().route("/", get(event_handlers::workspace_events));
+
+    // Notification routes (nested under workspaces)
+    let notification_routes = Router::new()
+        .route("/", get(notification_handlers::list_notifications))
+        .route("/read-all", post(notification_handlers::mark_all_read))
+        .route("/settings", get(notification_handlers::get_settings))
+        .route("/settings", put(notification_handlers::update_settings))
+        .route("/:notification_id/read", post(notification_handlers::mark_read));
 
     // Search routes (nested under workspaces)
     let search_routes = Router::new().route("/", get(search_handlers::search));
@@ -93,6 +175,24 @@ pub fn create_router(db: DbPool, config: Config) -> Router {
         .route("/:tag_id", patch(tag_handlers::update_tag))
         .route("/:tag_id", delete(tag_handlers::delete_tag));
 
+    // Custom field definition routes (nested under workspaces)
+    let custom_field_routes = Router::new()
+        .route("/", get(custom_field_handlers::list_custom_fields))
+        .route("/", post(custom_field_handlers::create_custom_field))
+        .route("/:field_id", patch(custom_field_handlers::update_custom_field))
+        .route("/:field_id", delete(custom_field_handlers::delete_custom_field));
+
+    // Workflow transition routes (nested under workspaces)
+    let transition_routes = Router::new()
+        .route("/", get(transition_handlers::list_transitions))
+        .route("/", post(transition_handlers::create_transition))
+        .route("/:transition_id", patch(transition_handlers::update_transition))
+        .route("/:transition_id", delete(transition_handlers::delete_transition));
+
+    // Task custom field value routes (nested under tasks)
+    let task_custom_field_routes = Router::new()
+        .route("/", put(custom_field_handlers::set_task_custom_fields));
+
     // Task tag routes (nested under tasks)
     let task_tag_routes = Router::new()
         .route("/", get(tag_handlers::get_task_tags))
@@ -124,15 +224,28 @@ pub fn create_router(db: DbPool, config: Config) -> Router {
     let protected_routes = Router::new()
         .nest("/workspaces", workspace_routes)
         .nest("/workspaces/:id/statuses", status_routes)
+        .nest("/workspaces/:id/transitions", transition_routes)
         .nest("/workspaces/:id/tasks", task_routes)
         .nest("/workspaces/:id/tasks/:task_id/comments", comment_routes)
         .nest("/workspaces/:id/tasks/:task_id/tags", task_tag_routes)
+        .nest("/workspaces/:id/tasks/:task_id/custom-fields", task_custom_field_routes)
+        .nest("/workspaces/:id/tasks/:task_id/dependencies", dependency_routes)
+        .nest("/workspaces/:id/tasks/:task_id/work-logs", work_log_routes)
+        .nest("/workspaces/:id/tasks/:task_id/checklist", checklist_routes)
+        .nest("/workspaces/:id/tasks/:task_id/attachments", task_attachment_routes)
         .nest(
             "/workspaces/:id/tasks/:task_id/documents",
             task_documents_route,
         )
         .nest("/workspaces/:id/tags", tag_routes)
+        .nest("/workspaces/:id/custom-fields", custom_field_routes)
         .nest("/workspaces/:id/documents", document_routes)
+        .nest("/workspaces/:id/documents/:doc_id/attachments", document_attachment_routes)
+        .nest("/workspaces/:id/attachments", attachment_routes)
+        .nest("/workspaces/:id/trash", trash_routes)
+        .nest("/workspaces/:id/activity", activity_routes)
+        .nest("/workspaces/:id/events", event_routes)
+        .nest("/workspaces/:id/notifications", notification_routes)
         .nest("/workspaces/:id/search", search_routes)
         .layer(middleware::from_fn_with_state(
             state.clone(),
@@ -0,0 +1,42 @@
+mod local;
+mod s3;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::body::Bytes;
+
+use crate::config::StorageConfig;
+
+pub use local::LocalStorage;
+pub use s3::S3Storage;
+
+/// Blob store for attachment contents. Keys are opaque, `/`-separated paths.
+#[async_trait]
+pub trait Storage: Send + Sync {
+    async fn put(&self, key: &str, data: Bytes, content_type: &str) -> anyhow::Result<()>;
+    async fn get(&self, key: &str) -> anyhow::Result<Bytes>;
+    /// Deleting a missing key is not an error
+    async fn delete(&self, key: &str) -> anyhow::Result<()>;
+}
+
+pub type DynStorage = Arc<dyn Storage>;
+
+pub fn create_storage(config: &StorageConfig) -> anyhow::Result<DynStorage> {
+    match config {
+        StorageConfig::Local { path } => Ok(Arc::new(LocalStorage::new(path.clone())?)),
+        StorageConfig::S3 {
+            endpoint,
+            bucket,
+            region,
+            access_key,
+            secret_key,
+        } => Ok(Arc::new(S3Storage::new(
+            endpoint,
+            bucket,
+            region,
+            access_key,
+            secret_key,
+        )?)),
+    }
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::ActivityEntry;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ActivityListResponse {
+    pub entries: Vec<ActivityEntry>,
+    pub total: i64,
+    pub page: u32,
+    pub limit: u32,
+}
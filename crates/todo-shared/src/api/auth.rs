@@ -44,3 +44,23 @@ pub struct VerifyEmailRequest {
 pub struct ResendVerificationRequest {
     pub email: String,
 }
+
+/// Ask for a password reset code to be sent to `email`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ForgotPasswordRequest {
+    pub email: String,
+}
+
+/// Set a new password with the code from a [`ForgotPasswordRequest`]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResetPasswordRequest {
+    pub email: String,
+    pub code: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChangePasswordRequest {
+    pub current_password: String,
+    pub new_password: String,
+}
@@ -2,6 +2,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::Patch;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateDocumentRequest {
     pub title: String,
@@ -11,12 +13,16 @@ pub struct CreateDocumentRequest {
     pub content: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct UpdateDocumentRequest {
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub title: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub content: Patch<String>,
+    /// Version the edit is based on; a newer document is rejected with 409.
+    /// Same as sending `If-Match`.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub title: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub content: Option<String>,
+    pub expected_version: Option<i32>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
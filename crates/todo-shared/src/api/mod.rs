@@ -1,11 +1,17 @@
+mod activity;
 mod auth;
 mod documents;
+mod notifications;
+mod patch;
 mod search;
 mod tasks;
 mod workspaces;
 
+pub use activity::*;
 pub use auth::*;
 pub use documents::*;
+pub use notifications::*;
+pub use patch::*;
 pub use search::*;
 pub use tasks::*;
 pub use workspaces::*;
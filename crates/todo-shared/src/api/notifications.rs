@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::Notification;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NotificationListResponse {
+    pub notifications: Vec<Notification>,
+    pub total: i64,
+    /// Unread notifications in the workspace, across all pages
+    pub unread: i64,
+    pub page: u32,
+    pub limit: u32,
+}
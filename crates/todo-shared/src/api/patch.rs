@@ -0,0 +1,135 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// One field of a partial update: left out (keep the current value), sent as
+/// `null` (clear it) or sent with a new value.
+///
+/// Declare fields with `#[serde(default, skip_serializing_if = "Patch::is_absent")]`
+/// so a missing key stays `Absent` instead of reading as `null`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Patch<T> {
+    #[default]
+    Absent,
+    Null,
+    Value(T),
+}
+
+impl<T> Patch<T> {
+    /// Sets the field when there is a value and clears it otherwise, e.g. for
+    /// an edit form where an empty input means "remove"
+    pub fn set_or_clear(value: Option<T>) -> Self {
+        match value {
+            Some(value) => Patch::Value(value),
+            None => Patch::Null,
+        }
+    }
+
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Patch::Absent)
+    }
+
+    /// Whether the field gets written, either cleared or set
+    pub fn is_set(&self) -> bool {
+        !self.is_absent()
+    }
+
+    /// The new value, if one was sent
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            Patch::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Patch<U> {
+        match self {
+            Patch::Absent => Patch::Absent,
+            Patch::Null => Patch::Null,
+            Patch::Value(value) => Patch::Value(f(value)),
+        }
+    }
+
+    /// The field's value after applying this patch to `current`
+    pub fn apply(self, current: Option<T>) -> Option<T> {
+        match self {
+            Patch::Absent => current,
+            Patch::Null => None,
+            Patch::Value(value) => Some(value),
+        }
+    }
+
+    /// For fields that can't be empty: the new value if any, or an error
+    /// naming the field when it was sent as `null`
+    pub fn non_null(self, field: &str) -> Result<Option<T>, String> {
+        match self {
+            Patch::Absent => Ok(None),
+            Patch::Null => Err(format!("{} can't be cleared", field)),
+            Patch::Value(value) => Ok(Some(value)),
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for Patch<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            // Absent fields are skipped by `skip_serializing_if`
+            Patch::Absent | Patch::Null => serializer.serialize_none(),
+            Patch::Value(value) => serializer.serialize_some(value),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Patch<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Only called for keys that are present; missing ones use `Default`
+        Option::<T>::deserialize(deserializer).map(Patch::set_or_clear)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    struct Update {
+        #[serde(default, skip_serializing_if = "Patch::is_absent")]
+        name: Patch<String>,
+    }
+
+    #[test]
+    fn missing_key_is_absent() {
+        let update: Update = serde_json::from_value(json!({})).unwrap();
+        assert_eq!(update.name, Patch::Absent);
+    }
+
+    #[test]
+    fn null_is_null() {
+        let update: Update = serde_json::from_value(json!({ "name": null })).unwrap();
+        assert_eq!(update.name, Patch::Null);
+    }
+
+    #[test]
+    fn value_is_value() {
+        let update: Update = serde_json::from_value(json!({ "name": "x" })).unwrap();
+        assert_eq!(update.name, Patch::Value("x".to_string()));
+    }
+
+    #[test]
+    fn serializes_each_state() {
+        let absent = Update { name: Patch::Absent };
+        let null = Update { name: Patch::Null };
+        let value = Update { name: Patch::Value("x".to_string()) };
+        assert_eq!(serde_json::to_value(&absent).unwrap(), json!({}));
+        assert_eq!(serde_json::to_value(&null).unwrap(), json!({ "name": null }));
+        assert_eq!(serde_json::to_value(&value).unwrap(), json!({ "name": "x" }));
+    }
+
+    #[test]
+    fn round_trips() {
+        for name in [Patch::Absent, Patch::Null, Patch::Value("x".to_string())] {
+            let update = Update { name };
+            let json = serde_json::to_string(&update).unwrap();
+            assert_eq!(serde_json::from_str::<Update>(&json).unwrap(), update);
+        }
+    }
+}
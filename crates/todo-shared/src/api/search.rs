@@ -31,7 +31,7 @@ pub enum SearchResultItem {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchTaskResult {
-    pub task: Task,
+    pub task: Box<Task>,
     pub rank: f32,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title_highlights: Option<String>,
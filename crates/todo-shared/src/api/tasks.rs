@@ -1,8 +1,11 @@
+use std::collections::BTreeMap;
+
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::models::Priority;
+use super::Patch;
+use crate::models::{CustomFieldType, CustomFieldValue, Priority, RecurrenceRule, Task};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateTaskRequest {
@@ -16,26 +19,51 @@ pub struct CreateTaskRequest {
     pub due_date: Option<NaiveDate>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub time_estimate_minutes: Option<i32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assignees: Vec<Uuid>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub watchers: Vec<Uuid>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub assigned_to: Option<Uuid>,
+    pub parent_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<RecurrenceRule>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Fields left out are kept; `null` clears the optional ones
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct UpdateTaskRequest {
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub title: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub status_id: Patch<Uuid>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub description: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub priority: Patch<Priority>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub due_date: Patch<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub time_estimate_minutes: Patch<i32>,
+    /// Replaces the full assignee list; `null` unassigns everyone
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub assignees: Patch<Vec<Uuid>>,
+    /// Replaces the full watcher list; `null` removes all watchers
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub watchers: Patch<Vec<Uuid>>,
+    /// `null` turns a subtask back into a top-level task
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub parent_id: Patch<Uuid>,
+    /// `null` stops the task from repeating
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub recurrence: Patch<RecurrenceRule>,
+    /// Version the edit is based on; a newer task is rejected with 409.
+    /// Same as sending `If-Match`.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub title: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub status_id: Option<Uuid>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub description: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub priority: Option<Priority>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub due_date: Option<NaiveDate>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub time_estimate_minutes: Option<i32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub assigned_to: Option<Uuid>,
+    pub expected_version: Option<i32>,
+    /// Posted on the task when the update changes its status; some workflow
+    /// transitions require one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -43,6 +71,38 @@ pub struct MoveTaskRequest {
     pub status_id: Uuid,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub position: Option<i32>,
+    /// Posted on the task along with the move; some workflow transitions require one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// One change applied to every task of a bulk request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BulkTaskOperation {
+    /// Append to the end of another column
+    Move { status_id: Uuid },
+    SetPriority { priority: Option<Priority> },
+    /// Add assignees, keeping the existing ones
+    Assign { user_ids: Vec<Uuid> },
+    AddTags { tag_ids: Vec<Uuid> },
+    RemoveTags { tag_ids: Vec<Uuid> },
+    SetDueDate { due_date: Option<NaiveDate> },
+    /// Move to the trash, subtasks included
+    Delete,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkTaskRequest {
+    pub task_ids: Vec<Uuid>,
+    pub operation: BulkTaskOperation,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkTaskResponse {
+    /// The changed tasks as they are now; empty for deletes
+    pub tasks: Vec<Task>,
+    pub affected: usize,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -51,6 +111,7 @@ pub struct TaskListParams {
     pub status_id: Option<Uuid>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub priority: Option<Priority>,
+    /// Only return tasks this user is one of the assignees of
     #[serde(skip_serializing_if = "Option::is_none")]
     pub assigned_to: Option<Uuid>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -69,11 +130,80 @@ pub struct TaskListParams {
     pub limit: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tag_ids: Option<Vec<Uuid>>,
+    /// Only return direct subtasks of this task
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<Uuid>,
+    /// Only return tasks that are not subtasks of another task
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_level: Option<bool>,
+    /// Custom field filters: field id -> value as typed by the user
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_fields: Option<BTreeMap<Uuid, String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AddDependencyRequest {
+    pub blocking_task_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyTask {
+    pub task_id: Uuid,
+    pub task_title: String,
+    pub task_status_id: Uuid,
+    pub is_done: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TaskDependencies {
+    pub blocked_by: Vec<DependencyTask>,
+    pub blocks: Vec<DependencyTask>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateChecklistItemRequest {
+    pub content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateChecklistItemRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_checked: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReorderChecklistRequest {
+    pub item_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateWorkLogRequest {
+    pub minutes: i32,
+    /// Defaults to today
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub work_date: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateWorkLogRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minutes: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub work_date: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateCommentRequest {
     pub content: String,
+    /// Comment being replied to; replies to a reply join the same thread
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_comment_id: Option<Uuid>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -81,6 +211,13 @@ pub struct UpdateCommentRequest {
     pub content: String,
 }
 
+/// Add the caller's reaction to a comment, or take it back if it's there
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ToggleReactionRequest {
+    /// An emoji or a short word, e.g. "👍" or "+1"
+    pub emoji: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateTagRequest {
     pub name: String,
@@ -88,15 +225,39 @@ pub struct CreateTagRequest {
     pub color: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct UpdateTagRequest {
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub name: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub color: Patch<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SetTaskTagsRequest {
+    pub tag_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateCustomFieldRequest {
+    pub name: String,
+    pub field_type: CustomFieldType,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateCustomFieldRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub color: Option<String>,
+    pub options: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<i32>,
 }
 
+/// Upsert custom field values on a task; a `null` value clears the field
 #[derive(Debug, Serialize, Deserialize)]
-pub struct SetTaskTagsRequest {
-    pub tag_ids: Vec<Uuid>,
+pub struct SetCustomFieldValuesRequest {
+    pub values: Vec<CustomFieldValue>,
 }
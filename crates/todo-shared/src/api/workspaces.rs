@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::Patch;
 use crate::models::{WorkspaceRole, WorkspaceSettings};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -9,16 +10,20 @@ pub struct CreateWorkspaceRequest {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_key_prefix: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct UpdateWorkspaceRequest {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub name: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub description: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub settings: Option<WorkspaceSettings>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub name: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub description: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub task_key_prefix: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub settings: Patch<WorkspaceSettings>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -38,16 +43,43 @@ pub struct CreateStatusRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub color: Option<String>,
     pub is_done: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wip_limit: Option<i32>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct UpdateStatusRequest {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub name: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub color: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub is_done: Option<bool>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub name: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub color: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub is_done: Patch<bool>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub wip_limit: Patch<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateTransitionRequest {
+    pub from_status_id: Uuid,
+    pub to_status_id: Uuid,
+    #[serde(default)]
+    pub require_assignee: bool,
+    #[serde(default)]
+    pub require_comment: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_role: Option<WorkspaceRole>,
+}
+
+/// Changes a transition's rules; `null` for the role lets anyone who can edit make the move
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UpdateTransitionRequest {
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub require_assignee: Patch<bool>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub require_comment: Patch<bool>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub required_role: Patch<WorkspaceRole>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -58,6 +90,7 @@ pub struct ReorderStatusesRequest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkspaceMemberWithUser {
     pub user_id: Uuid,
+    pub username: String,
     pub display_name: String,
     pub email: String,
     pub role: WorkspaceRole,
@@ -92,3 +125,10 @@ pub struct WorkspaceStats {
     pub assigned_to_me: i64,
     pub documents_count: i64,
 }
+
+/// Attachment storage used by a workspace against its quota
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageUsage {
+    pub used_bytes: i64,
+    pub quota_bytes: i64,
+}
@@ -0,0 +1,144 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityEntityType {
+    Task,
+    Document,
+    Workspace,
+    Member,
+    Status,
+}
+
+impl ActivityEntityType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Task => "task",
+            Self::Document => "document",
+            Self::Workspace => "workspace",
+            Self::Member => "member",
+            Self::Status => "status",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "task" => Some(Self::Task),
+            "document" => Some(Self::Document),
+            "workspace" => Some(Self::Workspace),
+            "member" => Some(Self::Member),
+            "status" => Some(Self::Status),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityAction {
+    Created,
+    Updated,
+    Moved,
+    Reordered,
+    Deleted,
+    Restored,
+    Purged,
+    Invited,
+    Joined,
+    RoleChanged,
+    Removed,
+}
+
+impl ActivityAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Updated => "updated",
+            Self::Moved => "moved",
+            Self::Reordered => "reordered",
+            Self::Deleted => "deleted",
+            Self::Restored => "restored",
+            Self::Purged => "purged",
+            Self::Invited => "invited",
+            Self::Joined => "joined",
+            Self::RoleChanged => "role_changed",
+            Self::Removed => "removed",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "created" => Some(Self::Created),
+            "updated" => Some(Self::Updated),
+            "moved" => Some(Self::Moved),
+            "reordered" => Some(Self::Reordered),
+            "deleted" => Some(Self::Deleted),
+            "restored" => Some(Self::Restored),
+            "purged" => Some(Self::Purged),
+            "invited" => Some(Self::Invited),
+            "joined" => Some(Self::Joined),
+            "role_changed" => Some(Self::RoleChanged),
+            "removed" => Some(Self::Removed),
+            _ => None,
+        }
+    }
+
+    /// Past-tense verb for feeds, e.g. "changed the role of"
+    pub fn verb(&self) -> &'static str {
+        match self {
+            Self::RoleChanged => "changed the role of",
+            other => other.as_str(),
+        }
+    }
+}
+
+/// One field of an activity's `changes`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    /// Both `None` for long text fields, where only the fact of a change is kept
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+/// Entry of the workspace audit trail.
+/// `changes` maps field names to `{"old": .., "new": ..}`, or to
+/// `{"changed": true}` for long text such as descriptions and page content.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityEntry {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    pub entity_type: ActivityEntityType,
+    pub entity_id: Uuid,
+    /// Name of the entity at the time, e.g. "TODO-12 Fix login"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entity_label: Option<String>,
+    pub action: ActivityAction,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changes: Option<Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ActivityEntry {
+    pub fn field_changes(&self) -> Vec<FieldChange> {
+        let Some(Value::Object(fields)) = &self.changes else {
+            return Vec::new();
+        };
+
+        fields
+            .iter()
+            .map(|(field, change)| FieldChange {
+                field: field.clone(),
+                old: change.get("old").cloned(),
+                new: change.get("new").cloned(),
+            })
+            .collect()
+    }
+}
@@ -2,11 +2,14 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::MentionSpan;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Comment {
     pub id: Uuid,
     pub task_id: Uuid,
     pub user_id: Uuid,
+    pub parent_comment_id: Option<Uuid>,
     pub content: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -18,7 +21,47 @@ pub struct CommentWithAuthor {
     pub task_id: Uuid,
     pub user_id: Uuid,
     pub author_username: String,
+    /// Top-level comment this replies to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_comment_id: Option<Uuid>,
     pub content: String,
+    /// Members mentioned in `content`, in order of appearance
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mentions: Vec<MentionSpan>,
+    /// Reaction counts, in the order they were first used
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reactions: Vec<CommentReaction>,
+    /// Replies, oldest first. Only filled in on comment lists.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub replies: Vec<CommentWithAuthor>,
+    /// Whether the content was changed after posting; see its revisions
+    #[serde(default)]
+    pub edited: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
+
+/// Everyone who reacted to a comment with the same emoji or word
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommentReaction {
+    pub emoji: String,
+    pub count: i64,
+    /// Whether the caller is one of them
+    pub reacted: bool,
+}
+
+/// The text a comment had before one of its edits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentRevision {
+    pub id: Uuid,
+    pub content: String,
+    /// When this version was saved
+    pub written_at: DateTime<Utc>,
+    /// Who replaced it; an admin when the edit was moderation.
+    /// `None` once that account has been deleted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edited_by: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub editor_username: Option<String>,
+    pub edited_at: DateTime<Utc>,
+}
@@ -0,0 +1,134 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(feature = "sqlx", sqlx(type_name = "custom_field_type", rename_all = "snake_case"))]
+#[serde(rename_all = "snake_case")]
+pub enum CustomFieldType {
+    Text,
+    Number,
+    Date,
+    Select,
+    MultiSelect,
+    User,
+}
+
+impl CustomFieldType {
+    pub fn has_options(&self) -> bool {
+        matches!(self, Self::Select | Self::MultiSelect)
+    }
+}
+
+/// Workspace-defined task field
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomField {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub name: String,
+    pub field_type: CustomFieldType,
+    /// Allowed values for select and multi-select fields
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+    pub position: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Value of a custom field on a task.
+/// Text, date (YYYY-MM-DD), select and user (UUID) values are JSON strings,
+/// numbers are JSON numbers and multi-select values are arrays of strings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomFieldValue {
+    pub field_id: Uuid,
+    pub value: Value,
+}
+
+impl CustomField {
+    /// Find an option by name, ignoring case
+    fn find_option(&self, input: &str) -> Result<&str, String> {
+        self.options
+            .iter()
+            .find(|o| o.eq_ignore_ascii_case(input))
+            .map(|o| o.as_str())
+            .ok_or_else(|| format!("'{}' is not an option of {}", input, self.name))
+    }
+
+    /// Parse user input into a value for this field. Empty input parses to `Null` (clear).
+    pub fn parse_input(&self, input: &str) -> Result<Value, String> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(Value::Null);
+        }
+
+        match self.field_type {
+            CustomFieldType::Text => Ok(Value::String(input.to_string())),
+            CustomFieldType::Number => {
+                if let Ok(n) = input.parse::<i64>() {
+                    return Ok(Value::from(n));
+                }
+                input
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(serde_json::Number::from_f64)
+                    .map(Value::Number)
+                    .ok_or_else(|| format!("{} must be a number", self.name))
+            }
+            CustomFieldType::Date => NaiveDate::parse_from_str(input, "%Y-%m-%d")
+                .map(|d| Value::String(d.to_string()))
+                .map_err(|_| format!("{} must be a date (YYYY-MM-DD)", self.name)),
+            CustomFieldType::Select => Ok(Value::String(self.find_option(input)?.to_string())),
+            CustomFieldType::MultiSelect => {
+                let mut selected: Vec<Value> = Vec::new();
+                for part in input.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+                    let option = Value::String(self.find_option(part)?.to_string());
+                    if !selected.contains(&option) {
+                        selected.push(option);
+                    }
+                }
+                Ok(Value::Array(selected))
+            }
+            CustomFieldType::User => Uuid::parse_str(input)
+                .map(|id| Value::String(id.to_string()))
+                .map_err(|_| format!("{} must be a user id", self.name)),
+        }
+    }
+
+    /// Check a stored or submitted value matches this field's type and options
+    pub fn validate_value(&self, value: &Value) -> Result<(), String> {
+        let valid = match (self.field_type, value) {
+            (CustomFieldType::Text, Value::String(_)) => true,
+            (CustomFieldType::Number, Value::Number(_)) => true,
+            (CustomFieldType::Date, Value::String(s)) => {
+                NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok()
+            }
+            (CustomFieldType::Select, Value::String(s)) => self.options.contains(s),
+            (CustomFieldType::MultiSelect, Value::Array(items)) => items
+                .iter()
+                .all(|i| i.as_str().is_some_and(|s| self.options.iter().any(|o| o == s))),
+            (CustomFieldType::User, Value::String(s)) => Uuid::parse_str(s).is_ok(),
+            _ => false,
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("Invalid value for {}", self.name))
+        }
+    }
+
+    /// Render a value as plain text (the inverse of `parse_input`)
+    pub fn format_value(&self, value: &Value) -> String {
+        match value {
+            Value::Null => String::new(),
+            Value::String(s) => s.clone(),
+            Value::Array(items) => items
+                .iter()
+                .filter_map(|i| i.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            other => other.to_string(),
+        }
+    }
+}
@@ -16,6 +16,9 @@ pub struct Document {
     pub created_by: Uuid,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Bumped on every edit; also sent as the ETag
+    #[serde(default)]
+    pub version: i32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
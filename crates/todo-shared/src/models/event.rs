@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A change pushed to everyone watching a workspace. Events only name what
+/// changed; clients fetch the new state themselves.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceEvent {
+    pub workspace_id: Uuid,
+    /// Who made the change
+    pub user_id: Uuid,
+    #[serde(flatten)]
+    pub kind: WorkspaceEventKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WorkspaceEventKind {
+    /// Created, edited, moved or restored
+    TaskChanged {
+        task_id: Uuid,
+    },
+    /// Moved to the trash or purged
+    TaskDeleted {
+        task_id: Uuid,
+    },
+    CommentsChanged {
+        task_id: Uuid,
+    },
+    TagsChanged,
+    /// Columns or the workflow between them
+    StatusesChanged,
+    DocumentChanged {
+        document_id: Uuid,
+    },
+    DocumentDeleted {
+        document_id: Uuid,
+    },
+}
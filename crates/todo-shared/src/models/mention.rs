@@ -0,0 +1,62 @@
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A workspace member mentioned as `@username` in a comment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MentionSpan {
+    /// Byte offset of the `@` in the text
+    pub start: usize,
+    /// Byte offset just past the username
+    pub end: usize,
+    pub user_id: Uuid,
+    pub username: String,
+}
+
+/// Every `@name` in `text`, with the byte range it covers (`@` included) and the
+/// name without the `@`. Names start with a letter and hold letters, digits and
+/// underscores; an `@` inside a word, as in an email address, doesn't count.
+pub fn find_mentions(text: &str) -> Vec<(Range<usize>, &str)> {
+    let mut found = Vec::new();
+    let mut prev: Option<char> = None;
+
+    for (idx, c) in text.char_indices() {
+        let starts_mention = c == '@' && !prev.is_some_and(|p| p.is_alphanumeric() || p == '_');
+        prev = Some(c);
+        if !starts_mention {
+            continue;
+        }
+
+        let rest = &text[idx + 1..];
+        let len = rest
+            .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_'))
+            .unwrap_or(rest.len());
+        let name = &rest[..len];
+
+        if name.starts_with(|ch: char| ch.is_ascii_alphabetic()) {
+            found.push((idx..idx + 1 + len, name));
+        }
+    }
+
+    found
+}
+
+/// Spans for the mentions in `text` that name one of `users`, matched without
+/// regard to case
+pub fn mention_spans(text: &str, users: &[(Uuid, String)]) -> Vec<MentionSpan> {
+    find_mentions(text)
+        .into_iter()
+        .filter_map(|(range, name)| {
+            let (user_id, username) = users
+                .iter()
+                .find(|(_, username)| username.eq_ignore_ascii_case(name))?;
+            Some(MentionSpan {
+                start: range.start,
+                end: range.end,
+                user_id: *user_id,
+                username: username.clone(),
+            })
+        })
+        .collect()
+}
@@ -3,9 +3,31 @@ mod user;
 mod workspace;
 mod comment;
 mod document;
+mod recurrence;
+mod custom_field;
+mod work_log;
+mod checklist;
+mod attachment;
+mod trash;
+mod activity;
+mod event;
+mod notification;
+mod mention;
+mod session;
 
 pub use task::*;
 pub use user::*;
 pub use workspace::*;
 pub use comment::*;
 pub use document::*;
+pub use recurrence::*;
+pub use custom_field::*;
+pub use work_log::*;
+pub use checklist::*;
+pub use attachment::*;
+pub use trash::*;
+pub use activity::*;
+pub use event::*;
+pub use notification::*;
+pub use mention::*;
+pub use session::*;
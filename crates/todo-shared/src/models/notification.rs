@@ -0,0 +1,109 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    TaskAssigned,
+    TaskCommented,
+    Mentioned,
+    StatusChanged,
+    DueSoon,
+}
+
+impl NotificationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TaskAssigned => "task_assigned",
+            Self::TaskCommented => "task_commented",
+            Self::Mentioned => "mentioned",
+            Self::StatusChanged => "status_changed",
+            Self::DueSoon => "due_soon",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "task_assigned" => Some(Self::TaskAssigned),
+            "task_commented" => Some(Self::TaskCommented),
+            "mentioned" => Some(Self::Mentioned),
+            "status_changed" => Some(Self::StatusChanged),
+            "due_soon" => Some(Self::DueSoon),
+            _ => None,
+        }
+    }
+}
+
+/// Something that happened to a task the user is involved in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Notification {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub kind: NotificationKind,
+    pub task_id: Uuid,
+    /// Name of the task at the time, e.g. "TODO-12 Fix login"
+    pub task_label: String,
+    /// Who caused it; `None` for reminders
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor_name: Option<String>,
+    /// Comment excerpt or "Todo → Done" for moves
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Notification {
+    pub fn is_read(&self) -> bool {
+        self.read_at.is_some()
+    }
+
+    /// One-line description for lists, e.g. "alice assigned you to TODO-12 Fix login"
+    pub fn summary(&self) -> String {
+        let actor = self.actor_name.as_deref().unwrap_or("Someone");
+        match self.kind {
+            NotificationKind::TaskAssigned => {
+                format!("{} assigned you to {}", actor, self.task_label)
+            }
+            NotificationKind::TaskCommented => format!("{} commented on {}", actor, self.task_label),
+            NotificationKind::Mentioned => format!("{} mentioned you on {}", actor, self.task_label),
+            NotificationKind::StatusChanged => format!("{} moved {}", actor, self.task_label),
+            NotificationKind::DueSoon => format!("{} is due soon", self.task_label),
+        }
+    }
+}
+
+/// Which notifications a member gets in a workspace
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationSettings {
+    pub notify_task_assigned: bool,
+    /// Status changes on watched tasks
+    pub notify_task_updated: bool,
+    pub notify_task_commented: bool,
+    pub notify_due_date_approaching: bool,
+    pub notify_mention: bool,
+    /// Unread notifications from this workspace go out in the email digest
+    #[serde(default = "enabled")]
+    pub email_digest: bool,
+}
+
+fn enabled() -> bool {
+    true
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            notify_task_assigned: true,
+            notify_task_updated: true,
+            notify_task_commented: true,
+            notify_due_date_approaching: true,
+            notify_mention: true,
+            email_digest: true,
+        }
+    }
+}
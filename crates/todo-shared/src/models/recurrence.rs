@@ -0,0 +1,316 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecurrenceFrequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// Schedule for a recurring task. Stored as JSONB on the task.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecurrenceRule {
+    pub frequency: RecurrenceFrequency,
+    /// Repeat every N days/weeks/months
+    #[serde(default = "default_interval")]
+    pub interval: u32,
+    /// Specific weekdays for weekly rules (empty = same weekday as the due date)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub weekdays: Vec<Weekday>,
+    /// Day of the month monthly occurrences fall on, clamped in shorter months.
+    /// Taken from the due date when unset, so a task due on the 31st keeps
+    /// coming back at the end of the month instead of drifting to the 28th.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub day_of_month: Option<u32>,
+    /// Last date an occurrence may fall on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<NaiveDate>,
+}
+
+fn default_interval() -> u32 {
+    1
+}
+
+impl RecurrenceRule {
+    /// Check the rule is well-formed
+    pub fn validate(&self) -> Result<(), String> {
+        if self.interval == 0 {
+            return Err("Recurrence interval must be at least 1".to_string());
+        }
+        if !self.weekdays.is_empty() && self.frequency != RecurrenceFrequency::Weekly {
+            return Err("Weekdays are only supported for weekly recurrence".to_string());
+        }
+        if let Some(day) = self.day_of_month {
+            if self.frequency != RecurrenceFrequency::Monthly {
+                return Err("A day of the month is only supported for monthly recurrence".to_string());
+            }
+            if !(1..=31).contains(&day) {
+                return Err("Day of the month must be between 1 and 31".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// The rule with a monthly day taken from `due_date` if it has none yet
+    pub fn anchored(&self, due_date: Option<NaiveDate>) -> RecurrenceRule {
+        let mut rule = self.clone();
+        if rule.frequency == RecurrenceFrequency::Monthly && rule.day_of_month.is_none() {
+            rule.day_of_month = due_date.map(|d| d.day());
+        }
+        rule
+    }
+
+    /// Due date of the occurrence following one due on `from`, ignoring `until`
+    fn step(&self, from: NaiveDate) -> Option<NaiveDate> {
+        let interval = self.interval.max(1);
+        match self.frequency {
+            RecurrenceFrequency::Daily => from.checked_add_days(Days::new(interval as u64)),
+            RecurrenceFrequency::Monthly => match self.day_of_month {
+                Some(day) => {
+                    let month = from.with_day(1)?.checked_add_months(Months::new(interval))?;
+                    let last_day = month
+                        .checked_add_months(Months::new(1))?
+                        .pred_opt()?
+                        .day();
+                    month.with_day(day.min(last_day))
+                }
+                None => from.checked_add_months(Months::new(interval)),
+            },
+            RecurrenceFrequency::Weekly if self.weekdays.is_empty() => {
+                from.checked_add_days(Days::new(7 * interval as u64))
+            }
+            RecurrenceFrequency::Weekly => {
+                let mut days: Vec<u32> = self
+                    .weekdays
+                    .iter()
+                    .map(|d| d.num_days_from_monday())
+                    .collect();
+                days.sort_unstable();
+
+                // Later weekday in the same week, otherwise first weekday `interval` weeks on
+                let current = from.weekday().num_days_from_monday();
+                if let Some(&day) = days.iter().find(|&&d| d > current) {
+                    return from.checked_add_days(Days::new((day - current) as u64));
+                }
+                let week_start = from.checked_sub_days(Days::new(current as u64))?;
+                week_start.checked_add_days(Days::new(7 * interval as u64 + days[0] as u64))
+            }
+        }
+    }
+
+    /// Due date for the next occurrence after a task due on `due_date` is completed.
+    /// Rolls forward past `today` so overdue tasks don't spawn already-overdue copies.
+    /// Returns `None` once the rule's end date has passed.
+    pub fn next_occurrence(&self, due_date: Option<NaiveDate>, today: NaiveDate) -> Option<NaiveDate> {
+        let mut next = self.step(due_date.unwrap_or(today))?;
+        while next < today {
+            next = self.step(next)?;
+        }
+        match self.until {
+            Some(until) if next > until => None,
+            _ => Some(next),
+        }
+    }
+}
+
+/// Compact text form used by the TUI, e.g. `weekly/2 mon,thu until 2026-12-31`
+/// or `monthly day 31`
+impl fmt::Display for RecurrenceRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frequency = match self.frequency {
+            RecurrenceFrequency::Daily => "daily",
+            RecurrenceFrequency::Weekly => "weekly",
+            RecurrenceFrequency::Monthly => "monthly",
+        };
+        write!(f, "{}", frequency)?;
+        if self.interval > 1 {
+            write!(f, "/{}", self.interval)?;
+        }
+        if !self.weekdays.is_empty() {
+            let days: Vec<String> = self
+                .weekdays
+                .iter()
+                .map(|d| d.to_string().to_lowercase())
+                .collect();
+            write!(f, " {}", days.join(","))?;
+        }
+        if let Some(day) = self.day_of_month {
+            write!(f, " day {}", day)?;
+        }
+        if let Some(until) = self.until {
+            write!(f, " until {}", until)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for RecurrenceRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+
+        let head = parts.next().ok_or("Recurrence rule is empty")?;
+        let (frequency, interval) = match head.split_once('/') {
+            Some((freq, n)) => (
+                freq,
+                n.parse::<u32>()
+                    .map_err(|_| format!("Invalid interval: {}", n))?,
+            ),
+            None => (head, 1),
+        };
+        let frequency = match frequency.to_lowercase().as_str() {
+            "daily" => RecurrenceFrequency::Daily,
+            "weekly" => RecurrenceFrequency::Weekly,
+            "monthly" => RecurrenceFrequency::Monthly,
+            other => return Err(format!("Unknown frequency: {}", other)),
+        };
+
+        let mut weekdays = Vec::new();
+        let mut day_of_month = None;
+        let mut until = None;
+        while let Some(part) = parts.next() {
+            if part == "day" {
+                let day = parts.next().ok_or("Missing number after 'day'")?;
+                day_of_month = Some(
+                    day.parse::<u32>()
+                        .map_err(|_| format!("Invalid day of the month: {}", day))?,
+                );
+            } else if part == "until" {
+                let date = parts.next().ok_or("Missing date after 'until'")?;
+                until = Some(
+                    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                        .map_err(|_| format!("Invalid date: {}", date))?,
+                );
+            } else {
+                for day in part.split(',').filter(|d| !d.is_empty()) {
+                    let weekday = day
+                        .parse::<Weekday>()
+                        .map_err(|_| format!("Invalid weekday: {}", day))?;
+                    if !weekdays.contains(&weekday) {
+                        weekdays.push(weekday);
+                    }
+                }
+            }
+        }
+
+        let rule = RecurrenceRule {
+            frequency,
+            interval,
+            weekdays,
+            day_of_month,
+            until,
+        };
+        rule.validate()?;
+        Ok(rule)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn rule(s: &str) -> RecurrenceRule {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn weekly_weekdays_wrap_to_a_later_week() {
+        let every_other = rule("weekly/2 mon,thu");
+        // Monday 2026-10-12 -> Thursday the same week
+        assert_eq!(every_other.step(date(2026, 10, 12)), Some(date(2026, 10, 15)));
+        // Thursday -> Monday two weeks on
+        assert_eq!(every_other.step(date(2026, 10, 15)), Some(date(2026, 10, 26)));
+    }
+
+    #[test]
+    fn weekly_without_weekdays_keeps_the_weekday() {
+        assert_eq!(rule("weekly/3").step(date(2026, 10, 15)), Some(date(2026, 11, 5)));
+    }
+
+    #[test]
+    fn next_occurrence_stops_after_until() {
+        let daily = rule("daily until 2026-10-20");
+        let today = date(2026, 10, 1);
+        assert_eq!(
+            daily.next_occurrence(Some(date(2026, 10, 19)), today),
+            Some(date(2026, 10, 20))
+        );
+        assert_eq!(daily.next_occurrence(Some(date(2026, 10, 20)), today), None);
+    }
+
+    #[test]
+    fn next_occurrence_rolls_past_today() {
+        let weekly = rule("weekly");
+        assert_eq!(
+            weekly.next_occurrence(Some(date(2026, 9, 1)), date(2026, 10, 17)),
+            Some(date(2026, 10, 20))
+        );
+        assert_eq!(weekly.next_occurrence(None, date(2026, 10, 17)), Some(date(2026, 10, 24)));
+    }
+
+    #[test]
+    fn monthly_keeps_the_month_end() {
+        let monthly = rule("monthly").anchored(Some(date(2026, 1, 31)));
+        assert_eq!(monthly.day_of_month, Some(31));
+
+        let today = date(2026, 1, 1);
+        let feb = monthly.next_occurrence(Some(date(2026, 1, 31)), today).unwrap();
+        assert_eq!(feb, date(2026, 2, 28));
+        let mar = monthly.next_occurrence(Some(feb), today).unwrap();
+        assert_eq!(mar, date(2026, 3, 31));
+        let apr = monthly.next_occurrence(Some(mar), today).unwrap();
+        assert_eq!(apr, date(2026, 4, 30));
+    }
+
+    #[test]
+    fn monthly_interval_and_leap_year() {
+        let quarterly = rule("monthly/3 day 29");
+        assert_eq!(quarterly.step(date(2027, 11, 29)), Some(date(2028, 2, 29)));
+        assert_eq!(quarterly.step(date(2028, 2, 29)), Some(date(2028, 5, 29)));
+    }
+
+    #[test]
+    fn anchored_keeps_an_explicit_day() {
+        let monthly = rule("monthly day 15").anchored(Some(date(2026, 1, 31)));
+        assert_eq!(monthly.day_of_month, Some(15));
+        assert_eq!(rule("weekly").anchored(Some(date(2026, 1, 31))).day_of_month, None);
+    }
+
+    #[test]
+    fn display_round_trips_through_from_str() {
+        for text in [
+            "daily",
+            "weekly/2 mon,thu until 2026-12-31",
+            "monthly/3 day 31",
+            "monthly until 2027-01-01",
+        ] {
+            assert_eq!(rule(text).to_string(), text);
+            assert_eq!(rule(&rule(text).to_string()), rule(text));
+        }
+    }
+
+    #[test]
+    fn from_str_rejects_bad_rules() {
+        for text in [
+            "",
+            "hourly",
+            "daily/0",
+            "daily mon",
+            "weekly day 3",
+            "monthly day 32",
+            "monthly until soon",
+        ] {
+            assert!(text.parse::<RecurrenceRule>().is_err(), "{:?} should not parse", text);
+        }
+    }
+}
@@ -2,6 +2,8 @@ use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::{ChecklistProgress, CustomFieldValue, RecurrenceRule, WorkspaceRole};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
 #[cfg_attr(feature = "sqlx", sqlx(type_name = "task_priority", rename_all = "lowercase"))]
@@ -18,6 +20,12 @@ pub enum Priority {
 pub struct Task {
     pub id: Uuid,
     pub workspace_id: Uuid,
+    /// Sequence number within the workspace
+    #[serde(default)]
+    pub number: i32,
+    /// Human-readable key, e.g. "TODO-123"
+    #[serde(default)]
+    pub key: String,
     pub status_id: Uuid,
     pub title: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -28,16 +36,55 @@ pub struct Task {
     pub due_date: Option<NaiveDate>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub time_estimate_minutes: Option<i32>,
+    /// Total minutes recorded in work logs
+    #[serde(default)]
+    pub logged_minutes: i64,
     pub position: i32,
     pub created_by: Uuid,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub assigned_to: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assignees: Vec<Uuid>,
+    /// Users following the task without being responsible for it
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub watchers: Vec<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Bumped on every edit; also sent as the ETag
+    #[serde(default)]
+    pub version: i32,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub completed_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<RecurrenceRule>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<Tag>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_fields: Vec<CustomFieldValue>,
+    /// Unfinished tasks blocking this one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocked_by: Vec<Uuid>,
+    /// Completion rollup of direct subtasks (absent when the task has none)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtask_progress: Option<SubtaskProgress>,
+    /// Checked vs total checklist items (absent when the task has no checklist)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checklist_progress: Option<ChecklistProgress>,
+    /// Direct subtasks, only populated when fetching a single task
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subtasks: Vec<Task>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubtaskProgress {
+    pub done: i64,
+    pub total: i64,
+}
+
+impl SubtaskProgress {
+    pub fn is_complete(&self) -> bool {
+        self.total > 0 && self.done >= self.total
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,6 +97,28 @@ pub struct TaskStatus {
     pub color: Option<String>,
     pub position: i32,
     pub is_done: bool,
+    /// Most live tasks the column should hold at once
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wip_limit: Option<i32>,
+}
+
+/// An allowed move between two board columns, and what it takes to make it.
+/// Columns without outgoing transitions let tasks move anywhere.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusTransition {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub from_status_id: Uuid,
+    pub to_status_id: Uuid,
+    /// The task needs at least one assignee
+    #[serde(default)]
+    pub require_assignee: bool,
+    /// The move has to come with a comment
+    #[serde(default)]
+    pub require_comment: bool,
+    /// Lowest workspace role allowed to make the move
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_role: Option<WorkspaceRole>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
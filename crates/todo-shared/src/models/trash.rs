@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrashItemKind {
+    Task,
+    Document,
+    Comment,
+}
+
+impl TrashItemKind {
+    /// Path segment used by the trash endpoints
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Task => "task",
+            Self::Document => "document",
+            Self::Comment => "comment",
+        }
+    }
+}
+
+/// Something deleted from a workspace, waiting to be restored or purged.
+/// Subtasks and child documents trashed along with their parent are not
+/// listed separately; they are counted in `nested_count` and come back with it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashItem {
+    pub kind: TrashItemKind,
+    pub id: Uuid,
+    /// Task key and title, document title or the start of a comment
+    pub title: String,
+    /// Parent task, document path or the commented task
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+    pub nested_count: i64,
+    pub deleted_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_by: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_by_username: Option<String>,
+    /// When the retention job will delete it for good
+    pub purge_at: DateTime<Utc>,
+}
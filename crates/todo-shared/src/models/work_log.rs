@@ -0,0 +1,18 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Time spent on a task by one user on one day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkLog {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub user_id: Uuid,
+    pub author_username: String,
+    pub minutes: i32,
+    pub work_date: NaiveDate,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -6,6 +6,9 @@ use uuid::Uuid;
 pub struct WorkspaceSettings {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default_assignee: Option<Uuid>,
+    /// Refuse moves that would take a column over its WIP limit instead of only warning
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub strict_wip_limits: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -28,6 +31,17 @@ impl WorkspaceRole {
         matches!(self, Self::Owner | Self::Admin)
     }
 
+    /// Whether this role has at least the rights of `role`
+    pub fn at_least(&self, role: WorkspaceRole) -> bool {
+        let rank = |r: &WorkspaceRole| match r {
+            Self::Owner => 3,
+            Self::Admin => 2,
+            Self::Editor => 1,
+            Self::Reader => 0,
+        };
+        rank(self) >= rank(&role)
+    }
+
     pub fn is_owner(&self) -> bool {
         matches!(self, Self::Owner)
     }
@@ -41,6 +55,9 @@ pub struct Workspace {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     pub owner_id: Uuid,
+    /// Prefix for task keys, e.g. "TODO" in "TODO-123"
+    #[serde(default)]
+    pub task_key_prefix: String,
     #[serde(default)]
     pub settings: WorkspaceSettings,
     pub created_at: DateTime<Utc>,
@@ -3,21 +3,31 @@ use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use reqwest::{Client, StatusCode};
 use todo_shared::{
     api::{
-        AuthResponse, CreateCommentRequest, CreateDocumentRequest, CreateStatusRequest,
-        CreateTagRequest, CreateTaskRequest, CreateWorkspaceRequest, InviteDetails, LinkTaskRequest,
-        LinkedDocument, LinkedTask, LoginRequest, MoveTaskRequest, RefreshRequest, RegisterRequest,
-        RegisterResponse, ResendVerificationRequest, SearchResponse, SetTaskTagsRequest,
-        TaskListParams, UpdateCommentRequest, UpdateDocumentRequest, UpdateStatusRequest,
-        UpdateTagRequest, UpdateTaskRequest, UpdateWorkspaceRequest, VerifyEmailRequest,
+        ActivityListResponse, AddDependencyRequest, AuthResponse, BulkTaskOperation, BulkTaskRequest, BulkTaskResponse, ChangePasswordRequest, CreateChecklistItemRequest, CreateCommentRequest, CreateWorkLogRequest, CreateDocumentRequest, CreateStatusRequest,
+        CreateTagRequest, CreateTaskRequest, CreateTransitionRequest, CreateWorkspaceRequest, ForgotPasswordRequest, InviteDetails, LinkTaskRequest,
+        LinkedDocument, LinkedTask, LoginRequest, MoveTaskRequest, NotificationListResponse, Patch, RefreshRequest, RegisterRequest,
+        RegisterResponse, ReorderChecklistRequest, ResendVerificationRequest, ResetPasswordRequest, SearchResponse, SetCustomFieldValuesRequest, SetTaskTagsRequest, ToggleReactionRequest,
+        TaskDependencies, TaskListParams, UpdateChecklistItemRequest, UpdateCommentRequest, UpdateDocumentRequest, UpdateStatusRequest,
+        UpdateTagRequest, UpdateTaskRequest, UpdateTransitionRequest, UpdateWorkspaceRequest, VerifyEmailRequest,
         WorkspaceInvite, WorkspaceMemberWithUser, WorkspaceStats,
     },
-    CommentWithAuthor, Document, Tag, Task, TaskStatus, User, Workspace, WorkspaceRole,
-    WorkspaceSettings, WorkspaceWithRole,
+    ActivityEntityType, Attachment, ChecklistItem, CommentReaction, CommentRevision, CommentWithAuthor, CustomField, CustomFieldValue, Document, NotificationSettings, Session, StatusTransition, Tag, Task, TaskStatus, TrashItem, TrashItemKind, User, Workspace, WorkspaceRole,
+    WorkLog, WorkspaceSettings, WorkspaceWithRole,
 };
 use uuid::Uuid;
 
 use super::auth::AuthTokens;
 
+/// Sent as User-Agent so the server's session list can tell devices apart,
+/// e.g. "todo-tui/0.1.0 (laptop; linux)"
+fn user_agent() -> String {
+    let host = hostname::get()
+        .ok()
+        .and_then(|h| h.into_string().ok())
+        .unwrap_or_else(|| "unknown host".to_string());
+    format!("todo-tui/{} ({}; {})", env!("CARGO_PKG_VERSION"), host, std::env::consts::OS)
+}
+
 /// JWT payload claims we need for expiry checking
 #[derive(serde::Deserialize)]
 struct JwtClaims {
@@ -45,6 +55,8 @@ pub enum ApiError {
     NotFound,
     #[error("Validation error: {0}")]
     Validation(String),
+    #[error("Conflict: {0}")]
+    Conflict(String),
     #[error("Server error: {0}")]
     Server(String),
     #[error("Network error: {0}")]
@@ -63,7 +75,10 @@ pub struct ApiClient {
 impl ApiClient {
     pub fn new(base_url: &str) -> Self {
         Self {
-            client: Client::new(),
+            client: Client::builder()
+                .user_agent(user_agent())
+                .build()
+                .unwrap_or_default(),
             base_url: base_url.trim_end_matches('/').to_string(),
             tokens: None,
         }
@@ -276,6 +291,10 @@ impl ApiClient {
                 let text = response.text().await.unwrap_or_default();
                 Err(ApiError::Validation(text))
             }
+            StatusCode::CONFLICT => {
+                let text = response.text().await.unwrap_or_default();
+                Err(ApiError::Conflict(text))
+            }
             _ => {
                 let text = response.text().await.unwrap_or_default();
                 Err(ApiError::Server(format!("{}: {}", status, text)))
@@ -303,6 +322,10 @@ impl ApiClient {
                 let text = response.text().await.unwrap_or_default();
                 Err(ApiError::Validation(text))
             }
+            StatusCode::CONFLICT => {
+                let text = response.text().await.unwrap_or_default();
+                Err(ApiError::Conflict(text))
+            }
             _ => {
                 let text = response.text().await.unwrap_or_default();
                 Err(ApiError::Server(format!("{}: {}", status, text)))
@@ -394,6 +417,72 @@ impl ApiClient {
         }
     }
 
+    pub async fn forgot_password(&mut self, email: &str) -> Result<(), ApiError> {
+        let req = ForgotPasswordRequest {
+            email: email.to_string(),
+        };
+
+        let response = self
+            .client
+            .post(format!("{}/api/v1/auth/forgot-password", self.base_url))
+            .json(&req)
+            .send()
+            .await?;
+
+        let _: serde_json::Value = self.handle_response(response).await?;
+        Ok(())
+    }
+
+    pub async fn reset_password(
+        &mut self,
+        email: &str,
+        code: &str,
+        new_password: &str,
+    ) -> Result<(), ApiError> {
+        let req = ResetPasswordRequest {
+            email: email.to_string(),
+            code: code.to_string(),
+            new_password: new_password.to_string(),
+        };
+
+        let response = self
+            .client
+            .post(format!("{}/api/v1/auth/reset-password", self.base_url))
+            .json(&req)
+            .send()
+            .await?;
+
+        let _: serde_json::Value = self.handle_response(response).await?;
+        Ok(())
+    }
+
+    /// Change the password; other sessions are signed out and this one gets new tokens
+    pub async fn change_password(
+        &mut self,
+        current_password: &str,
+        new_password: &str,
+    ) -> Result<(), ApiError> {
+        let req = ChangePasswordRequest {
+            current_password: current_password.to_string(),
+            new_password: new_password.to_string(),
+        };
+
+        let response = self.authed_post("/auth/change-password", &req).await?;
+        let auth: AuthResponse = self.handle_response(response).await?;
+
+        self.tokens = Some(AuthTokens {
+            access_token: auth.access_token,
+            refresh_token: auth.refresh_token,
+            user_id: auth.user_id,
+        });
+
+        if let Some(ref tokens) = self.tokens {
+            tokens.save().map_err(ApiError::Other)?;
+        }
+
+        Ok(())
+    }
+
     pub async fn login(&mut self, email: &str, password: &str) -> Result<User, ApiError> {
         let req = LoginRequest {
             email: email.to_string(),
@@ -476,6 +565,26 @@ impl ApiClient {
         self.handle_response(response).await
     }
 
+    pub async fn list_sessions(&mut self) -> Result<Vec<Session>, ApiError> {
+        let response = self.authed_get("/auth/sessions").await?;
+        self.handle_response(response).await
+    }
+
+    pub async fn revoke_session(&mut self, session_id: Uuid) -> Result<(), ApiError> {
+        let response = self.authed_delete(&format!("/auth/sessions/{}", session_id)).await?;
+        self.handle_empty_response(response).await
+    }
+
+    /// Sign out every session, this one included, and forget the local tokens
+    pub async fn revoke_all_sessions(&mut self) -> Result<(), ApiError> {
+        let response = self.authed_delete("/auth/sessions").await?;
+        self.handle_empty_response(response).await?;
+
+        self.tokens = None;
+        AuthTokens::delete().map_err(ApiError::Other)?;
+        Ok(())
+    }
+
     // ============ Workspaces ============
 
     pub async fn list_workspaces(&mut self) -> Result<Vec<WorkspaceWithRole>, ApiError> {
@@ -491,6 +600,7 @@ impl ApiClient {
         let req = CreateWorkspaceRequest {
             name: name.to_string(),
             description: description.map(|s| s.to_string()),
+            task_key_prefix: None,
         };
         let response = self.authed_post("/workspaces", &req).await?;
         self.handle_response(response).await
@@ -501,17 +611,19 @@ impl ApiClient {
         self.handle_response(response).await
     }
 
+    /// `None` keeps a field; the description can also be cleared with `Patch::Null`
     pub async fn update_workspace(
         &mut self,
         id: Uuid,
         name: Option<&str>,
-        description: Option<&str>,
+        description: Patch<&str>,
         settings: Option<WorkspaceSettings>,
     ) -> Result<Workspace, ApiError> {
         let req = UpdateWorkspaceRequest {
-            name: name.map(|s| s.to_string()),
+            name: name.map_or(Patch::Absent, |s| Patch::Value(s.to_string())),
             description: description.map(|s| s.to_string()),
-            settings,
+            task_key_prefix: Patch::Absent,
+            settings: settings.map_or(Patch::Absent, Patch::Value),
         };
         let response = self.authed_patch(&format!("/workspaces/{}", id), &req).await?;
         self.handle_response(response).await
@@ -600,28 +712,33 @@ impl ApiClient {
         name: &str,
         color: Option<&str>,
         is_done: bool,
+        wip_limit: Option<i32>,
     ) -> Result<TaskStatus, ApiError> {
         let req = CreateStatusRequest {
             name: name.to_string(),
             color: color.map(|s| s.to_string()),
             is_done,
+            wip_limit,
         };
         let response = self.authed_post(&format!("/workspaces/{}/statuses", workspace_id), &req).await?;
         self.handle_response(response).await
     }
 
+    /// `None` keeps a field; the color and WIP limit can also be cleared with `Patch::Null`
     pub async fn update_status(
         &mut self,
         workspace_id: Uuid,
         status_id: Uuid,
         name: Option<&str>,
-        color: Option<&str>,
+        color: Patch<&str>,
         is_done: Option<bool>,
+        wip_limit: Patch<i32>,
     ) -> Result<TaskStatus, ApiError> {
         let req = UpdateStatusRequest {
-            name: name.map(|s| s.to_string()),
+            name: name.map_or(Patch::Absent, |s| Patch::Value(s.to_string())),
             color: color.map(|s| s.to_string()),
-            is_done,
+            is_done: is_done.map_or(Patch::Absent, Patch::Value),
+            wip_limit,
         };
         let response = self.authed_patch(
             &format!("/workspaces/{}/statuses/{}", workspace_id, status_id),
@@ -630,14 +747,18 @@ impl ApiClient {
         self.handle_response(response).await
     }
 
+    /// `move_to` takes over the column's tasks; without it only an empty column can go
     pub async fn delete_status(
         &mut self,
         workspace_id: Uuid,
         status_id: Uuid,
+        move_to: Option<Uuid>,
     ) -> Result<(), ApiError> {
-        let response = self.authed_delete(
-            &format!("/workspaces/{}/statuses/{}", workspace_id, status_id),
-        ).await?;
+        let mut url = format!("/workspaces/{}/statuses/{}", workspace_id, status_id);
+        if let Some(target) = move_to {
+            url.push_str(&format!("?move_to={}", target));
+        }
+        let response = self.authed_delete(&url).await?;
         self.handle_empty_response(response).await
     }
 
@@ -658,6 +779,57 @@ impl ApiClient {
         self.handle_response(response).await
     }
 
+    // ============ Live Updates ============
+
+    /// Request for the workspace's change events. It's sent by whoever reads
+    /// the stream, so a slow connect doesn't hold up the client.
+    pub async fn event_stream_request(
+        &mut self,
+        workspace_id: Uuid,
+    ) -> Result<reqwest::RequestBuilder, ApiError> {
+        if !self.ensure_valid_token().await {
+            return Err(ApiError::Unauthorized);
+        }
+        Ok(self
+            .client
+            .get(self.url(&format!("/workspaces/{}/events", workspace_id)))
+            .header("Authorization", self.auth_header().unwrap()))
+    }
+
+    // ============ Workflow ============
+
+    pub async fn list_transitions(&mut self, workspace_id: Uuid) -> Result<Vec<StatusTransition>, ApiError> {
+        let response = self.authed_get(&format!("/workspaces/{}/transitions", workspace_id)).await?;
+        self.handle_response(response).await
+    }
+
+    pub async fn create_transition(
+        &mut self,
+        workspace_id: Uuid,
+        req: &CreateTransitionRequest,
+    ) -> Result<StatusTransition, ApiError> {
+        let response = self.authed_post(&format!("/workspaces/{}/transitions", workspace_id), req).await?;
+        self.handle_response(response).await
+    }
+
+    pub async fn update_transition(
+        &mut self,
+        workspace_id: Uuid,
+        transition_id: Uuid,
+        req: &UpdateTransitionRequest,
+    ) -> Result<StatusTransition, ApiError> {
+        let response = self.authed_patch(
+            &format!("/workspaces/{}/transitions/{}", workspace_id, transition_id),
+            req,
+        ).await?;
+        self.handle_response(response).await
+    }
+
+    pub async fn delete_transition(&mut self, workspace_id: Uuid, transition_id: Uuid) -> Result<(), ApiError> {
+        let response = self.authed_delete(&format!("/workspaces/{}/transitions/{}", workspace_id, transition_id)).await?;
+        self.handle_empty_response(response).await
+    }
+
     // ============ Tasks ============
 
     pub async fn list_tasks(
@@ -693,6 +865,10 @@ impl ApiClient {
                 let ids: Vec<String> = tag_ids.iter().map(|id| id.to_string()).collect();
                 query_parts.push(format!("tag_ids={}", ids.join(",")));
             }
+            if let Some(custom_fields) = &params.custom_fields {
+                let json = serde_json::to_string(custom_fields).unwrap_or_default();
+                query_parts.push(format!("custom_fields={}", urlencoding::encode(&json)));
+            }
             if let Some(order_by) = &params.order_by {
                 query_parts.push(format!("order_by={}", order_by));
             }
@@ -705,6 +881,12 @@ impl ApiClient {
             if let Some(limit) = &params.limit {
                 query_parts.push(format!("limit={}", limit));
             }
+            if let Some(parent_id) = &params.parent_id {
+                query_parts.push(format!("parent_id={}", parent_id));
+            }
+            if let Some(top_level) = &params.top_level {
+                query_parts.push(format!("top_level={}", top_level));
+            }
 
             if !query_parts.is_empty() {
                 url.push_str("?");
@@ -730,6 +912,12 @@ impl ApiClient {
         self.handle_response(response).await
     }
 
+    /// Resolve a human-readable key like "TODO-123"
+    pub async fn get_task_by_key(&mut self, workspace_id: Uuid, key: &str) -> Result<Task, ApiError> {
+        let response = self.authed_get(&format!("/workspaces/{}/tasks/by-key/{}", workspace_id, key)).await?;
+        self.handle_response(response).await
+    }
+
     pub async fn update_task(
         &mut self,
         workspace_id: Uuid,
@@ -756,10 +944,12 @@ impl ApiClient {
         task_id: Uuid,
         status_id: Uuid,
         position: Option<i32>,
+        comment: Option<&str>,
     ) -> Result<Task, ApiError> {
         let req = MoveTaskRequest {
             status_id,
             position,
+            comment: comment.map(|c| c.to_string()),
         };
         let response = self.authed_post(
             &format!("/workspaces/{}/tasks/{}/move", workspace_id, task_id),
@@ -768,6 +958,21 @@ impl ApiClient {
         self.handle_response(response).await
     }
 
+    /// Apply one operation to several tasks at once; all of them change or none do
+    pub async fn bulk_tasks(
+        &mut self,
+        workspace_id: Uuid,
+        task_ids: Vec<Uuid>,
+        operation: BulkTaskOperation,
+    ) -> Result<BulkTaskResponse, ApiError> {
+        let req = BulkTaskRequest { task_ids, operation };
+        let response = self.authed_post(
+            &format!("/workspaces/{}/tasks/bulk", workspace_id),
+            &req,
+        ).await?;
+        self.handle_response(response).await
+    }
+
     // ============ Search ============
 
     pub async fn search(
@@ -813,9 +1018,11 @@ impl ApiClient {
         workspace_id: Uuid,
         task_id: Uuid,
         content: &str,
+        parent_comment_id: Option<Uuid>,
     ) -> Result<CommentWithAuthor, ApiError> {
         let req = CreateCommentRequest {
             content: content.to_string(),
+            parent_comment_id,
         };
         let response = self.authed_post(
             &format!("/workspaces/{}/tasks/{}/comments", workspace_id, task_id),
@@ -824,6 +1031,35 @@ impl ApiClient {
         self.handle_response(response).await
     }
 
+    pub async fn list_comment_revisions(
+        &mut self,
+        workspace_id: Uuid,
+        task_id: Uuid,
+        comment_id: Uuid,
+    ) -> Result<Vec<CommentRevision>, ApiError> {
+        let response = self.authed_get(
+            &format!("/workspaces/{}/tasks/{}/comments/{}/revisions", workspace_id, task_id, comment_id),
+        ).await?;
+        self.handle_response(response).await
+    }
+
+    pub async fn toggle_comment_reaction(
+        &mut self,
+        workspace_id: Uuid,
+        task_id: Uuid,
+        comment_id: Uuid,
+        emoji: &str,
+    ) -> Result<Vec<CommentReaction>, ApiError> {
+        let req = ToggleReactionRequest {
+            emoji: emoji.to_string(),
+        };
+        let response = self.authed_post(
+            &format!("/workspaces/{}/tasks/{}/comments/{}/reactions", workspace_id, task_id, comment_id),
+            &req,
+        ).await?;
+        self.handle_response(response).await
+    }
+
     pub async fn update_comment(
         &mut self,
         workspace_id: Uuid,
@@ -853,6 +1089,245 @@ impl ApiClient {
         self.handle_empty_response(response).await
     }
 
+    // ============ Work Logs ============
+
+    pub async fn list_work_logs(
+        &mut self,
+        workspace_id: Uuid,
+        task_id: Uuid,
+    ) -> Result<Vec<WorkLog>, ApiError> {
+        let response = self.authed_get(
+            &format!("/workspaces/{}/tasks/{}/work-logs", workspace_id, task_id),
+        ).await?;
+        self.handle_response(response).await
+    }
+
+    pub async fn create_work_log(
+        &mut self,
+        workspace_id: Uuid,
+        task_id: Uuid,
+        req: CreateWorkLogRequest,
+    ) -> Result<WorkLog, ApiError> {
+        let response = self.authed_post(
+            &format!("/workspaces/{}/tasks/{}/work-logs", workspace_id, task_id),
+            &req,
+        ).await?;
+        self.handle_response(response).await
+    }
+
+    // ============ Checklists ============
+
+    pub async fn list_checklist(
+        &mut self,
+        workspace_id: Uuid,
+        task_id: Uuid,
+    ) -> Result<Vec<ChecklistItem>, ApiError> {
+        let response = self.authed_get(
+            &format!("/workspaces/{}/tasks/{}/checklist", workspace_id, task_id),
+        ).await?;
+        self.handle_response(response).await
+    }
+
+    pub async fn create_checklist_item(
+        &mut self,
+        workspace_id: Uuid,
+        task_id: Uuid,
+        content: &str,
+    ) -> Result<ChecklistItem, ApiError> {
+        let req = CreateChecklistItemRequest {
+            content: content.to_string(),
+        };
+        let response = self.authed_post(
+            &format!("/workspaces/{}/tasks/{}/checklist", workspace_id, task_id),
+            &req,
+        ).await?;
+        self.handle_response(response).await
+    }
+
+    pub async fn update_checklist_item(
+        &mut self,
+        workspace_id: Uuid,
+        task_id: Uuid,
+        item_id: Uuid,
+        req: UpdateChecklistItemRequest,
+    ) -> Result<ChecklistItem, ApiError> {
+        let response = self.authed_patch(
+            &format!("/workspaces/{}/tasks/{}/checklist/{}", workspace_id, task_id, item_id),
+            &req,
+        ).await?;
+        self.handle_response(response).await
+    }
+
+    pub async fn delete_checklist_item(
+        &mut self,
+        workspace_id: Uuid,
+        task_id: Uuid,
+        item_id: Uuid,
+    ) -> Result<(), ApiError> {
+        let response = self.authed_delete(
+            &format!("/workspaces/{}/tasks/{}/checklist/{}", workspace_id, task_id, item_id),
+        ).await?;
+        self.handle_empty_response(response).await
+    }
+
+    pub async fn reorder_checklist(
+        &mut self,
+        workspace_id: Uuid,
+        task_id: Uuid,
+        item_ids: Vec<Uuid>,
+    ) -> Result<Vec<ChecklistItem>, ApiError> {
+        let req = ReorderChecklistRequest { item_ids };
+        let response = self.authed_post(
+            &format!("/workspaces/{}/tasks/{}/checklist/reorder", workspace_id, task_id),
+            &req,
+        ).await?;
+        self.handle_response(response).await
+    }
+
+    // ============ Attachments ============
+
+    pub async fn list_task_attachments(
+        &mut self,
+        workspace_id: Uuid,
+        task_id: Uuid,
+    ) -> Result<Vec<Attachment>, ApiError> {
+        let response = self.authed_get(
+            &format!("/workspaces/{}/tasks/{}/attachments", workspace_id, task_id),
+        ).await?;
+        self.handle_response(response).await
+    }
+
+    /// Fetch the raw contents of an attachment
+    pub async fn download_attachment(
+        &mut self,
+        workspace_id: Uuid,
+        attachment_id: Uuid,
+    ) -> Result<Vec<u8>, ApiError> {
+        let response = self.authed_get(
+            &format!("/workspaces/{}/attachments/{}/download", workspace_id, attachment_id),
+        ).await?;
+        if response.status() == StatusCode::OK {
+            let bytes = response.bytes().await.map_err(ApiError::Network)?;
+            return Ok(bytes.to_vec());
+        }
+        // Not a file, so map the error body as usual
+        self.handle_response(response).await
+    }
+
+    // ============ Trash ============
+
+    pub async fn list_trash(&mut self, workspace_id: Uuid) -> Result<Vec<TrashItem>, ApiError> {
+        let response = self.authed_get(&format!("/workspaces/{}/trash", workspace_id)).await?;
+        self.handle_response(response).await
+    }
+
+    pub async fn restore_trash_item(
+        &mut self,
+        workspace_id: Uuid,
+        kind: TrashItemKind,
+        item_id: Uuid,
+    ) -> Result<(), ApiError> {
+        let response = self.authed_post_empty(
+            &format!("/workspaces/{}/trash/{}/{}/restore", workspace_id, kind.as_str(), item_id),
+        ).await?;
+        self.handle_empty_response(response).await
+    }
+
+    pub async fn purge_trash_item(
+        &mut self,
+        workspace_id: Uuid,
+        kind: TrashItemKind,
+        item_id: Uuid,
+    ) -> Result<(), ApiError> {
+        let response = self.authed_delete(
+            &format!("/workspaces/{}/trash/{}/{}", workspace_id, kind.as_str(), item_id),
+        ).await?;
+        self.handle_empty_response(response).await
+    }
+
+    // ============ Activity ============
+
+    pub async fn list_activity(
+        &mut self,
+        workspace_id: Uuid,
+        page: u32,
+        limit: u32,
+    ) -> Result<ActivityListResponse, ApiError> {
+        let response = self
+            .authed_get(&format!("/workspaces/{}/activity?page={}&limit={}", workspace_id, page, limit))
+            .await?;
+        self.handle_response(response).await
+    }
+
+    pub async fn entity_history(
+        &mut self,
+        workspace_id: Uuid,
+        entity_type: ActivityEntityType,
+        entity_id: Uuid,
+    ) -> Result<ActivityListResponse, ApiError> {
+        let response = self
+            .authed_get(&format!(
+                "/workspaces/{}/activity/{}/{}",
+                workspace_id,
+                entity_type.as_str(),
+                entity_id
+            ))
+            .await?;
+        self.handle_response(response).await
+    }
+
+    // ============ Notifications ============
+
+    pub async fn list_notifications(
+        &mut self,
+        workspace_id: Uuid,
+        unread_only: bool,
+    ) -> Result<NotificationListResponse, ApiError> {
+        let response = self
+            .authed_get(&format!("/workspaces/{}/notifications?unread_only={}", workspace_id, unread_only))
+            .await?;
+        self.handle_response(response).await
+    }
+
+    pub async fn mark_notification_read(
+        &mut self,
+        workspace_id: Uuid,
+        notification_id: Uuid,
+    ) -> Result<(), ApiError> {
+        let response = self.authed_post_empty(
+            &format!("/workspaces/{}/notifications/{}/read", workspace_id, notification_id),
+        ).await?;
+        self.handle_empty_response(response).await
+    }
+
+    pub async fn mark_all_notifications_read(&mut self, workspace_id: Uuid) -> Result<(), ApiError> {
+        let response = self
+            .authed_post_empty(&format!("/workspaces/{}/notifications/read-all", workspace_id))
+            .await?;
+        self.handle_empty_response(response).await
+    }
+
+    pub async fn get_notification_settings(
+        &mut self,
+        workspace_id: Uuid,
+    ) -> Result<NotificationSettings, ApiError> {
+        let response = self
+            .authed_get(&format!("/workspaces/{}/notifications/settings", workspace_id))
+            .await?;
+        self.handle_response(response).await
+    }
+
+    pub async fn update_notification_settings(
+        &mut self,
+        workspace_id: Uuid,
+        settings: &NotificationSettings,
+    ) -> Result<NotificationSettings, ApiError> {
+        let response = self
+            .authed_put(&format!("/workspaces/{}/notifications/settings", workspace_id), settings)
+            .await?;
+        self.handle_response(response).await
+    }
+
     // ============ Tags ============
 
     pub async fn list_tags(&mut self, workspace_id: Uuid) -> Result<Vec<Tag>, ApiError> {
@@ -879,10 +1354,10 @@ impl ApiClient {
         workspace_id: Uuid,
         tag_id: Uuid,
         name: Option<&str>,
-        color: Option<&str>,
+        color: Patch<&str>,
     ) -> Result<Tag, ApiError> {
         let req = UpdateTagRequest {
-            name: name.map(|n| n.to_string()),
+            name: name.map_or(Patch::Absent, |n| Patch::Value(n.to_string())),
             color: color.map(|c| c.to_string()),
         };
         let response = self.authed_patch(
@@ -924,6 +1399,27 @@ impl ApiClient {
         self.handle_response(response).await
     }
 
+    // ============ Custom Fields ============
+
+    pub async fn list_custom_fields(&mut self, workspace_id: Uuid) -> Result<Vec<CustomField>, ApiError> {
+        let response = self.authed_get(&format!("/workspaces/{}/custom-fields", workspace_id)).await?;
+        self.handle_response(response).await
+    }
+
+    pub async fn set_task_custom_fields(
+        &mut self,
+        workspace_id: Uuid,
+        task_id: Uuid,
+        values: Vec<CustomFieldValue>,
+    ) -> Result<Vec<CustomFieldValue>, ApiError> {
+        let req = SetCustomFieldValuesRequest { values };
+        let response = self.authed_put(
+            &format!("/workspaces/{}/tasks/{}/custom-fields", workspace_id, task_id),
+            &req,
+        ).await?;
+        self.handle_response(response).await
+    }
+
     // ============ Documents ============
 
     pub async fn list_documents(&mut self, workspace_id: Uuid) -> Result<Vec<Document>, ApiError> {
@@ -1023,4 +1519,43 @@ impl ApiClient {
         ).await?;
         self.handle_empty_response(response).await
     }
+
+    // ============ Task Dependencies ============
+
+    pub async fn list_dependencies(
+        &mut self,
+        workspace_id: Uuid,
+        task_id: Uuid,
+    ) -> Result<TaskDependencies, ApiError> {
+        let response = self.authed_get(
+            &format!("/workspaces/{}/tasks/{}/dependencies", workspace_id, task_id),
+        ).await?;
+        self.handle_response(response).await
+    }
+
+    pub async fn add_dependency(
+        &mut self,
+        workspace_id: Uuid,
+        task_id: Uuid,
+        blocking_task_id: Uuid,
+    ) -> Result<TaskDependencies, ApiError> {
+        let req = AddDependencyRequest { blocking_task_id };
+        let response = self.authed_post(
+            &format!("/workspaces/{}/tasks/{}/dependencies", workspace_id, task_id),
+            &req,
+        ).await?;
+        self.handle_response(response).await
+    }
+
+    pub async fn remove_dependency(
+        &mut self,
+        workspace_id: Uuid,
+        task_id: Uuid,
+        blocking_task_id: Uuid,
+    ) -> Result<(), ApiError> {
+        let response = self.authed_delete(
+            &format!("/workspaces/{}/tasks/{}/dependencies/{}", workspace_id, task_id, blocking_task_id),
+        ).await?;
+        self.handle_empty_response(response).await
+    }
 }
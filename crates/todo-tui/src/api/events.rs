@@ -0,0 +1,84 @@
+use reqwest::StatusCode;
+use todo_shared::WorkspaceEvent;
+
+use super::ApiError;
+
+/// Something the workspace's event stream told us
+#[derive(Debug)]
+pub enum StreamEvent {
+    Change(WorkspaceEvent),
+    /// Events were dropped on the way; reload everything
+    Resync,
+}
+
+/// Reader for the server-sent events of one workspace
+pub struct EventStream {
+    response: reqwest::Response,
+    buffer: Vec<u8>,
+    event_name: String,
+    data: String,
+}
+
+impl EventStream {
+    /// Send the subscription request and start reading its response
+    pub async fn connect(request: reqwest::RequestBuilder) -> Result<Self, ApiError> {
+        let response = request.send().await?;
+        match response.status() {
+            StatusCode::OK => Ok(Self {
+                response,
+                buffer: Vec::new(),
+                event_name: String::new(),
+                data: String::new(),
+            }),
+            StatusCode::UNAUTHORIZED => Err(ApiError::Unauthorized),
+            StatusCode::FORBIDDEN => Err(ApiError::Forbidden),
+            StatusCode::NOT_FOUND => Err(ApiError::NotFound),
+            status => Err(ApiError::Server(status.to_string())),
+        }
+    }
+
+    /// Wait for the next event. `None` means the connection is gone.
+    pub async fn next(&mut self) -> Option<StreamEvent> {
+        loop {
+            while let Some(end) = self.buffer.iter().position(|&b| b == b'\n') {
+                let line: Vec<u8> = self.buffer.drain(..=end).collect();
+                let line = String::from_utf8_lossy(&line);
+                if let Some(event) = self.handle_line(line.trim_end_matches(['\r', '\n'])) {
+                    return Some(event);
+                }
+            }
+
+            let chunk = self.response.chunk().await.ok()??;
+            self.buffer.extend_from_slice(&chunk);
+        }
+    }
+
+    /// Feed one line; a blank line ends the event being collected
+    fn handle_line(&mut self, line: &str) -> Option<StreamEvent> {
+        if line.is_empty() {
+            let name = std::mem::take(&mut self.event_name);
+            let data = std::mem::take(&mut self.data);
+            return match name.as_str() {
+                "change" => serde_json::from_str(&data).ok().map(StreamEvent::Change),
+                "resync" => Some(StreamEvent::Resync),
+                _ => None,
+            };
+        }
+
+        // Lines starting with ':' are keep-alive comments
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "event" => self.event_name = value.to_string(),
+            "data" => {
+                if !self.data.is_empty() {
+                    self.data.push('\n');
+                }
+                self.data.push_str(value);
+            }
+            _ => {}
+        }
+
+        None
+    }
+}
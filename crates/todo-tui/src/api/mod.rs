@@ -1,9 +1,11 @@
 mod auth;
 mod client;
+mod events;
 mod preferences;
 pub mod quote;
 mod workspace_state;
 
 pub use client::{ApiClient, ApiError};
+pub use events::{EventStream, StreamEvent};
 pub use preferences::UserPreferences;
 pub use workspace_state::WorkspaceState;
@@ -3,12 +3,16 @@ use std::path::PathBuf;
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 use crate::app::FilterPreset;
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct UserPreferences {
     pub filter_presets: Vec<FilterPreset>,
+    /// Board columns shown as narrow strips
+    #[serde(default)]
+    pub collapsed_columns: Vec<Uuid>,
 }
 
 impl UserPreferences {
@@ -2,13 +2,14 @@ use anyhow::Result;
 use chrono::{Datelike, NaiveDate};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::collections::HashSet;
-use todo_shared::api::{CreateDocumentRequest, CreateTaskRequest, SearchResultItem, TaskListParams, UpdateDocumentRequest, UpdateTaskRequest, WorkspaceMemberWithUser};
-use todo_shared::{CommentWithAuthor, Document, Priority, Tag, Task, TaskStatus, User, Workspace, WorkspaceWithRole};
+use todo_shared::api::{BulkTaskOperation, CreateDocumentRequest, UpdateChecklistItemRequest, CreateTaskRequest, CreateWorkLogRequest, Patch, SearchResultItem, TaskListParams, UpdateDocumentRequest, UpdateTaskRequest, WorkspaceMemberWithUser};
+use todo_shared::{ActivityEntityType, ActivityEntry, Attachment, ChecklistItem, ChecklistProgress, CommentRevision, CommentWithAuthor, CustomField, CustomFieldType, CustomFieldValue, Document, Notification, NotificationSettings, Priority, RecurrenceRule, Session, StatusTransition, Tag, Task, TaskStatus, TrashItem, User, WorkLog, Workspace, WorkspaceEvent, WorkspaceEventKind, WorkspaceRole, WorkspaceWithRole};
 use tokio::sync::mpsc;
 use tui_textarea::TextArea;
 
 use crate::api::{ApiClient, UserPreferences, WorkspaceState};
 use crate::editor::{self, EditorContext};
+use crate::merge::{self, DiffLine};
 
 /// Preset colors for tags (hex format)
 pub const TAG_COLORS: &[&str] = &[
@@ -34,6 +35,9 @@ pub enum View {
     Dashboard,  // Kanban board
     TaskDetail,
     KnowledgeBase,
+    Trash,
+    Notifications,
+    Profile,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -46,6 +50,10 @@ pub enum VimMode {
 pub enum AuthMode {
     Login,
     Register,
+    /// Asking for a reset code by email
+    ForgotPassword,
+    /// Entering the reset code and a new password
+    ResetPassword,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -57,6 +65,13 @@ pub enum InputField {
     VerificationCode,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordField {
+    Current,
+    New,
+    Confirm,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NewTaskField {
     Title,
@@ -70,10 +85,148 @@ pub enum TaskEditField {
     Priority,
     DueDate,
     TimeEstimate,
-    Assignee,
+    Recurrence,
+    Assignees,
+    Watchers,
+    CustomFields,
     Tags,
 }
 
+impl TaskEditField {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Title => Self::Description,
+            Self::Description => Self::Priority,
+            Self::Priority => Self::DueDate,
+            Self::DueDate => Self::TimeEstimate,
+            Self::TimeEstimate => Self::Recurrence,
+            Self::Recurrence => Self::Assignees,
+            Self::Assignees => Self::Watchers,
+            Self::Watchers => Self::CustomFields,
+            Self::CustomFields => Self::Tags,
+            Self::Tags => Self::Title,
+        }
+    }
+
+    pub fn prev(self) -> Self {
+        match self {
+            Self::Title => Self::Tags,
+            Self::Description => Self::Title,
+            Self::Priority => Self::Description,
+            Self::DueDate => Self::Priority,
+            Self::TimeEstimate => Self::DueDate,
+            Self::Recurrence => Self::TimeEstimate,
+            Self::Assignees => Self::Recurrence,
+            Self::Watchers => Self::Assignees,
+            Self::CustomFields => Self::Watchers,
+            Self::Tags => Self::CustomFields,
+        }
+    }
+}
+
+/// Longest stretch a single timer run may log
+const MAX_TIMER_MINUTES: i64 = 24 * 60;
+
+/// Running work timer for a task
+#[derive(Debug, Clone)]
+pub struct WorkTimer {
+    pub workspace_id: uuid::Uuid,
+    pub task_id: uuid::Uuid,
+    pub task_key: String,
+    pub started_at: chrono::DateTime<chrono::Local>,
+}
+
+impl WorkTimer {
+    /// Elapsed time as HH:MM:SS
+    pub fn elapsed_display(&self) -> String {
+        let secs = (chrono::Local::now() - self.started_at).num_seconds().max(0);
+        format!("{:02}:{:02}:{:02}", secs / 3600, (secs % 3600) / 60, secs % 60)
+    }
+}
+
+/// Usernames offered for the `@name` being typed in a comment
+#[derive(Debug, Clone)]
+pub struct MentionCompletion {
+    /// Characters typed after the `@`
+    pub typed: usize,
+    pub usernames: Vec<String>,
+}
+
+/// Most usernames offered at once
+const MAX_MENTION_COMPLETIONS: usize = 5;
+
+/// Reactions offered in the task detail picker, picked with 1-6
+pub const REACTIONS: [&str; 6] = ["👍", "👎", "😄", "🎉", "👀", "🚀"];
+
+/// One field that differs between the latest saved copy and a rejected edit
+#[derive(Debug, Clone)]
+pub struct ConflictField {
+    pub name: &'static str,
+    pub theirs: String,
+    pub mine: String,
+    /// Both sides changed the field, so merging keeps only mine
+    pub clash: bool,
+}
+
+/// The requests to retry with once the user picks a side
+#[derive(Debug, Clone)]
+pub enum ConflictResolution {
+    Task {
+        overwrite: Box<UpdateTaskRequest>,
+        /// `None` when both sides changed the same description lines
+        merge: Option<Box<UpdateTaskRequest>>,
+        custom_field_values: Vec<CustomFieldValue>,
+    },
+    Document {
+        overwrite: UpdateDocumentRequest,
+        /// `None` when both sides changed the same content lines
+        merge: Option<UpdateDocumentRequest>,
+    },
+}
+
+/// A save rejected because someone else saved first
+#[derive(Debug, Clone)]
+pub struct EditConflict {
+    pub label: String,
+    pub server_version: i32,
+    pub fields: Vec<ConflictField>,
+    /// Line diff of the description or page content, theirs to mine
+    pub text_diff: Vec<DiffLine>,
+    pub scroll: u16,
+    pub resolution: ConflictResolution,
+}
+
+impl EditConflict {
+    pub fn can_merge(&self) -> bool {
+        match &self.resolution {
+            ConflictResolution::Task { merge, .. } => merge.is_some(),
+            ConflictResolution::Document { merge, .. } => merge.is_some(),
+        }
+    }
+}
+
+/// Compare one field across base, theirs and mine. Adds it to the conflict
+/// list when theirs and mine differ, and returns whether mine changed it.
+fn compare_field<T: PartialEq>(
+    fields: &mut Vec<ConflictField>,
+    name: &'static str,
+    base: &T,
+    theirs: &T,
+    mine: &T,
+    show: impl Fn(&T) -> String,
+) -> bool {
+    let mine_changed = mine != base;
+    if theirs != mine {
+        fields.push(ConflictField {
+            name,
+            theirs: show(theirs),
+            mine: show(mine),
+            clash: mine_changed && theirs != base,
+        });
+    }
+    mine_changed
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TagManagementMode {
     List,
@@ -81,6 +234,14 @@ pub enum TagManagementMode {
     Edit,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnManagementMode {
+    List,
+    Create,
+    Rename,
+    Delete,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum FilterPanelSection {
     #[default]
@@ -88,6 +249,7 @@ pub enum FilterPanelSection {
     Tags,
     Assignee,
     DueDate,
+    CustomFields,
     OrderBy,
     Actions,
 }
@@ -98,7 +260,8 @@ impl FilterPanelSection {
             Self::Priority => Self::Tags,
             Self::Tags => Self::Assignee,
             Self::Assignee => Self::DueDate,
-            Self::DueDate => Self::OrderBy,
+            Self::DueDate => Self::CustomFields,
+            Self::CustomFields => Self::OrderBy,
             Self::OrderBy => Self::Actions,
             Self::Actions => Self::Priority,
         }
@@ -110,7 +273,8 @@ impl FilterPanelSection {
             Self::Tags => Self::Priority,
             Self::Assignee => Self::Tags,
             Self::DueDate => Self::Assignee,
-            Self::OrderBy => Self::DueDate,
+            Self::CustomFields => Self::DueDate,
+            Self::OrderBy => Self::CustomFields,
             Self::Actions => Self::OrderBy,
         }
     }
@@ -138,19 +302,25 @@ pub enum HomeMenuItem {
     #[default]
     Kanban,
     KnowledgeBase,
+    Notifications,
+    Trash,
+    Profile,
     WorkspaceSwitch,
     Logout,
 }
 
 impl HomeMenuItem {
     pub fn all() -> &'static [Self] {
-        &[Self::Kanban, Self::KnowledgeBase, Self::WorkspaceSwitch, Self::Logout]
+        &[Self::Kanban, Self::KnowledgeBase, Self::Notifications, Self::Trash, Self::Profile, Self::WorkspaceSwitch, Self::Logout]
     }
 
     pub fn label(self) -> &'static str {
         match self {
             Self::Kanban => "Kanban Board",
             Self::KnowledgeBase => "Knowledge Base",
+            Self::Notifications => "Notifications",
+            Self::Trash => "Trash",
+            Self::Profile => "Account",
             Self::WorkspaceSwitch => "Switch Workspace",
             Self::Logout => "Logout",
         }
@@ -160,12 +330,27 @@ impl HomeMenuItem {
         match self {
             Self::Kanban => "📋",
             Self::KnowledgeBase => "📚",
+            Self::Notifications => "🔔",
+            Self::Trash => "🗑",
+            Self::Profile => "👤",
             Self::WorkspaceSwitch => "🔄",
             Self::Logout => "🚪",
         }
     }
 }
 
+/// Rows of the notification settings popup, in display order
+pub fn notification_setting_rows(settings: &NotificationSettings) -> [(&'static str, bool); 6] {
+    [
+        ("Assigned to a task", settings.notify_task_assigned),
+        ("Watched task changes status", settings.notify_task_updated),
+        ("New comment on my task", settings.notify_task_commented),
+        ("Due date approaching", settings.notify_due_date_approaching),
+        ("Mentioned in a comment", settings.notify_mention),
+        ("Email digest of unread ones", settings.email_digest),
+    ]
+}
+
 /// Knowledge Base panel focus
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum KbFocus {
@@ -197,6 +382,12 @@ pub enum AppEvent {
         statuses: Vec<TaskStatus>,
         tasks: Vec<Task>,
     },
+    /// Someone changed the open workspace
+    WorkspaceChanged(WorkspaceEvent),
+    /// The event stream missed changes to this workspace
+    WorkspaceResync(uuid::Uuid),
+    /// The event stream for this workspace disconnected
+    EventStreamClosed(uuid::Uuid),
     Error(String),
 }
 
@@ -209,6 +400,7 @@ pub struct App {
     pub loading: bool,
     pub loading_message: String,
     pub error_message: Option<String>,
+    pub info_message: Option<String>,
 
     // Current user
     pub user: Option<User>,
@@ -238,6 +430,7 @@ pub struct App {
     pub home_quote: Option<String>,
     pub home_quote_author: Option<String>,
     pub home_stats: todo_shared::api::WorkspaceStats,
+    pub home_activity: Vec<ActivityEntry>,
     pub calendar_year: i32,
     pub calendar_month: u32,
     pub calendar_tasks: std::collections::HashMap<NaiveDate, usize>,
@@ -248,6 +441,13 @@ pub struct App {
     pub selected_column: usize,
     pub selected_task: usize,
     pub moving_task: bool,
+    /// Comment prompt for workflow transitions that need one
+    pub entering_move_comment: bool,
+    pub move_comment_input: String,
+    pub move_comment_target: usize,
+    /// Visual mode: tasks marked for a bulk action
+    pub selecting_tasks: bool,
+    pub selected_task_ids: Vec<uuid::Uuid>,
     #[allow(dead_code)] // Prepared for scroll feature
     pub column_scroll_offsets: Vec<usize>,
 
@@ -256,6 +456,21 @@ pub struct App {
     pub task_comments: Vec<CommentWithAuthor>,
     pub adding_comment: bool,
     pub comment_textarea: Option<TextArea<'static>>,
+    pub mention_completion_idx: usize,
+    pub comment_cursor: usize,
+    /// Comment the one being written replies to
+    pub replying_to: Option<uuid::Uuid>,
+    pub reaction_picker_visible: bool,
+    pub reaction_cursor: usize,
+    /// Comment the comment box is rewriting, if it isn't a new one
+    pub editing_comment: Option<uuid::Uuid>,
+    pub comment_confirming_delete: bool,
+    /// Earlier versions of the selected comment, newest first
+    pub comment_revisions: Vec<CommentRevision>,
+    pub comment_revisions_visible: bool,
+    pub comment_revisions_scroll: u16,
+    pub creating_subtask: bool,
+    pub new_subtask_title: String,
 
     // Create task state
     pub creating_task: bool,
@@ -274,7 +489,11 @@ pub struct App {
     pub edit_task_priority: Option<Priority>,
     pub edit_task_due_date_str: String,
     pub edit_task_time_estimate_str: String,
-    pub edit_task_assignee: Option<uuid::Uuid>,
+    pub edit_task_recurrence_str: String,
+    pub edit_task_assignees: Vec<uuid::Uuid>,
+    pub edit_task_watchers: Vec<uuid::Uuid>,
+    pub assignee_selector_cursor: usize,
+    pub watcher_selector_cursor: usize,
 
     // Workspace members (for assignee selection)
     pub workspace_members: Vec<WorkspaceMemberWithUser>,
@@ -300,6 +519,8 @@ pub struct App {
     pub filter_assignee_cursor: usize,        // 0=None, 1..=N=members
     pub filter_due_mode: DueDateMode,
     pub filter_due_input: String,
+    pub filter_custom_field_cursor: usize,    // Index into workspace_custom_fields
+    pub filter_custom_field_inputs: Vec<String>,
     pub filter_order_cursor: usize,           // Index into SORT_FIELDS
     pub filter_order_desc: bool,
 
@@ -323,6 +544,14 @@ pub struct App {
     pub task_edit_selected_tags: Vec<uuid::Uuid>,
     pub tag_selector_cursor: usize,
 
+    // Workflow transitions between columns
+    pub workspace_transitions: Vec<StatusTransition>,
+
+    // Custom fields
+    pub workspace_custom_fields: Vec<CustomField>,
+    pub edit_custom_field_inputs: Vec<String>, // Aligned with workspace_custom_fields
+    pub edit_custom_field_cursor: usize,
+
     // Tag management popup
     pub tag_management_visible: bool,
     pub tag_management_cursor: usize,
@@ -331,6 +560,15 @@ pub struct App {
     pub tag_create_color_idx: usize,
     pub tag_edit_id: Option<uuid::Uuid>,
 
+    // Column management popup
+    pub column_management_visible: bool,
+    pub column_management_cursor: usize,
+    pub column_management_mode: ColumnManagementMode,
+    pub column_name_input: String,
+    pub column_color_idx: usize,
+    pub column_delete_target: usize, // Column that takes the deleted column's tasks
+    pub collapsed_columns: HashSet<uuid::Uuid>, // Saved in preferences
+
     // Member panel
     pub member_panel_visible: bool,
     pub selected_member_idx: usize,
@@ -364,6 +602,62 @@ pub struct App {
     pub linking_task_mode: bool,
     pub link_task_cursor: usize,
 
+    // Task dependency state
+    pub task_dependencies: todo_shared::api::TaskDependencies,
+    pub adding_blocker_mode: bool,
+    pub removing_blocker_mode: bool,
+    pub blocker_cursor: usize,
+
+    // Time tracking state
+    pub task_work_logs: Vec<WorkLog>,
+    pub work_timer: Option<WorkTimer>,
+
+    // Checklist state
+    pub task_checklist: Vec<ChecklistItem>,
+    pub checklist_mode: bool,
+    pub checklist_cursor: usize,
+    pub adding_checklist_item: bool,
+    pub checklist_input: String,
+
+    // Attachment state
+    pub task_attachments: Vec<Attachment>,
+    pub attachment_mode: bool,
+    pub attachment_cursor: usize,
+
+    // History state
+    pub task_history: Vec<ActivityEntry>,
+    pub show_task_history: bool,
+
+    // Trash state
+    pub trash_items: Vec<TrashItem>,
+    pub trash_cursor: usize,
+    pub trash_confirming_purge: bool,
+    pub trash_return_view: View,
+
+    // Notifications state
+    pub notifications: Vec<Notification>,
+    pub notification_cursor: usize,
+    pub notifications_unread: i64,
+    pub notifications_unread_only: bool,
+    pub notification_return_view: View,
+    pub notification_settings: NotificationSettings,
+    pub notification_settings_visible: bool,
+    pub notification_settings_cursor: usize,
+    /// Change events came in that may have notified us; refreshed on the next tick
+    pub notifications_refresh_due: bool,
+
+    // Profile state
+    pub profile_return_view: View,
+    pub changing_password: bool,
+    pub password_field: PasswordField,
+    pub current_password_input: String,
+    pub new_password_input: String,
+    pub confirm_password_input: String,
+    pub sessions: Vec<Session>,
+    pub session_cursor: usize,
+    pub session_confirming_revoke: bool,
+    pub sessions_confirming_logout_all: bool,
+
     // Menu state
     pub menu_visible: bool,
     pub menu_selected_idx: usize,
@@ -375,6 +669,9 @@ pub struct App {
     pub help_visible: bool,
     pub help_scroll: usize,
 
+    // Rejected save waiting for merge or overwrite
+    pub edit_conflict: Option<EditConflict>,
+
     // Terminal clear flag (set after external editor)
     pub needs_terminal_clear: bool,
 
@@ -393,6 +690,24 @@ pub struct Column {
     pub tasks: Vec<Task>,
 }
 
+/// Organize tasks into one column per status
+fn build_columns(statuses: Vec<TaskStatus>, tasks: &[Task]) -> Vec<Column> {
+    statuses
+        .into_iter()
+        .map(|status| {
+            let column_tasks: Vec<Task> = tasks
+                .iter()
+                .filter(|t| t.status_id == status.id)
+                .cloned()
+                .collect();
+            Column {
+                status,
+                tasks: column_tasks,
+            }
+        })
+        .collect()
+}
+
 impl App {
     pub fn new(api: ApiClient, has_tokens: bool) -> Self {
         let view = if has_tokens {
@@ -401,6 +716,8 @@ impl App {
             View::Login
         };
 
+        let prefs = UserPreferences::load().unwrap_or_default();
+
         Self {
             api,
             view,
@@ -408,6 +725,7 @@ impl App {
             loading: false,
             loading_message: String::new(),
             error_message: None,
+            info_message: None,
             user: None,
             auth_mode: AuthMode::Login,
             login_email: String::new(),
@@ -427,6 +745,7 @@ impl App {
             home_quote: None,
             home_quote_author: None,
             home_stats: Default::default(),
+            home_activity: Vec::new(),
             calendar_year: chrono::Local::now().year(),
             calendar_month: chrono::Local::now().month(),
             calendar_tasks: std::collections::HashMap::new(),
@@ -435,11 +754,28 @@ impl App {
             selected_column: 0,
             selected_task: 0,
             moving_task: false,
+            entering_move_comment: false,
+            move_comment_input: String::new(),
+            move_comment_target: 0,
+            selecting_tasks: false,
+            selected_task_ids: Vec::new(),
             column_scroll_offsets: Vec::new(),
             selected_task_detail: None,
             task_comments: Vec::new(),
             adding_comment: false,
             comment_textarea: None,
+            mention_completion_idx: 0,
+            comment_cursor: 0,
+            replying_to: None,
+            reaction_picker_visible: false,
+            reaction_cursor: 0,
+            editing_comment: None,
+            comment_confirming_delete: false,
+            comment_revisions: Vec::new(),
+            comment_revisions_visible: false,
+            comment_revisions_scroll: 0,
+            creating_subtask: false,
+            new_subtask_title: String::new(),
             creating_task: false,
             new_task_title: String::new(),
             new_task_description_textarea: None,
@@ -452,7 +788,11 @@ impl App {
             edit_task_priority: None,
             edit_task_due_date_str: String::new(),
             edit_task_time_estimate_str: String::new(),
-            edit_task_assignee: None,
+            edit_task_recurrence_str: String::new(),
+            edit_task_assignees: Vec::new(),
+            edit_task_watchers: Vec::new(),
+            assignee_selector_cursor: 0,
+            watcher_selector_cursor: 0,
             workspace_members: Vec::new(),
             searching: false,
             search_query: String::new(),
@@ -470,6 +810,8 @@ impl App {
             filter_assignee_cursor: 0,
             filter_due_mode: DueDateMode::default(),
             filter_due_input: String::new(),
+            filter_custom_field_cursor: 0,
+            filter_custom_field_inputs: Vec::new(),
             filter_order_cursor: 0,
             filter_order_desc: false,
             preset_panel_visible: false,
@@ -478,18 +820,27 @@ impl App {
             new_preset_name: String::new(),
             command_mode: false,
             command_input: String::new(),
-            filter_presets: UserPreferences::load()
-                .map(|p| p.filter_presets)
-                .unwrap_or_default(),
+            filter_presets: prefs.filter_presets,
             workspace_tags: Vec::new(),
             task_edit_selected_tags: Vec::new(),
             tag_selector_cursor: 0,
+            workspace_transitions: Vec::new(),
+            workspace_custom_fields: Vec::new(),
+            edit_custom_field_inputs: Vec::new(),
+            edit_custom_field_cursor: 0,
             tag_management_visible: false,
             tag_management_cursor: 0,
             tag_management_mode: TagManagementMode::List,
             tag_create_name: String::new(),
             tag_create_color_idx: 0,
             tag_edit_id: None,
+            column_management_visible: false,
+            column_management_cursor: 0,
+            column_management_mode: ColumnManagementMode::List,
+            column_name_input: String::new(),
+            column_color_idx: 0,
+            column_delete_target: 0,
+            collapsed_columns: prefs.collapsed_columns.into_iter().collect(),
             member_panel_visible: false,
             selected_member_idx: 0,
             inviting_member: false,
@@ -519,6 +870,46 @@ impl App {
             linking_task_mode: false,
             link_task_cursor: 0,
 
+            task_dependencies: todo_shared::api::TaskDependencies::default(),
+            task_work_logs: Vec::new(),
+            work_timer: None,
+            task_checklist: Vec::new(),
+            checklist_mode: false,
+            checklist_cursor: 0,
+            adding_checklist_item: false,
+            checklist_input: String::new(),
+            task_attachments: Vec::new(),
+            attachment_mode: false,
+            attachment_cursor: 0,
+            trash_items: Vec::new(),
+            trash_cursor: 0,
+            trash_confirming_purge: false,
+            trash_return_view: View::Home,
+            notifications: Vec::new(),
+            notification_cursor: 0,
+            notifications_unread: 0,
+            notifications_unread_only: false,
+            notification_return_view: View::Home,
+            notification_settings: NotificationSettings::default(),
+            notification_settings_visible: false,
+            notification_settings_cursor: 0,
+            notifications_refresh_due: false,
+            profile_return_view: View::Home,
+            changing_password: false,
+            password_field: PasswordField::Current,
+            current_password_input: String::new(),
+            new_password_input: String::new(),
+            confirm_password_input: String::new(),
+            sessions: Vec::new(),
+            session_cursor: 0,
+            session_confirming_revoke: false,
+            sessions_confirming_logout_all: false,
+            task_history: Vec::new(),
+            show_task_history: false,
+            adding_blocker_mode: false,
+            removing_blocker_mode: false,
+            blocker_cursor: 0,
+
             menu_visible: false,
             menu_selected_idx: 0,
 
@@ -527,6 +918,8 @@ impl App {
             help_visible: false,
             help_scroll: 0,
 
+            edit_conflict: None,
+
             needs_terminal_clear: false,
 
             is_connected: true,
@@ -544,10 +937,16 @@ impl App {
             || self.searching
             || self.command_mode
             || self.creating_task
+            || self.creating_subtask
             || self.kb_creating
             || self.kb_editing
             || self.inviting_member
             || (self.tag_management_visible && self.tag_management_mode != TagManagementMode::List)
+            || (self.column_management_visible
+                && matches!(
+                    self.column_management_mode,
+                    ColumnManagementMode::Create | ColumnManagementMode::Rename
+                ))
             || self.creating_preset
     }
 
@@ -584,6 +983,44 @@ impl App {
             .unwrap_or_default()
     }
 
+    /// Members whose username completes the `@name` left of the comment cursor
+    pub fn mention_completion(&self) -> Option<MentionCompletion> {
+        let textarea = self.comment_textarea.as_ref()?;
+        let (row, col) = textarea.cursor();
+        let before: String = textarea.lines().get(row)?.chars().take(col).collect();
+
+        let prefix = match todo_shared::find_mentions(&before).last() {
+            Some((range, name)) if range.end == before.len() => name.to_lowercase(),
+            // A bare `@` at the start of a word
+            _ if before.ends_with('@')
+                && !before[..before.len() - 1]
+                    .chars()
+                    .next_back()
+                    .is_some_and(|c| c.is_alphanumeric() || c == '_') =>
+            {
+                String::new()
+            }
+            _ => return None,
+        };
+
+        let mut usernames: Vec<String> = self
+            .workspace_members
+            .iter()
+            .filter(|m| m.username.to_lowercase().starts_with(&prefix))
+            .map(|m| m.username.clone())
+            .collect();
+        usernames.sort();
+        usernames.truncate(MAX_MENTION_COMPLETIONS);
+
+        if usernames.is_empty() {
+            return None;
+        }
+        Some(MentionCompletion {
+            typed: prefix.chars().count(),
+            usernames,
+        })
+    }
+
     /// Get current new task description as String
     fn get_new_task_description(&self) -> String {
         self.new_task_description_textarea
@@ -620,6 +1057,11 @@ impl App {
         self.error_message = None;
     }
 
+    /// Show a confirmation that isn't an error; dismissed like one
+    pub fn set_info(&mut self, message: String) {
+        self.info_message = Some(message);
+    }
+
     /// Handle key events, returns true if app should quit
     pub async fn handle_key(
         &mut self,
@@ -630,9 +1072,11 @@ impl App {
         if self.error_message.is_some() && key.code != KeyCode::Esc {
             self.clear_error();
         }
+        self.info_message = None;
 
-        // Global quit with Ctrl+C
+        // Global quit with Ctrl+C; a timer that can't be logged is dropped
         if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.log_running_timer().await;
             return Ok(true);
         }
 
@@ -642,6 +1086,12 @@ impl App {
             return Ok(false);
         }
 
+        // Edit conflict prompt (global overlay)
+        if self.edit_conflict.is_some() {
+            self.handle_edit_conflict_key(key).await;
+            return Ok(false);
+        }
+
         // Global help toggle with ?
         if key.code == KeyCode::Char('?') && !self.is_text_input_mode() {
             self.help_visible = true;
@@ -658,6 +1108,9 @@ impl App {
             View::Dashboard => self.handle_dashboard_key(key, tx).await,
             View::TaskDetail => self.handle_task_detail_key(key, tx).await,
             View::KnowledgeBase => self.handle_knowledge_base_key(key, tx).await,
+            View::Trash => self.handle_trash_key(key, tx).await,
+            View::Notifications => self.handle_notifications_key(key, tx).await,
+            View::Profile => self.handle_profile_key(key).await,
         }
     }
 
@@ -689,6 +1142,10 @@ impl App {
                 self.auth_mode = AuthMode::Login;
                 self.login_field = InputField::Email;
             }
+            KeyCode::Char('f') if self.vim_mode == VimMode::Normal => {
+                self.auth_mode = AuthMode::ForgotPassword;
+                self.login_field = InputField::Email;
+            }
             KeyCode::Tab | KeyCode::BackTab => {
                 self.login_field = match (self.auth_mode, self.login_field) {
                     (AuthMode::Login, InputField::Email) => InputField::Password,
@@ -699,6 +1156,9 @@ impl App {
                     (AuthMode::Register, InputField::Password) => InputField::DisplayName,
                     (AuthMode::Register, InputField::DisplayName) => InputField::Username,
                     (AuthMode::Register, _) => InputField::Username,
+                    (AuthMode::ForgotPassword, _) => InputField::Email,
+                    (AuthMode::ResetPassword, InputField::VerificationCode) => InputField::Password,
+                    (AuthMode::ResetPassword, _) => InputField::VerificationCode,
                 };
             }
             KeyCode::Char('j') | KeyCode::Down if self.vim_mode == VimMode::Normal => {
@@ -707,6 +1167,7 @@ impl App {
                     (AuthMode::Register, InputField::Username) => InputField::Email,
                     (AuthMode::Register, InputField::Email) => InputField::Password,
                     (AuthMode::Register, InputField::Password) => InputField::DisplayName,
+                    (AuthMode::ResetPassword, InputField::VerificationCode) => InputField::Password,
                     _ => self.login_field,
                 };
             }
@@ -716,6 +1177,7 @@ impl App {
                     (AuthMode::Register, InputField::Email) => InputField::Username,
                     (AuthMode::Register, InputField::Password) => InputField::Email,
                     (AuthMode::Register, InputField::DisplayName) => InputField::Password,
+                    (AuthMode::ResetPassword, InputField::Password) => InputField::VerificationCode,
                     _ => self.login_field,
                 };
             }
@@ -735,6 +1197,16 @@ impl App {
                             self.do_register(tx).await;
                         }
                     }
+                    AuthMode::ForgotPassword => {
+                        if !self.login_email.is_empty() {
+                            self.do_forgot_password().await;
+                        }
+                    }
+                    AuthMode::ResetPassword => {
+                        if self.verification_code.len() == 6 && !self.login_password.is_empty() {
+                            self.do_reset_password().await;
+                        }
+                    }
                 }
             }
             KeyCode::Char(c) if self.vim_mode == VimMode::Insert => {
@@ -743,7 +1215,12 @@ impl App {
                     InputField::Email => self.login_email.push(c),
                     InputField::Password => self.login_password.push(c),
                     InputField::DisplayName => self.register_display_name.push(c),
-                    InputField::VerificationCode => {} // Not used in login view
+                    InputField::VerificationCode => {
+                        // Reset codes are 6 digits
+                        if c.is_ascii_digit() && self.verification_code.len() < 6 {
+                            self.verification_code.push(c);
+                        }
+                    }
                 }
             }
             KeyCode::Backspace if self.vim_mode == VimMode::Insert => {
@@ -752,7 +1229,7 @@ impl App {
                     InputField::Email => { self.login_email.pop(); }
                     InputField::Password => { self.login_password.pop(); }
                     InputField::DisplayName => { self.register_display_name.pop(); }
-                    InputField::VerificationCode => {} // Not used in login view
+                    InputField::VerificationCode => { self.verification_code.pop(); }
                 }
             }
             _ => {}
@@ -822,7 +1299,7 @@ impl App {
         }
 
         match key.code {
-            KeyCode::Char('q') => return Ok(true),
+            KeyCode::Char('q') => return Ok(self.log_running_timer().await),
             KeyCode::Char('j') | KeyCode::Down => {
                 let items = HomeMenuItem::all();
                 if self.home_menu_idx < items.len() - 1 {
@@ -857,6 +1334,15 @@ impl App {
                 HomeMenuItem::KnowledgeBase => {
                     self.open_knowledge_base().await;
                 }
+                HomeMenuItem::Notifications => {
+                    self.open_notifications().await;
+                }
+                HomeMenuItem::Trash => {
+                    self.open_trash().await;
+                }
+                HomeMenuItem::Profile => {
+                    self.open_profile().await;
+                }
                 HomeMenuItem::WorkspaceSwitch => {
                     self.open_workspace_modal().await;
                 }
@@ -925,7 +1411,7 @@ impl App {
 
         // Normal workspace selection mode
         match key.code {
-            KeyCode::Char('q') => return Ok(true),
+            KeyCode::Char('q') => return Ok(self.log_running_timer().await),
             KeyCode::Char('L') => {
                 self.do_logout().await;
             }
@@ -1006,11 +1492,15 @@ impl App {
     }
 
     async fn do_logout(&mut self) {
+        if !self.log_running_timer().await {
+            return;
+        }
         let _ = self.api.logout().await;
         self.user = None;
         self.workspaces.clear();
         self.current_workspace = None;
         self.columns.clear();
+        self.sessions.clear();
         self.view = View::Login;
     }
 
@@ -1044,6 +1534,11 @@ impl App {
             return self.handle_tag_management_key(key).await;
         }
 
+        // Handle column management popup
+        if self.column_management_visible {
+            return self.handle_column_management_key(key).await;
+        }
+
         // Handle member panel
         if self.member_panel_visible {
             return self.handle_member_panel_key(key).await;
@@ -1149,7 +1644,14 @@ impl App {
         if self.confirming_delete {
             match key.code {
                 KeyCode::Char('y') | KeyCode::Char('Y') => {
-                    self.do_delete_task().await;
+                    if self.selecting_tasks {
+                        self.confirming_delete = false;
+                        if let Err(e) = self.do_bulk_operation(BulkTaskOperation::Delete).await {
+                            self.set_error(e);
+                        }
+                    } else {
+                        self.do_delete_task().await;
+                    }
                 }
                 KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
                     self.confirming_delete = false;
@@ -1159,6 +1661,31 @@ impl App {
             return Ok(false);
         }
 
+        // Handle the comment prompt of a workflow move
+        if self.entering_move_comment {
+            match key.code {
+                KeyCode::Esc => {
+                    self.entering_move_comment = false;
+                    self.move_comment_input.clear();
+                    self.vim_mode = VimMode::Normal;
+                }
+                KeyCode::Enter if !self.move_comment_input.trim().is_empty() => {
+                    let comment = std::mem::take(&mut self.move_comment_input);
+                    self.entering_move_comment = false;
+                    self.vim_mode = VimMode::Normal;
+                    self.do_move_task_to(self.move_comment_target, Some(comment)).await;
+                }
+                KeyCode::Char(c) => {
+                    self.move_comment_input.push(c);
+                }
+                KeyCode::Backspace => {
+                    self.move_comment_input.pop();
+                }
+                _ => {}
+            }
+            return Ok(false);
+        }
+
         // Handle move mode
         if self.moving_task {
             match key.code {
@@ -1176,6 +1703,44 @@ impl App {
             return Ok(false);
         }
 
+        // Handle visual mode; keys not used here still navigate the board
+        if self.selecting_tasks {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('v') => {
+                    self.selecting_tasks = false;
+                    self.selected_task_ids.clear();
+                    return Ok(false);
+                }
+                KeyCode::Char(' ') => {
+                    if let Some(task_id) = self.get_selected_task().map(|t| t.id) {
+                        self.toggle_task_selection(task_id);
+                    }
+                    return Ok(false);
+                }
+                KeyCode::Char('a') => {
+                    self.toggle_column_selection();
+                    return Ok(false);
+                }
+                KeyCode::Char('m') => {
+                    // Move the marked tasks into the column under the cursor
+                    if let Some(column) = self.columns.get(self.selected_column) {
+                        let status_id = column.status.id;
+                        if let Err(e) = self.do_bulk_operation(BulkTaskOperation::Move { status_id }).await {
+                            self.set_error(e);
+                        }
+                    }
+                    return Ok(false);
+                }
+                KeyCode::Char('d') => {
+                    if !self.selected_task_ids.is_empty() {
+                        self.confirming_delete = true;
+                    }
+                    return Ok(false);
+                }
+                _ => {}
+            }
+        }
+
         match key.code {
             KeyCode::Char('q') | KeyCode::Esc | KeyCode::Backspace => {
                 // Go back to Home
@@ -1218,6 +1783,13 @@ impl App {
                     self.confirming_delete = true;
                 }
             }
+            KeyCode::Char('v') => {
+                // Enter visual mode, starting with the current task
+                if let Some(task_id) = self.get_selected_task().map(|t| t.id) {
+                    self.selecting_tasks = true;
+                    self.selected_task_ids = vec![task_id];
+                }
+            }
             KeyCode::Enter => {
                 self.open_task_detail().await;
             }
@@ -1248,6 +1820,19 @@ impl App {
                 self.tag_create_color_idx = 0;
                 self.tag_edit_id = None;
             }
+            KeyCode::Char('C') => {
+                // Open column management popup on the current column
+                self.open_column_management();
+            }
+            KeyCode::Char('I') => {
+                self.open_notifications().await;
+            }
+            KeyCode::Char('z') => {
+                if let Some(column) = self.columns.get(self.selected_column) {
+                    let status_id = column.status.id;
+                    self.toggle_column_collapsed(status_id);
+                }
+            }
             KeyCode::Char('F') => {
                 // Open filter panel
                 self.open_filter_panel().await;
@@ -1694,7 +2279,8 @@ impl App {
 
         let color = TAG_COLORS.get(self.tag_create_color_idx).map(|s| s.to_string());
 
-        match self.api.update_tag(workspace_id, tag_id, Some(&self.tag_create_name), color.as_deref()).await {
+        let color = Patch::set_or_clear(color.as_deref());
+        match self.api.update_tag(workspace_id, tag_id, Some(&self.tag_create_name), color).await {
             Ok(updated_tag) => {
                 // Update in workspace_tags
                 if let Some(tag) = self.workspace_tags.iter_mut().find(|t| t.id == tag_id) {
@@ -1736,78 +2322,426 @@ impl App {
         }
     }
 
-    /// Open the filter panel and populate it with current filter state
-    async fn open_filter_panel(&mut self) {
-        // Load workspace members for assignee selection
-        if let Some(ref workspace) = self.current_workspace {
-            if let Ok(members) = self.api.list_members(workspace.id).await {
-                self.workspace_members = members;
+    fn open_column_management(&mut self) {
+        self.column_management_visible = true;
+        self.column_management_cursor = self.selected_column;
+        self.column_management_mode = ColumnManagementMode::List;
+        self.column_name_input.clear();
+        self.column_color_idx = 0;
+    }
+
+    fn toggle_column_collapsed(&mut self, status_id: uuid::Uuid) {
+        if !self.collapsed_columns.remove(&status_id) {
+            self.collapsed_columns.insert(status_id);
+        }
+        self.save_preferences();
+    }
+
+    async fn handle_column_management_key(&mut self, key: KeyEvent) -> Result<bool> {
+        match self.column_management_mode {
+            ColumnManagementMode::List => {
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => {
+                        self.column_management_visible = false;
+                    }
+                    KeyCode::Char('j') | KeyCode::Down if !self.columns.is_empty() => {
+                        self.column_management_cursor = (self.column_management_cursor + 1) % self.columns.len();
+                    }
+                    KeyCode::Char('k') | KeyCode::Up if !self.columns.is_empty() => {
+                        self.column_management_cursor = self.column_management_cursor
+                            .checked_sub(1)
+                            .unwrap_or(self.columns.len() - 1);
+                    }
+                    KeyCode::Char('J') => {
+                        self.do_move_column(1).await;
+                    }
+                    KeyCode::Char('K') => {
+                        self.do_move_column(-1).await;
+                    }
+                    KeyCode::Char('n') => {
+                        // Create new column
+                        self.column_management_mode = ColumnManagementMode::Create;
+                        self.column_name_input.clear();
+                        self.column_color_idx = 0;
+                        self.vim_mode = VimMode::Insert;
+                    }
+                    KeyCode::Char('r') => {
+                        // Rename selected column
+                        if let Some(column) = self.columns.get(self.column_management_cursor) {
+                            self.column_name_input = column.status.name.clone();
+                            self.column_management_mode = ColumnManagementMode::Rename;
+                            self.vim_mode = VimMode::Insert;
+                        }
+                    }
+                    KeyCode::Char('c') => {
+                        self.do_cycle_column_color().await;
+                    }
+                    KeyCode::Char('x') => {
+                        self.do_toggle_column_done().await;
+                    }
+                    KeyCode::Char('z') => {
+                        if let Some(column) = self.columns.get(self.column_management_cursor) {
+                            let status_id = column.status.id;
+                            self.toggle_column_collapsed(status_id);
+                        }
+                    }
+                    KeyCode::Char('d') if self.column_management_cursor < self.columns.len() => {
+                        // Pick where the column's tasks go, defaulting to a neighbour
+                        self.column_delete_target = if self.column_management_cursor == 0 { 1 } else { self.column_management_cursor - 1 };
+                        self.column_management_mode = ColumnManagementMode::Delete;
+                    }
+                    _ => {}
+                }
+            }
+            ColumnManagementMode::Create | ColumnManagementMode::Rename => {
+                match key.code {
+                    KeyCode::Esc => {
+                        self.column_management_mode = ColumnManagementMode::List;
+                        self.vim_mode = VimMode::Normal;
+                    }
+                    KeyCode::Enter => {
+                        if self.column_management_mode == ColumnManagementMode::Create {
+                            self.do_create_column().await;
+                        } else {
+                            self.do_rename_column().await;
+                        }
+                    }
+                    KeyCode::Tab if self.column_management_mode == ColumnManagementMode::Create => {
+                        // Cycle through colors
+                        self.column_color_idx = (self.column_color_idx + 1) % TAG_COLORS.len();
+                    }
+                    KeyCode::Char(c) => {
+                        self.column_name_input.push(c);
+                    }
+                    KeyCode::Backspace => {
+                        self.column_name_input.pop();
+                    }
+                    _ => {}
+                }
+            }
+            ColumnManagementMode::Delete => {
+                // Only the last column can go without somewhere to put its tasks
+                let has_target = self.columns.len() > 1;
+                match key.code {
+                    KeyCode::Esc | KeyCode::Char('n') => {
+                        self.column_management_mode = ColumnManagementMode::List;
+                    }
+                    KeyCode::Char('j') | KeyCode::Down if has_target => {
+                        let mut target = (self.column_delete_target + 1) % self.columns.len();
+                        if target == self.column_management_cursor {
+                            target = (target + 1) % self.columns.len();
+                        }
+                        self.column_delete_target = target;
+                    }
+                    KeyCode::Char('k') | KeyCode::Up if has_target => {
+                        let len = self.columns.len();
+                        let mut target = (self.column_delete_target + len - 1) % len;
+                        if target == self.column_management_cursor {
+                            target = (target + len - 1) % len;
+                        }
+                        self.column_delete_target = target;
+                    }
+                    KeyCode::Enter | KeyCode::Char('y') => {
+                        self.do_delete_column().await;
+                    }
+                    _ => {}
+                }
             }
         }
+        Ok(false)
+    }
 
-        self.filter_panel_visible = true;
-        self.filter_panel_section = FilterPanelSection::Priority;
+    /// Put an updated status back into its column
+    fn replace_column_status(&mut self, status: TaskStatus) {
+        if let Some(column) = self.columns.iter_mut().find(|c| c.status.id == status.id) {
+            column.status = status;
+        }
+    }
 
-        // Initialize from current filters
-        self.filter_priority_cursor = match self.active_filters.priority {
-            None => 0,
-            Some(Priority::Highest) => 1,
-            Some(Priority::High) => 2,
-            Some(Priority::Medium) => 3,
-            Some(Priority::Low) => 4,
-            Some(Priority::Lowest) => 5,
+    async fn do_create_column(&mut self) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
         };
 
-        // Initialize tag selection from current filters
-        self.filter_selected_tags = self.active_filters.tag_ids.clone().unwrap_or_default();
-        self.filter_tag_cursor = 0;
+        let name = self.column_name_input.trim().to_string();
+        if name.is_empty() {
+            return;
+        }
 
-        // Initialize assignee
-        self.filter_assignee_cursor = if let Some(assigned_id) = self.active_filters.assigned_to {
-            self.workspace_members
-                .iter()
-                .position(|m| m.user_id == assigned_id)
-                .map(|i| i + 1)
-                .unwrap_or(0)
-        } else {
-            0
-        };
+        let color = TAG_COLORS.get(self.column_color_idx).copied();
 
-        // Initialize due date
-        if let Some(date) = self.active_filters.due_before {
-            self.filter_due_mode = DueDateMode::Before;
-            self.filter_due_input = date.to_string();
-        } else if let Some(date) = self.active_filters.due_after {
-            self.filter_due_mode = DueDateMode::After;
-            self.filter_due_input = date.to_string();
-        } else {
-            self.filter_due_mode = DueDateMode::Before;
-            self.filter_due_input.clear();
+        match self.api.create_status(workspace_id, &name, color, false, None).await {
+            Ok(status) => {
+                self.columns.push(Column {
+                    status,
+                    tasks: Vec::new(),
+                });
+                self.column_scroll_offsets.push(0);
+                self.column_management_cursor = self.columns.len() - 1;
+                self.column_management_mode = ColumnManagementMode::List;
+                self.column_name_input.clear();
+                self.vim_mode = VimMode::Normal;
+            }
+            Err(e) => {
+                self.set_error(format!("Failed to create column: {}", e));
+            }
         }
-
-        // Initialize order by
-        self.filter_order_cursor = self.active_filters.order_by
-            .as_ref()
-            .and_then(|field| SORT_FIELDS.iter().position(|(f, _)| f == field))
-            .unwrap_or(0);
-        self.filter_order_desc = self.active_filters.order
-            .as_ref()
-            .map(|o| o == "DESC")
-            .unwrap_or(false);
     }
 
-    async fn handle_filter_panel_key(&mut self, key: KeyEvent) -> Result<bool> {
-        // Handle insert mode for date input
-        if self.vim_mode == VimMode::Insert && self.filter_panel_section == FilterPanelSection::DueDate {
-            match key.code {
-                KeyCode::Esc => {
-                    self.vim_mode = VimMode::Normal;
-                }
-                KeyCode::Enter => {
-                    self.vim_mode = VimMode::Normal;
-                }
-                // Allow navigation keys to exit insert mode and navigate
-                KeyCode::Tab => {
+    async fn do_rename_column(&mut self) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+
+        let status_id = match self.columns.get(self.column_management_cursor) {
+            Some(column) => column.status.id,
+            None => return,
+        };
+
+        let name = self.column_name_input.trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+
+        match self
+            .api
+            .update_status(workspace_id, status_id, Some(&name), Patch::Absent, None, Patch::Absent)
+            .await
+        {
+            Ok(status) => {
+                self.replace_column_status(status);
+                self.column_management_mode = ColumnManagementMode::List;
+                self.column_name_input.clear();
+                self.vim_mode = VimMode::Normal;
+            }
+            Err(e) => {
+                self.set_error(format!("Failed to rename column: {}", e));
+            }
+        }
+    }
+
+    /// Step the selected column to the next preset color
+    async fn do_cycle_column_color(&mut self) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+
+        let status = match self.columns.get(self.column_management_cursor) {
+            Some(column) => &column.status,
+            None => return,
+        };
+
+        let next = status
+            .color
+            .as_deref()
+            .and_then(|current| TAG_COLORS.iter().position(|c| c.eq_ignore_ascii_case(current)))
+            .map_or(0, |idx| (idx + 1) % TAG_COLORS.len());
+        let status_id = status.id;
+
+        match self
+            .api
+            .update_status(workspace_id, status_id, None, Patch::Value(TAG_COLORS[next]), None, Patch::Absent)
+            .await
+        {
+            Ok(status) => self.replace_column_status(status),
+            Err(e) => self.set_error(format!("Failed to recolor column: {}", e)),
+        }
+    }
+
+    async fn do_toggle_column_done(&mut self) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+
+        let (status_id, is_done) = match self.columns.get(self.column_management_cursor) {
+            Some(column) => (column.status.id, column.status.is_done),
+            None => return,
+        };
+
+        match self
+            .api
+            .update_status(workspace_id, status_id, None, Patch::Absent, Some(!is_done), Patch::Absent)
+            .await
+        {
+            Ok(status) => self.replace_column_status(status),
+            Err(e) => self.set_error(format!("Failed to update column: {}", e)),
+        }
+    }
+
+    /// Swap the selected column with its neighbour; `offset` is -1 for left, 1 for right
+    async fn do_move_column(&mut self, offset: isize) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+
+        let from = self.column_management_cursor;
+        let to = match from.checked_add_signed(offset) {
+            Some(to) if to < self.columns.len() => to,
+            _ => return,
+        };
+
+        let mut status_ids: Vec<uuid::Uuid> = self.columns.iter().map(|c| c.status.id).collect();
+        status_ids.swap(from, to);
+
+        match self.api.reorder_statuses(workspace_id, status_ids).await {
+            Ok(_) => {
+                self.columns.swap(from, to);
+                self.column_scroll_offsets.swap(from, to);
+                self.column_management_cursor = to;
+                // Keep the board selection on the same column
+                if self.selected_column == from {
+                    self.selected_column = to;
+                } else if self.selected_column == to {
+                    self.selected_column = from;
+                }
+            }
+            Err(e) => {
+                self.set_error(format!("Failed to reorder columns: {}", e));
+            }
+        }
+    }
+
+    async fn do_delete_column(&mut self) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+
+        let status_id = match self.columns.get(self.column_management_cursor) {
+            Some(column) => column.status.id,
+            None => return,
+        };
+        let move_to = self
+            .columns
+            .get(self.column_delete_target)
+            .map(|c| c.status.id)
+            .filter(|id| *id != status_id);
+
+        self.set_loading(true, "Deleting column...");
+        let result = self.api.delete_status(workspace_id, status_id, move_to).await;
+        self.set_loading(false, "");
+
+        match result {
+            Ok(()) => {
+                self.column_management_mode = ColumnManagementMode::List;
+                if self.collapsed_columns.remove(&status_id) {
+                    self.save_preferences();
+                }
+
+                // Tasks changed columns and the workflow lost the column's transitions
+                self.workspace_transitions = self.api.list_transitions(workspace_id).await.unwrap_or_default();
+                self.reload_workspace_data().await;
+
+                self.column_management_cursor = self
+                    .column_management_cursor
+                    .min(self.columns.len().saturating_sub(1));
+            }
+            Err(e) => {
+                self.column_management_mode = ColumnManagementMode::List;
+                self.set_error(format!("Failed to delete column: {}", e));
+            }
+        }
+    }
+
+    /// Open the filter panel and populate it with current filter state
+    async fn open_filter_panel(&mut self) {
+        // Load workspace members for assignee selection
+        if let Some(ref workspace) = self.current_workspace {
+            if let Ok(members) = self.api.list_members(workspace.id).await {
+                self.workspace_members = members;
+            }
+        }
+
+        self.filter_panel_visible = true;
+        self.filter_panel_section = FilterPanelSection::Priority;
+
+        // Initialize from current filters
+        self.filter_priority_cursor = match self.active_filters.priority {
+            None => 0,
+            Some(Priority::Highest) => 1,
+            Some(Priority::High) => 2,
+            Some(Priority::Medium) => 3,
+            Some(Priority::Low) => 4,
+            Some(Priority::Lowest) => 5,
+        };
+
+        // Initialize tag selection from current filters
+        self.filter_selected_tags = self.active_filters.tag_ids.clone().unwrap_or_default();
+        self.filter_tag_cursor = 0;
+
+        // Initialize assignee
+        self.filter_assignee_cursor = if let Some(assigned_id) = self.active_filters.assigned_to {
+            self.workspace_members
+                .iter()
+                .position(|m| m.user_id == assigned_id)
+                .map(|i| i + 1)
+                .unwrap_or(0)
+        } else {
+            0
+        };
+
+        // Initialize due date
+        if let Some(date) = self.active_filters.due_before {
+            self.filter_due_mode = DueDateMode::Before;
+            self.filter_due_input = date.to_string();
+        } else if let Some(date) = self.active_filters.due_after {
+            self.filter_due_mode = DueDateMode::After;
+            self.filter_due_input = date.to_string();
+        } else {
+            self.filter_due_mode = DueDateMode::Before;
+            self.filter_due_input.clear();
+        }
+
+        // Initialize custom field filters
+        let custom_field_inputs: Vec<String> = self
+            .workspace_custom_fields
+            .iter()
+            .map(|field| {
+                let value = self.active_filters.custom_fields
+                    .as_ref()
+                    .and_then(|filters| filters.get(&field.id))
+                    .cloned()
+                    .unwrap_or_default();
+                if field.field_type == CustomFieldType::User {
+                    self.custom_field_display(field, &serde_json::Value::String(value))
+                } else {
+                    value
+                }
+            })
+            .collect();
+        self.filter_custom_field_inputs = custom_field_inputs;
+        self.filter_custom_field_cursor = 0;
+
+        // Initialize order by
+        self.filter_order_cursor = self.active_filters.order_by
+            .as_ref()
+            .and_then(|field| SORT_FIELDS.iter().position(|(f, _)| f == field))
+            .unwrap_or(0);
+        self.filter_order_desc = self.active_filters.order
+            .as_ref()
+            .map(|o| o == "DESC")
+            .unwrap_or(false);
+    }
+
+    async fn handle_filter_panel_key(&mut self, key: KeyEvent) -> Result<bool> {
+        // Handle insert mode for date and custom field input
+        if self.vim_mode == VimMode::Insert
+            && matches!(self.filter_panel_section, FilterPanelSection::DueDate | FilterPanelSection::CustomFields)
+        {
+            match key.code {
+                KeyCode::Esc => {
+                    self.vim_mode = VimMode::Normal;
+                }
+                KeyCode::Enter => {
+                    self.vim_mode = VimMode::Normal;
+                }
+                // Allow navigation keys to exit insert mode and navigate
+                KeyCode::Tab => {
                     self.vim_mode = VimMode::Normal;
                     self.filter_panel_section = self.filter_panel_section.next();
                 }
@@ -1823,6 +2757,16 @@ impl App {
                     self.vim_mode = VimMode::Normal;
                     self.filter_panel_section = self.filter_panel_section.prev();
                 }
+                KeyCode::Char(c) if self.filter_panel_section == FilterPanelSection::CustomFields => {
+                    if let Some(input) = self.filter_custom_field_inputs.get_mut(self.filter_custom_field_cursor) {
+                        input.push(c);
+                    }
+                }
+                KeyCode::Backspace if self.filter_panel_section == FilterPanelSection::CustomFields => {
+                    if let Some(input) = self.filter_custom_field_inputs.get_mut(self.filter_custom_field_cursor) {
+                        input.pop();
+                    }
+                }
                 KeyCode::Char(c) => {
                     // Only allow date characters
                     if c.is_ascii_digit() || c == '-' {
@@ -1873,8 +2817,11 @@ impl App {
                 }
             }
             KeyCode::Char('i') => {
-                // Enter insert mode for date
-                if self.filter_panel_section == FilterPanelSection::DueDate {
+                // Enter insert mode for date or custom field value
+                if self.filter_panel_section == FilterPanelSection::DueDate
+                    || (self.filter_panel_section == FilterPanelSection::CustomFields
+                        && !self.workspace_custom_fields.is_empty())
+                {
                     self.vim_mode = VimMode::Insert;
                 }
             }
@@ -1889,6 +2836,7 @@ impl App {
                 self.filter_selected_tags.clear();
                 self.filter_assignee_cursor = 0;
                 self.filter_due_input.clear();
+                self.filter_custom_field_inputs.iter_mut().for_each(|input| input.clear());
                 self.filter_order_cursor = 0;
                 self.filter_order_desc = false;
             }
@@ -1922,6 +2870,12 @@ impl App {
             FilterPanelSection::DueDate => {
                 self.filter_due_mode = self.filter_due_mode.toggle();
             }
+            FilterPanelSection::CustomFields => {
+                if !self.workspace_custom_fields.is_empty() {
+                    self.filter_custom_field_cursor =
+                        (self.filter_custom_field_cursor + 1) % self.workspace_custom_fields.len();
+                }
+            }
             FilterPanelSection::OrderBy => {
                 self.filter_order_cursor = (self.filter_order_cursor + 1) % SORT_FIELDS.len();
             }
@@ -1954,6 +2908,13 @@ impl App {
             FilterPanelSection::DueDate => {
                 self.filter_due_mode = self.filter_due_mode.toggle();
             }
+            FilterPanelSection::CustomFields => {
+                if !self.workspace_custom_fields.is_empty() {
+                    self.filter_custom_field_cursor = self.filter_custom_field_cursor
+                        .checked_sub(1)
+                        .unwrap_or(self.workspace_custom_fields.len() - 1);
+                }
+            }
             FilterPanelSection::OrderBy => {
                 self.filter_order_cursor = self.filter_order_cursor
                     .checked_sub(1)
@@ -2005,6 +2966,24 @@ impl App {
             }
         }
 
+        // Custom fields (user fields are sent as member ids)
+        let mut custom_field_filters = std::collections::BTreeMap::new();
+        for (field, input) in self.workspace_custom_fields.iter().zip(&self.filter_custom_field_inputs) {
+            if input.trim().is_empty() {
+                continue;
+            }
+            let value = match (field.field_type, self.parse_custom_field_input(field, input)) {
+                (CustomFieldType::User, Ok(serde_json::Value::String(id))) => id,
+                _ => input.trim().to_string(),
+            };
+            custom_field_filters.insert(field.id, value);
+        }
+        self.active_filters.custom_fields = if custom_field_filters.is_empty() {
+            None
+        } else {
+            Some(custom_field_filters)
+        };
+
         // Order by
         if let Some((field, _)) = SORT_FIELDS.get(self.filter_order_cursor) {
             self.active_filters.order_by = Some(field.to_string());
@@ -2017,6 +2996,7 @@ impl App {
             || self.active_filters.due_before.is_some()
             || self.active_filters.due_after.is_some()
             || self.active_filters.tag_ids.is_some()
+            || self.active_filters.custom_fields.is_some()
             || self.active_filters.order_by.is_some();
 
         // Reload data with new filters
@@ -2088,7 +3068,7 @@ impl App {
                     if self.preset_list_cursor >= self.filter_presets.len() && !self.filter_presets.is_empty() {
                         self.preset_list_cursor = self.filter_presets.len() - 1;
                     }
-                    self.save_presets();
+                    self.save_preferences();
                 }
             }
             _ => {}
@@ -2103,16 +3083,20 @@ impl App {
         };
         self.filter_presets.push(preset);
         self.new_preset_name.clear();
-        self.save_presets();
+        self.save_preferences();
     }
 
-    fn save_presets(&self) {
-        let prefs = UserPreferences {
+    fn preferences(&self) -> UserPreferences {
+        UserPreferences {
             filter_presets: self.filter_presets.clone(),
-        };
-        if let Err(e) = prefs.save() {
+            collapsed_columns: self.collapsed_columns.iter().copied().collect(),
+        }
+    }
+
+    fn save_preferences(&self) {
+        if let Err(e) = self.preferences().save() {
             // Log error but don't fail
-            eprintln!("Failed to save presets: {}", e);
+            eprintln!("Failed to save preferences: {}", e);
         }
     }
 
@@ -2132,6 +3116,13 @@ impl App {
                 Ok(())
             }
             "preset" => self.parse_preset_command(&parts[1..]).await,
+            "priority" | "due" | "assign" | "tag" | "untag" => {
+                self.parse_bulk_command(parts[0], &parts[1..]).await
+            }
+            "open" => match parts.get(1) {
+                Some(key) => self.open_task_by_key(key).await,
+                None => Err("Usage: open <KEY>, e.g. open TODO-123".to_string()),
+            },
             "q" | "quit" => {
                 // This will be handled specially - return error to signal quit
                 Err("__QUIT__".to_string())
@@ -2140,20 +3131,34 @@ impl App {
         }
     }
 
+    /// Jump to a task by its human-readable key and open its detail view
+    async fn open_task_by_key(&mut self, key: &str) -> Result<(), String> {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return Err("No workspace selected".to_string()),
+        };
+
+        let task = self
+            .api
+            .get_task_by_key(workspace_id, key)
+            .await
+            .map_err(|e| format!("Task {} not found: {}", key, e))?;
+
+        self.select_task_by_id(task.id);
+        if self.get_selected_task().map(|t| t.id) != Some(task.id) {
+            return Err(format!("{} is hidden by the active filters", task.key));
+        }
+
+        self.open_task_detail().await;
+        Ok(())
+    }
+
     async fn parse_filter_command(&mut self, args: &[&str]) -> Result<(), String> {
         for arg in args {
             if let Some((key, value)) = arg.split_once('=') {
                 match key {
                     "priority" => {
-                        self.active_filters.priority = match value.to_lowercase().as_str() {
-                            "highest" => Some(Priority::Highest),
-                            "high" => Some(Priority::High),
-                            "medium" => Some(Priority::Medium),
-                            "low" => Some(Priority::Low),
-                            "lowest" => Some(Priority::Lowest),
-                            "none" => None,
-                            _ => return Err(format!("Invalid priority: {}", value)),
-                        };
+                        self.active_filters.priority = parse_priority(value)?;
                     }
                     "assigned" | "assignee" => {
                         if value == "me" {
@@ -2198,6 +3203,83 @@ impl App {
         Ok(())
     }
 
+    /// Bulk edits of the tasks marked in visual mode, e.g. `tag urgent` or `due none`
+    async fn parse_bulk_command(&mut self, command: &str, args: &[&str]) -> Result<(), String> {
+        if self.selected_task_ids.is_empty() {
+            return Err("No tasks selected; press v on the board to select some".to_string());
+        }
+
+        let operation = match command {
+            "priority" => {
+                let value = args
+                    .first()
+                    .ok_or_else(|| "Usage: priority <highest|high|medium|low|lowest|none>".to_string())?;
+                BulkTaskOperation::SetPriority { priority: parse_priority(value)? }
+            }
+            "due" => {
+                let value = args.first().ok_or_else(|| "Usage: due <YYYY-MM-DD|none>".to_string())?;
+                let due_date = if *value == "none" {
+                    None
+                } else {
+                    Some(
+                        value
+                            .parse::<NaiveDate>()
+                            .map_err(|_| format!("Invalid date format: {}", value))?,
+                    )
+                };
+                BulkTaskOperation::SetDueDate { due_date }
+            }
+            "assign" => {
+                if args.is_empty() {
+                    return Err("Usage: assign <me|name>...".to_string());
+                }
+                if self.workspace_members.is_empty() {
+                    if let Some(ref workspace) = self.current_workspace {
+                        if let Ok(members) = self.api.list_members(workspace.id).await {
+                            self.workspace_members = members;
+                        }
+                    }
+                }
+
+                let mut user_ids = Vec::new();
+                for value in args {
+                    let user_id = if *value == "me" {
+                        self.user.as_ref().map(|u| u.id)
+                    } else {
+                        self.workspace_members
+                            .iter()
+                            .find(|m| m.display_name.to_lowercase().contains(&value.to_lowercase()))
+                            .map(|m| m.user_id)
+                    };
+                    user_ids.push(user_id.ok_or_else(|| format!("Member not found: {}", value))?);
+                }
+                BulkTaskOperation::Assign { user_ids }
+            }
+            _ => {
+                if args.is_empty() {
+                    return Err(format!("Usage: {} <tag>...", command));
+                }
+
+                let mut tag_ids = Vec::new();
+                for value in args {
+                    let tag = self
+                        .workspace_tags
+                        .iter()
+                        .find(|t| t.name.eq_ignore_ascii_case(value))
+                        .ok_or_else(|| format!("Tag not found: {}", value))?;
+                    tag_ids.push(tag.id);
+                }
+                if command == "tag" {
+                    BulkTaskOperation::AddTags { tag_ids }
+                } else {
+                    BulkTaskOperation::RemoveTags { tag_ids }
+                }
+            }
+        };
+
+        self.do_bulk_operation(operation).await
+    }
+
     fn parse_sort_command(&mut self, args: &[&str]) -> Result<(), String> {
         if args.is_empty() {
             return Err("Usage: sort <field> or sort -<field> (descending)".to_string());
@@ -2239,10 +3321,7 @@ impl App {
                 self.filter_presets.push(preset);
 
                 // Save to disk
-                let prefs = UserPreferences {
-                    filter_presets: self.filter_presets.clone(),
-                };
-                if let Err(e) = prefs.save() {
+                if let Err(e) = self.preferences().save() {
                     return Err(format!("Failed to save preferences: {}", e));
                 }
                 Ok(())
@@ -2303,6 +3382,7 @@ impl App {
             || self.active_filters.due_after.is_some()
             || self.active_filters.q.is_some()
             || self.active_filters.tag_ids.is_some()
+            || self.active_filters.custom_fields.is_some()
             || self.active_filters.order_by.is_some()
     }
 
@@ -2338,30 +3418,131 @@ impl App {
             return self.handle_unlink_document_key(key).await;
         }
 
-        // Handle comment input mode with TextArea
-        if self.adding_comment {
-            if let Some(ref mut textarea) = self.comment_textarea {
-                match key.code {
-                    KeyCode::Esc => {
-                        self.adding_comment = false;
-                        self.comment_textarea = None;
-                        self.vim_mode = VimMode::Normal;
-                    }
-                    KeyCode::Enter if key.modifiers.contains(KeyModifiers::ALT) => {
-                        // Alt+Enter to submit
-                        let content = self.get_comment_content();
-                        if !content.is_empty() {
-                            self.do_add_comment().await;
-                        }
-                    }
-                    KeyCode::Char('e') | KeyCode::Char('E') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                        // Ctrl+E: external editor
-                        let content = self.get_comment_content();
-                        match editor::launch_external_editor(&content, ".md") {
-                            Ok(edited) => {
-                                self.needs_terminal_clear = true;
-                                self.comment_textarea =
-                                    Some(editor::create_textarea(&edited, EditorContext::Comment));
+        if self.reaction_picker_visible {
+            return self.handle_reaction_picker_key(key).await;
+        }
+
+        if self.comment_confirming_delete {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    self.comment_confirming_delete = false;
+                    self.do_delete_comment().await;
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    self.comment_confirming_delete = false;
+                }
+                _ => {}
+            }
+            return Ok(false);
+        }
+
+        if self.comment_revisions_visible {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('v') => {
+                    self.comment_revisions_visible = false;
+                    self.comment_revisions.clear();
+                }
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.comment_revisions_scroll = self.comment_revisions_scroll.saturating_add(1);
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.comment_revisions_scroll = self.comment_revisions_scroll.saturating_sub(1);
+                }
+                _ => {}
+            }
+            return Ok(false);
+        }
+
+        // Handle blocker picker modes
+        if self.adding_blocker_mode {
+            return self.handle_add_blocker_key(key).await;
+        }
+        if self.removing_blocker_mode {
+            return self.handle_remove_blocker_key(key).await;
+        }
+
+        // Handle checklist mode
+        if self.checklist_mode {
+            return self.handle_checklist_key(key).await;
+        }
+
+        // Handle attachment picker
+        if self.attachment_mode {
+            return self.handle_attachment_key(key).await;
+        }
+
+        // Handle subtask title input
+        if self.creating_subtask {
+            match key.code {
+                KeyCode::Esc => {
+                    self.creating_subtask = false;
+                    self.new_subtask_title.clear();
+                    self.vim_mode = VimMode::Normal;
+                }
+                KeyCode::Enter if !self.new_subtask_title.is_empty() => {
+                    self.do_create_subtask().await;
+                }
+                KeyCode::Char(c) => {
+                    self.new_subtask_title.push(c);
+                }
+                KeyCode::Backspace => {
+                    self.new_subtask_title.pop();
+                }
+                _ => {}
+            }
+            return Ok(false);
+        }
+
+        // Handle comment input mode with TextArea
+        if self.adding_comment {
+            let completion = self.mention_completion();
+            if let Some(ref mut textarea) = self.comment_textarea {
+                match key.code {
+                    KeyCode::Tab if completion.is_some() => {
+                        // Complete the @name being typed
+                        if let Some(completion) = completion {
+                            let idx = self.mention_completion_idx % completion.usernames.len();
+                            for _ in 0..completion.typed {
+                                textarea.delete_char();
+                            }
+                            textarea.insert_str(format!("{} ", completion.usernames[idx]));
+                        }
+                        self.mention_completion_idx = 0;
+                    }
+                    KeyCode::Down if completion.is_some() => {
+                        self.mention_completion_idx += 1;
+                    }
+                    KeyCode::Up if completion.is_some() => {
+                        let count = completion.as_ref().map_or(1, |c| c.usernames.len());
+                        self.mention_completion_idx =
+                            (self.mention_completion_idx % count + count - 1) % count;
+                    }
+                    KeyCode::Esc => {
+                        self.adding_comment = false;
+                        self.comment_textarea = None;
+                        self.replying_to = None;
+                        self.editing_comment = None;
+                        self.vim_mode = VimMode::Normal;
+                    }
+                    KeyCode::Enter if key.modifiers.contains(KeyModifiers::ALT) => {
+                        // Alt+Enter to submit
+                        let content = self.get_comment_content();
+                        if !content.is_empty() {
+                            if self.editing_comment.is_some() {
+                                self.do_update_comment().await;
+                            } else {
+                                self.do_add_comment().await;
+                            }
+                        }
+                    }
+                    KeyCode::Char('e') | KeyCode::Char('E') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        // Ctrl+E: external editor
+                        let content = self.get_comment_content();
+                        match editor::launch_external_editor(&content, ".md") {
+                            Ok(edited) => {
+                                self.needs_terminal_clear = true;
+                                self.comment_textarea =
+                                    Some(editor::create_textarea(&edited, EditorContext::Comment));
                             }
                             Err(e) => {
                                 self.needs_terminal_clear = true;
@@ -2372,6 +3553,7 @@ impl App {
                     _ => {
                         // Pass to textarea for normal input handling
                         textarea.input(key);
+                        self.mention_completion_idx = 0;
                     }
                 }
                 return Ok(false);
@@ -2383,19 +3565,102 @@ impl App {
                 self.close_task_detail();
             }
             KeyCode::Char('a') => {
-                // Add comment
+                // Add comment, back in the comments panel
+                self.show_task_history = false;
+                self.adding_comment = true;
+                self.replying_to = None;
+                self.editing_comment = None;
+                self.init_comment_textarea();
+                self.vim_mode = VimMode::Insert;
+            }
+            KeyCode::Char('r') if !self.show_task_history && self.selected_comment().is_some() => {
+                // Reply in the selected comment's thread
                 self.adding_comment = true;
+                self.replying_to = self.selected_comment().map(|c| c.id);
+                self.editing_comment = None;
                 self.init_comment_textarea();
                 self.vim_mode = VimMode::Insert;
             }
+            KeyCode::Char('+') if !self.show_task_history && self.selected_comment().is_some() => {
+                self.reaction_picker_visible = true;
+                self.reaction_cursor = 0;
+            }
+            KeyCode::Char('E') if !self.show_task_history && self.selected_comment().is_some() => {
+                // Rewrite the selected comment
+                match self.selected_comment() {
+                    Some(c) if self.can_modify_comment(c) => {
+                        let (id, content) = (c.id, c.content.clone());
+                        self.adding_comment = true;
+                        self.replying_to = None;
+                        self.editing_comment = Some(id);
+                        self.comment_textarea =
+                            Some(editor::create_textarea(&content, EditorContext::Comment));
+                        self.vim_mode = VimMode::Insert;
+                    }
+                    _ => self.set_error("Only the author or an admin can edit this comment".to_string()),
+                }
+            }
+            KeyCode::Char('D') if !self.show_task_history && self.selected_comment().is_some() => {
+                match self.selected_comment() {
+                    Some(c) if self.can_modify_comment(c) => self.comment_confirming_delete = true,
+                    _ => self.set_error("Only the author or an admin can delete this comment".to_string()),
+                }
+            }
+            KeyCode::Char('v') if !self.show_task_history && self.selected_comment().is_some_and(|c| c.edited) => {
+                // Earlier versions of the selected comment
+                self.open_comment_revisions().await;
+            }
             KeyCode::Char('e') => {
                 // Enter edit mode
                 self.enter_edit_mode();
             }
+            KeyCode::Char('s') => {
+                // Add subtask
+                self.creating_subtask = true;
+                self.new_subtask_title.clear();
+                self.vim_mode = VimMode::Insert;
+            }
+            KeyCode::Char('b') => {
+                // Add blocker
+                if self.available_blockers().is_empty() {
+                    self.set_error("No tasks available to add as blocker".to_string());
+                } else {
+                    self.adding_blocker_mode = true;
+                    self.blocker_cursor = 0;
+                }
+            }
+            KeyCode::Char('B') if !self.task_dependencies.blocked_by.is_empty() => {
+                // Remove blocker
+                self.removing_blocker_mode = true;
+                self.blocker_cursor = 0;
+            }
+            KeyCode::Char('t') => {
+                // Start/stop work timer
+                self.toggle_work_timer().await;
+            }
+            KeyCode::Char('c') => {
+                // Work through the checklist, starting a new item if it's empty
+                self.checklist_mode = true;
+                self.checklist_cursor = 0;
+                if self.task_checklist.is_empty() {
+                    self.adding_checklist_item = true;
+                    self.checklist_input.clear();
+                    self.vim_mode = VimMode::Insert;
+                }
+            }
+            KeyCode::Char('f') if !self.task_attachments.is_empty() => {
+                // Pick an attachment to open or download
+                self.attachment_mode = true;
+                self.attachment_cursor = 0;
+            }
             KeyCode::Char('L') => {
                 // Link document to task
                 self.open_link_document_picker().await;
             }
+            KeyCode::Char('H') => {
+                // Swap the comments panel for the task's history
+                self.toggle_task_history().await;
+            }
             KeyCode::Char('U') => {
                 // Unlink document from task
                 if !self.task_linked_documents.is_empty() {
@@ -2403,11 +3668,11 @@ impl App {
                     self.unlink_document_cursor = 0;
                 }
             }
-            KeyCode::Char('j') | KeyCode::Down => {
-                // Scroll comments down (future enhancement)
+            KeyCode::Char('j') | KeyCode::Down if self.comment_cursor + 1 < self.comment_rows().len() => {
+                self.comment_cursor += 1;
             }
             KeyCode::Char('k') | KeyCode::Up => {
-                // Scroll comments up (future enhancement)
+                self.comment_cursor = self.comment_cursor.saturating_sub(1);
             }
             _ => {}
         }
@@ -2516,1756 +3781,4100 @@ impl App {
         }
     }
 
-    async fn handle_unlink_document_key(&mut self, key: KeyEvent) -> Result<bool> {
+    /// Tasks on the board that could be added as blockers of the open task
+    pub fn available_blockers(&self) -> Vec<&Task> {
+        let task_id = match self.selected_task_detail {
+            Some(ref t) => t.id,
+            None => return Vec::new(),
+        };
+        let existing: HashSet<_> = self.task_dependencies
+            .blocked_by
+            .iter()
+            .map(|d| d.task_id)
+            .collect();
+
+        self.get_all_tasks()
+            .into_iter()
+            .filter(|t| t.id != task_id && !existing.contains(&t.id))
+            .collect()
+    }
+
+    async fn handle_add_blocker_key(&mut self, key: KeyEvent) -> Result<bool> {
+        let available: Vec<uuid::Uuid> = self.available_blockers().iter().map(|t| t.id).collect();
+
         match key.code {
             KeyCode::Esc | KeyCode::Char('q') => {
-                self.unlinking_document_mode = false;
-                self.unlink_document_cursor = 0;
+                self.adding_blocker_mode = false;
+                self.blocker_cursor = 0;
             }
             KeyCode::Char('j') | KeyCode::Down => {
-                if self.unlink_document_cursor < self.task_linked_documents.len().saturating_sub(1) {
-                    self.unlink_document_cursor += 1;
-                }
+                self.blocker_cursor = (self.blocker_cursor + 1).min(available.len().saturating_sub(1));
             }
             KeyCode::Char('k') | KeyCode::Up => {
-                if self.unlink_document_cursor > 0 {
-                    self.unlink_document_cursor -= 1;
-                }
+                self.blocker_cursor = self.blocker_cursor.saturating_sub(1);
             }
             KeyCode::Enter => {
-                if let Some(doc) = self.task_linked_documents.get(self.unlink_document_cursor) {
-                    let doc_id = doc.document_id;
-                    self.do_unlink_document(doc_id).await;
+                if let Some(&blocking_task_id) = available.get(self.blocker_cursor) {
+                    self.do_add_blocker(blocking_task_id).await;
                 }
-                self.unlinking_document_mode = false;
-                self.unlink_document_cursor = 0;
+                self.adding_blocker_mode = false;
+                self.blocker_cursor = 0;
             }
             _ => {}
         }
         Ok(false)
     }
 
-    async fn do_unlink_document(&mut self, doc_id: uuid::Uuid) {
-        let workspace_id = match self.current_workspace {
-            Some(ref ws) => ws.id,
-            None => return,
-        };
-        let task_id = match self.selected_task_detail {
-            Some(ref t) => t.id,
-            None => return,
-        };
-
-        match self.api.unlink_task_from_document(workspace_id, doc_id, task_id).await {
-            Ok(_) => {
-                // Remove from local list
-                self.task_linked_documents.retain(|d| d.document_id != doc_id);
+    async fn handle_remove_blocker_key(&mut self, key: KeyEvent) -> Result<bool> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.removing_blocker_mode = false;
+                self.blocker_cursor = 0;
             }
-            Err(e) => {
-                self.set_error(format!("Failed to unlink document: {}", e));
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.blocker_cursor = (self.blocker_cursor + 1)
+                    .min(self.task_dependencies.blocked_by.len().saturating_sub(1));
             }
-        }
-    }
-
-    async fn handle_edit_task_key(&mut self, key: KeyEvent) -> Result<bool> {
-        // Insert mode - editing current field
-        if self.vim_mode == VimMode::Insert {
-            // Special handling for description field with TextArea
-            if self.edit_field == TaskEditField::Description {
-                if let Some(ref mut textarea) = self.edit_task_description_textarea {
-                    match key.code {
-                        KeyCode::Esc => {
-                            self.vim_mode = VimMode::Normal;
-                        }
-                        KeyCode::Char('e') | KeyCode::Char('E') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                            // Ctrl+E: external editor
-                            let content = self.get_edit_task_description();
-                            match editor::launch_external_editor(&content, ".md") {
-                                Ok(edited) => {
-                                    self.needs_terminal_clear = true;
-                                    self.edit_task_description_textarea = Some(
-                                        editor::create_textarea(&edited, EditorContext::TaskDescription),
-                                    );
-                                }
-                                Err(e) => {
-                                    self.needs_terminal_clear = true;
-                                    self.set_error(format!("Editor failed: {}", e));
-                                }
-                            }
-                        }
-                        _ => {
-                            // Pass to textarea for normal input handling
-                            textarea.input(key);
-                        }
-                    }
-                    return Ok(false);
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.blocker_cursor = self.blocker_cursor.saturating_sub(1);
+            }
+            KeyCode::Enter => {
+                if let Some(dep) = self.task_dependencies.blocked_by.get(self.blocker_cursor) {
+                    let blocking_task_id = dep.task_id;
+                    self.do_remove_blocker(blocking_task_id).await;
                 }
+                self.removing_blocker_mode = false;
+                self.blocker_cursor = 0;
             }
+            _ => {}
+        }
+        Ok(false)
+    }
 
-            // Other fields use simple string handling
+    async fn handle_checklist_key(&mut self, key: KeyEvent) -> Result<bool> {
+        // Handle new item input
+        if self.adding_checklist_item {
             match key.code {
                 KeyCode::Esc => {
+                    self.adding_checklist_item = false;
+                    self.checklist_input.clear();
                     self.vim_mode = VimMode::Normal;
+                    if self.task_checklist.is_empty() {
+                        self.checklist_mode = false;
+                    }
                 }
-                KeyCode::Enter => {
-                    // Save and exit insert mode
-                    self.vim_mode = VimMode::Normal;
+                KeyCode::Enter if !self.checklist_input.trim().is_empty() => {
+                    // Stay in input so several items can be added in a row
+                    self.do_add_checklist_item().await;
                 }
                 KeyCode::Char(c) => {
-                    match self.edit_field {
-                        TaskEditField::Title => self.edit_task_title.push(c),
-                        TaskEditField::Description => {} // Handled above with TextArea
-                        TaskEditField::DueDate => self.edit_task_due_date_str.push(c),
-                        TaskEditField::TimeEstimate => self.edit_task_time_estimate_str.push(c),
-                        TaskEditField::Priority | TaskEditField::Assignee | TaskEditField::Tags => {}
-                    }
+                    self.checklist_input.push(c);
                 }
                 KeyCode::Backspace => {
-                    match self.edit_field {
-                        TaskEditField::Title => { self.edit_task_title.pop(); }
-                        TaskEditField::Description => {} // Handled above with TextArea
-                        TaskEditField::DueDate => { self.edit_task_due_date_str.pop(); }
-                        TaskEditField::TimeEstimate => { self.edit_task_time_estimate_str.pop(); }
-                        TaskEditField::Priority | TaskEditField::Assignee | TaskEditField::Tags => {}
-                    }
+                    self.checklist_input.pop();
                 }
                 _ => {}
             }
             return Ok(false);
         }
 
-        // Normal mode in edit
+        let len = self.task_checklist.len();
         match key.code {
-            KeyCode::Char('q') | KeyCode::Esc => {
-                // Cancel edit mode
-                self.editing_task = false;
-                self.vim_mode = VimMode::Normal;
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.checklist_mode = false;
+                self.checklist_cursor = 0;
             }
-            KeyCode::Char('i') => {
-                // Enter insert mode for current field (except Priority, Assignee, Tags)
-                if self.edit_field != TaskEditField::Priority
-                    && self.edit_field != TaskEditField::Assignee
-                    && self.edit_field != TaskEditField::Tags {
-                    self.vim_mode = VimMode::Insert;
-                }
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.checklist_cursor = (self.checklist_cursor + 1).min(len.saturating_sub(1));
             }
-            KeyCode::Tab => {
-                // Next field
-                self.edit_field = match self.edit_field {
-                    TaskEditField::Title => TaskEditField::Description,
-                    TaskEditField::Description => TaskEditField::Priority,
-                    TaskEditField::Priority => TaskEditField::DueDate,
-                    TaskEditField::DueDate => TaskEditField::TimeEstimate,
-                    TaskEditField::TimeEstimate => TaskEditField::Assignee,
-                    TaskEditField::Assignee => TaskEditField::Tags,
-                    TaskEditField::Tags => TaskEditField::Title,
-                };
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.checklist_cursor = self.checklist_cursor.saturating_sub(1);
             }
-            KeyCode::BackTab => {
-                // Previous field
-                self.edit_field = match self.edit_field {
-                    TaskEditField::Title => TaskEditField::Tags,
-                    TaskEditField::Description => TaskEditField::Title,
-                    TaskEditField::Priority => TaskEditField::Description,
-                    TaskEditField::DueDate => TaskEditField::Priority,
-                    TaskEditField::TimeEstimate => TaskEditField::DueDate,
-                    TaskEditField::Assignee => TaskEditField::TimeEstimate,
-                    TaskEditField::Tags => TaskEditField::Assignee,
-                };
+            KeyCode::Char('g') => {
+                self.checklist_cursor = 0;
             }
-            KeyCode::Char('l') | KeyCode::Right if self.edit_field == TaskEditField::Tags => {
-                // Navigate to next tag
-                if !self.workspace_tags.is_empty() {
-                    self.tag_selector_cursor = (self.tag_selector_cursor + 1) % self.workspace_tags.len();
-                }
+            KeyCode::Char('G') => {
+                self.checklist_cursor = len.saturating_sub(1);
             }
-            KeyCode::Char('h') | KeyCode::Left if self.edit_field == TaskEditField::Tags => {
-                // Navigate to previous tag
-                if !self.workspace_tags.is_empty() {
-                    self.tag_selector_cursor = self.tag_selector_cursor
-                        .checked_sub(1)
-                        .unwrap_or(self.workspace_tags.len().saturating_sub(1));
-                }
+            KeyCode::Char(' ') | KeyCode::Char('x') | KeyCode::Enter if len > 0 => {
+                self.do_toggle_checklist_item().await;
             }
-            KeyCode::Char(' ') if self.edit_field == TaskEditField::Tags => {
-                // Toggle tag selection
-                if let Some(tag) = self.workspace_tags.get(self.tag_selector_cursor) {
-                    let tag_id = tag.id;
-                    if self.task_edit_selected_tags.contains(&tag_id) {
-                        self.task_edit_selected_tags.retain(|&id| id != tag_id);
-                    } else {
-                        self.task_edit_selected_tags.push(tag_id);
-                    }
-                }
+            KeyCode::Char('J') if self.checklist_cursor + 1 < len => {
+                // Move item down
+                self.do_move_checklist_item(self.checklist_cursor + 1).await;
             }
-            KeyCode::Char('j') | KeyCode::Down => {
-                // Next field (for non-Tags fields)
-                self.edit_field = match self.edit_field {
-                    TaskEditField::Title => TaskEditField::Description,
-                    TaskEditField::Description => TaskEditField::Priority,
-                    TaskEditField::Priority => TaskEditField::DueDate,
-                    TaskEditField::DueDate => TaskEditField::TimeEstimate,
-                    TaskEditField::TimeEstimate => TaskEditField::Assignee,
-                    TaskEditField::Assignee => TaskEditField::Tags,
-                    TaskEditField::Tags => TaskEditField::Title,
-                };
+            KeyCode::Char('K') if self.checklist_cursor > 0 => {
+                // Move item up
+                self.do_move_checklist_item(self.checklist_cursor - 1).await;
             }
-            KeyCode::Char('k') | KeyCode::Up => {
-                // Previous field (for non-Tags fields)
-                self.edit_field = match self.edit_field {
-                    TaskEditField::Title => TaskEditField::Tags,
-                    TaskEditField::Description => TaskEditField::Title,
-                    TaskEditField::Priority => TaskEditField::Description,
-                    TaskEditField::DueDate => TaskEditField::Priority,
-                    TaskEditField::TimeEstimate => TaskEditField::DueDate,
-                    TaskEditField::Assignee => TaskEditField::TimeEstimate,
-                    TaskEditField::Tags => TaskEditField::Assignee,
-                };
+            KeyCode::Char('o') | KeyCode::Char('a') => {
+                self.adding_checklist_item = true;
+                self.checklist_input.clear();
+                self.vim_mode = VimMode::Insert;
             }
-            KeyCode::Char('h') | KeyCode::Left if self.edit_field == TaskEditField::Priority => {
-                // Decrease priority
-                self.edit_task_priority = Some(match self.edit_task_priority {
-                    Some(Priority::Highest) => Priority::High,
-                    Some(Priority::High) => Priority::Medium,
-                    Some(Priority::Medium) => Priority::Low,
-                    Some(Priority::Low) => Priority::Lowest,
-                    Some(Priority::Lowest) | None => Priority::Lowest,
-                });
+            KeyCode::Char('d') if len > 0 => {
+                self.do_delete_checklist_item().await;
             }
-            KeyCode::Char('l') | KeyCode::Right if self.edit_field == TaskEditField::Priority => {
-                // Increase priority
-                self.edit_task_priority = Some(match self.edit_task_priority {
-                    Some(Priority::Lowest) => Priority::Low,
-                    Some(Priority::Low) => Priority::Medium,
-                    Some(Priority::Medium) => Priority::High,
-                    Some(Priority::High) => Priority::Highest,
-                    Some(Priority::Highest) | None => Priority::Highest,
-                });
+            _ => {}
+        }
+        Ok(false)
+    }
+
+    async fn do_add_checklist_item(&mut self) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+        let task_id = match self.selected_task_detail {
+            Some(ref t) => t.id,
+            None => return,
+        };
+
+        let content = self.checklist_input.trim().to_string();
+        match self.api.create_checklist_item(workspace_id, task_id, &content).await {
+            Ok(item) => {
+                self.task_checklist.push(item);
+                self.checklist_cursor = self.task_checklist.len() - 1;
+                self.checklist_input.clear();
+                self.apply_checklist_progress();
             }
-            KeyCode::Char('h') | KeyCode::Left if self.edit_field == TaskEditField::Assignee => {
-                // Previous assignee (or none)
-                if self.workspace_members.is_empty() {
-                    self.edit_task_assignee = None;
-                } else {
-                    let current_idx = self.edit_task_assignee
-                        .and_then(|id| self.workspace_members.iter().position(|m| m.user_id == id));
-                    self.edit_task_assignee = match current_idx {
-                        None => Some(self.workspace_members.last().unwrap().user_id),
-                        Some(0) => None, // Go to "none"
-                        Some(i) => Some(self.workspace_members[i - 1].user_id),
-                    };
-                }
+            Err(e) => {
+                self.set_error(format!("Failed to add checklist item: {}", e));
             }
-            KeyCode::Char('l') | KeyCode::Right if self.edit_field == TaskEditField::Assignee => {
-                // Next assignee
-                if self.workspace_members.is_empty() {
-                    self.edit_task_assignee = None;
-                } else {
-                    let current_idx = self.edit_task_assignee
-                        .and_then(|id| self.workspace_members.iter().position(|m| m.user_id == id));
-                    self.edit_task_assignee = match current_idx {
-                        None => Some(self.workspace_members[0].user_id),
-                        Some(i) if i + 1 >= self.workspace_members.len() => None, // Wrap to "none"
-                        Some(i) => Some(self.workspace_members[i + 1].user_id),
-                    };
+        }
+    }
+
+    async fn do_toggle_checklist_item(&mut self) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+        let (item_id, task_id, is_checked) = match self.task_checklist.get(self.checklist_cursor) {
+            Some(item) => (item.id, item.task_id, item.is_checked),
+            None => return,
+        };
+
+        let req = UpdateChecklistItemRequest {
+            content: None,
+            is_checked: Some(!is_checked),
+        };
+
+        match self.api.update_checklist_item(workspace_id, task_id, item_id, req).await {
+            Ok(updated) => {
+                if let Some(item) = self.task_checklist.iter_mut().find(|i| i.id == item_id) {
+                    *item = updated;
                 }
+                self.apply_checklist_progress();
             }
-            KeyCode::Enter => {
-                // Save changes
-                self.do_update_task().await;
+            Err(e) => {
+                self.set_error(format!("Failed to update checklist item: {}", e));
             }
-            _ => {}
         }
-
-        Ok(false)
     }
 
-    fn go_back_to_workspace_select(&mut self) {
-        self.current_workspace = None;
-        self.columns.clear();
-        self.selected_column = 0;
-        self.selected_task = 0;
-        self.view = View::WorkspaceSelect;
-    }
+    /// Swap the item under the cursor with the one at `target` and save the new order
+    async fn do_move_checklist_item(&mut self, target: usize) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+        let task_id = match self.selected_task_detail {
+            Some(ref t) => t.id,
+            None => return,
+        };
 
-    /// Open workspace modal and refresh the workspace list
-    async fn open_workspace_modal(&mut self) {
-        // Refresh workspace list before showing modal
-        self.load_workspaces().await;
+        self.task_checklist.swap(self.checklist_cursor, target);
+        self.checklist_cursor = target;
 
-        // Find current workspace in the list
-        if let Some(ref current) = self.current_workspace {
-            if let Some(idx) = self.workspaces.iter().position(|w| w.workspace.id == current.id) {
-                self.selected_workspace_idx = idx;
+        let item_ids: Vec<uuid::Uuid> = self.task_checklist.iter().map(|i| i.id).collect();
+        match self.api.reorder_checklist(workspace_id, task_id, item_ids).await {
+            Ok(items) => {
+                self.task_checklist = items;
+            }
+            Err(e) => {
+                self.set_error(format!("Failed to reorder checklist: {}", e));
+                // Reload to get back in sync with the server
+                if let Ok(items) = self.api.list_checklist(workspace_id, task_id).await {
+                    self.task_checklist = items;
+                }
             }
         }
-
-        self.workspace_modal_visible = true;
-        self.creating_workspace = false;
-        self.new_workspace_name.clear();
     }
 
-    /// Handle key events in the workspace modal
-    async fn handle_workspace_modal_key(
-        &mut self,
-        key: KeyEvent,
-        tx: mpsc::Sender<AppEvent>,
-    ) -> Result<bool> {
-        if self.loading {
-            return Ok(false);
+    async fn do_delete_checklist_item(&mut self) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+        let (item_id, task_id) = match self.task_checklist.get(self.checklist_cursor) {
+            Some(item) => (item.id, item.task_id),
+            None => return,
+        };
+
+        match self.api.delete_checklist_item(workspace_id, task_id, item_id).await {
+            Ok(()) => {
+                self.task_checklist.retain(|i| i.id != item_id);
+                self.checklist_cursor = self
+                    .checklist_cursor
+                    .min(self.task_checklist.len().saturating_sub(1));
+                self.apply_checklist_progress();
+            }
+            Err(e) => {
+                self.set_error(format!("Failed to delete checklist item: {}", e));
+            }
         }
+    }
 
-        // Handle workspace creation mode
-        if self.creating_workspace {
-            match key.code {
-                KeyCode::Esc => {
-                    self.creating_workspace = false;
-                    self.new_workspace_name.clear();
-                    self.vim_mode = VimMode::Normal;
-                }
-                KeyCode::Enter => {
-                    if !self.new_workspace_name.is_empty() {
-                        self.do_create_workspace_modal().await;
+    /// Recompute checklist progress of the open task and mirror it on the board
+    fn apply_checklist_progress(&mut self) {
+        let progress = if self.task_checklist.is_empty() {
+            None
+        } else {
+            Some(ChecklistProgress {
+                checked: self.task_checklist.iter().filter(|i| i.is_checked).count() as i64,
+                total: self.task_checklist.len() as i64,
+            })
+        };
+
+        if let Some(ref mut detail) = self.selected_task_detail {
+            detail.checklist_progress = progress;
+            let task_id = detail.id;
+            for col in &mut self.columns {
+                for task in &mut col.tasks {
+                    if task.id == task_id {
+                        task.checklist_progress = progress;
                     }
                 }
-                KeyCode::Char(c) => {
-                    self.new_workspace_name.push(c);
-                }
-                KeyCode::Backspace => {
-                    self.new_workspace_name.pop();
-                }
-                _ => {}
             }
-            return Ok(false);
         }
+    }
 
-        // Normal workspace selection mode
+    async fn handle_attachment_key(&mut self, key: KeyEvent) -> Result<bool> {
         match key.code {
             KeyCode::Esc | KeyCode::Char('q') => {
-                self.workspace_modal_visible = false;
-            }
-            KeyCode::Char('n') => {
-                self.creating_workspace = true;
-                self.new_workspace_name.clear();
-                self.vim_mode = VimMode::Insert;
+                self.attachment_mode = false;
+                self.attachment_cursor = 0;
             }
             KeyCode::Char('j') | KeyCode::Down => {
-                if self.selected_workspace_idx < self.workspaces.len().saturating_sub(1) {
-                    self.selected_workspace_idx += 1;
-                }
+                self.attachment_cursor = (self.attachment_cursor + 1)
+                    .min(self.task_attachments.len().saturating_sub(1));
             }
             KeyCode::Char('k') | KeyCode::Up => {
-                if self.selected_workspace_idx > 0 {
-                    self.selected_workspace_idx -= 1;
-                }
+                self.attachment_cursor = self.attachment_cursor.saturating_sub(1);
             }
-            KeyCode::Enter => {
-                if let Some(ws) = self.workspaces.get(self.selected_workspace_idx) {
-                    self.current_workspace = Some(ws.workspace.clone());
-                    let _ = WorkspaceState::save(ws.workspace.id);
-                    self.workspace_modal_visible = false;
-                    self.load_workspace_data(tx).await;
-                }
+            KeyCode::Enter | KeyCode::Char('o') => {
+                self.open_attachment().await;
             }
-            KeyCode::Char('L') => {
-                self.workspace_modal_visible = false;
-                self.do_logout().await;
+            KeyCode::Char('d') => {
+                self.download_attachment().await;
             }
             _ => {}
         }
-
         Ok(false)
     }
 
-    /// Create workspace from modal
-    async fn do_create_workspace_modal(&mut self) {
-        self.set_loading(true, "Creating workspace...");
+    /// Fetch the selected attachment and write it into `dir`, returning the file path
+    async fn save_attachment_to(&mut self, dir: std::path::PathBuf, unique: bool) -> Option<std::path::PathBuf> {
+        let workspace_id = self.current_workspace.as_ref()?.id;
+        let attachment = self.task_attachments.get(self.attachment_cursor)?.clone();
 
-        let name = self.new_workspace_name.clone();
+        self.set_loading(true, "Downloading attachment...");
+        let result = self.api.download_attachment(workspace_id, attachment.id).await;
+        self.set_loading(false, "");
 
-        match self.api.create_workspace(&name, None).await {
-            Ok(ws) => {
-                self.creating_workspace = false;
-                self.new_workspace_name.clear();
-                self.vim_mode = VimMode::Normal;
-                // Add new workspace to list and select it
-                self.workspaces.push(WorkspaceWithRole {
-                    workspace: ws.clone(),
-                    role: todo_shared::WorkspaceRole::Owner,
-                });
-                self.selected_workspace_idx = self.workspaces.len() - 1;
+        let data = match result {
+            Ok(data) => data,
+            Err(e) => {
+                self.set_error(format!("Failed to download attachment: {}", e));
+                return None;
             }
+        };
+
+        // Never trust the name to stay inside the target directory
+        let filename = std::path::Path::new(&attachment.filename)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| attachment.id.to_string());
+
+        let mut path = dir.join(&filename);
+        if unique {
+            // Don't overwrite earlier downloads: "name (1).ext", "name (2).ext", ...
+            let stem = std::path::Path::new(&filename)
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let ext = std::path::Path::new(&filename)
+                .extension()
+                .map(|e| format!(".{}", e.to_string_lossy()))
+                .unwrap_or_default();
+            let mut n = 1;
+            while path.exists() {
+                path = dir.join(format!("{} ({}){}", stem, n, ext));
+                n += 1;
+            }
+        }
+
+        let written = std::fs::create_dir_all(&dir).and_then(|_| std::fs::write(&path, data));
+        match written {
+            Ok(()) => Some(path),
             Err(e) => {
-                self.set_error(format!("Failed to create workspace: {}", e));
+                self.set_error(format!("Failed to save {}: {}", path.display(), e));
+                None
             }
         }
-
-        self.set_loading(false, "");
     }
 
-    async fn do_login(&mut self, tx: mpsc::Sender<AppEvent>) {
-        self.set_loading(true, "Logging in...");
-
-        let email = self.login_email.clone();
-        let password = self.login_password.clone();
+    async fn download_attachment(&mut self) {
+        let dir = dirs::download_dir()
+            .or_else(dirs::home_dir)
+            .unwrap_or_else(|| std::path::PathBuf::from("."));
 
-        match self.api.login(&email, &password).await {
-            Ok(user) => {
-                self.user = Some(user);
-                let _ = tx.send(AppEvent::AuthSuccess).await;
-            }
-            Err(e) => {
-                let _ = tx.send(AppEvent::AuthFailed(e.to_string())).await;
-            }
+        if let Some(path) = self.save_attachment_to(dir, true).await {
+            self.set_error(format!("Saved to {}", path.display()));
         }
-
-        self.set_loading(false, "");
     }
 
-    async fn do_register(&mut self, _tx: mpsc::Sender<AppEvent>) {
-        self.set_loading(true, "Registering...");
+    async fn open_attachment(&mut self) {
+        let attachment_id = match self.task_attachments.get(self.attachment_cursor) {
+            Some(a) => a.id,
+            None => return,
+        };
+        // One directory per attachment so the original filename can be kept
+        let dir = std::env::temp_dir()
+            .join("todo-tui")
+            .join(attachment_id.to_string());
 
-        let username = self.register_username.clone();
-        let email = self.login_email.clone();
-        let password = self.login_password.clone();
-        let display_name = self.register_display_name.clone();
+        let Some(path) = self.save_attachment_to(dir, false).await else {
+            return;
+        };
 
-        match self.api.register(&username, &email, &password, &display_name).await {
-            Ok(response) => {
-                // Store email for verification
-                self.verification_email = response.email;
-                self.verification_code.clear();
-                self.login_field = InputField::VerificationCode;
-                self.vim_mode = VimMode::Normal;
-                self.view = View::EmailVerification;
-            }
-            Err(e) => {
-                self.set_error(format!("Registration failed: {}", e));
-            }
-        }
+        let opener = if cfg!(target_os = "macos") { "open" } else { "xdg-open" };
+        let spawned = std::process::Command::new(opener)
+            .arg(&path)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn();
 
-        self.set_loading(false, "");
+        if let Err(e) = spawned {
+            self.set_error(format!("Failed to run {}: {}", opener, e));
+        }
     }
 
-    async fn do_verify_email(&mut self, tx: mpsc::Sender<AppEvent>) {
-        self.set_loading(true, "Verifying email...");
-
-        let email = self.verification_email.clone();
-        let code = self.verification_code.clone();
+    async fn do_add_blocker(&mut self, blocking_task_id: uuid::Uuid) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+        let task_id = match self.selected_task_detail {
+            Some(ref t) => t.id,
+            None => return,
+        };
 
-        match self.api.verify_email(&email, &code).await {
-            Ok(user) => {
-                self.user = Some(user);
-                let _ = tx.send(AppEvent::AuthSuccess).await;
+        match self.api.add_dependency(workspace_id, task_id, blocking_task_id).await {
+            Ok(deps) => {
+                self.task_dependencies = deps;
+                self.apply_open_task_blockers();
             }
             Err(e) => {
-                self.set_error(format!("Verification failed: {}", e));
+                self.set_error(format!("Failed to add blocker: {}", e));
             }
         }
-
-        self.set_loading(false, "");
     }
 
-    async fn do_resend_verification(&mut self) {
-        self.set_loading(true, "Resending verification code...");
-
-        let email = self.verification_email.clone();
+    async fn do_remove_blocker(&mut self, blocking_task_id: uuid::Uuid) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+        let task_id = match self.selected_task_detail {
+            Some(ref t) => t.id,
+            None => return,
+        };
 
-        match self.api.resend_verification(&email).await {
-            Ok(()) => {
-                self.set_error("Verification code resent. Check server logs.".to_string());
+        match self.api.remove_dependency(workspace_id, task_id, blocking_task_id).await {
+            Ok(_) => {
+                self.task_dependencies.blocked_by.retain(|d| d.task_id != blocking_task_id);
+                self.apply_open_task_blockers();
             }
             Err(e) => {
-                self.set_error(format!("Failed to resend: {}", e));
+                self.set_error(format!("Failed to remove blocker: {}", e));
             }
         }
+    }
 
-        self.set_loading(false, "");
-    }
-
-    pub async fn verify_auth(&mut self) {
-        self.set_loading(true, "Verifying authentication...");
-
-        match self.api.me().await {
-            Ok(user) => {
-                self.user = Some(user);
-                self.load_workspaces().await;
-
-                // Try to restore last opened workspace
-                let last_workspace_id = WorkspaceState::load()
-                    .ok()
-                    .flatten()
-                    .map(|s| s.workspace_id);
-
-                let restored = if let Some(id) = last_workspace_id {
-                    self.workspaces
-                        .iter()
-                        .enumerate()
-                        .find(|(_, w)| w.workspace.id == id)
-                        .map(|(idx, ws)| {
-                            self.selected_workspace_idx = idx;
-                            self.current_workspace = Some(ws.workspace.clone());
-                        })
-                        .is_some()
-                } else {
-                    false
-                };
+    /// Copy the open task's unfinished blockers onto its detail and kanban card
+    fn apply_open_task_blockers(&mut self) {
+        let blocked_by: Vec<uuid::Uuid> = self.task_dependencies
+            .blocked_by
+            .iter()
+            .filter(|d| !d.is_done)
+            .map(|d| d.task_id)
+            .collect();
 
-                // Fallback to first workspace if not restored
-                if !restored {
-                    if let Some(ws) = self.workspaces.first() {
-                        self.current_workspace = Some(ws.workspace.clone());
-                        self.selected_workspace_idx = 0;
+        if let Some(ref mut detail) = self.selected_task_detail {
+            detail.blocked_by = blocked_by.clone();
+            let task_id = detail.id;
+            for col in &mut self.columns {
+                for task in &mut col.tasks {
+                    if task.id == task_id {
+                        task.blocked_by = blocked_by.clone();
                     }
                 }
-
-                if self.current_workspace.is_some() {
-                    self.view = View::Home;
-                    self.load_home_data().await;
-                } else {
-                    // No workspaces available
-                    self.view = View::WorkspaceSelect;
-                }
-            }
-            Err(_) => {
-                // Token invalid, go to login
-                let _ = self.api.logout().await;
-                self.view = View::Login;
-            }
-        }
-
-        self.set_loading(false, "");
-    }
-
-    pub async fn on_auth_success(&mut self) {
-        self.login_password.clear();
-        self.load_workspaces().await;
-
-        // Try to restore last opened workspace
-        let last_workspace_id = WorkspaceState::load()
-            .ok()
-            .flatten()
-            .map(|s| s.workspace_id);
-
-        let restored = if let Some(id) = last_workspace_id {
-            self.workspaces
-                .iter()
-                .enumerate()
-                .find(|(_, w)| w.workspace.id == id)
-                .map(|(idx, ws)| {
-                    self.selected_workspace_idx = idx;
-                    self.current_workspace = Some(ws.workspace.clone());
-                })
-                .is_some()
-        } else {
-            false
-        };
-
-        // Fallback to first workspace if not restored
-        if !restored {
-            if let Some(ws) = self.workspaces.first() {
-                self.current_workspace = Some(ws.workspace.clone());
-                self.selected_workspace_idx = 0;
-            }
-        }
-
-        if self.current_workspace.is_some() {
-            self.view = View::Home;
-            self.load_home_data().await;
-        } else {
-            // No workspaces available
-            self.view = View::WorkspaceSelect;
-        }
-    }
-
-    async fn load_home_data(&mut self) {
-        // Load quote asynchronously (don't block UI)
-        let (quote, author) = crate::api::quote::get_quote().await;
-        self.home_quote = Some(quote);
-        self.home_quote_author = Some(author);
-
-        // Load workspace stats and calendar tasks
-        if let Some(ref workspace) = self.current_workspace {
-            if let Ok(stats) = self.api.get_workspace_stats(workspace.id).await {
-                self.home_stats = stats;
             }
-
-            // Load tasks for calendar (current month)
-            self.load_calendar_tasks().await;
         }
     }
 
-    async fn load_calendar_tasks(&mut self) {
-        let Some(ref workspace) = self.current_workspace else { return };
-
-        // Calculate date range for current calendar month
-        let first_day = match NaiveDate::from_ymd_opt(self.calendar_year, self.calendar_month, 1) {
-            Some(d) => d,
+    /// Update blocked markers on tasks that a moved task blocks
+    async fn sync_blocked_markers(&mut self, task_id: uuid::Uuid, is_done: bool) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
             None => return,
         };
 
-        // Get last day of month
-        let last_day = if self.calendar_month == 12 {
-            NaiveDate::from_ymd_opt(self.calendar_year + 1, 1, 1)
-        } else {
-            NaiveDate::from_ymd_opt(self.calendar_year, self.calendar_month + 1, 1)
-        }
-        .and_then(|d| d.pred_opt())
-        .unwrap_or(first_day);
-
-        // Fetch tasks with due dates in this range
-        let params = TaskListParams {
-            due_after: Some(first_day),
-            due_before: Some(last_day),
-            limit: Some(500),
-            ..Default::default()
+        let deps = match self.api.list_dependencies(workspace_id, task_id).await {
+            Ok(deps) => deps,
+            Err(_) => return,
         };
 
-        if let Ok(response) = self.api.list_tasks(workspace.id, Some(&params)).await {
-            self.calendar_tasks.clear();
-            for task in response.tasks {
-                if let Some(due_date) = task.due_date {
-                    *self.calendar_tasks.entry(due_date).or_insert(0) += 1;
+        let blocked_ids: HashSet<_> = deps.blocks.iter().map(|d| d.task_id).collect();
+        for col in &mut self.columns {
+            for task in &mut col.tasks {
+                if !blocked_ids.contains(&task.id) {
+                    continue;
+                }
+                task.blocked_by.retain(|id| *id != task_id);
+                if !is_done {
+                    task.blocked_by.push(task_id);
                 }
             }
         }
     }
 
-    pub fn on_auth_failed(&mut self, msg: String) {
-        self.set_error(format!("Login failed: {}", msg));
-        self.login_password.clear();
-    }
-
-    async fn load_workspaces(&mut self) {
-        self.set_loading(true, "Loading workspaces...");
-
-        match self.api.list_workspaces().await {
-            Ok(workspaces) => {
-                self.workspaces = workspaces;
+    async fn handle_unlink_document_key(&mut self, key: KeyEvent) -> Result<bool> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.unlinking_document_mode = false;
+                self.unlink_document_cursor = 0;
             }
-            Err(e) => {
-                self.set_error(format!("Failed to load workspaces: {}", e));
+            KeyCode::Char('j') | KeyCode::Down => {
+                if self.unlink_document_cursor < self.task_linked_documents.len().saturating_sub(1) {
+                    self.unlink_document_cursor += 1;
+                }
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                if self.unlink_document_cursor > 0 {
+                    self.unlink_document_cursor -= 1;
+                }
+            }
+            KeyCode::Enter => {
+                if let Some(doc) = self.task_linked_documents.get(self.unlink_document_cursor) {
+                    let doc_id = doc.document_id;
+                    self.do_unlink_document(doc_id).await;
+                }
+                self.unlinking_document_mode = false;
+                self.unlink_document_cursor = 0;
             }
+            _ => {}
         }
-
-        self.set_loading(false, "");
-    }
-
-    pub fn on_workspaces_loaded(&mut self, workspaces: Vec<WorkspaceWithRole>) {
-        self.workspaces = workspaces;
-        self.is_connected = true;
-        self.set_loading(false, "");
+        Ok(false)
     }
 
-    async fn load_workspace_data(&mut self, _tx: mpsc::Sender<AppEvent>) {
+    async fn do_unlink_document(&mut self, doc_id: uuid::Uuid) {
         let workspace_id = match self.current_workspace {
             Some(ref ws) => ws.id,
             None => return,
         };
-
-        self.set_loading(true, "Loading workspace data...");
-
-        // Load statuses
-        let statuses = match self.api.list_statuses(workspace_id).await {
-            Ok(s) => s,
-            Err(e) => {
-                self.set_error(format!("Failed to load statuses: {}", e));
-                self.set_loading(false, "");
-                return;
-            }
+        let task_id = match self.selected_task_detail {
+            Some(ref t) => t.id,
+            None => return,
         };
 
-        // Load tasks
-        let tasks = match self.api.list_tasks(workspace_id, None).await {
-            Ok(response) => response.tasks,
-            Err(e) => {
-                self.set_error(format!("Failed to load tasks: {}", e));
-                self.set_loading(false, "");
-                return;
+        match self.api.unlink_task_from_document(workspace_id, doc_id, task_id).await {
+            Ok(_) => {
+                // Remove from local list
+                self.task_linked_documents.retain(|d| d.document_id != doc_id);
             }
-        };
-
-        // Load workspace tags
-        self.workspace_tags = match self.api.list_tags(workspace_id).await {
-            Ok(tags) => tags,
-            Err(_) => Vec::new(), // Silently fail for tags
-        };
-
-        self.on_workspace_data_loaded(statuses, tasks);
-    }
-
-    pub fn on_workspace_data_loaded(&mut self, statuses: Vec<TaskStatus>, tasks: Vec<Task>) {
-        // Organize tasks into columns
-        self.columns = statuses
-            .into_iter()
-            .map(|status| {
-                let column_tasks: Vec<Task> = tasks
-                    .iter()
-                    .filter(|t| t.status_id == status.id)
-                    .cloned()
-                    .collect();
-                Column {
-                    status,
-                    tasks: column_tasks,
-                }
-            })
-            .collect();
-
-        // Initialize scroll offsets for each column
-        self.column_scroll_offsets = vec![0; self.columns.len()];
-
-        self.selected_column = 0;
-        self.selected_task = 0;
-        self.view = View::Dashboard;
-        self.is_connected = true;
-        self.set_loading(false, "");
-    }
-
-    pub fn move_left(&mut self) {
-        if self.selected_column > 0 {
-            self.selected_column -= 1;
-            self.selected_task = 0;
-            // Reset scroll for new column
-            if let Some(offset) = self.column_scroll_offsets.get_mut(self.selected_column) {
-                *offset = 0;
+            Err(e) => {
+                self.set_error(format!("Failed to unlink document: {}", e));
             }
         }
     }
 
-    pub fn move_right(&mut self) {
-        if !self.columns.is_empty() && self.selected_column < self.columns.len() - 1 {
-            self.selected_column += 1;
-            self.selected_task = 0;
-            // Reset scroll for new column
-            if let Some(offset) = self.column_scroll_offsets.get_mut(self.selected_column) {
-                *offset = 0;
+    async fn handle_edit_task_key(&mut self, key: KeyEvent) -> Result<bool> {
+        // Insert mode - editing current field
+        if self.vim_mode == VimMode::Insert {
+            // Special handling for description field with TextArea
+            if self.edit_field == TaskEditField::Description {
+                if let Some(ref mut textarea) = self.edit_task_description_textarea {
+                    match key.code {
+                        KeyCode::Esc => {
+                            self.vim_mode = VimMode::Normal;
+                        }
+                        KeyCode::Char('e') | KeyCode::Char('E') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            // Ctrl+E: external editor
+                            let content = self.get_edit_task_description();
+                            match editor::launch_external_editor(&content, ".md") {
+                                Ok(edited) => {
+                                    self.needs_terminal_clear = true;
+                                    self.edit_task_description_textarea = Some(
+                                        editor::create_textarea(&edited, EditorContext::TaskDescription),
+                                    );
+                                }
+                                Err(e) => {
+                                    self.needs_terminal_clear = true;
+                                    self.set_error(format!("Editor failed: {}", e));
+                                }
+                            }
+                        }
+                        _ => {
+                            // Pass to textarea for normal input handling
+                            textarea.input(key);
+                        }
+                    }
+                    return Ok(false);
+                }
             }
-        }
-    }
 
-    pub fn move_up(&mut self) {
-        if self.selected_task > 0 {
-            self.selected_task -= 1;
-            // Adjust scroll if selection is above visible area
-            if let Some(offset) = self.column_scroll_offsets.get_mut(self.selected_column) {
-                if self.selected_task < *offset {
-                    *offset = self.selected_task;
+            // Other fields use simple string handling
+            match key.code {
+                KeyCode::Esc => {
+                    self.vim_mode = VimMode::Normal;
+                }
+                KeyCode::Enter => {
+                    // Save and exit insert mode
+                    self.vim_mode = VimMode::Normal;
+                }
+                KeyCode::Char(c) => {
+                    match self.edit_field {
+                        TaskEditField::Title => self.edit_task_title.push(c),
+                        TaskEditField::Description => {} // Handled above with TextArea
+                        TaskEditField::DueDate => self.edit_task_due_date_str.push(c),
+                        TaskEditField::TimeEstimate => self.edit_task_time_estimate_str.push(c),
+                        TaskEditField::Recurrence => self.edit_task_recurrence_str.push(c),
+                        TaskEditField::CustomFields => {
+                            if let Some(input) = self.edit_custom_field_inputs.get_mut(self.edit_custom_field_cursor) {
+                                input.push(c);
+                            }
+                        }
+                        TaskEditField::Priority
+                        | TaskEditField::Assignees
+                        | TaskEditField::Watchers
+                        | TaskEditField::Tags => {}
+                    }
+                }
+                KeyCode::Backspace => {
+                    match self.edit_field {
+                        TaskEditField::Title => { self.edit_task_title.pop(); }
+                        TaskEditField::Description => {} // Handled above with TextArea
+                        TaskEditField::DueDate => { self.edit_task_due_date_str.pop(); }
+                        TaskEditField::TimeEstimate => { self.edit_task_time_estimate_str.pop(); }
+                        TaskEditField::Recurrence => { self.edit_task_recurrence_str.pop(); }
+                        TaskEditField::CustomFields => {
+                            if let Some(input) = self.edit_custom_field_inputs.get_mut(self.edit_custom_field_cursor) {
+                                input.pop();
+                            }
+                        }
+                        TaskEditField::Priority
+                        | TaskEditField::Assignees
+                        | TaskEditField::Watchers
+                        | TaskEditField::Tags => {}
+                    }
                 }
+                _ => {}
             }
+            return Ok(false);
         }
-    }
 
-    pub fn move_down(&mut self) {
-        if let Some(column) = self.columns.get(self.selected_column) {
-            if self.selected_task < column.tasks.len().saturating_sub(1) {
-                self.selected_task += 1;
-                // Adjust scroll if selection is below visible area
-                // Assume ~3 tasks visible per column (conservative estimate)
-                // The actual visible count depends on terminal height
-                if let Some(offset) = self.column_scroll_offsets.get_mut(self.selected_column) {
-                    let visible_tasks = 5; // Conservative default, UI will handle actual rendering
-                    if self.selected_task >= *offset + visible_tasks {
-                        *offset = self.selected_task.saturating_sub(visible_tasks - 1);
+        // Normal mode in edit
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => {
+                // Cancel edit mode
+                self.editing_task = false;
+                self.vim_mode = VimMode::Normal;
+            }
+            KeyCode::Char('i') => {
+                // Enter insert mode for current field (except Priority, Assignee, Tags)
+                if self.edit_field != TaskEditField::Priority
+                    && self.edit_field != TaskEditField::Assignees
+                    && self.edit_field != TaskEditField::Watchers
+                    && self.edit_field != TaskEditField::Tags
+                    && (self.edit_field != TaskEditField::CustomFields || !self.workspace_custom_fields.is_empty()) {
+                    self.vim_mode = VimMode::Insert;
+                }
+            }
+            KeyCode::Tab => {
+                // Next field
+                self.edit_field = self.edit_field.next();
+            }
+            KeyCode::BackTab => {
+                // Previous field
+                self.edit_field = self.edit_field.prev();
+            }
+            KeyCode::Char('l') | KeyCode::Right if self.edit_field == TaskEditField::Tags => {
+                // Navigate to next tag
+                if !self.workspace_tags.is_empty() {
+                    self.tag_selector_cursor = (self.tag_selector_cursor + 1) % self.workspace_tags.len();
+                }
+            }
+            KeyCode::Char('h') | KeyCode::Left if self.edit_field == TaskEditField::Tags => {
+                // Navigate to previous tag
+                if !self.workspace_tags.is_empty() {
+                    self.tag_selector_cursor = self.tag_selector_cursor
+                        .checked_sub(1)
+                        .unwrap_or(self.workspace_tags.len().saturating_sub(1));
+                }
+            }
+            KeyCode::Char('l') | KeyCode::Right
+                if self.edit_field == TaskEditField::CustomFields && !self.workspace_custom_fields.is_empty() =>
+            {
+                // Navigate to next custom field
+                self.edit_custom_field_cursor = (self.edit_custom_field_cursor + 1) % self.workspace_custom_fields.len();
+            }
+            KeyCode::Char('h') | KeyCode::Left
+                if self.edit_field == TaskEditField::CustomFields && !self.workspace_custom_fields.is_empty() =>
+            {
+                // Navigate to previous custom field
+                self.edit_custom_field_cursor = self.edit_custom_field_cursor
+                    .checked_sub(1)
+                    .unwrap_or(self.workspace_custom_fields.len() - 1);
+            }
+            KeyCode::Char(' ') if self.edit_field == TaskEditField::Tags => {
+                // Toggle tag selection
+                if let Some(tag) = self.workspace_tags.get(self.tag_selector_cursor) {
+                    let tag_id = tag.id;
+                    if self.task_edit_selected_tags.contains(&tag_id) {
+                        self.task_edit_selected_tags.retain(|&id| id != tag_id);
+                    } else {
+                        self.task_edit_selected_tags.push(tag_id);
+                    }
+                }
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                // Next field (for non-Tags fields)
+                self.edit_field = self.edit_field.next();
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                // Previous field (for non-Tags fields)
+                self.edit_field = self.edit_field.prev();
+            }
+            KeyCode::Char('h') | KeyCode::Left if self.edit_field == TaskEditField::Priority => {
+                // Decrease priority
+                self.edit_task_priority = Some(match self.edit_task_priority {
+                    Some(Priority::Highest) => Priority::High,
+                    Some(Priority::High) => Priority::Medium,
+                    Some(Priority::Medium) => Priority::Low,
+                    Some(Priority::Low) => Priority::Lowest,
+                    Some(Priority::Lowest) | None => Priority::Lowest,
+                });
+            }
+            KeyCode::Char('l') | KeyCode::Right if self.edit_field == TaskEditField::Priority => {
+                // Increase priority
+                self.edit_task_priority = Some(match self.edit_task_priority {
+                    Some(Priority::Lowest) => Priority::Low,
+                    Some(Priority::Low) => Priority::Medium,
+                    Some(Priority::Medium) => Priority::High,
+                    Some(Priority::High) => Priority::Highest,
+                    Some(Priority::Highest) | None => Priority::Highest,
+                });
+            }
+            KeyCode::Char('h') | KeyCode::Left
+                if self.edit_field == TaskEditField::Assignees && !self.workspace_members.is_empty() =>
+            {
+                // Navigate to previous member
+                self.assignee_selector_cursor = self.assignee_selector_cursor
+                    .checked_sub(1)
+                    .unwrap_or(self.workspace_members.len() - 1);
+            }
+            KeyCode::Char('l') | KeyCode::Right
+                if self.edit_field == TaskEditField::Assignees && !self.workspace_members.is_empty() =>
+            {
+                // Navigate to next member
+                self.assignee_selector_cursor = (self.assignee_selector_cursor + 1) % self.workspace_members.len();
+            }
+            KeyCode::Char(' ') if self.edit_field == TaskEditField::Assignees => {
+                // Toggle assignee
+                if let Some(member) = self.workspace_members.get(self.assignee_selector_cursor) {
+                    let user_id = member.user_id;
+                    if self.edit_task_assignees.contains(&user_id) {
+                        self.edit_task_assignees.retain(|&id| id != user_id);
+                    } else {
+                        self.edit_task_assignees.push(user_id);
+                    }
+                }
+            }
+            KeyCode::Char('h') | KeyCode::Left
+                if self.edit_field == TaskEditField::Watchers && !self.workspace_members.is_empty() =>
+            {
+                // Navigate to previous member
+                self.watcher_selector_cursor = self.watcher_selector_cursor
+                    .checked_sub(1)
+                    .unwrap_or(self.workspace_members.len() - 1);
+            }
+            KeyCode::Char('l') | KeyCode::Right
+                if self.edit_field == TaskEditField::Watchers && !self.workspace_members.is_empty() =>
+            {
+                // Navigate to next member
+                self.watcher_selector_cursor = (self.watcher_selector_cursor + 1) % self.workspace_members.len();
+            }
+            KeyCode::Char(' ') if self.edit_field == TaskEditField::Watchers => {
+                // Toggle watcher
+                if let Some(member) = self.workspace_members.get(self.watcher_selector_cursor) {
+                    let user_id = member.user_id;
+                    if self.edit_task_watchers.contains(&user_id) {
+                        self.edit_task_watchers.retain(|&id| id != user_id);
+                    } else {
+                        self.edit_task_watchers.push(user_id);
+                    }
+                }
+            }
+            KeyCode::Char('x') => {
+                // Clear the focused field; saved as a removal
+                match self.edit_field {
+                    TaskEditField::Priority => self.edit_task_priority = None,
+                    TaskEditField::DueDate => self.edit_task_due_date_str.clear(),
+                    TaskEditField::TimeEstimate => self.edit_task_time_estimate_str.clear(),
+                    TaskEditField::Recurrence => self.edit_task_recurrence_str.clear(),
+                    TaskEditField::Assignees => self.edit_task_assignees.clear(),
+                    TaskEditField::Watchers => self.edit_task_watchers.clear(),
+                    TaskEditField::Tags => self.task_edit_selected_tags.clear(),
+                    TaskEditField::Description => self.init_edit_task_description_textarea(""),
+                    TaskEditField::CustomFields => {
+                        if let Some(input) = self.edit_custom_field_inputs.get_mut(self.edit_custom_field_cursor) {
+                            input.clear();
+                        }
                     }
+                    TaskEditField::Title => {}
                 }
             }
+            KeyCode::Enter => {
+                // Save changes
+                self.do_update_task().await;
+            }
+            _ => {}
         }
+
+        Ok(false)
     }
 
-    pub fn get_selected_task(&self) -> Option<&Task> {
-        self.columns
-            .get(self.selected_column)
-            .and_then(|col| col.tasks.get(self.selected_task))
+    fn go_back_to_workspace_select(&mut self) {
+        self.current_workspace = None;
+        self.columns.clear();
+        self.selected_column = 0;
+        self.selected_task = 0;
+        self.view = View::WorkspaceSelect;
     }
 
-    async fn do_move_task_left(&mut self) {
-        if self.selected_column == 0 {
-            return;
-        }
+    /// Open workspace modal and refresh the workspace list
+    async fn open_workspace_modal(&mut self) {
+        // Refresh workspace list before showing modal
+        self.load_workspaces().await;
 
-        let workspace_id = match self.current_workspace {
-            Some(ref ws) => ws.id,
-            None => return,
-        };
+        // Find current workspace in the list
+        if let Some(ref current) = self.current_workspace {
+            if let Some(idx) = self.workspaces.iter().position(|w| w.workspace.id == current.id) {
+                self.selected_workspace_idx = idx;
+            }
+        }
 
-        let task = match self.get_selected_task() {
-            Some(t) => t.clone(),
-            None => return,
-        };
+        self.workspace_modal_visible = true;
+        self.creating_workspace = false;
+        self.new_workspace_name.clear();
+    }
 
-        let target_column = self.selected_column - 1;
-        let target_status_id = self.columns[target_column].status.id;
+    /// Handle key events in the workspace modal
+    async fn handle_workspace_modal_key(
+        &mut self,
+        key: KeyEvent,
+        tx: mpsc::Sender<AppEvent>,
+    ) -> Result<bool> {
+        if self.loading {
+            return Ok(false);
+        }
 
-        match self.api.move_task(workspace_id, task.id, target_status_id, None).await {
-            Ok(updated_task) => {
-                // Remove from current column
-                if let Some(col) = self.columns.get_mut(self.selected_column) {
-                    col.tasks.retain(|t| t.id != task.id);
+        // Handle workspace creation mode
+        if self.creating_workspace {
+            match key.code {
+                KeyCode::Esc => {
+                    self.creating_workspace = false;
+                    self.new_workspace_name.clear();
+                    self.vim_mode = VimMode::Normal;
                 }
-                // Add to target column
-                if let Some(col) = self.columns.get_mut(target_column) {
-                    col.tasks.push(updated_task);
-                    col.tasks.sort_by_key(|t| t.position);
+                KeyCode::Enter => {
+                    if !self.new_workspace_name.is_empty() {
+                        self.do_create_workspace_modal().await;
+                    }
                 }
-                // Move selection
-                self.selected_column = target_column;
-                self.selected_task = self.columns[target_column].tasks.len().saturating_sub(1);
-                self.moving_task = false;
-            }
-            Err(e) => {
-                self.set_error(format!("Failed to move task: {}", e));
+                KeyCode::Char(c) => {
+                    self.new_workspace_name.push(c);
+                }
+                KeyCode::Backspace => {
+                    self.new_workspace_name.pop();
+                }
+                _ => {}
             }
+            return Ok(false);
         }
+
+        // Normal workspace selection mode
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.workspace_modal_visible = false;
+            }
+            KeyCode::Char('n') => {
+                self.creating_workspace = true;
+                self.new_workspace_name.clear();
+                self.vim_mode = VimMode::Insert;
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                if self.selected_workspace_idx < self.workspaces.len().saturating_sub(1) {
+                    self.selected_workspace_idx += 1;
+                }
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                if self.selected_workspace_idx > 0 {
+                    self.selected_workspace_idx -= 1;
+                }
+            }
+            KeyCode::Enter => {
+                if let Some(ws) = self.workspaces.get(self.selected_workspace_idx) {
+                    self.current_workspace = Some(ws.workspace.clone());
+                    let _ = WorkspaceState::save(ws.workspace.id);
+                    self.workspace_modal_visible = false;
+                    self.load_workspace_data(tx).await;
+                }
+            }
+            KeyCode::Char('L') => {
+                self.workspace_modal_visible = false;
+                self.do_logout().await;
+            }
+            _ => {}
+        }
+
+        Ok(false)
     }
 
-    async fn do_move_task_right(&mut self) {
-        if self.columns.is_empty() || self.selected_column >= self.columns.len() - 1 {
-            return;
+    /// Create workspace from modal
+    async fn do_create_workspace_modal(&mut self) {
+        self.set_loading(true, "Creating workspace...");
+
+        let name = self.new_workspace_name.clone();
+
+        match self.api.create_workspace(&name, None).await {
+            Ok(ws) => {
+                self.creating_workspace = false;
+                self.new_workspace_name.clear();
+                self.vim_mode = VimMode::Normal;
+                // Add new workspace to list and select it
+                self.workspaces.push(WorkspaceWithRole {
+                    workspace: ws.clone(),
+                    role: todo_shared::WorkspaceRole::Owner,
+                });
+                self.selected_workspace_idx = self.workspaces.len() - 1;
+            }
+            Err(e) => {
+                self.set_error(format!("Failed to create workspace: {}", e));
+            }
         }
 
-        let workspace_id = match self.current_workspace {
-            Some(ref ws) => ws.id,
-            None => return,
-        };
+        self.set_loading(false, "");
+    }
 
-        let task = match self.get_selected_task() {
-            Some(t) => t.clone(),
-            None => return,
-        };
+    async fn do_login(&mut self, tx: mpsc::Sender<AppEvent>) {
+        self.set_loading(true, "Logging in...");
 
-        let target_column = self.selected_column + 1;
-        let target_status_id = self.columns[target_column].status.id;
+        let email = self.login_email.clone();
+        let password = self.login_password.clone();
 
-        match self.api.move_task(workspace_id, task.id, target_status_id, None).await {
-            Ok(updated_task) => {
-                // Remove from current column
-                if let Some(col) = self.columns.get_mut(self.selected_column) {
-                    col.tasks.retain(|t| t.id != task.id);
-                }
-                // Add to target column
-                if let Some(col) = self.columns.get_mut(target_column) {
-                    col.tasks.push(updated_task);
-                    col.tasks.sort_by_key(|t| t.position);
-                }
-                // Move selection
-                self.selected_column = target_column;
-                self.selected_task = self.columns[target_column].tasks.len().saturating_sub(1);
-                self.moving_task = false;
+        match self.api.login(&email, &password).await {
+            Ok(user) => {
+                self.user = Some(user);
+                let _ = tx.send(AppEvent::AuthSuccess).await;
             }
             Err(e) => {
-                self.set_error(format!("Failed to move task: {}", e));
+                let _ = tx.send(AppEvent::AuthFailed(e.to_string())).await;
             }
         }
+
+        self.set_loading(false, "");
     }
 
-    async fn open_task_detail(&mut self) {
-        let workspace_id = match self.current_workspace {
-            Some(ref ws) => ws.id,
-            None => return,
-        };
+    async fn do_register(&mut self, _tx: mpsc::Sender<AppEvent>) {
+        self.set_loading(true, "Registering...");
 
-        let task = match self.get_selected_task() {
-            Some(t) => t.clone(),
-            None => return,
-        };
+        let username = self.register_username.clone();
+        let email = self.login_email.clone();
+        let password = self.login_password.clone();
+        let display_name = self.register_display_name.clone();
 
-        self.set_loading(true, "Loading task details...");
+        match self.api.register(&username, &email, &password, &display_name).await {
+            Ok(response) => {
+                // Store email for verification
+                self.verification_email = response.email;
+                self.verification_code.clear();
+                self.login_field = InputField::VerificationCode;
+                self.vim_mode = VimMode::Normal;
+                self.view = View::EmailVerification;
+            }
+            Err(e) => {
+                self.set_error(format!("Registration failed: {}", e));
+            }
+        }
 
-        // Load comments
-        match self.api.list_comments(workspace_id, task.id).await {
-            Ok(comments) => {
-                self.task_comments = comments;
+        self.set_loading(false, "");
+    }
+
+    async fn do_verify_email(&mut self, tx: mpsc::Sender<AppEvent>) {
+        self.set_loading(true, "Verifying email...");
+
+        let email = self.verification_email.clone();
+        let code = self.verification_code.clone();
+
+        match self.api.verify_email(&email, &code).await {
+            Ok(user) => {
+                self.user = Some(user);
+                let _ = tx.send(AppEvent::AuthSuccess).await;
             }
             Err(e) => {
-                self.set_error(format!("Failed to load comments: {}", e));
-                self.set_loading(false, "");
-                return;
+                self.set_error(format!("Verification failed: {}", e));
             }
         }
 
-        // Load workspace members for assignee selection
-        match self.api.list_members(workspace_id).await {
-            Ok(members) => {
-                self.workspace_members = members;
+        self.set_loading(false, "");
+    }
+
+    async fn do_resend_verification(&mut self) {
+        self.set_loading(true, "Resending verification code...");
+
+        let email = self.verification_email.clone();
+
+        match self.api.resend_verification(&email).await {
+            Ok(()) => {
+                self.set_info("Verification code resent. Check your email.".to_string());
             }
-            Err(_) => {
-                // Non-critical, continue without members
-                self.workspace_members.clear();
+            Err(e) => {
+                self.set_error(format!("Failed to resend: {}", e));
             }
         }
 
-        // Load linked documents
-        match self.api.list_linked_documents(workspace_id, task.id).await {
-            Ok(docs) => {
-                self.task_linked_documents = docs;
+        self.set_loading(false, "");
+    }
+
+    async fn do_forgot_password(&mut self) {
+        self.set_loading(true, "Sending reset code...");
+
+        let email = self.login_email.clone();
+
+        match self.api.forgot_password(&email).await {
+            Ok(()) => {
+                self.auth_mode = AuthMode::ResetPassword;
+                self.login_field = InputField::VerificationCode;
+                self.verification_code.clear();
+                self.login_password.clear();
+                self.set_info("If that email has an account, a reset code was sent. Check your email.".to_string());
+            }
+            Err(e) => {
+                self.set_error(format!("Failed to request reset code: {}", e));
+            }
+        }
+
+        self.set_loading(false, "");
+    }
+
+    async fn do_reset_password(&mut self) {
+        self.set_loading(true, "Resetting password...");
+
+        let email = self.login_email.clone();
+        let code = self.verification_code.clone();
+        let password = self.login_password.clone();
+
+        match self.api.reset_password(&email, &code, &password).await {
+            Ok(()) => {
+                self.auth_mode = AuthMode::Login;
+                self.login_field = InputField::Password;
+                self.verification_code.clear();
+                self.login_password.clear();
+                self.set_error("Password reset. Log in with your new password.".to_string());
+            }
+            Err(e) => {
+                self.set_error(format!("Password reset failed: {}", e));
+            }
+        }
+
+        self.set_loading(false, "");
+    }
+
+    pub async fn verify_auth(&mut self) {
+        self.set_loading(true, "Verifying authentication...");
+
+        match self.api.me().await {
+            Ok(user) => {
+                self.user = Some(user);
+                self.load_workspaces().await;
+
+                // Try to restore last opened workspace
+                let last_workspace_id = WorkspaceState::load()
+                    .ok()
+                    .flatten()
+                    .map(|s| s.workspace_id);
+
+                let restored = if let Some(id) = last_workspace_id {
+                    self.workspaces
+                        .iter()
+                        .enumerate()
+                        .find(|(_, w)| w.workspace.id == id)
+                        .map(|(idx, ws)| {
+                            self.selected_workspace_idx = idx;
+                            self.current_workspace = Some(ws.workspace.clone());
+                        })
+                        .is_some()
+                } else {
+                    false
+                };
+
+                // Fallback to first workspace if not restored
+                if !restored {
+                    if let Some(ws) = self.workspaces.first() {
+                        self.current_workspace = Some(ws.workspace.clone());
+                        self.selected_workspace_idx = 0;
+                    }
+                }
+
+                if self.current_workspace.is_some() {
+                    self.view = View::Home;
+                    self.load_home_data().await;
+                } else {
+                    // No workspaces available
+                    self.view = View::WorkspaceSelect;
+                }
             }
             Err(_) => {
-                // Non-critical, continue without linked documents
-                self.task_linked_documents.clear();
+                // Token invalid, go to login
+                let _ = self.api.logout().await;
+                self.view = View::Login;
+            }
+        }
+
+        self.set_loading(false, "");
+    }
+
+    pub async fn on_auth_success(&mut self) {
+        self.login_password.clear();
+        self.load_workspaces().await;
+
+        // Try to restore last opened workspace
+        let last_workspace_id = WorkspaceState::load()
+            .ok()
+            .flatten()
+            .map(|s| s.workspace_id);
+
+        let restored = if let Some(id) = last_workspace_id {
+            self.workspaces
+                .iter()
+                .enumerate()
+                .find(|(_, w)| w.workspace.id == id)
+                .map(|(idx, ws)| {
+                    self.selected_workspace_idx = idx;
+                    self.current_workspace = Some(ws.workspace.clone());
+                })
+                .is_some()
+        } else {
+            false
+        };
+
+        // Fallback to first workspace if not restored
+        if !restored {
+            if let Some(ws) = self.workspaces.first() {
+                self.current_workspace = Some(ws.workspace.clone());
+                self.selected_workspace_idx = 0;
+            }
+        }
+
+        if self.current_workspace.is_some() {
+            self.view = View::Home;
+            self.load_home_data().await;
+        } else {
+            // No workspaces available
+            self.view = View::WorkspaceSelect;
+        }
+    }
+
+    async fn load_home_data(&mut self) {
+        // Load quote asynchronously (don't block UI)
+        let (quote, author) = crate::api::quote::get_quote().await;
+        self.home_quote = Some(quote);
+        self.home_quote_author = Some(author);
+
+        // Load workspace stats and calendar tasks
+        if let Some(ref workspace) = self.current_workspace {
+            if let Ok(stats) = self.api.get_workspace_stats(workspace.id).await {
+                self.home_stats = stats;
+            }
+
+            // Recent activity for the feed panel
+            if let Ok(response) = self.api.list_activity(workspace.id, 1, 20).await {
+                self.home_activity = response.entries;
+            }
+
+            let workspace_id = workspace.id;
+            self.refresh_notifications(workspace_id).await;
+
+            // Load tasks for calendar (current month)
+            self.load_calendar_tasks().await;
+        }
+    }
+
+    async fn load_calendar_tasks(&mut self) {
+        let Some(ref workspace) = self.current_workspace else { return };
+
+        // Calculate date range for current calendar month
+        let first_day = match NaiveDate::from_ymd_opt(self.calendar_year, self.calendar_month, 1) {
+            Some(d) => d,
+            None => return,
+        };
+
+        // Get last day of month
+        let last_day = if self.calendar_month == 12 {
+            NaiveDate::from_ymd_opt(self.calendar_year + 1, 1, 1)
+        } else {
+            NaiveDate::from_ymd_opt(self.calendar_year, self.calendar_month + 1, 1)
+        }
+        .and_then(|d| d.pred_opt())
+        .unwrap_or(first_day);
+
+        // Fetch tasks with due dates in this range
+        let params = TaskListParams {
+            due_after: Some(first_day),
+            due_before: Some(last_day),
+            limit: Some(500),
+            ..Default::default()
+        };
+
+        if let Ok(response) = self.api.list_tasks(workspace.id, Some(&params)).await {
+            self.calendar_tasks.clear();
+            for task in response.tasks {
+                if let Some(due_date) = task.due_date {
+                    *self.calendar_tasks.entry(due_date).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+
+    pub fn on_auth_failed(&mut self, msg: String) {
+        self.set_error(format!("Login failed: {}", msg));
+        self.login_password.clear();
+    }
+
+    async fn load_workspaces(&mut self) {
+        self.set_loading(true, "Loading workspaces...");
+
+        match self.api.list_workspaces().await {
+            Ok(workspaces) => {
+                self.workspaces = workspaces;
+            }
+            Err(e) => {
+                self.set_error(format!("Failed to load workspaces: {}", e));
+            }
+        }
+
+        self.set_loading(false, "");
+    }
+
+    pub fn on_workspaces_loaded(&mut self, workspaces: Vec<WorkspaceWithRole>) {
+        self.workspaces = workspaces;
+        self.is_connected = true;
+        self.set_loading(false, "");
+    }
+
+    async fn load_workspace_data(&mut self, _tx: mpsc::Sender<AppEvent>) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+
+        self.set_loading(true, "Loading workspace data...");
+
+        // Load statuses
+        let statuses = match self.api.list_statuses(workspace_id).await {
+            Ok(s) => s,
+            Err(e) => {
+                self.set_error(format!("Failed to load statuses: {}", e));
+                self.set_loading(false, "");
+                return;
+            }
+        };
+
+        // Load tasks
+        let tasks = match self.api.list_tasks(workspace_id, None).await {
+            Ok(response) => response.tasks,
+            Err(e) => {
+                self.set_error(format!("Failed to load tasks: {}", e));
+                self.set_loading(false, "");
+                return;
+            }
+        };
+
+        // Load workspace tags
+        self.workspace_tags = match self.api.list_tags(workspace_id).await {
+            Ok(tags) => tags,
+            Err(_) => Vec::new(), // Silently fail for tags
+        };
+
+        // Load custom field definitions
+        self.workspace_custom_fields = self.api.list_custom_fields(workspace_id).await.unwrap_or_default();
+
+        // Load the workflow; without it every move is offered and the server decides
+        self.workspace_transitions = self.api.list_transitions(workspace_id).await.unwrap_or_default();
+
+        self.refresh_notifications(workspace_id).await;
+
+        self.on_workspace_data_loaded(statuses, tasks);
+    }
+
+    pub fn on_workspace_data_loaded(&mut self, statuses: Vec<TaskStatus>, tasks: Vec<Task>) {
+        self.columns = build_columns(statuses, &tasks);
+
+        // Initialize scroll offsets for each column
+        self.column_scroll_offsets = vec![0; self.columns.len()];
+
+        self.selected_column = 0;
+        self.selected_task = 0;
+        self.view = View::Dashboard;
+        self.is_connected = true;
+        self.set_loading(false, "");
+    }
+
+    /// Apply a change someone made to the open workspace
+    pub async fn on_workspace_event(&mut self, event: WorkspaceEvent) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) if ws.id == event.workspace_id => ws.id,
+            _ => return,
+        };
+
+        // Our own changes are already on screen, and never notify us
+        if self.user.as_ref().is_some_and(|u| u.id == event.user_id) {
+            return;
+        }
+
+        match event.kind {
+            WorkspaceEventKind::TaskChanged { task_id } => {
+                match self.api.get_task(workspace_id, task_id).await {
+                    Ok(task) => self.upsert_board_task(task),
+                    // Deleted again before we got to it
+                    Err(crate::api::ApiError::NotFound) => self.remove_board_task(task_id),
+                    Err(_) => {}
+                }
+                // Assignments and moves may have notified us
+                self.notifications_refresh_due = true;
+            }
+            WorkspaceEventKind::TaskDeleted { task_id } => self.remove_board_task(task_id),
+            WorkspaceEventKind::CommentsChanged { task_id } => {
+                if self.selected_task_detail.as_ref().is_some_and(|t| t.id == task_id) {
+                    if let Ok(comments) = self.api.list_comments(workspace_id, task_id).await {
+                        self.task_comments = comments;
+                    }
+                }
+                self.notifications_refresh_due = true;
+            }
+            WorkspaceEventKind::TagsChanged => self.refresh_tags(workspace_id).await,
+            WorkspaceEventKind::StatusesChanged => self.refresh_board(workspace_id).await,
+            WorkspaceEventKind::DocumentChanged { .. }
+            | WorkspaceEventKind::DocumentDeleted { .. } => {
+                self.refresh_documents(workspace_id).await
+            }
+        }
+    }
+
+    /// Reload everything shown for the workspace after missing some of its events
+    pub async fn on_workspace_resync(&mut self, workspace_id: uuid::Uuid) {
+        if self.current_workspace.as_ref().map(|ws| ws.id) != Some(workspace_id) {
+            return;
+        }
+
+        self.refresh_tags(workspace_id).await;
+        self.refresh_board(workspace_id).await;
+        self.refresh_documents(workspace_id).await;
+        self.refresh_notifications(workspace_id).await;
+
+        if let Some(task_id) = self.selected_task_detail.as_ref().map(|t| t.id) {
+            if let Ok(comments) = self.api.list_comments(workspace_id, task_id).await {
+                self.task_comments = comments;
+            }
+        }
+    }
+
+    /// Put a fetched task where it now belongs on the board
+    fn upsert_board_task(&mut self, mut task: Task) {
+        if !self.editing_task && self.selected_task_detail.as_ref().is_some_and(|t| t.id == task.id) {
+            self.selected_task_detail = Some(task.clone());
+        }
+
+        let shown = self.columns.iter().any(|c| c.tasks.iter().any(|t| t.id == task.id));
+        // We can't tell whether a task matches the filters, so only keep shown ones fresh
+        if !shown && self.has_active_filters() {
+            return;
+        }
+
+        // Board entries come from the task list, which doesn't carry subtasks
+        task.subtasks.clear();
+
+        let selected_id = self.get_selected_task().map(|t| t.id);
+
+        let same_column = self
+            .columns
+            .iter_mut()
+            .find(|c| c.status.id == task.status_id)
+            .and_then(|c| c.tasks.iter_mut().find(|t| t.id == task.id));
+        if let Some(existing) = same_column {
+            *existing = task;
+        } else {
+            for column in &mut self.columns {
+                column.tasks.retain(|t| t.id != task.id);
+            }
+            if let Some(column) = self.columns.iter_mut().find(|c| c.status.id == task.status_id) {
+                let idx = column
+                    .tasks
+                    .iter()
+                    .position(|t| t.position > task.position)
+                    .unwrap_or(column.tasks.len());
+                column.tasks.insert(idx, task);
+            }
+        }
+
+        self.reselect_task(selected_id);
+    }
+
+    /// Drop a task that was deleted elsewhere
+    fn remove_board_task(&mut self, task_id: uuid::Uuid) {
+        let selected_id = self.get_selected_task().map(|t| t.id);
+        if selected_id == Some(task_id) {
+            // The prompt would otherwise apply to whichever task slides into its place
+            self.confirming_delete = false;
+        }
+
+        for column in &mut self.columns {
+            column.tasks.retain(|t| t.id != task_id);
+        }
+        self.selected_task_ids.retain(|id| *id != task_id);
+        self.reselect_task(selected_id);
+
+        if self.view == View::TaskDetail
+            && self.selected_task_detail.as_ref().is_some_and(|t| t.id == task_id)
+        {
+            self.close_task_detail();
+            self.editing_task = false;
+            self.view = View::Dashboard;
+            self.set_error("The task was deleted by someone else".to_string());
+        }
+    }
+
+    /// Point the cursor back at a task after the board changed around it
+    fn reselect_task(&mut self, task_id: Option<uuid::Uuid>) {
+        if let Some(task_id) = task_id {
+            self.select_task_by_id(task_id);
+        }
+
+        self.selected_column = self.selected_column.min(self.columns.len().saturating_sub(1));
+        let len = self.columns.get(self.selected_column).map_or(0, |c| c.tasks.len());
+        self.selected_task = self.selected_task.min(len.saturating_sub(1));
+    }
+
+    /// Reload workspace tags and the copies embedded in tasks
+    async fn refresh_tags(&mut self, workspace_id: uuid::Uuid) {
+        let Ok(tags) = self.api.list_tags(workspace_id).await else {
+            return;
+        };
+
+        let refresh = |task: &mut Task| {
+            task.tags = task
+                .tags
+                .iter()
+                .filter_map(|tag| tags.iter().find(|t| t.id == tag.id).cloned())
+                .collect();
+        };
+        self.columns
+            .iter_mut()
+            .flat_map(|c| c.tasks.iter_mut())
+            .for_each(refresh);
+        if let Some(task) = self.selected_task_detail.as_mut() {
+            refresh(task);
+        }
+
+        self.workspace_tags = tags;
+    }
+
+    /// Reload columns, tasks and the workflow without leaving the current view
+    async fn refresh_board(&mut self, workspace_id: uuid::Uuid) {
+        let params = if self.has_active_filters() {
+            Some(&self.active_filters)
+        } else {
+            None
+        };
+
+        let Ok(tasks) = self.api.list_tasks(workspace_id, params).await else {
+            return;
+        };
+        let Ok(statuses) = self.api.list_statuses(workspace_id).await else {
+            return;
+        };
+        self.workspace_transitions = self.api.list_transitions(workspace_id).await.unwrap_or_default();
+
+        let selected_id = self.get_selected_task().map(|t| t.id);
+        let selected_status = self.columns.get(self.selected_column).map(|c| c.status.id);
+
+        self.columns = build_columns(statuses, &tasks.tasks);
+        self.column_scroll_offsets = vec![0; self.columns.len()];
+
+        if let Some(idx) = self.columns.iter().position(|c| Some(c.status.id) == selected_status) {
+            self.selected_column = idx;
+        }
+        self.reselect_task(selected_id);
+    }
+
+    /// Reload the knowledge base tree if it's open and nothing in it is being edited
+    async fn refresh_documents(&mut self, workspace_id: uuid::Uuid) {
+        if self.view != View::KnowledgeBase
+            || self.kb_editing
+            || self.kb_creating
+            || self.kb_confirming_delete
+        {
+            return;
+        }
+
+        let Ok(docs) = self.api.list_documents(workspace_id).await else {
+            return;
+        };
+
+        let selected_id = self.kb_selected_doc.as_ref().map(|d| d.id);
+        self.kb_documents = docs;
+        self.build_kb_visible_list();
+
+        if let Some(idx) = selected_id
+            .and_then(|id| self.kb_visible_list.iter().position(|(d, _)| d.id == id))
+        {
+            self.kb_selected_idx = idx;
+        }
+        self.kb_selected_idx = self.kb_selected_idx.min(self.kb_visible_list.len().saturating_sub(1));
+        // The selected document may sit under a collapsed parent
+        self.kb_selected_doc = selected_id
+            .and_then(|id| self.kb_documents.iter().find(|d| d.id == id))
+            .or_else(|| self.kb_visible_list.get(self.kb_selected_idx).map(|(d, _)| d))
+            .cloned();
+
+        if self.kb_selected_doc.as_ref().map(|d| d.id) != selected_id {
+            self.load_kb_linked_tasks().await;
+        }
+    }
+
+    pub fn move_left(&mut self) {
+        if self.selected_column > 0 {
+            self.selected_column -= 1;
+            self.selected_task = 0;
+            // Reset scroll for new column
+            if let Some(offset) = self.column_scroll_offsets.get_mut(self.selected_column) {
+                *offset = 0;
+            }
+        }
+    }
+
+    pub fn move_right(&mut self) {
+        if !self.columns.is_empty() && self.selected_column < self.columns.len() - 1 {
+            self.selected_column += 1;
+            self.selected_task = 0;
+            // Reset scroll for new column
+            if let Some(offset) = self.column_scroll_offsets.get_mut(self.selected_column) {
+                *offset = 0;
+            }
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        if self.selected_task > 0 {
+            self.selected_task -= 1;
+            // Adjust scroll if selection is above visible area
+            if let Some(offset) = self.column_scroll_offsets.get_mut(self.selected_column) {
+                if self.selected_task < *offset {
+                    *offset = self.selected_task;
+                }
+            }
+        }
+    }
+
+    pub fn move_down(&mut self) {
+        if let Some(column) = self.columns.get(self.selected_column) {
+            if self.selected_task < column.tasks.len().saturating_sub(1) {
+                self.selected_task += 1;
+                // Adjust scroll if selection is below visible area
+                // Assume ~3 tasks visible per column (conservative estimate)
+                // The actual visible count depends on terminal height
+                if let Some(offset) = self.column_scroll_offsets.get_mut(self.selected_column) {
+                    let visible_tasks = 5; // Conservative default, UI will handle actual rendering
+                    if self.selected_task >= *offset + visible_tasks {
+                        *offset = self.selected_task.saturating_sub(visible_tasks - 1);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn get_selected_task(&self) -> Option<&Task> {
+        self.columns
+            .get(self.selected_column)
+            .filter(|col| !self.collapsed_columns.contains(&col.status.id))
+            .and_then(|col| col.tasks.get(self.selected_task))
+    }
+
+    fn toggle_task_selection(&mut self, task_id: uuid::Uuid) {
+        if let Some(idx) = self.selected_task_ids.iter().position(|id| *id == task_id) {
+            self.selected_task_ids.remove(idx);
+        } else {
+            self.selected_task_ids.push(task_id);
+        }
+    }
+
+    /// Mark every task in the current column, or unmark them if all already are
+    fn toggle_column_selection(&mut self) {
+        let Some(column) = self.columns.get(self.selected_column) else {
+            return;
+        };
+        let ids: Vec<uuid::Uuid> = column.tasks.iter().map(|t| t.id).collect();
+
+        if ids.iter().all(|id| self.selected_task_ids.contains(id)) {
+            self.selected_task_ids.retain(|id| !ids.contains(id));
+        } else {
+            for id in ids {
+                if !self.selected_task_ids.contains(&id) {
+                    self.selected_task_ids.push(id);
+                }
+            }
+        }
+    }
+
+    /// Apply one operation to every task marked in visual mode
+    async fn do_bulk_operation(&mut self, operation: BulkTaskOperation) -> Result<(), String> {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return Err("No workspace selected".to_string()),
+        };
+
+        if self.selected_task_ids.is_empty() {
+            return Err("No tasks selected".to_string());
+        }
+
+        let deleting = matches!(operation, BulkTaskOperation::Delete);
+        let focused = self.get_selected_task().map(|t| t.id);
+
+        self.set_loading(true, "Updating tasks...");
+        let result = self
+            .api
+            .bulk_tasks(workspace_id, self.selected_task_ids.clone(), operation)
+            .await;
+        self.set_loading(false, "");
+
+        result.map_err(|e| format!("Bulk update failed: {}", e))?;
+
+        // Moves can spawn recurring tasks and deletes take subtasks along, so
+        // reload the board instead of patching columns in place
+        self.reload_workspace_data().await;
+
+        if deleting {
+            self.selecting_tasks = false;
+            self.selected_task_ids.clear();
+        } else if let Some(task_id) = focused {
+            self.select_task_by_id(task_id);
+        }
+
+        Ok(())
+    }
+
+    async fn do_move_task_left(&mut self) {
+        let targets: Vec<usize> = (0..self.selected_column).rev().collect();
+        self.move_selected_task_towards(&targets).await;
+    }
+
+    async fn do_move_task_right(&mut self) {
+        let targets: Vec<usize> = (self.selected_column + 1..self.columns.len()).collect();
+        self.move_selected_task_towards(&targets).await;
+    }
+
+    /// The user's role in the open workspace
+    fn current_role(&self) -> Option<WorkspaceRole> {
+        let workspace_id = self.current_workspace.as_ref()?.id;
+        self.workspaces
+            .iter()
+            .find(|w| w.workspace.id == workspace_id)
+            .map(|w| w.role)
+    }
+
+    /// Why the workflow won't let a task go straight from one column to another,
+    /// or `None` if it may. Rules that depend on the task itself, like a
+    /// required assignee, are left for the server to explain.
+    fn transition_blocker(&self, from: &TaskStatus, to: &TaskStatus) -> Option<String> {
+        let outgoing: Vec<&StatusTransition> = self
+            .workspace_transitions
+            .iter()
+            .filter(|t| t.from_status_id == from.id)
+            .collect();
+
+        // Columns without outgoing transitions are unrestricted
+        if outgoing.is_empty() {
+            return None;
+        }
+
+        let Some(transition) = outgoing.iter().find(|t| t.to_status_id == to.id) else {
+            return Some(format!("Tasks can't move from \"{}\" to \"{}\"", from.name, to.name));
+        };
+
+        let required = transition.required_role?;
+        if self.current_role().is_some_and(|role| role.at_least(required)) {
+            return None;
+        }
+        Some(format!(
+            "Moving tasks from \"{}\" to \"{}\" needs the {} role or higher",
+            from.name,
+            to.name,
+            format!("{:?}", required).to_lowercase()
+        ))
+    }
+
+    /// Move the selected task to the nearest of `targets` the workflow allows,
+    /// asking for a comment first when that transition needs one
+    async fn move_selected_task_towards(&mut self, targets: &[usize]) {
+        let Some(&nearest) = targets.first() else {
+            return;
+        };
+        if self.get_selected_task().is_none() {
+            return;
+        }
+
+        let from = &self.columns[self.selected_column].status;
+        let allowed = targets
+            .iter()
+            .copied()
+            .find(|&i| self.transition_blocker(from, &self.columns[i].status).is_none());
+
+        let Some(target) = allowed else {
+            let reason = self
+                .transition_blocker(from, &self.columns[nearest].status)
+                .unwrap_or_default();
+            self.set_error(reason);
+            return;
+        };
+
+        let to_id = self.columns[target].status.id;
+        let needs_comment = self
+            .workspace_transitions
+            .iter()
+            .any(|t| t.from_status_id == from.id && t.to_status_id == to_id && t.require_comment);
+
+        if needs_comment {
+            self.entering_move_comment = true;
+            self.move_comment_target = target;
+            self.move_comment_input.clear();
+            self.vim_mode = VimMode::Insert;
+            return;
+        }
+
+        self.do_move_task_to(target, None).await;
+    }
+
+    async fn do_move_task_to(&mut self, target_column: usize, comment: Option<String>) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+
+        let task = match self.get_selected_task() {
+            Some(t) => t.clone(),
+            None => return,
+        };
+
+        let target_status_id = self.columns[target_column].status.id;
+        let source_is_done = self.columns[self.selected_column].status.is_done;
+        let target_is_done = self.columns[target_column].status.is_done;
+
+        match self
+            .api
+            .move_task(workspace_id, task.id, target_status_id, None, comment.as_deref())
+            .await
+        {
+            Ok(updated_task) => {
+                // Remove from current column
+                if let Some(col) = self.columns.get_mut(self.selected_column) {
+                    col.tasks.retain(|t| t.id != task.id);
+                }
+                // Add to target column
+                if let Some(col) = self.columns.get_mut(target_column) {
+                    col.tasks.push(updated_task);
+                    col.tasks.sort_by_key(|t| t.position);
+                }
+                // Move selection
+                self.selected_column = target_column;
+                self.selected_task = self.columns[target_column].tasks.len().saturating_sub(1);
+                self.moving_task = false;
+                // Moving a subtask may change its parent's rollup
+                if let Some(parent_id) = task.parent_id {
+                    self.refresh_subtask_progress(parent_id).await;
+                }
+                // Completing or reopening a task changes what it blocks
+                if source_is_done != target_is_done {
+                    self.sync_blocked_markers(task.id, target_is_done).await;
+                }
+                // Completing a recurring task spawns its next occurrence server-side
+                if !source_is_done && target_is_done && task.recurrence.is_some() {
+                    self.reload_workspace_data().await;
+                    self.select_task_by_id(task.id);
+                }
+            }
+            Err(e) => {
+                self.set_error(format!("Failed to move task: {}", e));
+            }
+        }
+    }
+
+    async fn open_task_detail(&mut self) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+
+        let task = match self.get_selected_task() {
+            Some(t) => t.clone(),
+            None => return,
+        };
+
+        self.set_loading(true, "Loading task details...");
+
+        // Load comments
+        match self.api.list_comments(workspace_id, task.id).await {
+            Ok(comments) => {
+                self.task_comments = comments;
+            }
+            Err(e) => {
+                self.set_error(format!("Failed to load comments: {}", e));
+                self.set_loading(false, "");
+                return;
+            }
+        }
+
+        // Load workspace members for assignee selection
+        match self.api.list_members(workspace_id).await {
+            Ok(members) => {
+                self.workspace_members = members;
+            }
+            Err(_) => {
+                // Non-critical, continue without members
+                self.workspace_members.clear();
+            }
+        }
+
+        // Load linked documents
+        match self.api.list_linked_documents(workspace_id, task.id).await {
+            Ok(docs) => {
+                self.task_linked_documents = docs;
+            }
+            Err(_) => {
+                // Non-critical, continue without linked documents
+                self.task_linked_documents.clear();
+            }
+        }
+
+        // Load dependencies
+        match self.api.list_dependencies(workspace_id, task.id).await {
+            Ok(deps) => {
+                self.task_dependencies = deps;
+            }
+            Err(_) => {
+                // Non-critical, continue without dependencies
+                self.task_dependencies = todo_shared::api::TaskDependencies::default();
+            }
+        }
+
+        // Load work logs
+        match self.api.list_work_logs(workspace_id, task.id).await {
+            Ok(logs) => {
+                self.task_work_logs = logs;
+            }
+            Err(_) => {
+                // Non-critical, continue without work logs
+                self.task_work_logs.clear();
+            }
+        }
+
+        // Load attachments
+        match self.api.list_task_attachments(workspace_id, task.id).await {
+            Ok(attachments) => {
+                self.task_attachments = attachments;
+            }
+            Err(_) => {
+                // Non-critical, continue without attachments
+                self.task_attachments.clear();
+            }
+        }
+
+        // Load checklist
+        match self.api.list_checklist(workspace_id, task.id).await {
+            Ok(items) => {
+                self.task_checklist = items;
+            }
+            Err(_) => {
+                // Non-critical, continue without checklist
+                self.task_checklist.clear();
+            }
+        }
+
+        // Reload the task itself to get its subtasks
+        let task = match self.api.get_task(workspace_id, task.id).await {
+            Ok(full) => Task { tags: task.tags, ..full },
+            Err(_) => task,
+        };
+
+        self.selected_task_detail = Some(task);
+        self.view = View::TaskDetail;
+        self.set_loading(false, "");
+    }
+
+    fn close_task_detail(&mut self) {
+        self.selected_task_detail = None;
+        self.task_comments.clear();
+        self.task_linked_documents.clear();
+        self.adding_comment = false;
+        self.comment_textarea = None;
+        self.comment_cursor = 0;
+        self.replying_to = None;
+        self.reaction_picker_visible = false;
+        self.editing_comment = None;
+        self.comment_confirming_delete = false;
+        self.comment_revisions.clear();
+        self.comment_revisions_visible = false;
+        self.creating_subtask = false;
+        self.new_subtask_title.clear();
+        self.task_dependencies = todo_shared::api::TaskDependencies::default();
+        self.task_work_logs.clear();
+        self.task_checklist.clear();
+        self.checklist_mode = false;
+        self.checklist_cursor = 0;
+        self.adding_checklist_item = false;
+        self.checklist_input.clear();
+        self.task_attachments.clear();
+        self.attachment_mode = false;
+        self.attachment_cursor = 0;
+        self.task_history.clear();
+        self.show_task_history = false;
+        self.adding_blocker_mode = false;
+        self.removing_blocker_mode = false;
+        self.blocker_cursor = 0;
+        self.linking_document_mode = false;
+        self.link_document_cursor = 0;
+        self.unlinking_document_mode = false;
+        self.unlink_document_cursor = 0;
+        self.vim_mode = VimMode::Normal;
+        self.view = View::Dashboard;
+    }
+
+    async fn toggle_task_history(&mut self) {
+        if self.show_task_history {
+            self.show_task_history = false;
+            return;
+        }
+
+        let Some(ref workspace) = self.current_workspace else { return };
+        let Some(ref task) = self.selected_task_detail else { return };
+        let (workspace_id, task_id) = (workspace.id, task.id);
+
+        // Reload every time so edits made in this session show up
+        match self.api.entity_history(workspace_id, ActivityEntityType::Task, task_id).await {
+            Ok(response) => {
+                self.task_history = response.entries;
+                self.show_task_history = true;
+            }
+            Err(e) => {
+                self.set_error(format!("Failed to load history: {}", e));
+            }
+        }
+    }
+
+    async fn toggle_work_timer(&mut self) {
+        let (task_id, task_key) = match self.selected_task_detail {
+            Some(ref t) => (t.id, t.key.clone()),
+            None => return,
+        };
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+
+        match self.work_timer.take() {
+            None => {
+                self.work_timer = Some(WorkTimer {
+                    workspace_id,
+                    task_id,
+                    task_key,
+                    started_at: chrono::Local::now(),
+                });
+            }
+            Some(timer) if timer.task_id == task_id => {
+                self.stop_work_timer(timer).await;
+            }
+            Some(timer) => {
+                self.set_error(format!("Timer already running on {}", timer.task_key));
+                self.work_timer = Some(timer);
+            }
+        }
+    }
+
+    /// Log a running timer before quitting or signing out. Returns false if
+    /// that failed, leaving the timer running and the error shown.
+    async fn log_running_timer(&mut self) -> bool {
+        let Some(timer) = self.work_timer.take() else {
+            return true;
+        };
+        self.stop_work_timer(timer).await;
+        self.work_timer.is_none()
+    }
+
+    /// Stop a timer and log the elapsed time against its task
+    async fn stop_work_timer(&mut self, timer: WorkTimer) {
+        // Round to the nearest minute
+        let elapsed = (chrono::Local::now() - timer.started_at).num_seconds();
+        let minutes = ((elapsed + 30) / 60).min(MAX_TIMER_MINUTES);
+        if minutes < 1 {
+            self.set_error("Timer ran for under a minute, nothing logged".to_string());
+            return;
+        }
+
+        let req = CreateWorkLogRequest {
+            minutes: minutes as i32,
+            work_date: Some(timer.started_at.date_naive()),
+            note: None,
+        };
+
+        self.set_loading(true, "Logging time...");
+
+        match self.api.create_work_log(timer.workspace_id, timer.task_id, req).await {
+            Ok(log) => {
+                let logged = log.minutes as i64;
+                if let Some(ref mut detail) = self.selected_task_detail {
+                    if detail.id == timer.task_id {
+                        detail.logged_minutes += logged;
+                        self.task_work_logs.insert(0, log);
+                    }
+                }
+                for col in &mut self.columns {
+                    if let Some(task) = col.tasks.iter_mut().find(|t| t.id == timer.task_id) {
+                        task.logged_minutes += logged;
+                    }
+                }
+            }
+            Err(e) => {
+                // Keep the timer so the time isn't lost
+                self.set_error(format!("Failed to log time: {}", e));
+                self.work_timer = Some(timer);
+            }
+        }
+
+        self.set_loading(false, "");
+    }
+
+    /// Comments in display order: each thread followed by its replies
+    pub fn comment_rows(&self) -> Vec<&CommentWithAuthor> {
+        self.task_comments
+            .iter()
+            .flat_map(|c| std::iter::once(c).chain(c.replies.iter()))
+            .collect()
+    }
+
+    pub fn selected_comment(&self) -> Option<&CommentWithAuthor> {
+        let rows = self.comment_rows();
+        rows.get(self.comment_cursor.min(rows.len().saturating_sub(1))).copied()
+    }
+
+    /// Whether the user may edit or delete `comment`: its author, or an admin moderating
+    pub fn can_modify_comment(&self, comment: &CommentWithAuthor) -> bool {
+        self.user.as_ref().is_some_and(|u| u.id == comment.user_id)
+            || self.current_role().is_some_and(|role| role.can_admin())
+    }
+
+    async fn handle_reaction_picker_key(&mut self, key: KeyEvent) -> Result<bool> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.reaction_picker_visible = false;
+            }
+            KeyCode::Char('h') | KeyCode::Left => {
+                self.reaction_cursor = (self.reaction_cursor + REACTIONS.len() - 1) % REACTIONS.len();
+            }
+            KeyCode::Char('l') | KeyCode::Right => {
+                self.reaction_cursor = (self.reaction_cursor + 1) % REACTIONS.len();
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                self.reaction_picker_visible = false;
+                self.do_toggle_reaction(REACTIONS[self.reaction_cursor]).await;
+            }
+            KeyCode::Char(c @ '1'..='6') => {
+                self.reaction_picker_visible = false;
+                self.do_toggle_reaction(REACTIONS[c as usize - '1' as usize]).await;
+            }
+            _ => {}
+        }
+        Ok(false)
+    }
+
+    async fn do_toggle_reaction(&mut self, emoji: &str) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+        let task_id = match self.selected_task_detail {
+            Some(ref t) => t.id,
+            None => return,
+        };
+        let comment_id = match self.selected_comment() {
+            Some(c) => c.id,
+            None => return,
+        };
+
+        match self.api.toggle_comment_reaction(workspace_id, task_id, comment_id, emoji).await {
+            Ok(reactions) => {
+                for thread in self.task_comments.iter_mut() {
+                    if thread.id == comment_id {
+                        thread.reactions = reactions;
+                        break;
+                    }
+                    if let Some(reply) = thread.replies.iter_mut().find(|r| r.id == comment_id) {
+                        reply.reactions = reactions;
+                        break;
+                    }
+                }
+            }
+            Err(e) => self.set_error(format!("Failed to react: {}", e)),
+        }
+    }
+
+    async fn do_update_comment(&mut self) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+        let task_id = match self.selected_task_detail {
+            Some(ref t) => t.id,
+            None => return,
+        };
+        let Some(comment_id) = self.editing_comment else { return };
+
+        let content = self.get_comment_content();
+
+        match self.api.update_comment(workspace_id, task_id, comment_id, &content).await {
+            Ok(mut updated) => {
+                // The response doesn't carry the thread, so keep the replies we have
+                for thread in self.task_comments.iter_mut() {
+                    if thread.id == comment_id {
+                        updated.replies = std::mem::take(&mut thread.replies);
+                        *thread = updated;
+                        break;
+                    }
+                    if let Some(reply) = thread.replies.iter_mut().find(|r| r.id == comment_id) {
+                        *reply = updated;
+                        break;
+                    }
+                }
+                self.comment_textarea = None;
+                self.adding_comment = false;
+                self.editing_comment = None;
+                self.vim_mode = VimMode::Normal;
+            }
+            Err(e) => {
+                self.set_error(format!("Failed to update comment: {}", e));
+            }
+        }
+    }
+
+    async fn do_delete_comment(&mut self) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+        let task_id = match self.selected_task_detail {
+            Some(ref t) => t.id,
+            None => return,
+        };
+        let comment_id = match self.selected_comment() {
+            Some(c) => c.id,
+            None => return,
+        };
+
+        match self.api.delete_comment(workspace_id, task_id, comment_id).await {
+            Ok(()) => {
+                // Replies go to the trash with their thread
+                self.task_comments.retain(|c| c.id != comment_id);
+                for thread in self.task_comments.iter_mut() {
+                    thread.replies.retain(|r| r.id != comment_id);
+                }
+                self.comment_cursor = self.comment_cursor.min(self.comment_rows().len().saturating_sub(1));
+            }
+            Err(e) => {
+                self.set_error(format!("Failed to delete comment: {}", e));
+            }
+        }
+    }
+
+    async fn open_comment_revisions(&mut self) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+        let task_id = match self.selected_task_detail {
+            Some(ref t) => t.id,
+            None => return,
+        };
+        let comment_id = match self.selected_comment() {
+            Some(c) => c.id,
+            None => return,
+        };
+
+        match self.api.list_comment_revisions(workspace_id, task_id, comment_id).await {
+            Ok(revisions) => {
+                self.comment_revisions = revisions;
+                self.comment_revisions_scroll = 0;
+                self.comment_revisions_visible = true;
+            }
+            Err(e) => {
+                self.set_error(format!("Failed to load comment history: {}", e));
+            }
+        }
+    }
+
+    async fn do_add_comment(&mut self) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+
+        let task_id = match self.selected_task_detail {
+            Some(ref t) => t.id,
+            None => return,
+        };
+
+        let content = self.get_comment_content();
+
+        match self.api.create_comment(workspace_id, task_id, &content, self.replying_to).await {
+            Ok(comment) => {
+                let comment_id = comment.id;
+                match comment.parent_comment_id {
+                    Some(parent_id) => {
+                        if let Some(parent) = self.task_comments.iter_mut().find(|c| c.id == parent_id) {
+                            parent.replies.push(comment);
+                        }
+                    }
+                    None => self.task_comments.push(comment),
+                }
+                if let Some(idx) = self.comment_rows().iter().position(|c| c.id == comment_id) {
+                    self.comment_cursor = idx;
+                }
+                self.comment_textarea = None;
+                self.adding_comment = false;
+                self.replying_to = None;
+                self.vim_mode = VimMode::Normal;
+            }
+            Err(e) => {
+                self.set_error(format!("Failed to add comment: {}", e));
+            }
+        }
+    }
+
+    async fn do_create_task(&mut self) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+
+        // Use currently selected column's status
+        let status_id = match self.columns.get(self.selected_column) {
+            Some(col) => col.status.id,
+            None => return,
+        };
+
+        // Determine default assignee: workspace setting > self
+        let assignee = self.current_workspace
+            .as_ref()
+            .and_then(|ws| ws.settings.default_assignee)
+            .or_else(|| self.user.as_ref().map(|u| u.id));
+
+        let description = self.get_new_task_description();
+        let req = CreateTaskRequest {
+            title: self.new_task_title.clone(),
+            status_id,
+            description: if description.is_empty() {
+                None
+            } else {
+                Some(description)
+            },
+            priority: None,
+            due_date: None,
+            time_estimate_minutes: None,
+            assignees: assignee.into_iter().collect(),
+            watchers: Vec::new(),
+            parent_id: None,
+            recurrence: None,
+        };
+
+        self.set_loading(true, "Creating task...");
+
+        match self.api.create_task(workspace_id, req).await {
+            Ok(task) => {
+                // Add to current column
+                if let Some(col) = self.columns.get_mut(self.selected_column) {
+                    col.tasks.push(task);
+                    col.tasks.sort_by_key(|t| t.position);
+                    // Select the new task
+                    self.selected_task = col.tasks.len().saturating_sub(1);
+                }
+                // Clear form
+                self.creating_task = false;
+                self.new_task_title.clear();
+                self.new_task_description_textarea = None;
+                self.new_task_field = NewTaskField::Title;
+                self.vim_mode = VimMode::Normal;
+            }
+            Err(e) => {
+                self.set_error(format!("Failed to create task: {}", e));
+            }
+        }
+
+        self.set_loading(false, "");
+    }
+
+    async fn do_create_subtask(&mut self) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+
+        let parent_id = match self.selected_task_detail {
+            Some(ref t) => t.id,
+            None => return,
+        };
+
+        // Subtasks start in the first column
+        let status_id = match self.columns.first() {
+            Some(col) => col.status.id,
+            None => return,
+        };
+
+        let assignee = self.current_workspace
+            .as_ref()
+            .and_then(|ws| ws.settings.default_assignee)
+            .or_else(|| self.user.as_ref().map(|u| u.id));
+
+        let req = CreateTaskRequest {
+            title: self.new_subtask_title.clone(),
+            status_id,
+            description: None,
+            priority: None,
+            due_date: None,
+            time_estimate_minutes: None,
+            assignees: assignee.into_iter().collect(),
+            watchers: Vec::new(),
+            parent_id: Some(parent_id),
+            recurrence: None,
+        };
+
+        self.set_loading(true, "Creating subtask...");
+
+        match self.api.create_task(workspace_id, req).await {
+            Ok(task) => {
+                if let Some(col) = self.columns.first_mut() {
+                    col.tasks.push(task.clone());
+                    col.tasks.sort_by_key(|t| t.position);
+                }
+                if let Some(ref mut detail) = self.selected_task_detail {
+                    detail.subtasks.push(task);
+                }
+                self.refresh_subtask_progress(parent_id).await;
+                self.creating_subtask = false;
+                self.new_subtask_title.clear();
+                self.vim_mode = VimMode::Normal;
+            }
+            Err(e) => {
+                self.set_error(format!("Failed to create subtask: {}", e));
+            }
+        }
+
+        self.set_loading(false, "");
+    }
+
+    /// Re-fetch a parent task's subtask rollup after one of its children changed
+    async fn refresh_subtask_progress(&mut self, parent_id: uuid::Uuid) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+
+        let progress = match self.api.get_task(workspace_id, parent_id).await {
+            Ok(parent) => parent.subtask_progress,
+            Err(_) => return,
+        };
+
+        for col in &mut self.columns {
+            for task in &mut col.tasks {
+                if task.id == parent_id {
+                    task.subtask_progress = progress;
+                }
+            }
+        }
+        if let Some(ref mut detail) = self.selected_task_detail {
+            if detail.id == parent_id {
+                detail.subtask_progress = progress;
+            }
+        }
+    }
+
+    async fn do_delete_task(&mut self) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+
+        let task = match self.get_selected_task() {
+            Some(t) => t.clone(),
+            None => return,
+        };
+
+        self.set_loading(true, "Deleting task...");
+
+        match self.api.delete_task(workspace_id, task.id).await {
+            Ok(()) => {
+                // Remove from current column
+                if let Some(col) = self.columns.get_mut(self.selected_column) {
+                    col.tasks.retain(|t| t.id != task.id);
+                    // Adjust selection if needed
+                    if self.selected_task >= col.tasks.len() && !col.tasks.is_empty() {
+                        self.selected_task = col.tasks.len() - 1;
+                    }
+                }
+                // Subtasks are deleted along with their parent
+                let mut removed = vec![task.id];
+                while let Some(id) = removed.pop() {
+                    for col in &mut self.columns {
+                        removed.extend(col.tasks.iter().filter(|t| t.parent_id == Some(id)).map(|t| t.id));
+                        col.tasks.retain(|t| t.parent_id != Some(id));
+                    }
+                }
+                // Dependencies on the deleted task are gone as well
+                for col in &mut self.columns {
+                    for t in &mut col.tasks {
+                        t.blocked_by.retain(|id| *id != task.id);
+                    }
+                }
+                if let Some(parent_id) = task.parent_id {
+                    self.refresh_subtask_progress(parent_id).await;
+                }
+                self.confirming_delete = false;
+            }
+            Err(e) => {
+                self.set_error(format!("Failed to delete task: {}", e));
+            }
+        }
+
+        self.set_loading(false, "");
+    }
+
+    fn enter_edit_mode(&mut self) {
+        // Extract all values first to avoid borrow conflicts
+        let task_data = self.selected_task_detail.as_ref().map(|task| {
+            (
+                task.title.clone(),
+                task.description.clone().unwrap_or_default(),
+                task.priority,
+                task.due_date.map(|d| d.to_string()).unwrap_or_default(),
+                task.time_estimate_minutes.map(|m| m.to_string()).unwrap_or_default(),
+                task.recurrence.as_ref().map(|r| r.to_string()).unwrap_or_default(),
+                task.assignees.clone(),
+                task.watchers.clone(),
+                task.tags.iter().map(|t| t.id).collect::<Vec<_>>(),
+            )
+        });
+
+        if let Some((title, description, priority, due_date, time_estimate, recurrence, assignees, watchers, tags)) = task_data {
+            self.editing_task = true;
+            self.edit_field = TaskEditField::Title;
+            self.edit_task_title = title;
+            self.init_edit_task_description_textarea(&description);
+            self.edit_task_priority = priority;
+            self.edit_task_due_date_str = due_date;
+            self.edit_task_time_estimate_str = time_estimate;
+            self.edit_task_recurrence_str = recurrence;
+            self.edit_task_assignees = assignees;
+            self.edit_task_watchers = watchers;
+            self.assignee_selector_cursor = 0;
+            self.watcher_selector_cursor = 0;
+            self.task_edit_selected_tags = tags;
+            self.tag_selector_cursor = 0;
+        }
+
+        // One text input per workspace custom field
+        let custom_field_inputs: Vec<String> = match self.selected_task_detail {
+            Some(ref task) => self
+                .workspace_custom_fields
+                .iter()
+                .map(|field| {
+                    task.custom_fields
+                        .iter()
+                        .find(|v| v.field_id == field.id)
+                        .map(|v| self.custom_field_display(field, &v.value))
+                        .unwrap_or_default()
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        self.edit_custom_field_inputs = custom_field_inputs;
+        self.edit_custom_field_cursor = 0;
+    }
+
+    /// Render a custom field value as text, showing member names for user fields
+    pub fn custom_field_display(&self, field: &CustomField, value: &serde_json::Value) -> String {
+        if field.field_type == CustomFieldType::User {
+            if let Some(member) = value
+                .as_str()
+                .and_then(|id| self.workspace_members.iter().find(|m| m.user_id.to_string() == id))
+            {
+                return member.display_name.clone();
+            }
+        }
+        field.format_value(value)
+    }
+
+    /// Parse a custom field text input, resolving member names for user fields
+    fn parse_custom_field_input(&self, field: &CustomField, input: &str) -> Result<serde_json::Value, String> {
+        let input = input.trim();
+        if field.field_type == CustomFieldType::User && !input.is_empty() {
+            let needle = input.to_lowercase();
+            if let Some(member) = self
+                .workspace_members
+                .iter()
+                .find(|m| m.display_name.to_lowercase() == needle)
+                .or_else(|| self.workspace_members.iter().find(|m| m.display_name.to_lowercase().contains(&needle)))
+            {
+                return Ok(serde_json::Value::String(member.user_id.to_string()));
+            }
+        }
+        field.parse_input(input)
+    }
+
+    async fn do_update_task(&mut self) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+
+        let task_id = match self.selected_task_detail {
+            Some(ref t) => t.id,
+            None => return,
+        };
+
+        // Parse due date; an empty input clears it, so a typo must not
+        let due_date = if self.edit_task_due_date_str.trim().is_empty() {
+            None
+        } else {
+            match NaiveDate::parse_from_str(self.edit_task_due_date_str.trim(), "%Y-%m-%d") {
+                Ok(date) => Some(date),
+                Err(_) => {
+                    self.set_error("Invalid due date, expected YYYY-MM-DD".to_string());
+                    return;
+                }
+            }
+        };
+
+        // Parse time estimate, likewise
+        let time_estimate_minutes = if self.edit_task_time_estimate_str.trim().is_empty() {
+            None
+        } else {
+            match self.edit_task_time_estimate_str.trim().parse::<i32>() {
+                Ok(minutes) if minutes >= 0 => Some(minutes),
+                _ => {
+                    self.set_error("Invalid time estimate, expected minutes".to_string());
+                    return;
+                }
+            }
+        };
+
+        // Parse recurrence rule, e.g. "weekly/2 mon,thu until 2026-12-31" or "monthly day 31"
+        let recurrence = if self.edit_task_recurrence_str.trim().is_empty() {
+            None
+        } else {
+            match self.edit_task_recurrence_str.parse::<RecurrenceRule>() {
+                Ok(rule) => Some(rule),
+                Err(e) => {
+                    self.set_error(format!("Invalid recurrence: {}", e));
+                    return;
+                }
+            }
+        };
+
+        // Parse custom field inputs; empty inputs clear the field
+        let parsed_custom_fields: Result<Vec<CustomFieldValue>, String> = self
+            .workspace_custom_fields
+            .iter()
+            .zip(&self.edit_custom_field_inputs)
+            .map(|(field, input)| {
+                self.parse_custom_field_input(field, input)
+                    .map(|value| CustomFieldValue { field_id: field.id, value })
+            })
+            .collect();
+        let custom_field_values = match parsed_custom_fields {
+            Ok(values) => values,
+            Err(e) => {
+                self.set_error(e);
+                return;
+            }
+        };
+
+        let req = self.edit_task_request(due_date, time_estimate_minutes, recurrence);
+
+        self.set_loading(true, "Updating task...");
+
+        // Try to update, refresh token if unauthorized
+        let result = self.update_task_with_retry(workspace_id, task_id, req.clone()).await;
+
+        match result {
+            Ok(updated_task) => {
+                self.finish_task_update(workspace_id, updated_task, custom_field_values).await;
+            }
+            Err(crate::api::ApiError::Conflict(_)) => {
+                self.open_task_conflict(workspace_id, req, custom_field_values).await;
+            }
+            Err(e) => {
+                self.set_error(format!("Failed to update task: {}", e));
+            }
+        }
+
+        self.set_loading(false, "");
+    }
+
+    /// Build the update from the edit form, based on the version being edited.
+    /// Fields left empty in the form are cleared.
+    fn edit_task_request(
+        &self,
+        due_date: Option<NaiveDate>,
+        time_estimate_minutes: Option<i32>,
+        recurrence: Option<RecurrenceRule>,
+    ) -> UpdateTaskRequest {
+        let description = self.get_edit_task_description();
+        UpdateTaskRequest {
+            title: Patch::Value(self.edit_task_title.clone()),
+            description: Patch::set_or_clear(Some(description).filter(|d| !d.is_empty())),
+            priority: Patch::set_or_clear(self.edit_task_priority),
+            due_date: Patch::set_or_clear(due_date),
+            time_estimate_minutes: Patch::set_or_clear(time_estimate_minutes),
+            assignees: Patch::Value(self.edit_task_assignees.clone()),
+            watchers: Patch::Value(self.edit_task_watchers.clone()),
+            recurrence: Patch::set_or_clear(recurrence),
+            expected_version: self.selected_task_detail.as_ref().map(|t| t.version),
+            ..Default::default()
+        }
+    }
+
+    /// Save tags and custom fields for a just-updated task and leave edit mode
+    async fn finish_task_update(
+        &mut self,
+        workspace_id: uuid::Uuid,
+        mut updated_task: Task,
+        custom_field_values: Vec<CustomFieldValue>,
+    ) {
+        let task_id = updated_task.id;
+
+        // Also update tags
+        let tag_ids = self.task_edit_selected_tags.clone();
+        if let Err(e) = self.api.set_task_tags(workspace_id, task_id, tag_ids.clone()).await {
+            self.set_error(format!("Failed to update tags: {}", e));
+        } else {
+            // Update tags in the task
+            updated_task.tags = self.workspace_tags
+                .iter()
+                .filter(|t| tag_ids.contains(&t.id))
+                .cloned()
+                .collect();
+        }
+
+        // Also update custom fields
+        if !custom_field_values.is_empty() {
+            match self.api.set_task_custom_fields(workspace_id, task_id, custom_field_values).await {
+                Ok(values) => updated_task.custom_fields = values,
+                Err(e) => self.set_error(format!("Failed to update custom fields: {}", e)),
+            }
+        }
+
+        // Update the task detail
+        self.selected_task_detail = Some(updated_task.clone());
+
+        // Update in columns
+        for col in &mut self.columns {
+            for task in &mut col.tasks {
+                if task.id == task_id {
+                    *task = updated_task.clone();
+                }
+            }
+        }
+
+        self.editing_task = false;
+        self.vim_mode = VimMode::Normal;
+    }
+
+    async fn update_task_with_retry(
+        &mut self,
+        workspace_id: uuid::Uuid,
+        task_id: uuid::Uuid,
+        req: UpdateTaskRequest,
+    ) -> Result<Task, crate::api::ApiError> {
+        // First attempt
+        match self.api.update_task(workspace_id, task_id, req.clone()).await {
+            Ok(task) => Ok(task),
+            Err(crate::api::ApiError::Unauthorized) => {
+                // Try to refresh token
+                if self.api.refresh().await.is_ok() {
+                    // Retry with new token
+                    self.api.update_task(workspace_id, task_id, req).await
+                } else {
+                    Err(crate::api::ApiError::Unauthorized)
+                }
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    // ============ Edit Conflicts ============
+
+    /// Someone saved the task while we were editing it. Fetch their version and
+    /// prepare both a merge (their changes plus the fields we touched) and an
+    /// overwrite, then let the user pick.
+    async fn open_task_conflict(
+        &mut self,
+        workspace_id: uuid::Uuid,
+        mine: UpdateTaskRequest,
+        custom_field_values: Vec<CustomFieldValue>,
+    ) {
+        let base = match self.selected_task_detail {
+            Some(ref t) => t.clone(),
+            None => return,
+        };
+
+        let theirs = match self.api.get_task(workspace_id, base.id).await {
+            Ok(task) => task,
+            Err(e) => {
+                self.set_error(format!("Task was changed by someone else, and reloading it failed: {}", e));
+                return;
+            }
+        };
+
+        let mut fields = Vec::new();
+        let mut merged = UpdateTaskRequest {
+            expected_version: Some(theirs.version),
+            ..Default::default()
+        };
+
+        // Resolve what the edit would leave in each field, then compare
+        let mine_title = mine.title.clone().apply(Some(base.title.clone())).unwrap_or_default();
+        if compare_field(&mut fields, "Title", &base.title, &theirs.title, &mine_title, |t| t.clone()) {
+            merged.title = Patch::Value(mine_title);
+        }
+        let mine_priority = mine.priority.clone().apply(base.priority);
+        let show_priority = |p: &Option<Priority>| p.map(|p| format!("{:?}", p)).unwrap_or_else(|| "-".to_string());
+        if compare_field(&mut fields, "Priority", &base.priority, &theirs.priority, &mine_priority, show_priority) {
+            merged.priority = Patch::set_or_clear(mine_priority);
+        }
+        let mine_due_date = mine.due_date.clone().apply(base.due_date);
+        let show_date = |d: &Option<NaiveDate>| d.map(|d| d.to_string()).unwrap_or_else(|| "-".to_string());
+        if compare_field(&mut fields, "Due date", &base.due_date, &theirs.due_date, &mine_due_date, show_date) {
+            merged.due_date = Patch::set_or_clear(mine_due_date);
+        }
+        let mine_estimate = mine.time_estimate_minutes.clone().apply(base.time_estimate_minutes);
+        let show_estimate = |m: &Option<i32>| m.map(|m| format!("{}m", m)).unwrap_or_else(|| "-".to_string());
+        if compare_field(
+            &mut fields,
+            "Estimate",
+            &base.time_estimate_minutes,
+            &theirs.time_estimate_minutes,
+            &mine_estimate,
+            show_estimate,
+        ) {
+            merged.time_estimate_minutes = Patch::set_or_clear(mine_estimate);
+        }
+        let mine_recurrence = mine.recurrence.clone().apply(base.recurrence.clone());
+        let show_recurrence = |r: &Option<RecurrenceRule>| {
+            r.as_ref().map(|r| r.to_string()).unwrap_or_else(|| "-".to_string())
+        };
+        if compare_field(&mut fields, "Repeats", &base.recurrence, &theirs.recurrence, &mine_recurrence, show_recurrence) {
+            merged.recurrence = Patch::set_or_clear(mine_recurrence);
+        }
+
+        // Member lists are compared as sets
+        let sorted = |ids: &[uuid::Uuid]| {
+            let mut ids = ids.to_vec();
+            ids.sort();
+            ids
+        };
+        let show_members = |ids: &Vec<uuid::Uuid>| self.member_names(ids);
+        let mine_assignees = sorted(&mine.assignees.clone().apply(Some(base.assignees.clone())).unwrap_or_default());
+        if compare_field(
+            &mut fields,
+            "Assignees",
+            &sorted(&base.assignees),
+            &sorted(&theirs.assignees),
+            &mine_assignees,
+            show_members,
+        ) {
+            merged.assignees = mine.assignees.clone();
+        }
+        let mine_watchers = sorted(&mine.watchers.clone().apply(Some(base.watchers.clone())).unwrap_or_default());
+        if compare_field(
+            &mut fields,
+            "Watchers",
+            &sorted(&base.watchers),
+            &sorted(&theirs.watchers),
+            &mine_watchers,
+            show_members,
+        ) {
+            merged.watchers = mine.watchers.clone();
+        }
+
+        // The description is merged line by line
+        let base_description = base.description.clone().unwrap_or_default();
+        let theirs_description = theirs.description.clone().unwrap_or_default();
+        let mine_description = mine.description.clone().apply(base.description.clone()).unwrap_or_default();
+        let merged_description = merge::merge3(&base_description, &theirs_description, &mine_description);
+        if let Some(ref text) = merged_description {
+            if *text != theirs_description {
+                merged.description = Patch::set_or_clear(Some(text.clone()).filter(|t| !t.is_empty()));
+            }
+        }
+
+        let label = if theirs.key.is_empty() {
+            theirs.title.clone()
+        } else {
+            format!("{} {}", theirs.key, theirs.title)
+        };
+
+        self.edit_conflict = Some(EditConflict {
+            label,
+            server_version: theirs.version,
+            fields,
+            text_diff: merge::diff_lines(&theirs_description, &mine_description),
+            scroll: 0,
+            resolution: ConflictResolution::Task {
+                overwrite: Box::new(UpdateTaskRequest {
+                    expected_version: Some(theirs.version),
+                    ..mine
+                }),
+                merge: merged_description.map(|_| Box::new(merged)),
+                custom_field_values,
+            },
+        });
+    }
+
+    /// Same as `open_task_conflict`, for a knowledge base page
+    async fn open_document_conflict(&mut self, workspace_id: uuid::Uuid, mine: UpdateDocumentRequest) {
+        let base = match self.kb_selected_doc {
+            Some(ref d) => d.clone(),
+            None => return,
+        };
+
+        let theirs = match self.api.get_document(workspace_id, base.id).await {
+            Ok(doc) => doc,
+            Err(e) => {
+                self.set_error(format!("Page was changed by someone else, and reloading it failed: {}", e));
+                return;
+            }
+        };
+
+        let mut fields = Vec::new();
+        let mut merged = UpdateDocumentRequest {
+            expected_version: Some(theirs.version),
+            ..Default::default()
+        };
+
+        let mine_title = mine.title.clone().apply(Some(base.title.clone())).unwrap_or_default();
+        if compare_field(&mut fields, "Title", &base.title, &theirs.title, &mine_title, |t| t.clone()) {
+            merged.title = Patch::Value(mine_title);
+        }
+
+        let base_content = base.content.clone().unwrap_or_default();
+        let theirs_content = theirs.content.clone().unwrap_or_default();
+        let mine_content = mine.content.clone().apply(base.content.clone()).unwrap_or_default();
+        let merged_content = merge::merge3(&base_content, &theirs_content, &mine_content);
+        if let Some(ref text) = merged_content {
+            if *text != theirs_content {
+                merged.content = Patch::set_or_clear(Some(text.clone()).filter(|t| !t.is_empty()));
+            }
+        }
+
+        self.edit_conflict = Some(EditConflict {
+            label: theirs.title.clone(),
+            server_version: theirs.version,
+            fields,
+            text_diff: merge::diff_lines(&theirs_content, &mine_content),
+            scroll: 0,
+            resolution: ConflictResolution::Document {
+                overwrite: UpdateDocumentRequest {
+                    expected_version: Some(theirs.version),
+                    ..mine
+                },
+                merge: merged_content.map(|_| merged),
+            },
+        });
+    }
+
+    async fn handle_edit_conflict_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('o') => {
+                if let Some(conflict) = self.edit_conflict.take() {
+                    self.resolve_edit_conflict(conflict, false).await;
+                }
+            }
+            KeyCode::Char('m') => match self.edit_conflict.take() {
+                Some(conflict) if conflict.can_merge() => {
+                    self.resolve_edit_conflict(conflict, true).await;
+                }
+                Some(conflict) => {
+                    self.edit_conflict = Some(conflict);
+                    self.set_error(
+                        "Both edits changed the same lines; overwrite, or go back and edit".to_string(),
+                    );
+                }
+                None => {}
+            },
+            // Back to the editor with the edit intact
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.edit_conflict = None;
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                if let Some(ref mut conflict) = self.edit_conflict {
+                    conflict.scroll = conflict.scroll.saturating_add(1);
+                }
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                if let Some(ref mut conflict) = self.edit_conflict {
+                    conflict.scroll = conflict.scroll.saturating_sub(1);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Retry the rejected save as a merge or an overwrite. If someone saved
+    /// yet again in the meantime, the prompt comes back with their new version.
+    async fn resolve_edit_conflict(&mut self, conflict: EditConflict, merge: bool) {
+        let workspace_id = match self.current_workspace {
+            Some(ref ws) => ws.id,
+            None => return,
+        };
+
+        match conflict.resolution {
+            ConflictResolution::Task { overwrite, merge: merged, custom_field_values } => {
+                let task_id = match self.selected_task_detail {
+                    Some(ref t) => t.id,
+                    None => return,
+                };
+                let req = match (merge, merged) {
+                    (true, Some(merged)) => *merged,
+                    _ => (*overwrite).clone(),
+                };
+
+                self.set_loading(true, "Updating task...");
+                match self.update_task_with_retry(workspace_id, task_id, req).await {
+                    Ok(updated_task) => {
+                        self.finish_task_update(workspace_id, updated_task, custom_field_values).await;
+                    }
+                    Err(crate::api::ApiError::Conflict(_)) => {
+                        self.open_task_conflict(workspace_id, *overwrite, custom_field_values).await;
+                    }
+                    Err(e) => self.set_error(format!("Failed to update task: {}", e)),
+                }
+                self.set_loading(false, "");
+            }
+            ConflictResolution::Document { overwrite, merge: merged } => {
+                let doc_id = match self.kb_selected_doc {
+                    Some(ref d) => d.id,
+                    None => return,
+                };
+                let req = match (merge, merged) {
+                    (true, Some(merged)) => merged,
+                    _ => overwrite.clone(),
+                };
+
+                self.set_loading(true, "Updating document...");
+                match self.api.update_document(workspace_id, doc_id, req).await {
+                    Ok(updated) => self.finish_document_update(updated),
+                    Err(crate::api::ApiError::Conflict(_)) => {
+                        self.open_document_conflict(workspace_id, overwrite).await;
+                    }
+                    Err(e) => self.set_error(format!("Failed to update document: {}", e)),
+                }
+                self.set_loading(false, "");
+            }
+        }
+    }
+
+    /// Display names for a list of member ids
+    fn member_names(&self, ids: &[uuid::Uuid]) -> String {
+        if ids.is_empty() {
+            return "-".to_string();
+        }
+        ids.iter()
+            .map(|id| {
+                self.workspace_members
+                    .iter()
+                    .find(|m| &m.user_id == id)
+                    .map(|m| m.display_name.as_str())
+                    .unwrap_or("Unknown")
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    // ============ Knowledge Base ============
+
+    async fn open_knowledge_base(&mut self) {
+        let workspace_id = match &self.current_workspace {
+            Some(w) => w.id,
+            None => return,
+        };
+
+        self.set_loading(true, "Loading knowledge base...");
+
+        match self.api.list_documents(workspace_id).await {
+            Ok(docs) => {
+                self.kb_documents = docs;
+                self.build_kb_visible_list();
+                self.kb_selected_idx = 0;
+                self.kb_selected_doc = self.kb_visible_list.first().map(|(d, _)| d.clone());
+                self.load_kb_linked_tasks().await;
+                self.view = View::KnowledgeBase;
+            }
+            Err(e) => {
+                self.set_error(format!("Failed to load documents: {}", e));
+            }
+        }
+
+        self.set_loading(false, "");
+    }
+
+    fn build_kb_visible_list(&mut self) {
+        self.kb_visible_list.clear();
+
+        // Clone documents to avoid borrow issues
+        let docs = self.kb_documents.clone();
+        let expanded = self.kb_expanded.clone();
+
+        // Build the list iteratively using a stack
+        let mut stack: Vec<(Document, usize)> = Vec::new();
+
+        // Get root documents (no parent)
+        let mut roots: Vec<Document> = docs
+            .iter()
+            .filter(|d| d.parent_id.is_none())
+            .cloned()
+            .collect();
+        roots.sort_by(|a, b| b.title.cmp(&a.title)); // Reverse for stack
+
+        for doc in roots {
+            stack.push((doc, 0));
+        }
+
+        while let Some((doc, depth)) = stack.pop() {
+            let doc_id = doc.id;
+            self.kb_visible_list.push((doc, depth));
+
+            // If expanded, add children
+            if expanded.contains(&doc_id) {
+                let mut children: Vec<Document> = docs
+                    .iter()
+                    .filter(|d| d.parent_id == Some(doc_id))
+                    .cloned()
+                    .collect();
+                children.sort_by(|a, b| b.title.cmp(&a.title)); // Reverse for stack
+
+                for child in children {
+                    stack.push((child, depth + 1));
+                }
+            }
+        }
+    }
+
+
+    async fn handle_knowledge_base_key(
+        &mut self,
+        key: KeyEvent,
+        tx: mpsc::Sender<AppEvent>,
+    ) -> Result<bool> {
+        // Handle workspace modal
+        if self.workspace_modal_visible {
+            return self.handle_workspace_modal_key(key, tx).await;
+        }
+
+        // Handle linking task mode
+        if self.linking_task_mode {
+            return self.handle_link_task_key(key).await;
+        }
+
+        // Handle delete confirmation
+        if self.kb_confirming_delete {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    self.do_delete_document().await;
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    self.kb_confirming_delete = false;
+                }
+                _ => {}
+            }
+            return Ok(false);
+        }
+
+        // Handle create document
+        if self.kb_creating {
+            match key.code {
+                KeyCode::Esc => {
+                    self.kb_creating = false;
+                    self.kb_create_title.clear();
+                    self.kb_create_parent_id = None;
+                    self.vim_mode = VimMode::Normal;
+                }
+                KeyCode::Enter => {
+                    if !self.kb_create_title.is_empty() {
+                        self.do_create_document().await;
+                    }
+                }
+                KeyCode::Char(c) => {
+                    self.kb_create_title.push(c);
+                }
+                KeyCode::Backspace => {
+                    self.kb_create_title.pop();
+                }
+                _ => {}
+            }
+            return Ok(false);
+        }
+
+        // Handle editing document with TextArea
+        if self.kb_editing {
+            if let Some(ref mut textarea) = self.kb_content_textarea {
+                match key.code {
+                    KeyCode::Esc => {
+                        self.kb_editing = false;
+                        self.kb_edit_title.clear();
+                        self.kb_content_textarea = None;
+                        self.vim_mode = VimMode::Normal;
+                    }
+                    KeyCode::Enter if key.modifiers.contains(KeyModifiers::ALT) => {
+                        // Save on Alt+Enter
+                        self.do_update_document().await;
+                    }
+                    KeyCode::Char('e') | KeyCode::Char('E') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        // Ctrl+E: external editor
+                        let content = self.get_kb_content();
+                        match editor::launch_external_editor(&content, ".md") {
+                            Ok(edited) => {
+                                self.needs_terminal_clear = true;
+                                self.kb_content_textarea = Some(
+                                    editor::create_textarea(&edited, EditorContext::DocumentContent),
+                                );
+                            }
+                            Err(e) => {
+                                self.needs_terminal_clear = true;
+                                self.set_error(format!("Editor failed: {}", e));
+                            }
+                        }
+                    }
+                    _ => {
+                        // Pass to textarea for normal input handling
+                        textarea.input(key);
+                    }
+                }
+                return Ok(false);
+            }
+        }
+
+        // Global keys (work in both panels)
+        match key.code {
+            KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.open_workspace_modal().await;
+                return Ok(false);
+            }
+            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Backspace => {
+                self.view = View::Home;
+                self.kb_selected_doc = None;
+                self.kb_focus = KbFocus::Tree;
+                self.kb_scroll_offset = 0;
+                return Ok(false);
+            }
+            KeyCode::Tab => {
+                // Switch focus between Tree and Content
+                self.kb_focus = match self.kb_focus {
+                    KbFocus::Tree => KbFocus::Content,
+                    KbFocus::Content => KbFocus::Tree,
+                };
+                return Ok(false);
+            }
+            KeyCode::Char('n') => {
+                // Create new document (global)
+                self.kb_creating = true;
+                self.kb_create_title.clear();
+                if let Some((doc, _)) = self.kb_visible_list.get(self.kb_selected_idx) {
+                    if self.kb_expanded.contains(&doc.id) {
+                        self.kb_create_parent_id = Some(doc.id);
+                    } else {
+                        self.kb_create_parent_id = doc.parent_id;
+                    }
+                }
+                self.vim_mode = VimMode::Insert;
+                return Ok(false);
+            }
+            _ => {}
+        }
+
+        // Focus-specific keys
+        match self.kb_focus {
+            KbFocus::Tree => {
+                match key.code {
+                    KeyCode::Char('j') | KeyCode::Down => {
+                        if !self.kb_visible_list.is_empty() {
+                            self.kb_selected_idx = (self.kb_selected_idx + 1).min(self.kb_visible_list.len() - 1);
+                            self.kb_selected_doc = self.kb_visible_list.get(self.kb_selected_idx).map(|(d, _)| d.clone());
+                            self.kb_scroll_offset = 0; // Reset scroll when selecting new doc
+                            self.load_kb_linked_tasks().await;
+                        }
+                    }
+                    KeyCode::Char('k') | KeyCode::Up => {
+                        if self.kb_selected_idx > 0 {
+                            self.kb_selected_idx -= 1;
+                            self.kb_selected_doc = self.kb_visible_list.get(self.kb_selected_idx).map(|(d, _)| d.clone());
+                            self.kb_scroll_offset = 0; // Reset scroll when selecting new doc
+                            self.load_kb_linked_tasks().await;
+                        }
+                    }
+                    KeyCode::Char('l') | KeyCode::Right | KeyCode::Enter => {
+                        // Expand node
+                        if let Some((doc, _)) = self.kb_visible_list.get(self.kb_selected_idx) {
+                            if !self.kb_expanded.contains(&doc.id) {
+                                self.kb_expanded.insert(doc.id);
+                                self.build_kb_visible_list();
+                            }
+                        }
+                    }
+                    KeyCode::Char('h') | KeyCode::Left => {
+                        // Collapse node or go to parent
+                        if let Some((doc, _)) = self.kb_visible_list.get(self.kb_selected_idx).cloned() {
+                            if self.kb_expanded.contains(&doc.id) {
+                                self.kb_expanded.remove(&doc.id);
+                                self.build_kb_visible_list();
+                            } else if let Some(parent_id) = doc.parent_id {
+                                if let Some(pos) = self.kb_visible_list.iter().position(|(d, _)| d.id == parent_id) {
+                                    self.kb_selected_idx = pos;
+                                    self.kb_selected_doc = self.kb_visible_list.get(pos).map(|(d, _)| d.clone());
+                                }
+                            }
+                        }
+                    }
+                    KeyCode::Char('e') => {
+                        if let Some(doc) = &self.kb_selected_doc {
+                            self.kb_editing = true;
+                            self.kb_edit_title = doc.title.clone();
+                            let content = doc.content.clone().unwrap_or_default();
+                            self.init_kb_content_textarea(&content);
+                            self.vim_mode = VimMode::Insert;
+                        }
+                    }
+                    KeyCode::Char('d') => {
+                        if self.kb_selected_doc.is_some() {
+                            self.kb_confirming_delete = true;
+                        }
+                    }
+                    KeyCode::Char('L') => {
+                        if self.kb_selected_doc.is_some() {
+                            self.open_link_task_picker().await;
+                        }
+                    }
+                    KeyCode::Char('U') => {
+                        if !self.kb_linked_tasks.is_empty() {
+                            self.unlink_task_from_kb().await;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            KbFocus::Content => {
+                match key.code {
+                    KeyCode::Char('j') | KeyCode::Down => {
+                        self.kb_scroll_offset = self.kb_scroll_offset.saturating_add(1);
+                    }
+                    KeyCode::Char('k') | KeyCode::Up => {
+                        self.kb_scroll_offset = self.kb_scroll_offset.saturating_sub(1);
+                    }
+                    KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        // Scroll down half page (10 lines)
+                        self.kb_scroll_offset = self.kb_scroll_offset.saturating_add(10);
+                    }
+                    KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        // Scroll up half page (10 lines)
+                        self.kb_scroll_offset = self.kb_scroll_offset.saturating_sub(10);
+                    }
+                    KeyCode::Char('g') => {
+                        // Scroll to top
+                        self.kb_scroll_offset = 0;
+                    }
+                    KeyCode::Char('G') => {
+                        // Scroll to bottom (will be clamped in UI)
+                        self.kb_scroll_offset = usize::MAX;
+                    }
+                    _ => {}
+                }
             }
         }
 
-        self.selected_task_detail = Some(task);
-        self.view = View::TaskDetail;
-        self.set_loading(false, "");
+        Ok(false)
     }
 
-    fn close_task_detail(&mut self) {
-        self.selected_task_detail = None;
-        self.task_comments.clear();
-        self.task_linked_documents.clear();
-        self.adding_comment = false;
-        self.comment_textarea = None;
-        self.linking_document_mode = false;
-        self.link_document_cursor = 0;
-        self.unlinking_document_mode = false;
-        self.unlink_document_cursor = 0;
-        self.vim_mode = VimMode::Normal;
-        self.view = View::Dashboard;
+    fn get_all_tasks(&self) -> Vec<&Task> {
+        self.columns.iter().flat_map(|c| c.tasks.iter()).collect()
     }
 
-    async fn do_add_comment(&mut self) {
-        let workspace_id = match self.current_workspace {
-            Some(ref ws) => ws.id,
-            None => return,
-        };
-
-        let task_id = match self.selected_task_detail {
-            Some(ref t) => t.id,
-            None => return,
-        };
+    async fn open_link_task_picker(&mut self) {
+        // Filter out already linked tasks
+        let linked_ids: std::collections::HashSet<_> = self.kb_linked_tasks
+            .iter()
+            .map(|t| t.task_id)
+            .collect();
 
-        let content = self.get_comment_content();
+        let available: Vec<_> = self.get_all_tasks()
+            .into_iter()
+            .filter(|t| !linked_ids.contains(&t.id))
+            .collect::<Vec<_>>();
 
-        match self.api.create_comment(workspace_id, task_id, &content).await {
-            Ok(comment) => {
-                self.task_comments.push(comment);
-                self.comment_textarea = None;
-                self.adding_comment = false;
-                self.vim_mode = VimMode::Normal;
-            }
-            Err(e) => {
-                self.set_error(format!("Failed to add comment: {}", e));
-            }
+        if available.is_empty() {
+            self.set_error("No tasks available to link".to_string());
+            return;
         }
-    }
-
-    async fn do_create_task(&mut self) {
-        let workspace_id = match self.current_workspace {
-            Some(ref ws) => ws.id,
-            None => return,
-        };
-
-        // Use currently selected column's status
-        let status_id = match self.columns.get(self.selected_column) {
-            Some(col) => col.status.id,
-            None => return,
-        };
 
-        // Determine default assignee: workspace setting > self
-        let assigned_to = self.current_workspace
-            .as_ref()
-            .and_then(|ws| ws.settings.default_assignee)
-            .or_else(|| self.user.as_ref().map(|u| u.id));
+        self.linking_task_mode = true;
+        self.link_task_cursor = 0;
+    }
 
-        let description = self.get_new_task_description();
-        let req = CreateTaskRequest {
-            title: self.new_task_title.clone(),
-            status_id,
-            description: if description.is_empty() {
-                None
-            } else {
-                Some(description)
-            },
-            priority: None,
-            due_date: None,
-            time_estimate_minutes: None,
-            assigned_to,
-        };
+    async fn handle_link_task_key(&mut self, key: KeyEvent) -> Result<bool> {
+        // Get available tasks (not already linked)
+        let linked_ids: std::collections::HashSet<_> = self.kb_linked_tasks
+            .iter()
+            .map(|t| t.task_id)
+            .collect();
 
-        self.set_loading(true, "Creating task...");
+        let all_tasks = self.get_all_tasks();
+        let available: Vec<_> = all_tasks
+            .into_iter()
+            .filter(|t| !linked_ids.contains(&t.id))
+            .collect();
 
-        match self.api.create_task(workspace_id, req).await {
-            Ok(task) => {
-                // Add to current column
-                if let Some(col) = self.columns.get_mut(self.selected_column) {
-                    col.tasks.push(task);
-                    col.tasks.sort_by_key(|t| t.position);
-                    // Select the new task
-                    self.selected_task = col.tasks.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.linking_task_mode = false;
+                self.link_task_cursor = 0;
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                if self.link_task_cursor < available.len().saturating_sub(1) {
+                    self.link_task_cursor += 1;
                 }
-                // Clear form
-                self.creating_task = false;
-                self.new_task_title.clear();
-                self.new_task_description_textarea = None;
-                self.new_task_field = NewTaskField::Title;
-                self.vim_mode = VimMode::Normal;
             }
-            Err(e) => {
-                self.set_error(format!("Failed to create task: {}", e));
+            KeyCode::Char('k') | KeyCode::Up => {
+                if self.link_task_cursor > 0 {
+                    self.link_task_cursor -= 1;
+                }
             }
+            KeyCode::Enter => {
+                if let Some(task) = available.get(self.link_task_cursor) {
+                    self.do_link_task(task.id).await;
+                }
+                self.linking_task_mode = false;
+                self.link_task_cursor = 0;
+            }
+            _ => {}
         }
-
-        self.set_loading(false, "");
+        Ok(false)
     }
 
-    async fn do_delete_task(&mut self) {
+    async fn do_link_task(&mut self, task_id: uuid::Uuid) {
         let workspace_id = match self.current_workspace {
             Some(ref ws) => ws.id,
             None => return,
         };
-
-        let task = match self.get_selected_task() {
-            Some(t) => t.clone(),
+        let doc_id = match self.kb_selected_doc {
+            Some(ref d) => d.id,
             None => return,
         };
 
-        self.set_loading(true, "Deleting task...");
-
-        match self.api.delete_task(workspace_id, task.id).await {
-            Ok(()) => {
-                // Remove from current column
-                if let Some(col) = self.columns.get_mut(self.selected_column) {
-                    col.tasks.retain(|t| t.id != task.id);
-                    // Adjust selection if needed
-                    if self.selected_task >= col.tasks.len() && !col.tasks.is_empty() {
-                        self.selected_task = col.tasks.len() - 1;
-                    }
-                }
-                self.confirming_delete = false;
+        match self.api.link_task_to_document(workspace_id, doc_id, task_id).await {
+            Ok(_) => {
+                // Reload linked tasks
+                self.load_kb_linked_tasks().await;
             }
             Err(e) => {
-                self.set_error(format!("Failed to delete task: {}", e));
+                self.set_error(format!("Failed to link task: {}", e));
             }
         }
-
-        self.set_loading(false, "");
     }
 
-    fn enter_edit_mode(&mut self) {
-        // Extract all values first to avoid borrow conflicts
-        let task_data = self.selected_task_detail.as_ref().map(|task| {
-            (
-                task.title.clone(),
-                task.description.clone().unwrap_or_default(),
-                task.priority,
-                task.due_date.map(|d| d.to_string()).unwrap_or_default(),
-                task.time_estimate_minutes.map(|m| m.to_string()).unwrap_or_default(),
-                task.assigned_to,
-                task.tags.iter().map(|t| t.id).collect::<Vec<_>>(),
-            )
-        });
+    async fn unlink_task_from_kb(&mut self) {
+        // Just unlink the first linked task for now (could add a picker later)
+        if let Some(linked_task) = self.kb_linked_tasks.first() {
+            let workspace_id = match self.current_workspace {
+                Some(ref ws) => ws.id,
+                None => return,
+            };
+            let doc_id = match self.kb_selected_doc {
+                Some(ref d) => d.id,
+                None => return,
+            };
+            let task_id = linked_task.task_id;
 
-        if let Some((title, description, priority, due_date, time_estimate, assignee, tags)) = task_data {
-            self.editing_task = true;
-            self.edit_field = TaskEditField::Title;
-            self.edit_task_title = title;
-            self.init_edit_task_description_textarea(&description);
-            self.edit_task_priority = priority;
-            self.edit_task_due_date_str = due_date;
-            self.edit_task_time_estimate_str = time_estimate;
-            self.edit_task_assignee = assignee;
-            self.task_edit_selected_tags = tags;
-            self.tag_selector_cursor = 0;
+            match self.api.unlink_task_from_document(workspace_id, doc_id, task_id).await {
+                Ok(_) => {
+                    self.kb_linked_tasks.retain(|t| t.task_id != task_id);
+                }
+                Err(e) => {
+                    self.set_error(format!("Failed to unlink task: {}", e));
+                }
+            }
         }
     }
 
-    async fn do_update_task(&mut self) {
-        let workspace_id = match self.current_workspace {
-            Some(ref ws) => ws.id,
-            None => return,
-        };
-
-        let task_id = match self.selected_task_detail {
-            Some(ref t) => t.id,
+    async fn do_create_document(&mut self) {
+        let workspace_id = match &self.current_workspace {
+            Some(w) => w.id,
             None => return,
         };
 
-        // Parse due date
-        let due_date = if self.edit_task_due_date_str.is_empty() {
-            None
-        } else {
-            NaiveDate::parse_from_str(&self.edit_task_due_date_str, "%Y-%m-%d").ok()
-        };
-
-        // Parse time estimate
-        let time_estimate_minutes = if self.edit_task_time_estimate_str.is_empty() {
-            None
-        } else {
-            self.edit_task_time_estimate_str.parse::<i32>().ok()
+        let req = CreateDocumentRequest {
+            title: self.kb_create_title.clone(),
+            parent_id: self.kb_create_parent_id,
+            content: None,
         };
 
-        self.set_loading(true, "Updating task...");
-
-        // Try to update, refresh token if unauthorized
-        let result = self.update_task_with_retry(workspace_id, task_id, due_date, time_estimate_minutes).await;
+        self.set_loading(true, "Creating document...");
 
-        match result {
-            Ok(mut updated_task) => {
-                // Also update tags
-                let tag_ids = self.task_edit_selected_tags.clone();
-                if let Err(e) = self.api.set_task_tags(workspace_id, task_id, tag_ids.clone()).await {
-                    self.set_error(format!("Failed to update tags: {}", e));
-                } else {
-                    // Update tags in the task
-                    updated_task.tags = self.workspace_tags
-                        .iter()
-                        .filter(|t| tag_ids.contains(&t.id))
-                        .cloned()
-                        .collect();
+        match self.api.create_document(workspace_id, req).await {
+            Ok(doc) => {
+                // If parent was set, expand it
+                if let Some(parent_id) = self.kb_create_parent_id {
+                    self.kb_expanded.insert(parent_id);
                 }
-
-                // Update the task detail
-                self.selected_task_detail = Some(updated_task.clone());
-
-                // Update in columns
-                for col in &mut self.columns {
-                    for task in &mut col.tasks {
-                        if task.id == task_id {
-                            *task = updated_task.clone();
-                        }
-                    }
+                self.kb_documents.push(doc.clone());
+                self.build_kb_visible_list();
+                // Select the new document
+                if let Some(pos) = self.kb_visible_list.iter().position(|(d, _)| d.id == doc.id) {
+                    self.kb_selected_idx = pos;
+                    self.kb_selected_doc = Some(doc);
                 }
-
-                self.editing_task = false;
+                self.kb_creating = false;
+                self.kb_create_title.clear();
+                self.kb_create_parent_id = None;
                 self.vim_mode = VimMode::Normal;
             }
             Err(e) => {
-                self.set_error(format!("Failed to update task: {}", e));
+                self.set_error(format!("Failed to create document: {}", e));
             }
         }
 
         self.set_loading(false, "");
     }
 
-    async fn update_task_with_retry(
-        &mut self,
-        workspace_id: uuid::Uuid,
-        task_id: uuid::Uuid,
-        due_date: Option<NaiveDate>,
-        time_estimate_minutes: Option<i32>,
-    ) -> Result<Task, crate::api::ApiError> {
-        let description = self.get_edit_task_description();
-        let req = UpdateTaskRequest {
-            title: Some(self.edit_task_title.clone()),
-            status_id: None,
-            description: if description.is_empty() {
-                None
-            } else {
-                Some(description)
-            },
-            priority: self.edit_task_priority,
-            due_date,
-            time_estimate_minutes,
-            assigned_to: self.edit_task_assignee,
+    async fn do_update_document(&mut self) {
+        let workspace_id = match &self.current_workspace {
+            Some(w) => w.id,
+            None => return,
         };
 
-        // First attempt
-        match self.api.update_task(workspace_id, task_id, req.clone()).await {
-            Ok(task) => Ok(task),
-            Err(crate::api::ApiError::Unauthorized) => {
-                // Try to refresh token
-                if self.api.refresh().await.is_ok() {
-                    // Retry with new token
-                    self.api.update_task(workspace_id, task_id, req).await
-                } else {
-                    Err(crate::api::ApiError::Unauthorized)
-                }
+        let doc_id = match &self.kb_selected_doc {
+            Some(d) => d.id,
+            None => return,
+        };
+
+        let content = self.get_kb_content();
+        let req = UpdateDocumentRequest {
+            title: Patch::Value(self.kb_edit_title.clone()),
+            content: Patch::set_or_clear(Some(content).filter(|c| !c.is_empty())),
+            expected_version: self.kb_selected_doc.as_ref().map(|d| d.version),
+        };
+
+        self.set_loading(true, "Updating document...");
+
+        match self.api.update_document(workspace_id, doc_id, req.clone()).await {
+            Ok(updated) => self.finish_document_update(updated),
+            Err(crate::api::ApiError::Conflict(_)) => {
+                self.open_document_conflict(workspace_id, req).await;
+            }
+            Err(e) => {
+                self.set_error(format!("Failed to update document: {}", e));
             }
-            Err(e) => Err(e),
         }
+
+        self.set_loading(false, "");
     }
 
-    // ============ Knowledge Base ============
+    /// Store a just-saved document and leave the editor
+    fn finish_document_update(&mut self, updated: Document) {
+        // Update in local list
+        if let Some(doc) = self.kb_documents.iter_mut().find(|d| d.id == updated.id) {
+            *doc = updated.clone();
+        }
+        self.kb_selected_doc = Some(updated);
+        self.build_kb_visible_list();
+        self.kb_editing = false;
+        self.kb_edit_title.clear();
+        self.kb_content_textarea = None;
+        self.vim_mode = VimMode::Normal;
+    }
 
-    async fn open_knowledge_base(&mut self) {
+    async fn do_delete_document(&mut self) {
         let workspace_id = match &self.current_workspace {
             Some(w) => w.id,
             None => return,
         };
 
-        self.set_loading(true, "Loading knowledge base...");
+        let doc_id = match &self.kb_selected_doc {
+            Some(d) => d.id,
+            None => return,
+        };
 
-        match self.api.list_documents(workspace_id).await {
-            Ok(docs) => {
-                self.kb_documents = docs;
+        self.set_loading(true, "Deleting document...");
+
+        match self.api.delete_document(workspace_id, doc_id).await {
+            Ok(_) => {
+                // Remove from local list (and any children)
+                self.kb_documents.retain(|d| d.id != doc_id && d.parent_id != Some(doc_id));
+                self.kb_expanded.remove(&doc_id);
                 self.build_kb_visible_list();
-                self.kb_selected_idx = 0;
-                self.kb_selected_doc = self.kb_visible_list.first().map(|(d, _)| d.clone());
-                self.load_kb_linked_tasks().await;
-                self.view = View::KnowledgeBase;
+                // Adjust selection
+                if self.kb_selected_idx >= self.kb_visible_list.len() {
+                    self.kb_selected_idx = self.kb_visible_list.len().saturating_sub(1);
+                }
+                self.kb_selected_doc = self.kb_visible_list.get(self.kb_selected_idx).map(|(d, _)| d.clone());
+                self.kb_confirming_delete = false;
             }
             Err(e) => {
-                self.set_error(format!("Failed to load documents: {}", e));
+                self.set_error(format!("Failed to delete document: {}", e));
+                self.kb_confirming_delete = false;
             }
         }
 
         self.set_loading(false, "");
     }
 
-    fn build_kb_visible_list(&mut self) {
-        self.kb_visible_list.clear();
+    async fn navigate_to_document(&mut self, doc: Document) {
+        // First open knowledge base to load documents
+        self.open_knowledge_base().await;
 
-        // Clone documents to avoid borrow issues
-        let docs = self.kb_documents.clone();
-        let expanded = self.kb_expanded.clone();
+        // Find the document in the visible list
+        if let Some(pos) = self.kb_visible_list.iter().position(|(d, _)| d.id == doc.id) {
+            self.kb_selected_idx = pos;
+            self.kb_selected_doc = Some(doc);
+            self.load_kb_linked_tasks().await;
+        } else {
+            // Document might be under a collapsed parent - expand parents and rebuild
+            // For simplicity, just select the first document and set kb_selected_doc
+            self.kb_selected_doc = Some(doc);
+        }
+    }
 
-        // Build the list iteratively using a stack
-        let mut stack: Vec<(Document, usize)> = Vec::new();
+    async fn load_kb_linked_tasks(&mut self) {
+        let workspace_id = match &self.current_workspace {
+            Some(w) => w.id,
+            None => {
+                self.kb_linked_tasks.clear();
+                return;
+            }
+        };
 
-        // Get root documents (no parent)
-        let mut roots: Vec<Document> = docs
-            .iter()
-            .filter(|d| d.parent_id.is_none())
-            .cloned()
-            .collect();
-        roots.sort_by(|a, b| b.title.cmp(&a.title)); // Reverse for stack
+        let doc_id = match &self.kb_selected_doc {
+            Some(d) => d.id,
+            None => {
+                self.kb_linked_tasks.clear();
+                return;
+            }
+        };
 
-        for doc in roots {
-            stack.push((doc, 0));
+        match self.api.list_linked_tasks(workspace_id, doc_id).await {
+            Ok(tasks) => {
+                self.kb_linked_tasks = tasks;
+            }
+            Err(_) => {
+                self.kb_linked_tasks.clear();
+            }
         }
+    }
 
-        while let Some((doc, depth)) = stack.pop() {
-            let doc_id = doc.id;
-            self.kb_visible_list.push((doc, depth));
-
-            // If expanded, add children
-            if expanded.contains(&doc_id) {
-                let mut children: Vec<Document> = docs
-                    .iter()
-                    .filter(|d| d.parent_id == Some(doc_id))
-                    .cloned()
-                    .collect();
-                children.sort_by(|a, b| b.title.cmp(&a.title)); // Reverse for stack
+    // ============ Help Modal ============
 
-                for child in children {
-                    stack.push((child, depth + 1));
-                }
+    fn handle_help_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => {
+                self.help_visible = false;
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.help_scroll = self.help_scroll.saturating_add(1);
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.help_scroll = self.help_scroll.saturating_sub(1);
             }
+            _ => {}
         }
     }
 
+    // ============ Trash ============
 
-    async fn handle_knowledge_base_key(
-        &mut self,
-        key: KeyEvent,
-        tx: mpsc::Sender<AppEvent>,
-    ) -> Result<bool> {
-        // Handle workspace modal
-        if self.workspace_modal_visible {
-            return self.handle_workspace_modal_key(key, tx).await;
+    async fn open_trash(&mut self) {
+        if self.current_workspace.is_none() {
+            return;
         }
 
-        // Handle linking task mode
-        if self.linking_task_mode {
-            return self.handle_link_task_key(key).await;
+        if self.view != View::Trash {
+            self.trash_return_view = self.view.clone();
         }
+        self.trash_cursor = 0;
+        self.trash_confirming_purge = false;
+        self.load_trash().await;
+        self.view = View::Trash;
+    }
 
-        // Handle delete confirmation
-        if self.kb_confirming_delete {
-            match key.code {
-                KeyCode::Char('y') | KeyCode::Char('Y') => {
-                    self.do_delete_document().await;
-                }
-                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
-                    self.kb_confirming_delete = false;
+    async fn load_trash(&mut self) {
+        let workspace_id = match &self.current_workspace {
+            Some(w) => w.id,
+            None => return,
+        };
+
+        self.set_loading(true, "Loading trash...");
+
+        match self.api.list_trash(workspace_id).await {
+            Ok(items) => {
+                self.trash_items = items;
+                if self.trash_cursor >= self.trash_items.len() {
+                    self.trash_cursor = self.trash_items.len().saturating_sub(1);
                 }
-                _ => {}
             }
-            return Ok(false);
+            Err(e) => {
+                self.set_error(format!("Failed to load trash: {}", e));
+            }
         }
 
-        // Handle create document
-        if self.kb_creating {
-            match key.code {
-                KeyCode::Esc => {
-                    self.kb_creating = false;
-                    self.kb_create_title.clear();
-                    self.kb_create_parent_id = None;
-                    self.vim_mode = VimMode::Normal;
-                }
-                KeyCode::Enter => {
-                    if !self.kb_create_title.is_empty() {
-                        self.do_create_document().await;
-                    }
-                }
-                KeyCode::Char(c) => {
-                    self.kb_create_title.push(c);
-                }
-                KeyCode::Backspace => {
-                    self.kb_create_title.pop();
-                }
-                _ => {}
-            }
+        self.set_loading(false, "");
+    }
+
+    async fn handle_trash_key(
+        &mut self,
+        key: KeyEvent,
+        tx: mpsc::Sender<AppEvent>,
+    ) -> Result<bool> {
+        if self.loading {
             return Ok(false);
         }
 
-        // Handle editing document with TextArea
-        if self.kb_editing {
-            if let Some(ref mut textarea) = self.kb_content_textarea {
-                match key.code {
-                    KeyCode::Esc => {
-                        self.kb_editing = false;
-                        self.kb_edit_title.clear();
-                        self.kb_content_textarea = None;
-                        self.vim_mode = VimMode::Normal;
-                    }
-                    KeyCode::Enter if key.modifiers.contains(KeyModifiers::ALT) => {
-                        // Save on Alt+Enter
-                        self.do_update_document().await;
-                    }
-                    KeyCode::Char('e') | KeyCode::Char('E') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                        // Ctrl+E: external editor
-                        let content = self.get_kb_content();
-                        match editor::launch_external_editor(&content, ".md") {
-                            Ok(edited) => {
-                                self.needs_terminal_clear = true;
-                                self.kb_content_textarea = Some(
-                                    editor::create_textarea(&edited, EditorContext::DocumentContent),
-                                );
-                            }
-                            Err(e) => {
-                                self.needs_terminal_clear = true;
-                                self.set_error(format!("Editor failed: {}", e));
-                            }
-                        }
-                    }
-                    _ => {
-                        // Pass to textarea for normal input handling
-                        textarea.input(key);
-                    }
+        // Handle purge confirmation
+        if self.trash_confirming_purge {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    self.trash_confirming_purge = false;
+                    self.do_purge_trash_item().await;
                 }
-                return Ok(false);
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    self.trash_confirming_purge = false;
+                }
+                _ => {}
             }
+            return Ok(false);
         }
 
-        // Global keys (work in both panels)
         match key.code {
-            KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.open_workspace_modal().await;
-                return Ok(false);
-            }
             KeyCode::Char('q') | KeyCode::Esc | KeyCode::Backspace => {
-                self.view = View::Home;
-                self.kb_selected_doc = None;
-                self.kb_focus = KbFocus::Tree;
-                self.kb_scroll_offset = 0;
-                return Ok(false);
+                self.view = self.trash_return_view.clone();
+                // Restored tasks should show up on the board again
+                if self.view == View::Dashboard {
+                    self.load_workspace_data(tx).await;
+                }
             }
-            KeyCode::Tab => {
-                // Switch focus between Tree and Content
-                self.kb_focus = match self.kb_focus {
-                    KbFocus::Tree => KbFocus::Content,
-                    KbFocus::Content => KbFocus::Tree,
-                };
-                return Ok(false);
+            KeyCode::Char('j') | KeyCode::Down if self.trash_cursor + 1 < self.trash_items.len() => {
+                self.trash_cursor += 1;
             }
-            KeyCode::Char('n') => {
-                // Create new document (global)
-                self.kb_creating = true;
-                self.kb_create_title.clear();
-                if let Some((doc, _)) = self.kb_visible_list.get(self.kb_selected_idx) {
-                    if self.kb_expanded.contains(&doc.id) {
-                        self.kb_create_parent_id = Some(doc.id);
-                    } else {
-                        self.kb_create_parent_id = doc.parent_id;
-                    }
-                }
-                self.vim_mode = VimMode::Insert;
-                return Ok(false);
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.trash_cursor = self.trash_cursor.saturating_sub(1);
             }
-            _ => {}
-        }
-
-        // Focus-specific keys
-        match self.kb_focus {
-            KbFocus::Tree => {
-                match key.code {
-                    KeyCode::Char('j') | KeyCode::Down => {
-                        if !self.kb_visible_list.is_empty() {
-                            self.kb_selected_idx = (self.kb_selected_idx + 1).min(self.kb_visible_list.len() - 1);
-                            self.kb_selected_doc = self.kb_visible_list.get(self.kb_selected_idx).map(|(d, _)| d.clone());
-                            self.kb_scroll_offset = 0; // Reset scroll when selecting new doc
-                            self.load_kb_linked_tasks().await;
-                        }
-                    }
-                    KeyCode::Char('k') | KeyCode::Up => {
-                        if self.kb_selected_idx > 0 {
-                            self.kb_selected_idx -= 1;
-                            self.kb_selected_doc = self.kb_visible_list.get(self.kb_selected_idx).map(|(d, _)| d.clone());
-                            self.kb_scroll_offset = 0; // Reset scroll when selecting new doc
-                            self.load_kb_linked_tasks().await;
-                        }
-                    }
-                    KeyCode::Char('l') | KeyCode::Right | KeyCode::Enter => {
-                        // Expand node
-                        if let Some((doc, _)) = self.kb_visible_list.get(self.kb_selected_idx) {
-                            if !self.kb_expanded.contains(&doc.id) {
-                                self.kb_expanded.insert(doc.id);
-                                self.build_kb_visible_list();
-                            }
-                        }
-                    }
-                    KeyCode::Char('h') | KeyCode::Left => {
-                        // Collapse node or go to parent
-                        if let Some((doc, _)) = self.kb_visible_list.get(self.kb_selected_idx).cloned() {
-                            if self.kb_expanded.contains(&doc.id) {
-                                self.kb_expanded.remove(&doc.id);
-                                self.build_kb_visible_list();
-                            } else if let Some(parent_id) = doc.parent_id {
-                                if let Some(pos) = self.kb_visible_list.iter().position(|(d, _)| d.id == parent_id) {
-                                    self.kb_selected_idx = pos;
-                                    self.kb_selected_doc = self.kb_visible_list.get(pos).map(|(d, _)| d.clone());
-                                }
-                            }
-                        }
-                    }
-                    KeyCode::Char('e') => {
-                        if let Some(doc) = &self.kb_selected_doc {
-                            self.kb_editing = true;
-                            self.kb_edit_title = doc.title.clone();
-                            let content = doc.content.clone().unwrap_or_default();
-                            self.init_kb_content_textarea(&content);
-                            self.vim_mode = VimMode::Insert;
-                        }
-                    }
-                    KeyCode::Char('d') => {
-                        if self.kb_selected_doc.is_some() {
-                            self.kb_confirming_delete = true;
-                        }
-                    }
-                    KeyCode::Char('L') => {
-                        if self.kb_selected_doc.is_some() {
-                            self.open_link_task_picker().await;
-                        }
-                    }
-                    KeyCode::Char('U') => {
-                        if !self.kb_linked_tasks.is_empty() {
-                            self.unlink_task_from_kb().await;
-                        }
-                    }
-                    _ => {}
-                }
+            KeyCode::Char('g') => {
+                self.trash_cursor = 0;
             }
-            KbFocus::Content => {
-                match key.code {
-                    KeyCode::Char('j') | KeyCode::Down => {
-                        self.kb_scroll_offset = self.kb_scroll_offset.saturating_add(1);
-                    }
-                    KeyCode::Char('k') | KeyCode::Up => {
-                        self.kb_scroll_offset = self.kb_scroll_offset.saturating_sub(1);
-                    }
-                    KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                        // Scroll down half page (10 lines)
-                        self.kb_scroll_offset = self.kb_scroll_offset.saturating_add(10);
-                    }
-                    KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                        // Scroll up half page (10 lines)
-                        self.kb_scroll_offset = self.kb_scroll_offset.saturating_sub(10);
-                    }
-                    KeyCode::Char('g') => {
-                        // Scroll to top
-                        self.kb_scroll_offset = 0;
-                    }
-                    KeyCode::Char('G') => {
-                        // Scroll to bottom (will be clamped in UI)
-                        self.kb_scroll_offset = usize::MAX;
-                    }
-                    _ => {}
-                }
+            KeyCode::Char('G') => {
+                self.trash_cursor = self.trash_items.len().saturating_sub(1);
+            }
+            KeyCode::Char('r') | KeyCode::Enter => {
+                self.do_restore_trash_item().await;
+            }
+            KeyCode::Char('D') if !self.trash_items.is_empty() => {
+                self.trash_confirming_purge = true;
             }
+            KeyCode::Char('R') => {
+                self.load_trash().await;
+            }
+            _ => {}
         }
 
         Ok(false)
     }
 
-    fn get_all_tasks(&self) -> Vec<&Task> {
-        self.columns.iter().flat_map(|c| c.tasks.iter()).collect()
+    async fn do_restore_trash_item(&mut self) {
+        let workspace_id = match &self.current_workspace {
+            Some(w) => w.id,
+            None => return,
+        };
+        let (kind, item_id) = match self.trash_items.get(self.trash_cursor) {
+            Some(item) => (item.kind, item.id),
+            None => return,
+        };
+
+        match self.api.restore_trash_item(workspace_id, kind, item_id).await {
+            Ok(()) => self.load_trash().await,
+            Err(e) => self.set_error(format!("Failed to restore: {}", e)),
+        }
     }
 
-    async fn open_link_task_picker(&mut self) {
-        // Filter out already linked tasks
-        let linked_ids: std::collections::HashSet<_> = self.kb_linked_tasks
-            .iter()
-            .map(|t| t.task_id)
-            .collect();
+    async fn do_purge_trash_item(&mut self) {
+        let workspace_id = match &self.current_workspace {
+            Some(w) => w.id,
+            None => return,
+        };
+        let (kind, item_id) = match self.trash_items.get(self.trash_cursor) {
+            Some(item) => (item.kind, item.id),
+            None => return,
+        };
 
-        let available: Vec<_> = self.get_all_tasks()
-            .into_iter()
-            .filter(|t| !linked_ids.contains(&t.id))
-            .collect::<Vec<_>>();
+        match self.api.purge_trash_item(workspace_id, kind, item_id).await {
+            Ok(()) => self.load_trash().await,
+            Err(e) => self.set_error(format!("Failed to delete permanently: {}", e)),
+        }
+    }
 
-        if available.is_empty() {
-            self.set_error("No tasks available to link".to_string());
+    // ============ Notifications ============
+
+    async fn open_notifications(&mut self) {
+        let workspace_id = match &self.current_workspace {
+            Some(w) => w.id,
+            None => return,
+        };
+
+        if self.view != View::Notifications {
+            self.notification_return_view = self.view.clone();
+        }
+        self.notification_cursor = 0;
+        self.notification_settings_visible = false;
+        self.load_notifications().await;
+        self.notification_settings = self
+            .api
+            .get_notification_settings(workspace_id)
+            .await
+            .unwrap_or_default();
+        self.view = View::Notifications;
+    }
+
+    async fn load_notifications(&mut self) {
+        let workspace_id = match &self.current_workspace {
+            Some(w) => w.id,
+            None => return,
+        };
+
+        self.set_loading(true, "Loading notifications...");
+
+        match self.api.list_notifications(workspace_id, self.notifications_unread_only).await {
+            Ok(response) => {
+                self.notifications = response.notifications;
+                self.notifications_unread = response.unread;
+                if self.notification_cursor >= self.notifications.len() {
+                    self.notification_cursor = self.notifications.len().saturating_sub(1);
+                }
+            }
+            Err(e) => {
+                self.set_error(format!("Failed to load notifications: {}", e));
+            }
+        }
+
+        self.set_loading(false, "");
+    }
+
+    /// Keep the unread badge current, and the list too while it is open
+    /// Refresh the inbox once for however many change events asked for it
+    /// since the last tick
+    pub async fn refresh_notifications_if_due(&mut self) {
+        if !std::mem::take(&mut self.notifications_refresh_due) {
             return;
         }
+        if let Some(workspace_id) = self.current_workspace.as_ref().map(|ws| ws.id) {
+            self.refresh_notifications(workspace_id).await;
+        }
+    }
 
-        self.linking_task_mode = true;
-        self.link_task_cursor = 0;
+    async fn refresh_notifications(&mut self, workspace_id: uuid::Uuid) {
+        let showing = self.view == View::Notifications;
+        let unread_only = !showing || self.notifications_unread_only;
+        if let Ok(response) = self.api.list_notifications(workspace_id, unread_only).await {
+            self.notifications_unread = response.unread;
+            if showing {
+                self.notifications = response.notifications;
+                if self.notification_cursor >= self.notifications.len() {
+                    self.notification_cursor = self.notifications.len().saturating_sub(1);
+                }
+            }
+        }
     }
 
-    async fn handle_link_task_key(&mut self, key: KeyEvent) -> Result<bool> {
-        // Get available tasks (not already linked)
-        let linked_ids: std::collections::HashSet<_> = self.kb_linked_tasks
-            .iter()
-            .map(|t| t.task_id)
-            .collect();
+    async fn handle_notifications_key(
+        &mut self,
+        key: KeyEvent,
+        tx: mpsc::Sender<AppEvent>,
+    ) -> Result<bool> {
+        if self.loading {
+            return Ok(false);
+        }
 
-        let all_tasks = self.get_all_tasks();
-        let available: Vec<_> = all_tasks
-            .into_iter()
-            .filter(|t| !linked_ids.contains(&t.id))
-            .collect();
+        if self.notification_settings_visible {
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc | KeyCode::Char('s') => {
+                    self.notification_settings_visible = false;
+                }
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.notification_settings_cursor = (self.notification_settings_cursor + 1) % 6;
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.notification_settings_cursor = (self.notification_settings_cursor + 5) % 6;
+                }
+                KeyCode::Char(' ') | KeyCode::Enter => {
+                    self.do_toggle_notification_setting().await;
+                }
+                _ => {}
+            }
+            return Ok(false);
+        }
 
         match key.code {
-            KeyCode::Esc | KeyCode::Char('q') => {
-                self.linking_task_mode = false;
-                self.link_task_cursor = 0;
+            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Backspace => {
+                self.view = self.notification_return_view.clone();
             }
-            KeyCode::Char('j') | KeyCode::Down => {
-                if self.link_task_cursor < available.len().saturating_sub(1) {
-                    self.link_task_cursor += 1;
-                }
+            KeyCode::Char('j') | KeyCode::Down if self.notification_cursor + 1 < self.notifications.len() => {
+                self.notification_cursor += 1;
             }
             KeyCode::Char('k') | KeyCode::Up => {
-                if self.link_task_cursor > 0 {
-                    self.link_task_cursor -= 1;
-                }
+                self.notification_cursor = self.notification_cursor.saturating_sub(1);
+            }
+            KeyCode::Char('g') => {
+                self.notification_cursor = 0;
+            }
+            KeyCode::Char('G') => {
+                self.notification_cursor = self.notifications.len().saturating_sub(1);
+            }
+            KeyCode::Enter => {
+                self.open_notification_task(tx).await;
+            }
+            KeyCode::Char('r') => {
+                self.do_mark_notification_read().await;
+            }
+            KeyCode::Char('a') => {
+                self.do_mark_all_notifications_read().await;
+            }
+            KeyCode::Char('u') => {
+                self.notifications_unread_only = !self.notifications_unread_only;
+                self.notification_cursor = 0;
+                self.load_notifications().await;
+            }
+            KeyCode::Char('s') => {
+                self.notification_settings_visible = true;
+                self.notification_settings_cursor = 0;
             }
-            KeyCode::Enter => {
-                if let Some(task) = available.get(self.link_task_cursor) {
-                    self.do_link_task(task.id).await;
-                }
-                self.linking_task_mode = false;
-                self.link_task_cursor = 0;
+            KeyCode::Char('R') => {
+                self.load_notifications().await;
             }
             _ => {}
         }
+
         Ok(false)
     }
 
-    async fn do_link_task(&mut self, task_id: uuid::Uuid) {
-        let workspace_id = match self.current_workspace {
-            Some(ref ws) => ws.id,
+    async fn do_mark_notification_read(&mut self) {
+        let workspace_id = match &self.current_workspace {
+            Some(w) => w.id,
             None => return,
         };
-        let doc_id = match self.kb_selected_doc {
-            Some(ref d) => d.id,
-            None => return,
+        let notification_id = match self.notifications.get(self.notification_cursor) {
+            Some(n) if !n.is_read() => n.id,
+            _ => return,
         };
 
-        match self.api.link_task_to_document(workspace_id, doc_id, task_id).await {
-            Ok(_) => {
-                // Reload linked tasks
-                self.load_kb_linked_tasks().await;
-            }
-            Err(e) => {
-                self.set_error(format!("Failed to link task: {}", e));
-            }
-        }
-    }
-
-    async fn unlink_task_from_kb(&mut self) {
-        // Just unlink the first linked task for now (could add a picker later)
-        if let Some(linked_task) = self.kb_linked_tasks.first() {
-            let workspace_id = match self.current_workspace {
-                Some(ref ws) => ws.id,
-                None => return,
-            };
-            let doc_id = match self.kb_selected_doc {
-                Some(ref d) => d.id,
-                None => return,
-            };
-            let task_id = linked_task.task_id;
-
-            match self.api.unlink_task_from_document(workspace_id, doc_id, task_id).await {
-                Ok(_) => {
-                    self.kb_linked_tasks.retain(|t| t.task_id != task_id);
-                }
-                Err(e) => {
-                    self.set_error(format!("Failed to unlink task: {}", e));
+        match self.api.mark_notification_read(workspace_id, notification_id).await {
+            Ok(()) => {
+                if let Some(n) = self.notifications.get_mut(self.notification_cursor) {
+                    n.read_at = Some(chrono::Utc::now());
                 }
+                self.notifications_unread = (self.notifications_unread - 1).max(0);
             }
+            Err(e) => self.set_error(format!("Failed to mark notification read: {}", e)),
         }
     }
 
-    async fn do_create_document(&mut self) {
+    async fn do_mark_all_notifications_read(&mut self) {
         let workspace_id = match &self.current_workspace {
             Some(w) => w.id,
             None => return,
         };
 
-        let req = CreateDocumentRequest {
-            title: self.kb_create_title.clone(),
-            parent_id: self.kb_create_parent_id,
-            content: None,
-        };
+        match self.api.mark_all_notifications_read(workspace_id).await {
+            Ok(()) => self.load_notifications().await,
+            Err(e) => self.set_error(format!("Failed to mark notifications read: {}", e)),
+        }
+    }
 
-        self.set_loading(true, "Creating document...");
+    /// Jump to the task a notification is about
+    async fn open_notification_task(&mut self, tx: mpsc::Sender<AppEvent>) {
+        let task_id = match self.notifications.get(self.notification_cursor) {
+            Some(n) => n.task_id,
+            None => return,
+        };
+        self.do_mark_notification_read().await;
 
-        match self.api.create_document(workspace_id, req).await {
-            Ok(doc) => {
-                // If parent was set, expand it
-                if let Some(parent_id) = self.kb_create_parent_id {
-                    self.kb_expanded.insert(parent_id);
-                }
-                self.kb_documents.push(doc.clone());
-                self.build_kb_visible_list();
-                // Select the new document
-                if let Some(pos) = self.kb_visible_list.iter().position(|(d, _)| d.id == doc.id) {
-                    self.kb_selected_idx = pos;
-                    self.kb_selected_doc = Some(doc);
-                }
-                self.kb_creating = false;
-                self.kb_create_title.clear();
-                self.kb_create_parent_id = None;
-                self.vim_mode = VimMode::Normal;
-            }
-            Err(e) => {
-                self.set_error(format!("Failed to create document: {}", e));
-            }
+        if self.columns.is_empty() {
+            self.load_workspace_data(tx).await;
         }
+        self.view = View::Dashboard;
+        self.select_task_by_id(task_id);
 
-        self.set_loading(false, "");
+        if self.get_selected_task().is_some_and(|t| t.id == task_id) {
+            self.open_task_detail().await;
+        } else {
+            self.set_error("Task is not on the board".to_string());
+        }
     }
 
-    async fn do_update_document(&mut self) {
+    async fn do_toggle_notification_setting(&mut self) {
         let workspace_id = match &self.current_workspace {
             Some(w) => w.id,
             None => return,
         };
 
-        let doc_id = match &self.kb_selected_doc {
-            Some(d) => d.id,
-            None => return,
+        let mut settings = self.notification_settings;
+        let flag = match self.notification_settings_cursor {
+            0 => &mut settings.notify_task_assigned,
+            1 => &mut settings.notify_task_updated,
+            2 => &mut settings.notify_task_commented,
+            3 => &mut settings.notify_due_date_approaching,
+            4 => &mut settings.notify_mention,
+            5 => &mut settings.email_digest,
+            _ => return,
         };
+        *flag = !*flag;
 
-        let content = self.get_kb_content();
-        let req = UpdateDocumentRequest {
-            title: Some(self.kb_edit_title.clone()),
-            content: Some(content),
-        };
+        match self.api.update_notification_settings(workspace_id, &settings).await {
+            Ok(saved) => self.notification_settings = saved,
+            Err(e) => self.set_error(format!("Failed to save notification settings: {}", e)),
+        }
+    }
 
-        self.set_loading(true, "Updating document...");
+    // ============ Profile ============
 
-        match self.api.update_document(workspace_id, doc_id, req).await {
-            Ok(updated) => {
-                // Update in local list
-                if let Some(doc) = self.kb_documents.iter_mut().find(|d| d.id == doc_id) {
-                    *doc = updated.clone();
-                }
-                self.kb_selected_doc = Some(updated);
-                self.build_kb_visible_list();
-                self.kb_editing = false;
-                self.kb_edit_title.clear();
-                self.kb_content_textarea = None;
-                self.vim_mode = VimMode::Normal;
-            }
-            Err(e) => {
-                self.set_error(format!("Failed to update document: {}", e));
-            }
+    async fn open_profile(&mut self) {
+        if self.view != View::Profile {
+            self.profile_return_view = self.view.clone();
         }
+        self.close_password_form();
+        self.session_confirming_revoke = false;
+        self.sessions_confirming_logout_all = false;
+        self.view = View::Profile;
+        self.load_sessions().await;
+    }
 
-        self.set_loading(false, "");
+    async fn load_sessions(&mut self) {
+        match self.api.list_sessions().await {
+            Ok(sessions) => {
+                self.sessions = sessions;
+                self.session_cursor = self.session_cursor.min(self.sessions.len().saturating_sub(1));
+            }
+            Err(e) => self.set_error(format!("Failed to load sessions: {}", e)),
+        }
     }
 
-    async fn do_delete_document(&mut self) {
-        let workspace_id = match &self.current_workspace {
-            Some(w) => w.id,
-            None => return,
-        };
+    fn close_password_form(&mut self) {
+        self.changing_password = false;
+        self.password_field = PasswordField::Current;
+        self.current_password_input.clear();
+        self.new_password_input.clear();
+        self.confirm_password_input.clear();
+        self.vim_mode = VimMode::Normal;
+    }
 
-        let doc_id = match &self.kb_selected_doc {
-            Some(d) => d.id,
-            None => return,
-        };
+    async fn handle_profile_key(&mut self, key: KeyEvent) -> Result<bool> {
+        if self.loading {
+            return Ok(false);
+        }
 
-        self.set_loading(true, "Deleting document...");
+        if self.changing_password {
+            match key.code {
+                KeyCode::Esc => self.close_password_form(),
+                KeyCode::Tab | KeyCode::Down => {
+                    self.password_field = match self.password_field {
+                        PasswordField::Current => PasswordField::New,
+                        PasswordField::New => PasswordField::Confirm,
+                        PasswordField::Confirm => PasswordField::Current,
+                    };
+                }
+                KeyCode::BackTab | KeyCode::Up => {
+                    self.password_field = match self.password_field {
+                        PasswordField::Current => PasswordField::Confirm,
+                        PasswordField::New => PasswordField::Current,
+                        PasswordField::Confirm => PasswordField::New,
+                    };
+                }
+                KeyCode::Enter => {
+                    if self.current_password_input.is_empty() {
+                        self.password_field = PasswordField::Current;
+                    } else if self.new_password_input.is_empty() {
+                        self.password_field = PasswordField::New;
+                    } else if self.confirm_password_input.is_empty() {
+                        self.password_field = PasswordField::Confirm;
+                    } else {
+                        self.do_change_password().await;
+                    }
+                }
+                KeyCode::Char(c) => match self.password_field {
+                    PasswordField::Current => self.current_password_input.push(c),
+                    PasswordField::New => self.new_password_input.push(c),
+                    PasswordField::Confirm => self.confirm_password_input.push(c),
+                },
+                KeyCode::Backspace => {
+                    match self.password_field {
+                        PasswordField::Current => self.current_password_input.pop(),
+                        PasswordField::New => self.new_password_input.pop(),
+                        PasswordField::Confirm => self.confirm_password_input.pop(),
+                    };
+                }
+                _ => {}
+            }
+            return Ok(false);
+        }
 
-        match self.api.delete_document(workspace_id, doc_id).await {
-            Ok(_) => {
-                // Remove from local list (and any children)
-                self.kb_documents.retain(|d| d.id != doc_id && d.parent_id != Some(doc_id));
-                self.kb_expanded.remove(&doc_id);
-                self.build_kb_visible_list();
-                // Adjust selection
-                if self.kb_selected_idx >= self.kb_visible_list.len() {
-                    self.kb_selected_idx = self.kb_visible_list.len().saturating_sub(1);
+        if self.session_confirming_revoke || self.sessions_confirming_logout_all {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    if self.session_confirming_revoke {
+                        self.session_confirming_revoke = false;
+                        self.do_revoke_session().await;
+                    } else {
+                        self.sessions_confirming_logout_all = false;
+                        self.do_logout_everywhere().await;
+                    }
                 }
-                self.kb_selected_doc = self.kb_visible_list.get(self.kb_selected_idx).map(|(d, _)| d.clone());
-                self.kb_confirming_delete = false;
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    self.session_confirming_revoke = false;
+                    self.sessions_confirming_logout_all = false;
+                }
+                _ => {}
             }
-            Err(e) => {
-                self.set_error(format!("Failed to delete document: {}", e));
-                self.kb_confirming_delete = false;
+            return Ok(false);
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => {
+                self.view = self.profile_return_view.clone();
+            }
+            KeyCode::Char('p') => {
+                self.close_password_form();
+                self.changing_password = true;
+                self.vim_mode = VimMode::Insert;
+            }
+            KeyCode::Char('j') | KeyCode::Down if self.session_cursor + 1 < self.sessions.len() => {
+                self.session_cursor += 1;
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.session_cursor = self.session_cursor.saturating_sub(1);
             }
+            KeyCode::Char('x') if !self.sessions.is_empty() => {
+                self.session_confirming_revoke = true;
+            }
+            KeyCode::Char('L') => {
+                self.sessions_confirming_logout_all = true;
+            }
+            KeyCode::Char('r') => {
+                self.load_sessions().await;
+            }
+            _ => {}
         }
 
-        self.set_loading(false, "");
+        Ok(false)
     }
 
-    async fn navigate_to_document(&mut self, doc: Document) {
-        // First open knowledge base to load documents
-        self.open_knowledge_base().await;
+    async fn do_revoke_session(&mut self) {
+        let Some(session) = self.sessions.get(self.session_cursor).cloned() else {
+            return;
+        };
 
-        // Find the document in the visible list
-        if let Some(pos) = self.kb_visible_list.iter().position(|(d, _)| d.id == doc.id) {
-            self.kb_selected_idx = pos;
-            self.kb_selected_doc = Some(doc);
-            self.load_kb_linked_tasks().await;
-        } else {
-            // Document might be under a collapsed parent - expand parents and rebuild
-            // For simplicity, just select the first document and set kb_selected_doc
-            self.kb_selected_doc = Some(doc);
+        // Signing this device out is just logging out
+        if session.current {
+            self.do_logout().await;
+            return;
         }
-    }
 
-    async fn load_kb_linked_tasks(&mut self) {
-        let workspace_id = match &self.current_workspace {
-            Some(w) => w.id,
-            None => {
-                self.kb_linked_tasks.clear();
-                return;
-            }
-        };
+        self.set_loading(true, "Signing out session...");
 
-        let doc_id = match &self.kb_selected_doc {
-            Some(d) => d.id,
-            None => {
-                self.kb_linked_tasks.clear();
-                return;
-            }
-        };
+        match self.api.revoke_session(session.id).await {
+            Ok(()) => self.load_sessions().await,
+            Err(e) => self.set_error(format!("Failed to sign out session: {}", e)),
+        }
 
-        match self.api.list_linked_tasks(workspace_id, doc_id).await {
-            Ok(tasks) => {
-                self.kb_linked_tasks = tasks;
-            }
-            Err(_) => {
-                self.kb_linked_tasks.clear();
-            }
+        self.set_loading(false, "");
+    }
+
+    async fn do_logout_everywhere(&mut self) {
+        // Log it while the session still works
+        if !self.log_running_timer().await {
+            return;
+        }
+        self.set_loading(true, "Signing out everywhere...");
+        let result = self.api.revoke_all_sessions().await;
+        self.set_loading(false, "");
+
+        match result {
+            Ok(()) => self.do_logout().await,
+            Err(e) => self.set_error(format!("Failed to sign out everywhere: {}", e)),
         }
     }
 
-    // ============ Help Modal ============
+    async fn do_change_password(&mut self) {
+        if self.new_password_input != self.confirm_password_input {
+            self.set_error("New passwords don't match".to_string());
+            self.confirm_password_input.clear();
+            self.password_field = PasswordField::Confirm;
+            return;
+        }
 
-    fn handle_help_key(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => {
-                self.help_visible = false;
-            }
-            KeyCode::Char('j') | KeyCode::Down => {
-                self.help_scroll = self.help_scroll.saturating_add(1);
+        self.set_loading(true, "Changing password...");
+
+        let current = self.current_password_input.clone();
+        let new = self.new_password_input.clone();
+
+        match self.api.change_password(&current, &new).await {
+            Ok(()) => {
+                self.close_password_form();
+                self.session_cursor = 0;
+                self.load_sessions().await;
+                self.set_info("Password changed. Your other sessions were signed out.".to_string());
             }
-            KeyCode::Char('k') | KeyCode::Up => {
-                self.help_scroll = self.help_scroll.saturating_sub(1);
+            Err(e) => {
+                self.set_error(format!("Failed to change password: {}", e));
             }
-            _ => {}
         }
+
+        self.set_loading(false, "");
     }
 
     // ============ Menu ============
 
-    const MENU_ITEMS: [(&'static str, &'static str); 7] = [
+    const MENU_ITEMS: [(&'static str, &'static str); 10] = [
         ("m", "Members"),
         ("k", "Knowledge Base"),
         ("t", "Tags"),
@@ -4273,6 +7882,9 @@ impl App {
         ("p", "Presets"),
         ("/", "Search"),
         ("w", "Workspaces"),
+        ("x", "Trash"),
+        ("c", "Columns"),
+        ("i", "Notifications"),
     ];
 
     async fn handle_menu_key(&mut self, key: KeyEvent, tx: mpsc::Sender<AppEvent>) -> Result<bool> {
@@ -4320,6 +7932,18 @@ impl App {
                 self.menu_selected_idx = 6;
                 self.execute_menu_action(tx).await?;
             }
+            KeyCode::Char('x') => {
+                self.menu_selected_idx = 7;
+                self.execute_menu_action(tx).await?;
+            }
+            KeyCode::Char('c') => {
+                self.menu_selected_idx = 8;
+                self.execute_menu_action(tx).await?;
+            }
+            KeyCode::Char('i') => {
+                self.menu_selected_idx = 9;
+                self.execute_menu_action(tx).await?;
+            }
             _ => {}
         }
         Ok(false)
@@ -4370,8 +7994,33 @@ impl App {
                 // Workspaces
                 self.open_workspace_modal().await;
             }
+            7 => {
+                // Trash
+                self.open_trash().await;
+            }
+            8 => {
+                // Columns
+                self.open_column_management();
+            }
+            9 => {
+                // Notifications
+                self.open_notifications().await;
+            }
             _ => {}
         }
         Ok(())
     }
 }
+
+/// Parse a priority name as typed in commands; "none" means no priority
+fn parse_priority(value: &str) -> Result<Option<Priority>, String> {
+    match value.to_lowercase().as_str() {
+        "highest" => Ok(Some(Priority::Highest)),
+        "high" => Ok(Some(Priority::High)),
+        "medium" => Ok(Some(Priority::Medium)),
+        "low" => Ok(Some(Priority::Low)),
+        "lowest" => Ok(Some(Priority::Lowest)),
+        "none" => Ok(None),
+        _ => Err(format!("Invalid priority: {}", value)),
+    }
+}
@@ -1,5 +1,5 @@
 use std::io;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use crossterm::{
@@ -9,6 +9,8 @@ use crossterm::{
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
 use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
 
 mod api;
 mod app;
@@ -16,11 +18,30 @@ mod calendar;
 mod editor;
 mod figlet;
 mod markdown;
+mod merge;
 mod ui;
 
-use api::ApiClient;
+use api::{ApiClient, EventStream, StreamEvent};
 use app::{App, AppEvent, View};
 
+/// How long to wait before reopening a dropped event stream
+const EVENT_STREAM_RETRY: Duration = Duration::from_secs(5);
+
+/// Live change feed for the open workspace
+struct Subscription {
+    workspace_id: Uuid,
+    reader: JoinHandle<()>,
+}
+
+/// State of the change feed across reconnects
+#[derive(Default)]
+struct Feed {
+    subscription: Option<Subscription>,
+    retry_at: Option<Instant>,
+    /// Set after a drop, so the next connect reloads what was missed
+    stale: bool,
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     dotenvy::dotenv().ok();
@@ -146,7 +167,11 @@ async fn run_app<B: ratatui::backend::Backend>(
         });
     }
 
+    let mut feed = Feed::default();
+
     loop {
+        sync_event_stream(&mut app, &mut feed, &tx).await;
+
         terminal.draw(|f| ui::draw(f, &app))?;
 
         if let Some(event) = rx.recv().await {
@@ -162,7 +187,8 @@ async fn run_app<B: ratatui::backend::Backend>(
                     }
                 }
                 AppEvent::Tick => {
-                    // Just refresh UI
+                    // Redraw, and catch up on notifications after a burst of changes
+                    app.refresh_notifications_if_due().await;
                 }
                 AppEvent::VerifyAuth => {
                     app.verify_auth().await;
@@ -179,6 +205,21 @@ async fn run_app<B: ratatui::backend::Backend>(
                 AppEvent::WorkspaceDataLoaded { statuses, tasks } => {
                     app.on_workspace_data_loaded(statuses, tasks);
                 }
+                AppEvent::WorkspaceChanged(event) => {
+                    app.on_workspace_event(event).await;
+                }
+                AppEvent::WorkspaceResync(workspace_id) => {
+                    app.is_connected = true;
+                    app.on_workspace_resync(workspace_id).await;
+                }
+                AppEvent::EventStreamClosed(workspace_id) => {
+                    if feed.subscription.as_ref().map(|s| s.workspace_id) == Some(workspace_id) {
+                        feed.subscription = None;
+                        feed.retry_at = Some(Instant::now() + EVENT_STREAM_RETRY);
+                        feed.stale = true;
+                        app.is_connected = false;
+                    }
+                }
                 AppEvent::Error(msg) => {
                     app.set_error(msg);
                 }
@@ -186,3 +227,59 @@ async fn run_app<B: ratatui::backend::Backend>(
         }
     }
 }
+
+/// Keep the change feed pointed at the open workspace, reconnecting when it drops
+async fn sync_event_stream(app: &mut App, feed: &mut Feed, tx: &mpsc::Sender<AppEvent>) {
+    let wanted = app.current_workspace.as_ref().map(|ws| ws.id);
+
+    if feed.subscription.as_ref().map(|s| s.workspace_id) != wanted {
+        if let Some(old) = feed.subscription.take() {
+            old.reader.abort();
+        }
+        feed.retry_at = None;
+        feed.stale = false;
+    }
+
+    let Some(workspace_id) = wanted else {
+        return;
+    };
+    if feed.subscription.is_some() || feed.retry_at.is_some_and(|at| Instant::now() < at) {
+        return;
+    }
+
+    let request = match app.api.event_stream_request(workspace_id).await {
+        Ok(request) => request,
+        Err(_) => {
+            feed.retry_at = Some(Instant::now() + EVENT_STREAM_RETRY);
+            return;
+        }
+    };
+
+    // Changes made while we were away were never sent, so reload once connected
+    let stale = std::mem::take(&mut feed.stale);
+
+    let tx_events = tx.clone();
+    let reader = tokio::spawn(async move {
+        if let Ok(mut stream) = EventStream::connect(request).await {
+            if stale {
+                let _ = tx_events.send(AppEvent::WorkspaceResync(workspace_id)).await;
+            }
+            while let Some(event) = stream.next().await {
+                let event = match event {
+                    StreamEvent::Change(event) => AppEvent::WorkspaceChanged(event),
+                    StreamEvent::Resync => AppEvent::WorkspaceResync(workspace_id),
+                };
+                if tx_events.send(event).await.is_err() {
+                    return;
+                }
+            }
+        }
+        let _ = tx_events.send(AppEvent::EventStreamClosed(workspace_id)).await;
+    });
+
+    feed.subscription = Some(Subscription {
+        workspace_id,
+        reader,
+    });
+    feed.retry_at = None;
+}
@@ -0,0 +1,266 @@
+//! Line diffs and three-way merges for resolving edit conflicts
+//! Used when a save is rejected because someone else changed the task or page first
+
+/// Above this many line pairs the diff stops looking for common lines in the
+/// changed middle and reports it as one replaced block
+const MAX_DIFF_CELLS: usize = 1_000_000;
+
+/// One line of a diff from their text to mine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Same(String),
+    Theirs(String),
+    Mine(String),
+}
+
+/// A run of base lines `[start, end)` replaced by `lines`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Hunk {
+    start: usize,
+    end: usize,
+    lines: Vec<String>,
+}
+
+/// Pairs of (old index, new index) for lines kept between `old` and `new`
+fn common_lines(old: &[&str], new: &[&str]) -> Vec<(usize, usize)> {
+    // Trim the shared prefix and suffix first; edits are usually local
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let mut pairs: Vec<(usize, usize)> = (0..prefix).map(|i| (i, i)).collect();
+
+    if !old_mid.is_empty()
+        && !new_mid.is_empty()
+        && old_mid.len() * new_mid.len() <= MAX_DIFF_CELLS
+    {
+        // Longest common subsequence table, filled from the end
+        let (n, m) = (old_mid.len(), new_mid.len());
+        let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i][j] = if old_mid[i] == new_mid[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < n && j < m {
+            if old_mid[i] == new_mid[j] {
+                pairs.push((prefix + i, prefix + j));
+                i += 1;
+                j += 1;
+            } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+    }
+
+    let old_tail = old.len() - suffix;
+    let new_tail = new.len() - suffix;
+    pairs.extend((0..suffix).map(|k| (old_tail + k, new_tail + k)));
+
+    pairs
+}
+
+/// Changed regions between `base` and `edited`
+fn hunks(base: &[&str], edited: &[&str]) -> Vec<Hunk> {
+    let mut result = Vec::new();
+    let (mut bi, mut ei) = (0, 0);
+
+    let sentinel = (base.len(), edited.len());
+    for (bm, em) in common_lines(base, edited).into_iter().chain(std::iter::once(sentinel)) {
+        if bm > bi || em > ei {
+            result.push(Hunk {
+                start: bi,
+                end: bm,
+                lines: edited[ei..em].iter().map(|l| l.to_string()).collect(),
+            });
+        }
+        bi = bm + 1;
+        ei = em + 1;
+    }
+
+    result
+}
+
+/// Line diff going from `theirs` to `mine`
+pub fn diff_lines(theirs: &str, mine: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = theirs.lines().collect();
+    let new: Vec<&str> = mine.lines().collect();
+
+    let mut result = Vec::new();
+    let (mut oi, mut ni) = (0, 0);
+
+    let sentinel = (old.len(), new.len());
+    for (om, nm) in common_lines(&old, &new).into_iter().chain(std::iter::once(sentinel)) {
+        result.extend(old[oi..om].iter().map(|l| DiffLine::Theirs(l.to_string())));
+        result.extend(new[ni..nm].iter().map(|l| DiffLine::Mine(l.to_string())));
+        if om < old.len() {
+            result.push(DiffLine::Same(old[om].to_string()));
+        }
+        oi = om + 1;
+        ni = nm + 1;
+    }
+
+    result
+}
+
+/// Combine two edits of `base`. Returns `None` when both sides touched the
+/// same lines (or inserted at the same spot) with different results.
+pub fn merge3(base: &str, theirs: &str, mine: &str) -> Option<String> {
+    if theirs == mine || mine == base {
+        return Some(theirs.to_string());
+    }
+    if theirs == base {
+        return Some(mine.to_string());
+    }
+
+    let base_lines: Vec<&str> = base.lines().collect();
+    let theirs_lines: Vec<&str> = theirs.lines().collect();
+    let mine_lines: Vec<&str> = mine.lines().collect();
+
+    let mut all: Vec<Hunk> = hunks(&base_lines, &theirs_lines);
+    for hunk in hunks(&base_lines, &mine_lines) {
+        // The same change made on both sides only needs applying once
+        if !all.contains(&hunk) {
+            all.push(hunk);
+        }
+    }
+    all.sort_by_key(|h| (h.start, h.end));
+
+    let mut merged: Vec<String> = Vec::new();
+    let mut pos = 0;
+    let mut applied_any = false;
+    for hunk in &all {
+        // Hunks from one side are always split by a kept line, so touching or
+        // overlapping hunks come from both sides and clash (same rule as diff3)
+        if applied_any && hunk.start <= pos {
+            return None;
+        }
+        merged.extend(base_lines[pos..hunk.start].iter().map(|l| l.to_string()));
+        merged.extend(hunk.lines.iter().cloned());
+        pos = hunk.end;
+        applied_any = true;
+    }
+    merged.extend(base_lines[pos..].iter().map(|l| l.to_string()));
+
+    let mut text = merged.join("\n");
+    if mine.ends_with('\n') || theirs.ends_with('\n') {
+        text.push('\n');
+    }
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_marks_replaced_lines() {
+        assert_eq!(
+            diff_lines("a\nb\nc", "a\nB\nc"),
+            vec![
+                DiffLine::Same("a".to_string()),
+                DiffLine::Theirs("b".to_string()),
+                DiffLine::Mine("B".to_string()),
+                DiffLine::Same("c".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_handles_pure_insertions_and_deletions() {
+        assert_eq!(
+            diff_lines("a\nc", "a\nb\nc"),
+            vec![
+                DiffLine::Same("a".to_string()),
+                DiffLine::Mine("b".to_string()),
+                DiffLine::Same("c".to_string()),
+            ]
+        );
+        assert_eq!(
+            diff_lines("a\nb\nc", "a\nc"),
+            vec![
+                DiffLine::Same("a".to_string()),
+                DiffLine::Theirs("b".to_string()),
+                DiffLine::Same("c".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_ignores_trailing_newline() {
+        assert_eq!(
+            diff_lines("a\nb\n", "a\nb"),
+            vec![DiffLine::Same("a".to_string()), DiffLine::Same("b".to_string())]
+        );
+    }
+
+    #[test]
+    fn merge_takes_the_only_changed_side() {
+        assert_eq!(merge3("a\nb", "a\nB", "a\nb").as_deref(), Some("a\nB"));
+        assert_eq!(merge3("a\nb", "a\nb", "a\nB").as_deref(), Some("a\nB"));
+        assert_eq!(merge3("a\nb", "a\nB", "a\nB").as_deref(), Some("a\nB"));
+    }
+
+    #[test]
+    fn merge_combines_non_overlapping_hunks() {
+        let base = "a\nb\nc\nd\ne";
+        assert_eq!(
+            merge3(base, "A\nb\nc\nd\ne", "a\nb\nc\nd\nE").as_deref(),
+            Some("A\nb\nc\nd\nE")
+        );
+        assert_eq!(
+            merge3(base, "a\nb\nc\nd\ne\nf", "a\nc\nd\ne").as_deref(),
+            Some("a\nc\nd\ne\nf")
+        );
+    }
+
+    #[test]
+    fn merge_rejects_overlapping_hunks() {
+        assert_eq!(merge3("a\nb\nc", "a\nX\nc", "a\nY\nc"), None);
+        assert_eq!(merge3("a\nb\nc\nd", "a\nX\nY\nd", "a\nb\nZ\nd"), None);
+    }
+
+    #[test]
+    fn merge_rejects_touching_hunks() {
+        // Adjacent changes with no kept line between them clash, as in diff3
+        assert_eq!(merge3("a\nb\nc\nd", "a\nB\nc\nd", "a\nb\nC\nd"), None);
+    }
+
+    #[test]
+    fn merge_insertions_at_the_same_spot() {
+        assert_eq!(merge3("a\nb", "a\nx\nb", "a\ny\nb"), None);
+        // The same insertion on both sides is applied once
+        assert_eq!(
+            merge3("a\nb\nc\nd", "a\nx\nb\nc\nd\ne", "a\nx\nb\nc\nd").as_deref(),
+            Some("a\nx\nb\nc\nd\ne")
+        );
+    }
+
+    #[test]
+    fn merge_keeps_a_trailing_newline() {
+        assert_eq!(
+            merge3("a\nb\nc\n", "A\nb\nc\n", "a\nb\nC\n").as_deref(),
+            Some("A\nb\nC\n")
+        );
+        assert_eq!(
+            merge3("a\nb\nc", "A\nb\nc", "a\nb\nC\n").as_deref(),
+            Some("A\nb\nC\n")
+        );
+        assert_eq!(merge3("a\nb\nc", "A\nb\nc", "a\nb\nC").as_deref(), Some("A\nb\nC"));
+    }
+}
@@ -6,10 +6,14 @@ use ratatui::{
     Frame,
 };
 
-use crate::app::{App, AuthMode, DueDateMode, FilterPanelSection, InputField, KbFocus, NewTaskField, TaskEditField, View, VimMode, SORT_FIELDS};
+use crate::app::{App, AuthMode, Column, DueDateMode, EditConflict, FilterPanelSection, InputField, KbFocus, NewTaskField, PasswordField, TaskEditField, View, VimMode, SORT_FIELDS};
 use crate::markdown;
+use crate::merge::DiffLine;
 use todo_shared::api::SearchResultItem;
-use todo_shared::Priority;
+use todo_shared::{ActivityAction, ActivityEntityType, ActivityEntry, Priority};
+
+/// Width of a collapsed board column, borders included
+const COLLAPSED_COLUMN_WIDTH: u16 = 5;
 
 /// Parse a hex color string like "#ff0000" to a ratatui Color
 fn parse_hex_color(hex: &str) -> Option<Color> {
@@ -98,6 +102,9 @@ pub fn draw(f: &mut Frame, app: &App) {
         View::Dashboard => draw_dashboard(f, app),
         View::TaskDetail => draw_task_detail(f, app),
         View::KnowledgeBase => draw_knowledge_base(f, app),
+        View::Trash => draw_trash(f, app),
+        View::Notifications => draw_notifications(f, app),
+        View::Profile => draw_profile(f, app),
     }
 
     // Draw workspace modal (can appear over Dashboard or KnowledgeBase)
@@ -105,9 +112,16 @@ pub fn draw(f: &mut Frame, app: &App) {
         draw_workspace_modal(f, app);
     }
 
+    // Draw edit conflict prompt (over the editor it came from)
+    if let Some(ref conflict) = app.edit_conflict {
+        draw_edit_conflict_popup(f, conflict);
+    }
+
     // Draw error overlay if present
     if let Some(ref error) = app.error_message {
         draw_error_popup(f, error);
+    } else if let Some(ref info) = app.info_message {
+        draw_info_popup(f, info);
     }
 
     // Draw loading overlay if loading
@@ -122,6 +136,11 @@ pub fn draw(f: &mut Frame, app: &App) {
 }
 
 fn draw_login(f: &mut Frame, app: &App) {
+    if matches!(app.auth_mode, AuthMode::ForgotPassword | AuthMode::ResetPassword) {
+        draw_password_reset(f, app);
+        return;
+    }
+
     let area = f.area();
 
     let is_register = app.auth_mode == AuthMode::Register;
@@ -302,7 +321,7 @@ fn draw_login(f: &mut Frame, app: &App) {
 
         // Submit hint
         let mode_text = match app.vim_mode {
-            VimMode::Normal => "'i' edit | Enter submit | 'r' register | 'q' quit",
+            VimMode::Normal => "'i' edit | Enter submit | 'r' register | 'f' forgot password | 'q' quit",
             VimMode::Insert => "Type to enter | Esc normal | Enter submit",
         };
         let hint = Paragraph::new(mode_text)
@@ -328,6 +347,146 @@ fn draw_login(f: &mut Frame, app: &App) {
     }
 }
 
+/// "Forgot password": ask for a code, then trade it for a new password
+fn draw_password_reset(f: &mut Frame, app: &App) {
+    let area = f.area();
+
+    let is_reset = app.auth_mode == AuthMode::ResetPassword;
+    let form_height = if is_reset { 15 } else { 12 };
+
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(25),
+            Constraint::Length(form_height),
+            Constraint::Percentage(25),
+        ])
+        .split(area);
+
+    let horizontal = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(25),
+            Constraint::Percentage(50),
+            Constraint::Percentage(25),
+        ])
+        .split(vertical[1]);
+
+    let form_area = horizontal[1];
+
+    let form_block = Block::default()
+        .title(" Reset Password ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let inner = form_block.inner(form_area);
+    f.render_widget(form_block, form_area);
+
+    let field_style = |field: InputField| {
+        if app.login_field == field {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::Gray)
+        }
+    };
+
+    if is_reset {
+        let form_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(2), // Info text
+                Constraint::Length(3), // Code
+                Constraint::Length(3), // New password
+                Constraint::Length(2), // Submit hint
+                Constraint::Min(0),    // Spacer
+            ])
+            .split(inner);
+
+        let info = Paragraph::new(format!("Enter the code sent to {}", app.login_email))
+            .style(Style::default().fg(Color::White))
+            .alignment(Alignment::Center);
+        f.render_widget(info, form_chunks[0]);
+
+        let code_block = Block::default()
+            .title(" 6-Digit Code ")
+            .borders(Borders::ALL)
+            .border_style(field_style(InputField::VerificationCode));
+        let code_text = Paragraph::new(app.verification_code.as_str()).block(code_block);
+        f.render_widget(code_text, form_chunks[1]);
+
+        let password_block = Block::default()
+            .title(" New Password ")
+            .borders(Borders::ALL)
+            .border_style(field_style(InputField::Password));
+        let password_display = "*".repeat(app.login_password.len());
+        let password_text = Paragraph::new(password_display.as_str()).block(password_block);
+        f.render_widget(password_text, form_chunks[2]);
+
+        let mode_text = match app.vim_mode {
+            VimMode::Normal => "'i' edit | Enter reset | 'f' new code | 'l' login",
+            VimMode::Insert => "Type to enter | Tab next field | Esc normal | Enter reset",
+        };
+        let hint = Paragraph::new(mode_text)
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
+        f.render_widget(hint, form_chunks[3]);
+
+        if app.vim_mode == VimMode::Insert {
+            let (x, y) = match app.login_field {
+                InputField::Password => (
+                    form_chunks[2].x + 1 + app.login_password.len() as u16,
+                    form_chunks[2].y + 1,
+                ),
+                _ => (
+                    form_chunks[1].x + 1 + app.verification_code.len() as u16,
+                    form_chunks[1].y + 1,
+                ),
+            };
+            f.set_cursor_position((x, y));
+        }
+    } else {
+        let form_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(2), // Info text
+                Constraint::Length(3), // Email
+                Constraint::Length(2), // Submit hint
+                Constraint::Min(0),    // Spacer
+            ])
+            .split(inner);
+
+        let info = Paragraph::new("We'll send a code to reset your password")
+            .style(Style::default().fg(Color::White))
+            .alignment(Alignment::Center);
+        f.render_widget(info, form_chunks[0]);
+
+        let email_block = Block::default()
+            .title(" Email ")
+            .borders(Borders::ALL)
+            .border_style(field_style(InputField::Email));
+        let email_text = Paragraph::new(app.login_email.as_str()).block(email_block);
+        f.render_widget(email_text, form_chunks[1]);
+
+        let mode_text = match app.vim_mode {
+            VimMode::Normal => "'i' edit | Enter send code | 'l' login",
+            VimMode::Insert => "Type to enter | Esc normal | Enter send code",
+        };
+        let hint = Paragraph::new(mode_text)
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
+        f.render_widget(hint, form_chunks[2]);
+
+        if app.vim_mode == VimMode::Insert {
+            f.set_cursor_position((
+                form_chunks[1].x + 1 + app.login_email.len() as u16,
+                form_chunks[1].y + 1,
+            ));
+        }
+    }
+}
+
 fn draw_email_verification(f: &mut Frame, app: &App) {
     let area = f.area();
 
@@ -513,13 +672,14 @@ fn draw_home(f: &mut Frame, app: &App) {
         ])
         .split(main_chunks[0]);
 
-    // Right panel: logo + calendar + quote
+    // Right panel: logo + calendar + activity + quote
     let right_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(8),  // Logo
             Constraint::Length(10), // Calendar
-            Constraint::Min(3),     // Quote
+            Constraint::Min(5),     // Activity
+            Constraint::Length(7),  // Quote
         ])
         .split(main_chunks[1]);
 
@@ -567,8 +727,11 @@ fn draw_home(f: &mut Frame, app: &App) {
     // Calendar
     draw_home_calendar(f, right_chunks[1], app);
 
+    // Recent activity
+    draw_home_activity(f, right_chunks[2], app);
+
     // Quote
-    draw_home_quote(f, right_chunks[2], app);
+    draw_home_quote(f, right_chunks[3], app);
 }
 
 fn draw_home_menu(f: &mut Frame, area: Rect, app: &App) {
@@ -731,6 +894,49 @@ fn draw_home_calendar(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(paragraph, inner);
 }
 
+fn draw_home_activity(f: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default()
+        .title(" Recent Activity ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Magenta));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    if app.home_activity.is_empty() {
+        let empty = Paragraph::new(Span::styled(
+            "Nothing has happened here yet",
+            Style::default().fg(Color::DarkGray),
+        ));
+        f.render_widget(empty, inner);
+        return;
+    }
+
+    let items: Vec<ListItem> = app
+        .home_activity
+        .iter()
+        .map(|entry| {
+            let mut spans = vec![Span::styled(
+                format!("{} ", entry.created_at.format("%m-%d %H:%M")),
+                Style::default().fg(Color::DarkGray),
+            )];
+            spans.extend(activity_headline(entry));
+
+            let fields: Vec<String> = entry.field_changes().into_iter().map(|c| c.field).collect();
+            if !fields.is_empty() {
+                spans.push(Span::styled(
+                    format!(" ({})", fields.join(", ")),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    f.render_widget(List::new(items), inner);
+}
+
 fn draw_home_quote(f: &mut Frame, area: Rect, app: &App) {
     let block = Block::default()
         .title(" Quote of the Day ")
@@ -888,6 +1094,11 @@ fn draw_dashboard(f: &mut Frame, app: &App) {
         draw_delete_confirm_popup(f, app);
     }
 
+    // Draw the workflow comment prompt if active
+    if app.entering_move_comment {
+        draw_move_comment_popup(f, app);
+    }
+
     // Draw search popup if active
     if app.searching {
         draw_search_popup(f, app);
@@ -898,6 +1109,11 @@ fn draw_dashboard(f: &mut Frame, app: &App) {
         draw_tag_management_popup(f, app);
     }
 
+    // Draw column management popup if active
+    if app.column_management_visible {
+        draw_column_management_popup(f, app);
+    }
+
     // Draw member panel popup if active
     if app.member_panel_visible {
         draw_member_panel(f, app);
@@ -1050,6 +1266,18 @@ fn draw_filter_bar(f: &mut Frame, area: Rect, app: &App) {
         has_filters = true;
     }
 
+    // Custom field filters
+    if let Some(ref custom_fields) = app.active_filters.custom_fields {
+        for (field_id, value) in custom_fields {
+            if let Some(field) = app.workspace_custom_fields.iter().find(|f| &f.id == field_id) {
+                let display = app.custom_field_display(field, &serde_json::Value::String(value.clone()));
+                spans.push(Span::styled(format!(" {}: {} ", field.name, display), filter_style));
+                spans.push(Span::raw(" "));
+                has_filters = true;
+            }
+        }
+    }
+
     // Sort indicator
     if let Some(order_by) = &app.active_filters.order_by {
         let direction = app
@@ -1103,7 +1331,7 @@ fn draw_command_input(f: &mut Frame, area: Rect, app: &App) {
 
 fn draw_kanban(f: &mut Frame, area: Rect, app: &App) {
     if app.columns.is_empty() {
-        let empty = Paragraph::new("No columns. Create a task to get started.")
+        let empty = Paragraph::new("No columns. Press C to add one.")
             .style(Style::default().fg(Color::DarkGray))
             .block(
                 Block::default()
@@ -1114,9 +1342,17 @@ fn draw_kanban(f: &mut Frame, area: Rect, app: &App) {
         return;
     }
 
-    let column_count = app.columns.len();
-    let constraints: Vec<Constraint> = (0..column_count)
-        .map(|_| Constraint::Percentage((100 / column_count) as u16))
+    // Collapsed columns shrink to a strip, the others share what's left
+    let constraints: Vec<Constraint> = app
+        .columns
+        .iter()
+        .map(|column| {
+            if app.collapsed_columns.contains(&column.status.id) {
+                Constraint::Length(COLLAPSED_COLUMN_WIDTH)
+            } else {
+                Constraint::Fill(1)
+            }
+        })
         .collect();
 
     let column_chunks = Layout::default()
@@ -1126,12 +1362,23 @@ fn draw_kanban(f: &mut Frame, area: Rect, app: &App) {
 
     for (i, column) in app.columns.iter().enumerate() {
         let is_selected = i == app.selected_column;
+        let over_limit = column
+            .status
+            .wip_limit
+            .is_some_and(|limit| column.tasks.len() > limit as usize);
         let column_border_style = if is_selected {
             Style::default().fg(Color::Cyan)
+        } else if over_limit {
+            Style::default().fg(Color::Red)
         } else {
             Style::default().fg(Color::DarkGray)
         };
 
+        if app.collapsed_columns.contains(&column.status.id) {
+            draw_collapsed_column(f, column_chunks[i], column, column_border_style);
+            continue;
+        }
+
         let scroll_offset = app.column_scroll_offsets.get(i).copied().unwrap_or(0);
 
         // Calculate scroll indicators
@@ -1148,25 +1395,38 @@ fn draw_kanban(f: &mut Frame, area: Rect, app: &App) {
             ""
         };
 
+        // Task count, against the WIP limit when the column has one
+        let count = match column.status.wip_limit {
+            Some(limit) => format!("({}/{})", column.tasks.len(), limit),
+            None => format!("({})", column.tasks.len()),
+        };
+        let count_style = if over_limit {
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+
         // Render column block first
         let column_block = Block::default()
             .borders(Borders::ALL)
             .border_style(column_border_style)
-            .title(format!(
-                " {} ({}){}",
-                column.status.name,
-                column.tasks.len(),
-                scroll_indicator
-            ));
+            .title(Line::from(vec![
+                Span::raw(format!(" {} ", column.status.name)),
+                Span::styled(count, count_style),
+                Span::raw(scroll_indicator),
+            ]));
         let inner_area = column_block.inner(column_chunks[i]);
         f.render_widget(column_block, column_chunks[i]);
 
         // Render each task card with its own border
         let mut y_offset: u16 = 0;
         for (j, task) in column.tasks.iter().enumerate().skip(scroll_offset) {
-            // Calculate task card height: 1 line for title, +1 if due date, +1 if tags, +2 for borders
+            // Calculate task card height: 1 line for title, +1 if due date or subtasks, +1 if tags, +2 for borders
+            let has_meta_line = task.due_date.is_some()
+                || task.subtask_progress.is_some()
+                || task.checklist_progress.is_some();
             let content_lines = 1
-                + if task.due_date.is_some() { 1 } else { 0 }
+                + if has_meta_line { 1 } else { 0 }
                 + if !task.tags.is_empty() { 1 } else { 0 };
             let card_height = (content_lines + 2) as u16; // +2 for top/bottom borders
 
@@ -1176,8 +1436,11 @@ fn draw_kanban(f: &mut Frame, area: Rect, app: &App) {
             }
 
             let is_task_selected = is_selected && j == app.selected_task;
+            let is_task_marked = app.selecting_tasks && app.selected_task_ids.contains(&task.id);
             let task_border_style = if is_task_selected {
                 Style::default().fg(Color::Cyan)
+            } else if is_task_marked {
+                Style::default().fg(Color::Magenta)
             } else {
                 Style::default().fg(Color::DarkGray)
             };
@@ -1193,31 +1456,64 @@ fn draw_kanban(f: &mut Frame, area: Rect, app: &App) {
             // Build task content lines
             let mut task_content: Vec<Line> = Vec::new();
 
-            // Line 1: Priority indicator + title (with search highlighting if filter active)
+            // Line 1: Priority indicator + blocked marker + title (with search highlighting if filter active)
             let (priority_symbol, priority_color) = priority_indicator(task.priority);
-            let title_spans = if let Some(ref query) = app.active_filters.q {
-                let mut spans = vec![
-                    Span::styled(priority_symbol, Style::default().fg(priority_color)),
-                    Span::styled(" ", Style::default()),
-                ];
-                spans.extend(highlight_search_matches(&task.title, query, Style::default().fg(Color::White)));
-                spans
+            let mut title_spans = Vec::new();
+            if is_task_marked {
+                title_spans.push(Span::styled("● ", Style::default().fg(Color::Magenta)));
+            }
+            title_spans.push(Span::styled(priority_symbol, Style::default().fg(priority_color)));
+            title_spans.push(Span::styled(" ", Style::default()));
+            if !task.key.is_empty() {
+                title_spans.push(Span::styled(format!("{} ", task.key), Style::default().fg(Color::DarkGray)));
+            }
+            if !task.blocked_by.is_empty() {
+                title_spans.push(Span::styled("⛔ ", Style::default().fg(Color::Red)));
+            }
+            if let Some(ref query) = app.active_filters.q {
+                title_spans.extend(highlight_search_matches(&task.title, query, Style::default().fg(Color::White)));
             } else {
-                vec![
-                    Span::styled(priority_symbol, Style::default().fg(priority_color)),
-                    Span::styled(" ", Style::default()),
-                    Span::styled(task.title.clone(), Style::default().fg(Color::White)),
-                ]
-            };
+                title_spans.push(Span::styled(task.title.clone(), Style::default().fg(Color::White)));
+            }
             task_content.push(Line::from(title_spans));
 
-            // Line 2: Due date (if set)
-            if let Some(due_date) = task.due_date {
-                let date_str = due_date.format("%b %d").to_string();
-                task_content.push(Line::from(vec![
-                    Span::styled("📅 ", Style::default().fg(Color::DarkGray)),
-                    Span::styled(date_str, Style::default().fg(Color::DarkGray)),
-                ]));
+            // Line 2: Due date and subtask progress (if set)
+            if has_meta_line {
+                let mut meta_spans: Vec<Span> = Vec::new();
+                if let Some(due_date) = task.due_date {
+                    let date_str = due_date.format("%b %d").to_string();
+                    meta_spans.push(Span::styled("📅 ", Style::default().fg(Color::DarkGray)));
+                    meta_spans.push(Span::styled(date_str, Style::default().fg(Color::DarkGray)));
+                }
+                if let Some(progress) = task.subtask_progress {
+                    if !meta_spans.is_empty() {
+                        meta_spans.push(Span::styled("  ", Style::default()));
+                    }
+                    let progress_color = if progress.is_complete() {
+                        Color::Green
+                    } else {
+                        Color::DarkGray
+                    };
+                    meta_spans.push(Span::styled(
+                        format!("☑ {}/{}", progress.done, progress.total),
+                        Style::default().fg(progress_color),
+                    ));
+                }
+                if let Some(progress) = task.checklist_progress {
+                    if !meta_spans.is_empty() {
+                        meta_spans.push(Span::styled("  ", Style::default()));
+                    }
+                    let progress_color = if progress.is_complete() {
+                        Color::Green
+                    } else {
+                        Color::DarkGray
+                    };
+                    meta_spans.push(Span::styled(
+                        format!("☰ {}/{}", progress.checked, progress.total),
+                        Style::default().fg(progress_color),
+                    ));
+                }
+                task_content.push(Line::from(meta_spans));
             }
 
             // Line 3: Tags (if any)
@@ -1267,15 +1563,38 @@ fn draw_kanban(f: &mut Frame, area: Rect, app: &App) {
     }
 }
 
+/// A collapsed column: the task count on top and the name running down the strip
+fn draw_collapsed_column(f: &mut Frame, area: Rect, column: &Column, border_style: Style) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(border_style)
+        .title(format!("{}", column.tasks.len()));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let name: Vec<Line> = column
+        .status
+        .name
+        .chars()
+        .take(inner.height as usize)
+        .map(|c| Line::from(c.to_string()))
+        .collect();
+    f.render_widget(Paragraph::new(name).alignment(Alignment::Center), inner);
+}
+
 fn draw_status_bar(f: &mut Frame, area: Rect, app: &App) {
     let (mode, mode_color) = if app.searching {
         ("SEARCH", Color::Cyan)
+    } else if app.entering_move_comment {
+        ("COMMENT", Color::Magenta)
     } else if app.moving_task {
         ("MOVE", Color::Magenta)
     } else if app.creating_task {
         ("CREATE", Color::Green)
     } else if app.confirming_delete {
         ("DELETE", Color::Red)
+    } else if app.selecting_tasks {
+        ("VISUAL", Color::Magenta)
     } else {
         match app.vim_mode {
             VimMode::Normal => ("NORMAL", Color::Blue),
@@ -1285,24 +1604,31 @@ fn draw_status_bar(f: &mut Frame, area: Rect, app: &App) {
 
     let hints = if app.searching {
         "Type to search | Enter: select | Ctrl+F: fuzzy | Esc: cancel"
+    } else if app.entering_move_comment {
+        "Type a comment for this move | Enter: move | Esc: cancel"
     } else if app.moving_task {
         "h/l: move task | Esc: cancel"
     } else if app.creating_task {
         "Tab: next field | Enter: create | Esc: cancel"
     } else if app.confirming_delete {
         "y: confirm | n/Esc: cancel"
+    } else if app.selecting_tasks {
+        "Space: mark | a: mark column | m: move here | d: del | :priority/due/assign/tag/untag | Esc: done"
     } else {
         "?: help | ^P: menu | /: search | n: new | d: del | m: move | Enter: open"
     };
 
-    let status = Paragraph::new(Line::from(vec![
+    let mut spans = vec![
         Span::styled(
             format!(" {} ", mode),
             Style::default().bg(mode_color).fg(Color::White),
         ),
         Span::raw(" "),
-        Span::styled(hints, Style::default().fg(Color::DarkGray)),
-    ]));
+    ];
+    spans.extend(timer_span(app));
+    spans.extend(unread_span(app));
+    spans.push(Span::styled(hints, Style::default().fg(Color::DarkGray)));
+    let status = Paragraph::new(Line::from(spans));
 
     f.render_widget(status, area);
 }
@@ -1388,10 +1714,15 @@ fn draw_delete_confirm_popup(f: &mut Frame, app: &App) {
 
     f.render_widget(Clear, area);
 
-    let task_title = app
-        .get_selected_task()
-        .map(|t| t.title.as_str())
-        .unwrap_or("Unknown");
+    let (label, task_title) = if app.selecting_tasks {
+        ("Move marked tasks to trash:".to_string(), format!("{} tasks", app.selected_task_ids.len()))
+    } else {
+        let title = app
+            .get_selected_task()
+            .map(|t| t.title.as_str())
+            .unwrap_or("Unknown");
+        ("Move task to trash:".to_string(), format!("\"{}\"", title))
+    };
 
     let block = Block::default()
         .title(" Confirm Delete ")
@@ -1412,21 +1743,63 @@ fn draw_delete_confirm_popup(f: &mut Frame, app: &App) {
         .split(inner);
 
     let message = Paragraph::new(vec![
-        Line::from(Span::raw("Delete task:")),
-        Line::from(Span::styled(
-            format!("\"{}\"", task_title),
-            Style::default().fg(Color::Yellow),
-        )),
+        Line::from(Span::raw(label)),
+        Line::from(Span::styled(task_title, Style::default().fg(Color::Yellow))),
     ])
     .alignment(Alignment::Center);
     f.render_widget(message, chunks[0]);
 
-    let hint = Paragraph::new("y: yes, delete | n: no, cancel")
+    let hint = Paragraph::new("y: yes, move to trash | n: no, cancel")
         .style(Style::default().fg(Color::DarkGray))
         .alignment(Alignment::Center);
     f.render_widget(hint, chunks[1]);
 }
 
+fn draw_move_comment_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(60, 20, f.area());
+
+    f.render_widget(Clear, area);
+
+    let target = app
+        .columns
+        .get(app.move_comment_target)
+        .map(|c| c.status.name.as_str())
+        .unwrap_or("Unknown");
+
+    let block = Block::default()
+        .title(format!(" Move to {} ", target))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Magenta));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(1), // Message
+            Constraint::Length(3), // Input
+            Constraint::Length(1), // Hint
+            Constraint::Min(0),    // Spacer
+        ])
+        .split(inner);
+
+    let message = Paragraph::new("This move needs a comment:");
+    f.render_widget(message, chunks[0]);
+
+    let input = Paragraph::new(format!("{}_", app.move_comment_input))
+        .style(Style::default().fg(Color::Yellow))
+        .block(Block::default().borders(Borders::ALL))
+        .wrap(Wrap { trim: false });
+    f.render_widget(input, chunks[1]);
+
+    let hint = Paragraph::new("Enter: move | Esc: cancel")
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
+    f.render_widget(hint, chunks[2]);
+}
+
 fn draw_search_popup(f: &mut Frame, app: &App) {
     let area = centered_rect(70, 60, f.area());
 
@@ -1486,6 +1859,7 @@ fn draw_search_popup(f: &mut Frame, app: &App) {
                         Span::styled("  ", style),
                         Span::styled(priority_symbol, style.fg(priority_color)),
                         Span::styled(" ", style),
+                        Span::styled(format!("{} ", task_result.task.key), style.fg(Color::DarkGray)),
                     ];
 
                     // Parse title with highlight markers
@@ -1718,44 +2092,256 @@ fn draw_tag_management_popup(f: &mut Frame, app: &App) {
     }
 }
 
-fn draw_member_panel(f: &mut Frame, app: &App) {
+fn draw_column_management_popup(f: &mut Frame, app: &App) {
+    use crate::app::{ColumnManagementMode, TAG_COLORS};
+
     let area = centered_rect(50, 60, f.area());
     f.render_widget(Clear, area);
 
-    let title = if app.inviting_member {
-        " Invite Member "
-    } else {
-        " Workspace Members "
+    let title = match app.column_management_mode {
+        ColumnManagementMode::List => " Manage Columns ",
+        ColumnManagementMode::Create => " Create Column ",
+        ColumnManagementMode::Rename => " Rename Column ",
+        ColumnManagementMode::Delete => " Delete Column ",
     };
 
     let block = Block::default()
         .title(title)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+        .border_style(Style::default().fg(Color::Magenta));
 
     let inner = block.inner(area);
     f.render_widget(block, area);
 
-    if app.inviting_member {
-        // Invite form
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .margin(1)
-            .constraints([
-                Constraint::Length(3), // Email input
-                Constraint::Length(3), // Role selector
-                Constraint::Min(0),    // Spacer
-                Constraint::Length(2), // Hints
-            ])
-            .split(inner);
+    match app.column_management_mode {
+        ColumnManagementMode::List => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .margin(1)
+                .constraints([
+                    Constraint::Min(0),    // Column list
+                    Constraint::Length(2), // Hints
+                ])
+                .split(inner);
 
-        // Email input
-        let email_block = Block::default()
-            .title(" Email ")
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Yellow));
-        let email_input = Paragraph::new(app.invite_email.as_str()).block(email_block);
-        f.render_widget(email_input, chunks[0]);
+            let column_items: Vec<ListItem> = app
+                .columns
+                .iter()
+                .enumerate()
+                .map(|(i, column)| {
+                    let is_selected = i == app.column_management_cursor;
+                    let style = if is_selected {
+                        Style::default().bg(Color::DarkGray).fg(Color::White)
+                    } else {
+                        Style::default()
+                    };
+
+                    let column_color = column.status.color.as_ref()
+                        .and_then(|c| parse_hex_color(c))
+                        .unwrap_or(Color::Gray);
+
+                    let mut spans = vec![
+                        Span::styled("  ", style),
+                        Span::styled("██ ", Style::default().fg(column_color)),
+                        Span::styled(column.status.name.clone(), style),
+                        Span::styled(format!("  ({})", column.tasks.len()), style.fg(Color::DarkGray)),
+                    ];
+                    if column.status.is_done {
+                        spans.push(Span::styled("  ✓ done", Style::default().fg(Color::Green)));
+                    }
+                    if let Some(limit) = column.status.wip_limit {
+                        spans.push(Span::styled(format!("  WIP {}", limit), Style::default().fg(Color::Yellow)));
+                    }
+                    if app.collapsed_columns.contains(&column.status.id) {
+                        spans.push(Span::styled("  collapsed", Style::default().fg(Color::DarkGray)));
+                    }
+
+                    ListItem::new(Line::from(spans))
+                })
+                .collect();
+
+            let list = List::new(column_items).block(
+                Block::default()
+                    .title(format!(" Columns ({}) ", app.columns.len()))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Gray)),
+            );
+            f.render_widget(list, chunks[0]);
+
+            // Hints
+            let hint = Paragraph::new(vec![
+                Line::from(vec![
+                    Span::styled("n", Style::default().fg(Color::Yellow)),
+                    Span::raw(": new | "),
+                    Span::styled("r", Style::default().fg(Color::Yellow)),
+                    Span::raw(": rename | "),
+                    Span::styled("c", Style::default().fg(Color::Yellow)),
+                    Span::raw(": color | "),
+                    Span::styled("x", Style::default().fg(Color::Yellow)),
+                    Span::raw(": done | "),
+                    Span::styled("d", Style::default().fg(Color::Yellow)),
+                    Span::raw(": delete"),
+                ]),
+                Line::from(vec![
+                    Span::styled("J/K", Style::default().fg(Color::Yellow)),
+                    Span::raw(": reorder | "),
+                    Span::styled("z", Style::default().fg(Color::Yellow)),
+                    Span::raw(": collapse | "),
+                    Span::styled("Esc", Style::default().fg(Color::Yellow)),
+                    Span::raw(": close"),
+                ]),
+            ])
+            .alignment(Alignment::Center);
+            f.render_widget(hint, chunks[1]);
+        }
+        ColumnManagementMode::Create | ColumnManagementMode::Rename => {
+            let creating = app.column_management_mode == ColumnManagementMode::Create;
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .margin(1)
+                .constraints([
+                    Constraint::Length(3),                              // Name input
+                    Constraint::Length(if creating { 3 } else { 0 }), // Color selector
+                    Constraint::Min(0),                                 // Spacer
+                    Constraint::Length(2),                              // Hints
+                ])
+                .split(inner);
+
+            // Name input
+            let name_block = Block::default()
+                .title(" Name ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow));
+            let name_input = Paragraph::new(app.column_name_input.as_str()).block(name_block);
+            f.render_widget(name_input, chunks[0]);
+
+            let mut hint_spans = vec![
+                Span::styled("Enter", Style::default().fg(Color::Yellow)),
+                Span::raw(": save | "),
+            ];
+
+            // Color selector, recoloring an existing column is done from the list
+            if creating {
+                let selected_color = TAG_COLORS.get(app.column_color_idx).unwrap_or(&"#6B7280");
+                let color_preview = parse_hex_color(selected_color).unwrap_or(Color::Gray);
+
+                let color_block = Block::default()
+                    .title(" Color (Tab to change) ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Gray));
+                let color_display = Paragraph::new(Line::from(vec![
+                    Span::styled("  ██  ", Style::default().fg(color_preview)),
+                    Span::raw(format!(" {} ", selected_color)),
+                ]))
+                .block(color_block);
+                f.render_widget(color_display, chunks[1]);
+
+                hint_spans.push(Span::styled("Tab", Style::default().fg(Color::Yellow)));
+                hint_spans.push(Span::raw(": change color | "));
+            }
+
+            hint_spans.push(Span::styled("Esc", Style::default().fg(Color::Yellow)));
+            hint_spans.push(Span::raw(": cancel"));
+            let hint = Paragraph::new(Line::from(hint_spans)).alignment(Alignment::Center);
+            f.render_widget(hint, chunks[3]);
+
+            // Set cursor position
+            f.set_cursor_position((
+                chunks[0].x + 1 + app.column_name_input.len() as u16,
+                chunks[0].y + 1,
+            ));
+        }
+        ColumnManagementMode::Delete => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .margin(1)
+                .constraints([
+                    Constraint::Min(0),    // Message
+                    Constraint::Length(1), // Hint
+                ])
+                .split(inner);
+
+            let name = app
+                .columns
+                .get(app.column_management_cursor)
+                .map(|c| c.status.name.as_str())
+                .unwrap_or("Unknown");
+            let target = app
+                .columns
+                .get(app.column_delete_target)
+                .filter(|_| app.column_delete_target != app.column_management_cursor);
+
+            let mut lines = vec![
+                Line::from(format!("Delete column \"{}\"?", name)),
+                Line::from(""),
+            ];
+            match target {
+                Some(target) => {
+                    lines.push(Line::from("Its tasks, trashed ones included, move to:"));
+                    lines.push(Line::from(Span::styled(
+                        format!("< {} >", target.status.name),
+                        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                    )));
+                }
+                None => {
+                    lines.push(Line::from(Span::styled(
+                        "This is the last column, so it has to be empty.",
+                        Style::default().fg(Color::DarkGray),
+                    )));
+                }
+            }
+
+            let message = Paragraph::new(lines)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true });
+            f.render_widget(message, chunks[0]);
+
+            let hint = Paragraph::new("j/k: pick target | y: delete | n: cancel")
+                .style(Style::default().fg(Color::DarkGray))
+                .alignment(Alignment::Center);
+            f.render_widget(hint, chunks[1]);
+        }
+    }
+}
+
+fn draw_member_panel(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let title = if app.inviting_member {
+        " Invite Member "
+    } else {
+        " Workspace Members "
+    };
+
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    if app.inviting_member {
+        // Invite form
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(3), // Email input
+                Constraint::Length(3), // Role selector
+                Constraint::Min(0),    // Spacer
+                Constraint::Length(2), // Hints
+            ])
+            .split(inner);
+
+        // Email input
+        let email_block = Block::default()
+            .title(" Email ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow));
+        let email_input = Paragraph::new(app.invite_email.as_str()).block(email_block);
+        f.render_widget(email_input, chunks[0]);
 
         // Role selector
         let roles = ["Reader", "Editor", "Admin"];
@@ -1976,197 +2562,194 @@ fn draw_task_detail(f: &mut Frame, app: &App) {
         draw_link_document_popup(f, app);
     } else if app.unlinking_document_mode {
         draw_unlink_document_popup(f, app);
+    } else if app.creating_subtask {
+        draw_create_subtask_popup(f, app);
+    } else if app.adding_blocker_mode {
+        draw_add_blocker_popup(f, app);
+    } else if app.removing_blocker_mode {
+        draw_remove_blocker_popup(f, app);
+    } else if app.comment_confirming_delete {
+        draw_comment_delete_confirm_popup(f, app);
+    } else if app.comment_revisions_visible {
+        draw_comment_revisions_popup(f, app);
     }
 }
 
-fn draw_task_view_mode(f: &mut Frame, area: Rect, app: &App, task: &todo_shared::Task) {
-    // Main content: split into task info and comments
-    let content_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(50), // Task details
-            Constraint::Percentage(50), // Comments
-        ])
-        .split(area);
+fn draw_comment_delete_confirm_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 20, f.area());
 
-    // Task details panel
-    let mut task_lines = vec![
-        Line::from(vec![
-            Span::styled("Title: ", Style::default().fg(Color::Cyan)),
-            Span::raw(&task.title),
-        ]),
-        Line::from(""),
-    ];
+    f.render_widget(Clear, area);
 
-    // Description
-    if let Some(ref desc) = task.description {
-        task_lines.push(Line::from(Span::styled(
-            "Description:",
-            Style::default().fg(Color::Cyan),
-        )));
-        for line in desc.lines() {
-            task_lines.push(Line::from(format!("  {}", line)));
-        }
-        task_lines.push(Line::from(""));
-    }
+    let Some(comment) = app.selected_comment() else { return };
+    let has_replies = !comment.replies.is_empty();
+    let excerpt: String = comment.content.lines().next().unwrap_or("").chars().take(60).collect();
 
-    // Priority
-    if let Some(ref priority) = task.priority {
-        let priority_color = match priority {
-            todo_shared::Priority::Highest => Color::Red,
-            todo_shared::Priority::High => Color::LightRed,
-            todo_shared::Priority::Medium => Color::Yellow,
-            todo_shared::Priority::Low => Color::Green,
-            todo_shared::Priority::Lowest => Color::DarkGray,
-        };
-        task_lines.push(Line::from(vec![
-            Span::styled("Priority: ", Style::default().fg(Color::Cyan)),
-            Span::styled(format!("{:?}", priority), Style::default().fg(priority_color)),
-        ]));
-    }
+    let block = Block::default()
+        .title(" Confirm Delete ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red));
 
-    // Due date
-    if let Some(ref due_date) = task.due_date {
-        task_lines.push(Line::from(vec![
-            Span::styled("Due Date: ", Style::default().fg(Color::Cyan)),
-            Span::raw(due_date.to_string()),
-        ]));
-    }
+    let inner = block.inner(area);
+    f.render_widget(block, area);
 
-    // Time estimate
-    if let Some(minutes) = task.time_estimate_minutes {
-        let hours = minutes / 60;
-        let mins = minutes % 60;
-        let estimate = if hours > 0 {
-            format!("{}h {}m", hours, mins)
-        } else {
-            format!("{}m", mins)
-        };
-        task_lines.push(Line::from(vec![
-            Span::styled("Time Estimate: ", Style::default().fg(Color::Cyan)),
-            Span::raw(estimate),
-        ]));
-    }
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(2), // Message
+            Constraint::Length(2), // Hint
+            Constraint::Min(0),    // Spacer
+        ])
+        .split(inner);
 
-    // Created at
-    task_lines.push(Line::from(vec![
-        Span::styled("Created: ", Style::default().fg(Color::Cyan)),
-        Span::raw(task.created_at.format("%Y-%m-%d %H:%M").to_string()),
-    ]));
+    let prompt = match (has_replies, app.user.as_ref().is_some_and(|u| u.id == comment.user_id)) {
+        (true, _) => "Move comment and its replies to trash:".to_string(),
+        (false, true) => "Move comment to trash:".to_string(),
+        (false, false) => format!("Move @{}'s comment to trash:", comment.author_username),
+    };
+    let message = Paragraph::new(vec![
+        Line::from(Span::raw(prompt)),
+        Line::from(Span::styled(
+            format!("\"{}\"", excerpt),
+            Style::default().fg(Color::Yellow),
+        )),
+    ])
+    .alignment(Alignment::Center);
+    f.render_widget(message, chunks[0]);
 
-    // Linked Documents section
-    task_lines.push(Line::from(""));
-    task_lines.push(Line::from(Span::styled(
-        format!("Linked Documents ({}):", app.task_linked_documents.len()),
-        Style::default().fg(Color::Cyan),
-    )));
-    if app.task_linked_documents.is_empty() {
-        task_lines.push(Line::from(Span::styled(
-            "  (none)",
-            Style::default().fg(Color::DarkGray),
+    let hint = Paragraph::new("y: yes, move to trash | n: no, cancel")
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
+    f.render_widget(hint, chunks[1]);
+}
+
+/// The selected comment's earlier versions, newest first below the current text
+fn draw_comment_revisions_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(70, 70, f.area());
+
+    f.render_widget(Clear, area);
+
+    let mut lines: Vec<Line> = Vec::new();
+    if let Some(comment) = app.selected_comment() {
+        lines.push(Line::from(Span::styled(
+            format!("Current, {}", comment.updated_at.format("%Y-%m-%d %H:%M")),
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
         )));
-    } else {
-        for doc in &app.task_linked_documents {
-            task_lines.push(Line::from(vec![
-                Span::raw("  "),
-                Span::styled(&doc.document_title, Style::default().fg(Color::Yellow)),
-            ]));
-        }
+        lines.extend(comment.content.lines().map(|l| Line::from(l.to_string())));
+    }
+    for revision in &app.comment_revisions {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled(
+                revision.written_at.format("%Y-%m-%d %H:%M").to_string(),
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(
+                    " (replaced {}{})",
+                    revision.edited_at.format("%Y-%m-%d %H:%M"),
+                    revision.editor_username
+                        .as_ref()
+                        .map(|u| format!(" by @{}", u))
+                        .unwrap_or_default()
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+        lines.extend(revision.content.lines().map(|l| Line::from(l.to_string())));
     }
 
-    let task_details = Paragraph::new(task_lines)
+    let history = Paragraph::new(lines)
         .block(
             Block::default()
-                .title(" Task Details ")
+                .title(format!(" Edit History ({} earlier) - j/k: scroll, q: close ", app.comment_revisions.len()))
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan)),
         )
-        .wrap(Wrap { trim: false });
+        .wrap(Wrap { trim: false })
+        .scroll((app.comment_revisions_scroll, 0));
+    f.render_widget(history, area);
+}
 
-    f.render_widget(task_details, content_chunks[0]);
-
-    // Comments panel
-    let comments_area = content_chunks[1];
+fn draw_add_blocker_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 50, f.area());
 
-    let inner_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Min(0),    // Comments list
-            Constraint::Length(3), // Comment input (if adding)
-        ])
-        .split(comments_area);
+    // Clear the background
+    f.render_widget(Clear, area);
 
-    // Comments list with text wrapping
-    let available_width = inner_chunks[0].width.saturating_sub(2) as usize; // -2 for borders
-    let comment_items: Vec<ListItem> = app
-        .task_comments
+    let items: Vec<ListItem> = app
+        .available_blockers()
         .iter()
-        .map(|comment| {
-            let timestamp = comment.created_at.format("%Y-%m-%d %H:%M").to_string();
-            let header_prefix = format!("[{}][@{}]: ", timestamp, comment.author_username);
-            let header_len = header_prefix.len();
+        .enumerate()
+        .map(|(i, task)| {
+            let style = if i == app.blocker_cursor {
+                Style::default().bg(Color::DarkGray).fg(Color::White)
+            } else {
+                Style::default()
+            };
+            let status_name = app
+                .columns
+                .iter()
+                .find(|c| c.status.id == task.status_id)
+                .map(|c| c.status.name.as_str())
+                .unwrap_or("");
+            ListItem::new(Line::from(vec![
+                Span::styled(task.title.clone(), style),
+                Span::styled(format!(" [{}]", status_name), style.fg(Color::DarkGray)),
+            ]))
+        })
+        .collect();
 
-            // Calculate available width for content (after header on first line)
-            let content_width = available_width.saturating_sub(header_len);
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(" Add Blocker (j/k: navigate, Enter: select, Esc: cancel) ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
 
-            // Wrap the comment content
-            let wrapped_lines = wrap_text(&comment.content, content_width);
+    f.render_widget(list, area);
+}
 
-            // Build multi-line ListItem
-            let mut lines: Vec<Line> = Vec::new();
-            for (i, line_text) in wrapped_lines.iter().enumerate() {
-                if i == 0 {
-                    // First line: header + content
-                    lines.push(Line::from(vec![
-                        Span::styled(
-                            format!("[{}]", timestamp),
-                            Style::default().fg(Color::DarkGray),
-                        ),
-                        Span::styled(
-                            format!("[@{}]: ", comment.author_username),
-                            Style::default().fg(Color::Cyan),
-                        ),
-                        Span::raw(line_text.clone()),
-                    ]));
-                } else {
-                    // Continuation lines: indent to align with content
-                    let indent = " ".repeat(header_len);
-                    lines.push(Line::from(Span::raw(format!("{}{}", indent, line_text))));
-                }
-            }
+fn draw_remove_blocker_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 50, f.area());
 
-            ListItem::new(lines)
+    // Clear the background
+    f.render_widget(Clear, area);
+
+    let items: Vec<ListItem> = app.task_dependencies.blocked_by
+        .iter()
+        .enumerate()
+        .map(|(i, dep)| {
+            let style = if i == app.blocker_cursor {
+                Style::default().bg(Color::Red).fg(Color::White)
+            } else {
+                Style::default()
+            };
+            ListItem::new(Line::from(Span::styled(&dep.task_title, style)))
         })
         .collect();
 
-    let comments_list = List::new(comment_items).block(
-        Block::default()
-            .title(format!(" Comments ({}) ", app.task_comments.len()))
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan)),
-    );
-
-    f.render_widget(comments_list, inner_chunks[0]);
-
-    // Comment input (if adding) - uses TextArea
-    if app.adding_comment {
-        if let Some(ref textarea) = app.comment_textarea {
-            let input_block = Block::default()
-                .title(" New Comment (Ctrl+E: editor, Alt+Enter: submit) ")
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(" Remove Blocker (j/k: navigate, Enter: remove, Esc: cancel) ")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Yellow));
-            let inner = input_block.inner(inner_chunks[1]);
-            f.render_widget(input_block, inner_chunks[1]);
-            f.render_widget(textarea, inner);
-        }
-    }
+                .border_style(Style::default().fg(Color::Red)),
+        );
+
+    f.render_widget(list, area);
 }
 
-fn draw_task_edit_mode(f: &mut Frame, area: Rect, app: &App) {
+fn draw_create_subtask_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 20, f.area());
+
+    f.render_widget(Clear, area);
+
     let block = Block::default()
-        .title(" Edit Task ")
+        .title(" New Subtask ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow));
+        .border_style(Style::default().fg(Color::Green));
 
     let inner = block.inner(area);
     f.render_widget(block, area);
@@ -2175,886 +2758,2251 @@ fn draw_task_edit_mode(f: &mut Frame, area: Rect, app: &App) {
         .direction(Direction::Vertical)
         .margin(1)
         .constraints([
-            Constraint::Length(3), // Title
-            Constraint::Length(5), // Description
-            Constraint::Length(3), // Priority
-            Constraint::Length(3), // Due Date
-            Constraint::Length(3), // Time Estimate
-            Constraint::Length(3), // Assignee
-            Constraint::Min(5),    // Tags
+            Constraint::Length(3), // Title input
+            Constraint::Length(2), // Hint
+            Constraint::Min(0),    // Spacer
         ])
         .split(inner);
 
-    // Helper to get field style
-    let field_style = |field: TaskEditField| -> Style {
-        if app.edit_field == field {
-            if app.vim_mode == VimMode::Insert {
-                Style::default().fg(Color::Green)
-            } else {
-                Style::default().fg(Color::Yellow)
-            }
-        } else {
-            Style::default().fg(Color::Gray)
-        }
-    };
-
-    // Title field
+    // Title input
     let title_block = Block::default()
         .title(" Title ")
         .borders(Borders::ALL)
-        .border_style(field_style(TaskEditField::Title));
-    let title_text = Paragraph::new(app.edit_task_title.as_str()).block(title_block);
-    f.render_widget(title_text, chunks[0]);
+        .border_style(Style::default().fg(Color::Yellow));
+    let title_input = Paragraph::new(app.new_subtask_title.as_str()).block(title_block);
+    f.render_widget(title_input, chunks[0]);
 
-    // Description field (uses TextArea)
-    if let Some(ref textarea) = app.edit_task_description_textarea {
-        let desc_block = Block::default()
-            .title(" Description (Ctrl+E: editor) ")
-            .borders(Borders::ALL)
-            .border_style(field_style(TaskEditField::Description));
-        let inner = desc_block.inner(chunks[1]);
-        f.render_widget(desc_block, chunks[1]);
-        f.render_widget(textarea, inner);
-    } else {
-        let desc_block = Block::default()
-            .title(" Description ")
-            .borders(Borders::ALL)
-            .border_style(field_style(TaskEditField::Description));
-        let desc_text = Paragraph::new("").block(desc_block);
-        f.render_widget(desc_text, chunks[1]);
-    }
+    // Hint
+    let hint = Paragraph::new("Enter: create | Esc: cancel")
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
+    f.render_widget(hint, chunks[1]);
 
-    // Priority field
-    let priority_str = match app.edit_task_priority {
-        Some(todo_shared::Priority::Highest) => "Highest",
-        Some(todo_shared::Priority::High) => "High",
-        Some(todo_shared::Priority::Medium) => "Medium",
-        Some(todo_shared::Priority::Low) => "Low",
-        Some(todo_shared::Priority::Lowest) => "Lowest",
-        None => "(none)",
-    };
-    let priority_block = Block::default()
-        .title(" Priority (h/l to change) ")
-        .borders(Borders::ALL)
-        .border_style(field_style(TaskEditField::Priority));
-    let priority_text = Paragraph::new(priority_str).block(priority_block);
-    f.render_widget(priority_text, chunks[2]);
+    // Set cursor position
+    f.set_cursor_position((
+        chunks[0].x + 1 + app.new_subtask_title.len() as u16,
+        chunks[0].y + 1,
+    ));
+}
 
-    // Due Date field
-    let due_date_block = Block::default()
-        .title(" Due Date (YYYY-MM-DD) ")
-        .borders(Borders::ALL)
-        .border_style(field_style(TaskEditField::DueDate));
-    let due_date_text = Paragraph::new(app.edit_task_due_date_str.as_str()).block(due_date_block);
-    f.render_widget(due_date_text, chunks[3]);
+fn draw_task_view_mode(f: &mut Frame, area: Rect, app: &App, task: &todo_shared::Task) {
+    // Main content: split into task info and comments
+    let content_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(50), // Task details
+            Constraint::Percentage(50), // Comments
+        ])
+        .split(area);
 
-    // Time Estimate field
-    let time_block = Block::default()
-        .title(" Time Estimate (minutes) ")
-        .borders(Borders::ALL)
-        .border_style(field_style(TaskEditField::TimeEstimate));
-    let time_text = Paragraph::new(app.edit_task_time_estimate_str.as_str()).block(time_block);
-    f.render_widget(time_text, chunks[4]);
+    // Task details panel
+    let mut task_lines = vec![
+        Line::from(vec![
+            Span::styled("Title: ", Style::default().fg(Color::Cyan)),
+            Span::styled(format!("{} ", task.key), Style::default().fg(Color::DarkGray)),
+            Span::raw(&task.title),
+        ]),
+        Line::from(""),
+    ];
 
-    // Assignee field
-    let assignee_str = match app.edit_task_assignee {
-        Some(id) => app
-            .workspace_members
-            .iter()
-            .find(|m| m.user_id == id)
-            .map(|m| m.display_name.as_str())
-            .unwrap_or("Unknown"),
-        None => "(none)",
-    };
-    let assignee_block = Block::default()
-        .title(" Assignee (h/l to change) ")
-        .borders(Borders::ALL)
-        .border_style(field_style(TaskEditField::Assignee));
-    let assignee_text = Paragraph::new(assignee_str).block(assignee_block);
-    f.render_widget(assignee_text, chunks[5]);
+    // Description
+    if let Some(ref desc) = task.description {
+        task_lines.push(Line::from(Span::styled(
+            "Description:",
+            Style::default().fg(Color::Cyan),
+        )));
+        for line in desc.lines() {
+            task_lines.push(Line::from(format!("  {}", line)));
+        }
+        task_lines.push(Line::from(""));
+    }
 
-    // Render Tags field
-    let tag_block = Block::default()
-        .title(" Tags (h/l: navigate, Space: toggle) ")
-        .borders(Borders::ALL)
-        .border_style(field_style(TaskEditField::Tags));
+    // Priority
+    if let Some(ref priority) = task.priority {
+        let priority_color = match priority {
+            todo_shared::Priority::Highest => Color::Red,
+            todo_shared::Priority::High => Color::LightRed,
+            todo_shared::Priority::Medium => Color::Yellow,
+            todo_shared::Priority::Low => Color::Green,
+            todo_shared::Priority::Lowest => Color::DarkGray,
+        };
+        task_lines.push(Line::from(vec![
+            Span::styled("Priority: ", Style::default().fg(Color::Cyan)),
+            Span::styled(format!("{:?}", priority), Style::default().fg(priority_color)),
+        ]));
+    }
 
-    // Build tag list display
-    let tag_lines: Vec<Line> = app.workspace_tags
-        .iter()
-        .enumerate()
-        .map(|(idx, tag)| {
-            let is_selected = app.task_edit_selected_tags.contains(&tag.id);
-            let is_cursor = app.edit_field == TaskEditField::Tags && idx == app.tag_selector_cursor;
-            let checkbox = if is_selected { "[x]" } else { "[ ]" };
-            let tag_color = tag.color.as_ref()
-                .and_then(|c| parse_hex_color(c))
-                .unwrap_or(Color::Gray);
+    // Due date
+    if let Some(ref due_date) = task.due_date {
+        task_lines.push(Line::from(vec![
+            Span::styled("Due Date: ", Style::default().fg(Color::Cyan)),
+            Span::raw(due_date.to_string()),
+        ]));
+    }
 
-            let style = if is_cursor {
-                Style::default().bg(Color::DarkGray).fg(Color::White)
-            } else {
-                Style::default()
-            };
-
-            Line::from(vec![
-                Span::styled(format!(" {} ", checkbox), style),
-                Span::styled(
-                    format!(" {} ", tag.name),
-                    Style::default().bg(tag_color).fg(Color::Black),
-                ),
-            ])
-        })
-        .collect();
+    // Recurrence
+    if let Some(ref rule) = task.recurrence {
+        task_lines.push(Line::from(vec![
+            Span::styled("Repeats: ", Style::default().fg(Color::Cyan)),
+            Span::raw(rule.to_string()),
+        ]));
+    }
 
-    let tags_widget = if tag_lines.is_empty() {
-        Paragraph::new("No tags. Press T in kanban to manage tags.").block(tag_block)
-    } else {
-        Paragraph::new(tag_lines).block(tag_block)
-    };
-    f.render_widget(tags_widget, chunks[6]);
+    // Time estimate
+    if let Some(minutes) = task.time_estimate_minutes {
+        task_lines.push(Line::from(vec![
+            Span::styled("Time Estimate: ", Style::default().fg(Color::Cyan)),
+            Span::raw(format_minutes(minutes as i64)),
+        ]));
+    }
 
-    // Set cursor position if in insert mode (not for Tags or Description fields - TextArea handles its own cursor)
-    if app.vim_mode == VimMode::Insert
-        && app.edit_field != TaskEditField::Tags
-        && app.edit_field != TaskEditField::Description
-    {
-        let (cursor_x, cursor_y) = match app.edit_field {
-            TaskEditField::Title => (
-                chunks[0].x + 1 + app.edit_task_title.len() as u16,
-                chunks[0].y + 1,
-            ),
-            TaskEditField::Description => (chunks[1].x + 1, chunks[1].y + 1), // Not used - TextArea handles cursor
-            TaskEditField::Priority => (chunks[2].x + 1, chunks[2].y + 1),
-            TaskEditField::DueDate => (
-                chunks[3].x + 1 + app.edit_task_due_date_str.len() as u16,
-                chunks[3].y + 1,
-            ),
-            TaskEditField::TimeEstimate => (
-                chunks[4].x + 1 + app.edit_task_time_estimate_str.len() as u16,
-                chunks[4].y + 1,
-            ),
-            TaskEditField::Assignee => (chunks[5].x + 1, chunks[5].y + 1),
-            TaskEditField::Tags => (chunks[6].x + 1, chunks[6].y + 1), // Not actually used
-        };
-        f.set_cursor_position((cursor_x, cursor_y));
+    // Logged time (red once it exceeds the estimate)
+    if task.logged_minutes > 0 {
+        let over_estimate = task
+            .time_estimate_minutes
+            .is_some_and(|estimate| task.logged_minutes > estimate as i64);
+        let logged_color = if over_estimate { Color::Red } else { Color::White };
+        task_lines.push(Line::from(vec![
+            Span::styled("Time Logged: ", Style::default().fg(Color::Cyan)),
+            Span::styled(format_minutes(task.logged_minutes), Style::default().fg(logged_color)),
+        ]));
     }
-}
 
-fn draw_task_detail_status_bar(f: &mut Frame, area: Rect, app: &App) {
-    let (mode, mode_color) = if app.editing_task {
-        ("EDIT", Color::Yellow)
-    } else {
-        match app.vim_mode {
-            VimMode::Normal => ("NORMAL", Color::Blue),
-            VimMode::Insert => ("INSERT", Color::Green),
+    // Running timer
+    if let Some(ref timer) = app.work_timer {
+        if timer.task_id == task.id {
+            task_lines.push(Line::from(vec![
+                Span::styled("Timer: ", Style::default().fg(Color::Cyan)),
+                Span::styled(timer.elapsed_display(), Style::default().fg(Color::Green)),
+                Span::styled(" (t to stop)", Style::default().fg(Color::DarkGray)),
+            ]));
         }
+    }
+
+    // Assignees and watchers
+    let member_names = |ids: &[uuid::Uuid]| -> String {
+        ids.iter()
+            .map(|id| {
+                app.workspace_members
+                    .iter()
+                    .find(|m| &m.user_id == id)
+                    .map(|m| m.display_name.as_str())
+                    .unwrap_or("Unknown")
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
     };
+    if !task.assignees.is_empty() {
+        task_lines.push(Line::from(vec![
+            Span::styled("Assignees: ", Style::default().fg(Color::Cyan)),
+            Span::raw(member_names(&task.assignees)),
+        ]));
+    }
+    if !task.watchers.is_empty() {
+        task_lines.push(Line::from(vec![
+            Span::styled("Watchers: ", Style::default().fg(Color::Cyan)),
+            Span::raw(member_names(&task.watchers)),
+        ]));
+    }
 
-    let hints = if app.editing_task {
-        if app.vim_mode == VimMode::Insert {
-            "Type to edit | Esc: normal mode"
-        } else {
-            "j/k: fields | i: edit | h/l: priority | Enter: save | q: cancel"
+    // Custom fields
+    for value in &task.custom_fields {
+        if let Some(field) = app.workspace_custom_fields.iter().find(|f| f.id == value.field_id) {
+            task_lines.push(Line::from(vec![
+                Span::styled(format!("{}: ", field.name), Style::default().fg(Color::Cyan)),
+                Span::raw(app.custom_field_display(field, &value.value)),
+            ]));
         }
-    } else if app.adding_comment {
-        "Type comment | Enter: submit | Esc: cancel"
-    } else if app.linking_document_mode {
-        "j/k: navigate | Enter: link | Esc: cancel"
-    } else if app.unlinking_document_mode {
-        "j/k: navigate | Enter: unlink | Esc: cancel"
-    } else {
-        "e: edit | a: comment | L: link doc | U: unlink doc | q/Esc: back"
-    };
+    }
 
-    let status = Paragraph::new(Line::from(vec![
-        Span::styled(
-            format!(" {} ", mode),
-            Style::default().bg(mode_color).fg(Color::White),
-        ),
-        Span::raw(" "),
-        Span::styled(hints, Style::default().fg(Color::DarkGray)),
+    // Created at
+    task_lines.push(Line::from(vec![
+        Span::styled("Created: ", Style::default().fg(Color::Cyan)),
+        Span::raw(task.created_at.format("%Y-%m-%d %H:%M").to_string()),
     ]));
 
-    f.render_widget(status, area);
-}
+    // Parent task
+    if let Some(parent_id) = task.parent_id {
+        let parent_title = app
+            .columns
+            .iter()
+            .flat_map(|c| c.tasks.iter())
+            .find(|t| t.id == parent_id)
+            .map(|t| t.title.as_str())
+            .unwrap_or("(not loaded)");
+        task_lines.push(Line::from(vec![
+            Span::styled("Parent: ", Style::default().fg(Color::Cyan)),
+            Span::raw(parent_title),
+        ]));
+    }
 
-fn draw_link_document_popup(f: &mut Frame, app: &App) {
-    let area = centered_rect(50, 50, f.area());
+    // Subtasks section
+    task_lines.push(Line::from(""));
+    let subtasks_header = match task.subtask_progress {
+        Some(progress) => format!("Subtasks ({}/{} done):", progress.done, progress.total),
+        None => format!("Subtasks ({}):", task.subtasks.len()),
+    };
+    task_lines.push(Line::from(Span::styled(
+        subtasks_header,
+        Style::default().fg(Color::Cyan),
+    )));
+    if task.subtasks.is_empty() {
+        task_lines.push(Line::from(Span::styled(
+            "  (none)",
+            Style::default().fg(Color::DarkGray),
+        )));
+    } else {
+        for subtask in &task.subtasks {
+            let status = app.columns.iter().find(|c| c.status.id == subtask.status_id);
+            let is_done = status.map(|c| c.status.is_done).unwrap_or(false);
+            let (marker, marker_color) = if is_done {
+                ("✓ ", Color::Green)
+            } else {
+                ("○ ", Color::DarkGray)
+            };
+            let mut spans = vec![
+                Span::raw("  "),
+                Span::styled(marker, Style::default().fg(marker_color)),
+                Span::raw(&subtask.title),
+            ];
+            if let Some(column) = status {
+                spans.push(Span::styled(
+                    format!(" [{}]", column.status.name),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            task_lines.push(Line::from(spans));
+        }
+    }
 
-    // Clear the background
-    f.render_widget(Clear, area);
+    // Checklist section
+    task_lines.push(Line::from(""));
+    let checklist_header = match task.checklist_progress {
+        Some(progress) => format!("Checklist ({}/{}):", progress.checked, progress.total),
+        None => "Checklist:".to_string(),
+    };
+    task_lines.push(Line::from(Span::styled(
+        checklist_header,
+        Style::default().fg(Color::Cyan),
+    )));
+    if app.task_checklist.is_empty() && !app.adding_checklist_item {
+        task_lines.push(Line::from(Span::styled(
+            "  (none)",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    for (idx, item) in app.task_checklist.iter().enumerate() {
+        let is_selected = app.checklist_mode && !app.adding_checklist_item && idx == app.checklist_cursor;
+        let (marker, marker_color) = if item.is_checked {
+            ("[x] ", Color::Green)
+        } else {
+            ("[ ] ", Color::DarkGray)
+        };
+        let text_style = if is_selected {
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+        } else if item.is_checked {
+            Style::default().fg(Color::DarkGray).add_modifier(Modifier::CROSSED_OUT)
+        } else {
+            Style::default()
+        };
+        task_lines.push(Line::from(vec![
+            Span::raw(if is_selected { "> " } else { "  " }),
+            Span::styled(marker, Style::default().fg(marker_color)),
+            Span::styled(&item.content, text_style),
+        ]));
+    }
+    if app.adding_checklist_item {
+        task_lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled("[ ] ", Style::default().fg(Color::DarkGray)),
+            Span::styled(&app.checklist_input, Style::default().fg(Color::Yellow)),
+            Span::styled("▏", Style::default().fg(Color::Yellow)),
+        ]));
+    }
 
-    // Get available documents (not already linked)
-    let linked_ids: std::collections::HashSet<_> = app.task_linked_documents
-        .iter()
-        .map(|d| d.document_id)
-        .collect();
+    // Dependencies section
+    task_lines.push(Line::from(""));
+    task_lines.push(Line::from(Span::styled(
+        format!("Blocked by ({}):", app.task_dependencies.blocked_by.len()),
+        Style::default().fg(Color::Cyan),
+    )));
+    if app.task_dependencies.blocked_by.is_empty() {
+        task_lines.push(Line::from(Span::styled(
+            "  (none)",
+            Style::default().fg(Color::DarkGray),
+        )));
+    } else {
+        for dep in &app.task_dependencies.blocked_by {
+            let (marker, marker_color) = if dep.is_done {
+                ("✓ ", Color::Green)
+            } else {
+                ("⛔ ", Color::Red)
+            };
+            task_lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(marker, Style::default().fg(marker_color)),
+                Span::raw(&dep.task_title),
+            ]));
+        }
+    }
+    if !app.task_dependencies.blocks.is_empty() {
+        task_lines.push(Line::from(Span::styled(
+            format!("Blocks ({}):", app.task_dependencies.blocks.len()),
+            Style::default().fg(Color::Cyan),
+        )));
+        for dep in &app.task_dependencies.blocks {
+            task_lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(&dep.task_title, Style::default().fg(Color::Yellow)),
+            ]));
+        }
+    }
 
-    let available: Vec<_> = app.kb_documents
-        .iter()
-        .filter(|d| !linked_ids.contains(&d.id))
-        .collect();
+    // Work log section
+    if !app.task_work_logs.is_empty() {
+        task_lines.push(Line::from(""));
+        task_lines.push(Line::from(Span::styled(
+            format!("Work Log ({}):", app.task_work_logs.len()),
+            Style::default().fg(Color::Cyan),
+        )));
+        for log in &app.task_work_logs {
+            let mut spans = vec![
+                Span::styled(format!("  {} ", log.work_date), Style::default().fg(Color::DarkGray)),
+                Span::styled(format!("@{} ", log.author_username), Style::default().fg(Color::Cyan)),
+                Span::raw(format_minutes(log.minutes as i64)),
+            ];
+            if let Some(ref note) = log.note {
+                spans.push(Span::styled(format!(" - {}", note), Style::default().fg(Color::DarkGray)));
+            }
+            task_lines.push(Line::from(spans));
+        }
+    }
 
-    let items: Vec<ListItem> = available
-        .iter()
-        .enumerate()
-        .map(|(i, doc)| {
-            let style = if i == app.link_document_cursor {
-                Style::default().bg(Color::DarkGray).fg(Color::White)
+    // Linked Documents section
+    task_lines.push(Line::from(""));
+    task_lines.push(Line::from(Span::styled(
+        format!("Linked Documents ({}):", app.task_linked_documents.len()),
+        Style::default().fg(Color::Cyan),
+    )));
+    if app.task_linked_documents.is_empty() {
+        task_lines.push(Line::from(Span::styled(
+            "  (none)",
+            Style::default().fg(Color::DarkGray),
+        )));
+    } else {
+        for doc in &app.task_linked_documents {
+            task_lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(&doc.document_title, Style::default().fg(Color::Yellow)),
+            ]));
+        }
+    }
+
+    // Attachments section
+    if !app.task_attachments.is_empty() {
+        task_lines.push(Line::from(""));
+        task_lines.push(Line::from(Span::styled(
+            format!("Attachments ({}):", app.task_attachments.len()),
+            Style::default().fg(Color::Cyan),
+        )));
+        for (idx, attachment) in app.task_attachments.iter().enumerate() {
+            let is_selected = app.attachment_mode && idx == app.attachment_cursor;
+            let name_style = if is_selected {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
             } else {
                 Style::default()
             };
-            ListItem::new(Line::from(vec![
-                Span::styled(&doc.title, style),
-                Span::styled(format!(" ({})", doc.path), style.fg(Color::DarkGray)),
-            ]))
-        })
-        .collect();
+            task_lines.push(Line::from(vec![
+                Span::raw(if is_selected { "> " } else { "  " }),
+                Span::styled(&attachment.filename, name_style),
+                Span::styled(
+                    format!(" ({}) @{}", format_bytes(attachment.size_bytes), attachment.uploader_username),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+    }
 
-    let list = List::new(items)
+    let task_details = Paragraph::new(task_lines)
         .block(
             Block::default()
-                .title(" Link Document (j/k: navigate, Enter: select, Esc: cancel) ")
+                .title(" Task Details ")
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan)),
-        );
+        )
+        .wrap(Wrap { trim: false });
 
-    f.render_widget(list, area);
-}
+    f.render_widget(task_details, content_chunks[0]);
 
-fn draw_unlink_document_popup(f: &mut Frame, app: &App) {
-    let area = centered_rect(50, 50, f.area());
+    // Comments panel, or the task's history when toggled
+    let comments_area = content_chunks[1];
 
-    // Clear the background
-    f.render_widget(Clear, area);
+    if app.show_task_history {
+        draw_task_history(f, comments_area, app);
+        return;
+    }
 
-    let items: Vec<ListItem> = app.task_linked_documents
+    let inner_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Comments list
+            Constraint::Length(3), // Comment input (if adding)
+        ])
+        .split(comments_area);
+
+    // Comments list with text wrapping; replies sit indented under their thread
+    let available_width = inner_chunks[0].width.saturating_sub(2) as usize; // -2 for borders
+    let my_id = app.user.as_ref().map(|u| u.id);
+    let rows = app.comment_rows();
+    let selected = app.comment_cursor.min(rows.len().saturating_sub(1));
+    let comment_lines: Vec<Vec<Line>> = rows
         .iter()
         .enumerate()
-        .map(|(i, doc)| {
-            let style = if i == app.unlink_document_cursor {
-                Style::default().bg(Color::Red).fg(Color::White)
+        .map(|(idx, comment)| {
+            let indent = if comment.parent_comment_id.is_some() { "  ↳ " } else { "" };
+            let timestamp = comment.created_at.format("%Y-%m-%d %H:%M").to_string();
+            let header_prefix = format!("{}[{}][@{}]: ", indent, timestamp, comment.author_username);
+            let header_len = header_prefix.chars().count();
+            let marker_len = if comment.edited { "(edited) ".len() } else { 0 };
+
+            // Calculate available width for content (after header on first line)
+            let content_width = available_width.saturating_sub(header_len + marker_len);
+
+            // Wrap the comment content
+            let wrapped_lines = wrap_text(&comment.content, content_width);
+
+            let (timestamp_style, author_style) = if idx == selected {
+                (
+                    Style::default().bg(Color::DarkGray).fg(Color::White),
+                    Style::default().bg(Color::DarkGray).fg(Color::Cyan),
+                )
             } else {
-                Style::default()
+                (Style::default().fg(Color::DarkGray), Style::default().fg(Color::Cyan))
             };
-            ListItem::new(Line::from(vec![
-                Span::styled(&doc.document_title, style),
-                Span::styled(format!(" ({})", doc.document_path), style.fg(Color::DarkGray)),
-            ]))
+
+            // Build multi-line ListItem
+            let mut lines: Vec<Line> = Vec::new();
+            for (i, line_text) in wrapped_lines.iter().enumerate() {
+                if i == 0 {
+                    // First line: header + content
+                    lines.push(Line::from(vec![
+                        Span::raw(indent),
+                        Span::styled(format!("[{}]", timestamp), timestamp_style),
+                        Span::styled(format!("[@{}]: ", comment.author_username), author_style),
+                    ]));
+                    if comment.edited {
+                        // Room for the marker was left in content_width
+                        if let Some(line) = lines.last_mut() {
+                            line.spans.push(Span::styled("(edited) ", Style::default().fg(Color::DarkGray)));
+                        }
+                    }
+                } else {
+                    // Continuation lines: indent to align with content
+                    lines.push(Line::from(Span::raw(" ".repeat(header_len))));
+                }
+                if let Some(line) = lines.last_mut() {
+                    line.spans.extend(highlight_mentions(line_text, &comment.mentions, my_id));
+                }
+            }
+
+            if !comment.reactions.is_empty() {
+                let mut spans = vec![Span::raw(format!("{}  ", " ".repeat(indent.chars().count())))];
+                for reaction in &comment.reactions {
+                    let style = if reaction.reacted {
+                        Style::default().fg(Color::Yellow)
+                    } else {
+                        Style::default().fg(Color::DarkGray)
+                    };
+                    spans.push(Span::styled(format!("{} {}", reaction.emoji, reaction.count), style));
+                    spans.push(Span::raw("  "));
+                }
+                lines.push(Line::from(spans));
+            }
+
+            lines
         })
         .collect();
 
-    let list = List::new(items)
-        .block(
-            Block::default()
-                .title(" Unlink Document (j/k: navigate, Enter: unlink, Esc: cancel) ")
+    // Skip whole comments from the top until the selected one fits
+    let visible_height = inner_chunks[0].height.saturating_sub(2) as usize;
+    let mut skip = 0;
+    while skip < selected
+        && comment_lines[skip..=selected].iter().map(Vec::len).sum::<usize>() > visible_height
+    {
+        skip += 1;
+    }
+
+    let comment_items: Vec<ListItem> = comment_lines.into_iter().skip(skip).map(ListItem::new).collect();
+
+    let comments_list = List::new(comment_items).block(
+        Block::default()
+            .title(format!(" Comments ({}) ", rows.len()))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+
+    f.render_widget(comments_list, inner_chunks[0]);
+
+    if app.reaction_picker_visible {
+        draw_reaction_picker(f, inner_chunks[1], app);
+    }
+
+    // Comment input (if adding) - uses TextArea
+    if app.adding_comment {
+        if let Some(ref textarea) = app.comment_textarea {
+            let replying_to = app
+                .replying_to
+                .and_then(|id| rows.iter().find(|c| c.id == id))
+                .map(|c| c.author_username.as_str());
+            let title = match (app.editing_comment, replying_to) {
+                (Some(_), _) => " Edit Comment (Ctrl+E: editor, Alt+Enter: save) ".to_string(),
+                (None, Some(author)) => format!(" Reply to @{} (Ctrl+E: editor, Alt+Enter: submit) ", author),
+                (None, None) => " New Comment (Ctrl+E: editor, Alt+Enter: submit) ".to_string(),
+            };
+            let input_block = Block::default()
+                .title(title)
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Red)),
-        );
+                .border_style(Style::default().fg(Color::Yellow));
+            let inner = input_block.inner(inner_chunks[1]);
+            f.render_widget(input_block, inner_chunks[1]);
+            f.render_widget(textarea, inner);
+        }
 
-    f.render_widget(list, area);
+        if let Some(completion) = app.mention_completion() {
+            draw_mention_completion(f, inner_chunks[1], app, &completion);
+        }
+    }
 }
 
-fn draw_loading(f: &mut Frame, message: &str) {
-    let area = f.area();
+/// Reactions to pick from for the selected comment, in place of the comment box
+fn draw_reaction_picker(f: &mut Frame, area: Rect, app: &App) {
+    let reacted: Vec<&str> = app
+        .selected_comment()
+        .map(|c| c.reactions.iter().filter(|r| r.reacted).map(|r| r.emoji.as_str()).collect())
+        .unwrap_or_default();
 
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+    let mut spans = Vec::new();
+    for (i, emoji) in crate::app::REACTIONS.iter().enumerate() {
+        let mut style = if reacted.contains(emoji) {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default()
+        };
+        if i == app.reaction_cursor {
+            style = style.bg(Color::DarkGray);
+        }
+        spans.push(Span::styled(format!(" {} {} ", i + 1, emoji), style));
+        spans.push(Span::raw(" "));
+    }
 
-    f.render_widget(block, area);
+    f.render_widget(Clear, area);
+    let picker = Paragraph::new(Line::from(spans)).block(
+        Block::default()
+            .title(" React (1-6 or h/l + Enter; again to remove) ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta)),
+    );
+    f.render_widget(picker, area);
+}
 
-    let text = Paragraph::new(message)
-        .style(Style::default().fg(Color::Yellow))
-        .alignment(Alignment::Center);
+/// Split a line of comment text so its @mentions stand out, yours the most
+fn highlight_mentions(
+    line: &str,
+    mentions: &[todo_shared::MentionSpan],
+    my_id: Option<uuid::Uuid>,
+) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut last = 0;
 
-    let center = centered_rect(50, 20, area);
-    f.render_widget(text, center);
+    for (range, name) in todo_shared::find_mentions(line) {
+        let Some(mention) = mentions.iter().find(|m| m.username.eq_ignore_ascii_case(name)) else {
+            continue;
+        };
+        let style = if Some(mention.user_id) == my_id {
+            Style::default().fg(Color::Black).bg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)
+        };
+
+        if range.start > last {
+            spans.push(Span::raw(line[last..range.start].to_string()));
+        }
+        spans.push(Span::styled(line[range.clone()].to_string(), style));
+        last = range.end;
+    }
+    if last < line.len() {
+        spans.push(Span::raw(line[last..].to_string()));
+    }
+
+    spans
 }
 
-fn draw_loading_overlay(f: &mut Frame, message: &str) {
-    let area = centered_rect(40, 10, f.area());
+/// Username suggestions for the `@name` being typed, just above the comment box
+fn draw_mention_completion(
+    f: &mut Frame,
+    input_area: Rect,
+    app: &App,
+    completion: &crate::app::MentionCompletion,
+) {
+    let height = completion.usernames.len() as u16 + 2;
+    let width = completion
+        .usernames
+        .iter()
+        .map(|u| u.chars().count() as u16 + 4)
+        .max()
+        .unwrap_or(0)
+        .max(24)
+        .min(input_area.width);
+    let area = Rect {
+        x: input_area.x + 1,
+        y: input_area.y.saturating_sub(height),
+        width,
+        height: height.min(input_area.y),
+    };
 
     f.render_widget(Clear, area);
 
-    let block = Block::default()
-        .title(" Loading ")
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow));
+    let selected = app.mention_completion_idx % completion.usernames.len();
+    let items: Vec<ListItem> = completion
+        .usernames
+        .iter()
+        .enumerate()
+        .map(|(i, username)| {
+            let style = if i == selected {
+                Style::default().bg(Color::DarkGray).fg(Color::White)
+            } else {
+                Style::default()
+            };
+            ListItem::new(Line::from(Span::styled(format!("@{}", username), style)))
+        })
+        .collect();
 
-    let text = Paragraph::new(message)
-        .style(Style::default().fg(Color::Yellow))
-        .alignment(Alignment::Center)
-        .block(block);
+    let list = List::new(items).block(
+        Block::default()
+            .title(" Tab: complete ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta)),
+    );
+    f.render_widget(list, area);
+}
 
-    f.render_widget(text, area);
+fn draw_task_history(f: &mut Frame, area: Rect, app: &App) {
+    let items: Vec<ListItem> = app
+        .task_history
+        .iter()
+        .map(|entry| {
+            let mut lines = vec![Line::from(vec![
+                Span::styled(
+                    format!("[{}]", entry.created_at.format("%Y-%m-%d %H:%M")),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(
+                    format!("[@{}] ", entry.username.as_deref().unwrap_or("system")),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(entry.action.verb(), activity_action_style(entry.action)),
+            ])];
+            lines.extend(activity_change_lines(entry));
+            ListItem::new(lines)
+        })
+        .collect();
+
+    let history = List::new(items).block(
+        Block::default()
+            .title(format!(" History ({}) ", app.task_history.len()))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta)),
+    );
+
+    f.render_widget(history, area);
 }
 
-fn draw_error_popup(f: &mut Frame, error: &str) {
-    let area = centered_rect(60, 20, f.area());
+/// "alice moved TODO-3 Fix login", with the entity kind spelled out where
+/// the label alone would be ambiguous
+fn activity_headline(entry: &ActivityEntry) -> Vec<Span<'static>> {
+    let noun = match entry.entity_type {
+        ActivityEntityType::Task | ActivityEntityType::Member => "",
+        ActivityEntityType::Document => "doc ",
+        ActivityEntityType::Status => "column ",
+        ActivityEntityType::Workspace if entry.action == ActivityAction::Reordered => {
+            "the columns of "
+        }
+        ActivityEntityType::Workspace => "workspace ",
+    };
 
-    f.render_widget(Clear, area);
+    vec![
+        Span::styled(
+            entry.username.clone().unwrap_or_else(|| "system".to_string()),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::raw(" "),
+        Span::styled(entry.action.verb(), activity_action_style(entry.action)),
+        Span::raw(format!(
+            " {}{}",
+            noun,
+            entry.entity_label.as_deref().unwrap_or("(unnamed)")
+        )),
+    ]
+}
 
-    let block = Block::default()
-        .title(" Error ")
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Red));
+fn activity_action_style(action: ActivityAction) -> Style {
+    match action {
+        ActivityAction::Created | ActivityAction::Restored | ActivityAction::Joined => {
+            Style::default().fg(Color::Green)
+        }
+        ActivityAction::Deleted | ActivityAction::Purged | ActivityAction::Removed => {
+            Style::default().fg(Color::Red)
+        }
+        _ => Style::default().fg(Color::Yellow),
+    }
+}
 
-    let text = Paragraph::new(error)
-        .style(Style::default().fg(Color::Red))
-        .wrap(Wrap { trim: true })
-        .block(block);
+/// Indented "field: old → new" lines for an entry's changes
+fn activity_change_lines(entry: &ActivityEntry) -> Vec<Line<'static>> {
+    entry
+        .field_changes()
+        .into_iter()
+        .map(|change| {
+            let detail = match (&change.old, &change.new) {
+                (None, None) => "changed".to_string(),
+                (old, new) => format!(
+                    "{} → {}",
+                    format_activity_value(old.as_ref()),
+                    format_activity_value(new.as_ref())
+                ),
+            };
 
-    f.render_widget(text, area);
+            Line::from(vec![
+                Span::styled(
+                    format!("    {}: ", change.field.replace('_', " ")),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::raw(detail),
+            ])
+        })
+        .collect()
 }
 
-fn draw_filter_panel(f: &mut Frame, app: &App) {
-    let area = centered_rect(60, 70, f.area());
-    f.render_widget(Clear, area);
+/// Render a logged value: strings bare, lists comma-separated, nothing as "none"
+fn format_activity_value(value: Option<&serde_json::Value>) -> String {
+    use serde_json::Value;
 
+    match value {
+        None | Some(Value::Null) => "none".to_string(),
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Array(items)) if items.is_empty() => "none".to_string(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|item| format_activity_value(Some(item)))
+            .collect::<Vec<_>>()
+            .join(", "),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn draw_task_edit_mode(f: &mut Frame, area: Rect, app: &App) {
     let block = Block::default()
-        .title(" Filter Tasks ")
+        .title(" Edit Task ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+        .border_style(Style::default().fg(Color::Yellow));
 
     let inner = block.inner(area);
     f.render_widget(block, area);
 
-    // Split into sections
+    let custom_fields_height = (app.workspace_custom_fields.len().max(1) + 2) as u16;
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
         .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Length(5), // Description
             Constraint::Length(3), // Priority
-            Constraint::Length(5), // Tags (scrollable)
-            Constraint::Length(3), // Assignee
             Constraint::Length(3), // Due Date
-            Constraint::Length(3), // Order By
-            Constraint::Length(3), // Actions
-            Constraint::Min(0),    // Spacer
-            Constraint::Length(2), // Hints
+            Constraint::Length(3), // Time Estimate
+            Constraint::Length(3), // Recurrence
+            Constraint::Length(3), // Assignees
+            Constraint::Length(3), // Watchers
+            Constraint::Length(custom_fields_height), // Custom Fields
+            Constraint::Min(5),    // Tags
         ])
         .split(inner);
 
-    // Helper for section styling
-    let section_style = |section: FilterPanelSection| -> Style {
-        if app.filter_panel_section == section {
-            Style::default().fg(Color::Yellow)
+    // Helper to get field style
+    let field_style = |field: TaskEditField| -> Style {
+        if app.edit_field == field {
+            if app.vim_mode == VimMode::Insert {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::Yellow)
+            }
         } else {
             Style::default().fg(Color::Gray)
         }
     };
 
-    // Priority section
-    let priorities: &[Option<Priority>] = &[
-        None,
-        Some(Priority::Highest),
-        Some(Priority::High),
-        Some(Priority::Medium),
-        Some(Priority::Low),
-        Some(Priority::Lowest),
-    ];
-    let mut priority_spans: Vec<Span> = vec![Span::styled(" ", Style::default())];
-    for (i, p) in priorities.iter().enumerate() {
-        let label = match p {
-            None => "None",
-            Some(Priority::Highest) => "Highest",
-            Some(Priority::High) => "High",
-            Some(Priority::Medium) => "Medium",
-            Some(Priority::Low) => "Low",
-            Some(Priority::Lowest) => "Lowest",
-        };
-        let is_selected = i == app.filter_priority_cursor;
-        let marker = if is_selected { "●" } else { "○" };
-        let style = if is_selected {
-            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::White)
-        };
-        priority_spans.push(Span::styled(format!("[{} {}] ", marker, label), style));
-    }
-    let priority_block = Block::default()
-        .title(" Priority (h/l) ")
+    // Title field
+    let title_block = Block::default()
+        .title(" Title ")
         .borders(Borders::ALL)
-        .border_style(section_style(FilterPanelSection::Priority));
-    let priority_widget = Paragraph::new(Line::from(priority_spans)).block(priority_block);
-    f.render_widget(priority_widget, chunks[0]);
+        .border_style(field_style(TaskEditField::Title));
+    let title_text = Paragraph::new(app.edit_task_title.as_str()).block(title_block);
+    f.render_widget(title_text, chunks[0]);
 
-    // Tags section
-    let tag_lines: Vec<Line> = if app.workspace_tags.is_empty() {
-        vec![Line::from(Span::styled("  No tags available", Style::default().fg(Color::DarkGray)))]
+    // Description field (uses TextArea)
+    if let Some(ref textarea) = app.edit_task_description_textarea {
+        let desc_block = Block::default()
+            .title(" Description (Ctrl+E: editor) ")
+            .borders(Borders::ALL)
+            .border_style(field_style(TaskEditField::Description));
+        let inner = desc_block.inner(chunks[1]);
+        f.render_widget(desc_block, chunks[1]);
+        f.render_widget(textarea, inner);
     } else {
-        app.workspace_tags
+        let desc_block = Block::default()
+            .title(" Description ")
+            .borders(Borders::ALL)
+            .border_style(field_style(TaskEditField::Description));
+        let desc_text = Paragraph::new("").block(desc_block);
+        f.render_widget(desc_text, chunks[1]);
+    }
+
+    // Priority field
+    let priority_str = match app.edit_task_priority {
+        Some(todo_shared::Priority::Highest) => "Highest",
+        Some(todo_shared::Priority::High) => "High",
+        Some(todo_shared::Priority::Medium) => "Medium",
+        Some(todo_shared::Priority::Low) => "Low",
+        Some(todo_shared::Priority::Lowest) => "Lowest",
+        None => "(none)",
+    };
+    let priority_block = Block::default()
+        .title(" Priority (h/l to change, x to clear) ")
+        .borders(Borders::ALL)
+        .border_style(field_style(TaskEditField::Priority));
+    let priority_text = Paragraph::new(priority_str).block(priority_block);
+    f.render_widget(priority_text, chunks[2]);
+
+    // Due Date field
+    let due_date_block = Block::default()
+        .title(" Due Date (YYYY-MM-DD) ")
+        .borders(Borders::ALL)
+        .border_style(field_style(TaskEditField::DueDate));
+    let due_date_text = Paragraph::new(app.edit_task_due_date_str.as_str()).block(due_date_block);
+    f.render_widget(due_date_text, chunks[3]);
+
+    // Time Estimate field
+    let time_block = Block::default()
+        .title(" Time Estimate (minutes) ")
+        .borders(Borders::ALL)
+        .border_style(field_style(TaskEditField::TimeEstimate));
+    let time_text = Paragraph::new(app.edit_task_time_estimate_str.as_str()).block(time_block);
+    f.render_widget(time_text, chunks[4]);
+
+    // Recurrence field
+    let recurrence_block = Block::default()
+        .title(" Repeat (daily|weekly|monthly[/N] [mon,fri] [until YYYY-MM-DD]) ")
+        .borders(Borders::ALL)
+        .border_style(field_style(TaskEditField::Recurrence));
+    let recurrence_text = Paragraph::new(app.edit_task_recurrence_str.as_str()).block(recurrence_block);
+    f.render_widget(recurrence_text, chunks[5]);
+
+    // Assignees and watchers (multi-select over workspace members)
+    let member_spans = |selected: &[uuid::Uuid], cursor: usize, field: TaskEditField| -> Vec<Span> {
+        app.workspace_members
             .iter()
             .enumerate()
-            .map(|(i, tag)| {
-                let is_selected = app.filter_selected_tags.contains(&tag.id);
-                let is_cursor = app.filter_panel_section == FilterPanelSection::Tags && i == app.filter_tag_cursor;
-                let checkbox = if is_selected { "[x]" } else { "[ ]" };
-                let tag_color = tag.color.as_ref()
-                    .and_then(|c| parse_hex_color(c))
-                    .unwrap_or(Color::Gray);
-
-                let style = if is_cursor {
+            .map(|(idx, member)| {
+                let checkbox = if selected.contains(&member.user_id) { "[x]" } else { "[ ]" };
+                let style = if app.edit_field == field && idx == cursor {
                     Style::default().bg(Color::DarkGray).fg(Color::White)
                 } else {
                     Style::default()
                 };
-
-                Line::from(vec![
-                    Span::styled(format!(" {} ", checkbox), style),
-                    Span::styled(
-                        format!(" {} ", tag.name),
-                        Style::default().bg(tag_color).fg(Color::Black),
-                    ),
-                ])
+                Span::styled(format!(" {} {} ", checkbox, member.display_name), style)
             })
             .collect()
     };
-    let tag_block = Block::default()
-        .title(" Tags (j/k, Space) ")
-        .borders(Borders::ALL)
-        .border_style(section_style(FilterPanelSection::Tags));
-    let tag_widget = Paragraph::new(tag_lines).block(tag_block);
-    f.render_widget(tag_widget, chunks[1]);
 
-    // Assignee section
-    let assignee_str = if app.filter_assignee_cursor == 0 {
-        "None".to_string()
-    } else {
-        app.workspace_members
-            .get(app.filter_assignee_cursor - 1)
-            .map(|m| m.display_name.clone())
-            .unwrap_or_else(|| "Unknown".to_string())
-    };
-    let assignee_block = Block::default()
-        .title(" Assignee (h/l) ")
+    let assignees_block = Block::default()
+        .title(" Assignees (h/l: navigate, Space: toggle) ")
         .borders(Borders::ALL)
-        .border_style(section_style(FilterPanelSection::Assignee));
-    let assignee_widget = Paragraph::new(Line::from(vec![
-        Span::styled(" < ", Style::default().fg(Color::DarkGray)),
-        Span::styled(&assignee_str, Style::default().fg(Color::White)),
-        Span::styled(" > ", Style::default().fg(Color::DarkGray)),
-    ])).block(assignee_block);
-    f.render_widget(assignee_widget, chunks[2]);
-
-    // Due Date section
-    let due_mode_str = match app.filter_due_mode {
-        DueDateMode::Before => "Before",
-        DueDateMode::After => "After",
-    };
-    let due_date_block = Block::default()
-        .title(" Due Date (h/l mode, i edit) ")
+        .border_style(field_style(TaskEditField::Assignees));
+    let assignees_text = Paragraph::new(Line::from(member_spans(
+        &app.edit_task_assignees,
+        app.assignee_selector_cursor,
+        TaskEditField::Assignees,
+    )))
+    .block(assignees_block);
+    f.render_widget(assignees_text, chunks[6]);
+
+    let watchers_block = Block::default()
+        .title(" Watchers (h/l: navigate, Space: toggle) ")
         .borders(Borders::ALL)
-        .border_style(section_style(FilterPanelSection::DueDate));
-    let due_date_widget = Paragraph::new(Line::from(vec![
-        Span::styled(format!(" [{}] ", due_mode_str), Style::default().fg(Color::Cyan)),
-        Span::styled(
-            if app.filter_due_input.is_empty() { "YYYY-MM-DD" } else { &app.filter_due_input },
-            Style::default().fg(if app.filter_due_input.is_empty() { Color::DarkGray } else { Color::White }),
-        ),
-    ])).block(due_date_block);
-    f.render_widget(due_date_widget, chunks[3]);
-
-    // Order By section
-    let (sort_field, sort_label) = SORT_FIELDS.get(app.filter_order_cursor).unwrap_or(&("position", "Position"));
-    let direction = if app.filter_order_desc { "↑" } else { "↓" };
-    let order_block = Block::default()
-        .title(" Order By (h/l field, Space dir) ")
+        .border_style(field_style(TaskEditField::Watchers));
+    let watchers_text = Paragraph::new(Line::from(member_spans(
+        &app.edit_task_watchers,
+        app.watcher_selector_cursor,
+        TaskEditField::Watchers,
+    )))
+    .block(watchers_block);
+    f.render_widget(watchers_text, chunks[7]);
+
+    // Custom fields, one line per workspace field
+    let custom_fields_block = Block::default()
+        .title(" Custom Fields (h/l: field, i: edit) ")
         .borders(Borders::ALL)
-        .border_style(section_style(FilterPanelSection::OrderBy));
-    let order_widget = Paragraph::new(Line::from(vec![
-        Span::styled(" < ", Style::default().fg(Color::DarkGray)),
-        Span::styled(format!("{} {}", sort_label, direction), Style::default().fg(Color::White)),
-        Span::styled(" > ", Style::default().fg(Color::DarkGray)),
-        Span::styled(format!(" ({})", sort_field), Style::default().fg(Color::DarkGray)),
-    ])).block(order_block);
-    f.render_widget(order_widget, chunks[4]);
+        .border_style(field_style(TaskEditField::CustomFields));
 
-    // Actions section
-    let actions_style = section_style(FilterPanelSection::Actions);
-    let actions_block = Block::default()
-        .title(" Actions ")
-        .borders(Borders::ALL)
-        .border_style(actions_style);
-    let actions_widget = Paragraph::new(Line::from(vec![
-        Span::styled(" [Enter: Apply] ", Style::default().fg(Color::Green)),
-        Span::styled(" [c: Clear] ", Style::default().fg(Color::Yellow)),
-        Span::styled(" [s: Save Preset] ", Style::default().fg(Color::Cyan)),
-    ])).block(actions_block);
-    f.render_widget(actions_widget, chunks[5]);
+    let custom_field_lines: Vec<Line> = app.workspace_custom_fields
+        .iter()
+        .zip(&app.edit_custom_field_inputs)
+        .enumerate()
+        .map(|(idx, (field, input))| {
+            let is_cursor = app.edit_field == TaskEditField::CustomFields && idx == app.edit_custom_field_cursor;
+            let style = if is_cursor {
+                Style::default().bg(Color::DarkGray).fg(Color::White)
+            } else {
+                Style::default()
+            };
 
-    // Hints
-    let hint = Paragraph::new(Line::from(vec![
-        Span::styled("Tab/j/k", Style::default().fg(Color::Yellow)),
-        Span::raw(": section | "),
-        Span::styled("h/l", Style::default().fg(Color::Yellow)),
-        Span::raw(": value | "),
-        Span::styled("Space", Style::default().fg(Color::Yellow)),
-        Span::raw(": toggle | "),
-        Span::styled("Esc", Style::default().fg(Color::Yellow)),
-        Span::raw(": cancel"),
-    ]))
-    .alignment(Alignment::Center);
-    f.render_widget(hint, chunks[7]);
-}
+            let mut spans = vec![
+                Span::styled(format!(" {}: ", field.name), style),
+                Span::raw(input.clone()),
+            ];
+            if is_cursor && field.field_type.has_options() {
+                spans.push(Span::styled(
+                    format!("  [{}]", field.options.join("|")),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            Line::from(spans)
+        })
+        .collect();
 
-fn draw_preset_panel(f: &mut Frame, app: &App) {
-    let area = centered_rect(50, 50, f.area());
-    f.render_widget(Clear, area);
+    let custom_fields_widget = if custom_field_lines.is_empty() {
+        Paragraph::new("No custom fields in this workspace.").block(custom_fields_block)
+    } else {
+        Paragraph::new(custom_field_lines).block(custom_fields_block)
+    };
+    f.render_widget(custom_fields_widget, chunks[8]);
 
-    let block = Block::default()
-        .title(" Filter Presets ")
+    // Render Tags field
+    let tag_block = Block::default()
+        .title(" Tags (h/l: navigate, Space: toggle) ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Magenta));
-
-    let inner = block.inner(area);
-    f.render_widget(block, area);
-
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .margin(1)
-        .constraints([
-            Constraint::Min(0),    // Preset list
-            Constraint::Length(3), // New preset input (if creating)
-            Constraint::Length(2), // Hints
-        ])
-        .split(inner);
+        .border_style(field_style(TaskEditField::Tags));
 
-    // Preset list
-    let preset_items: Vec<ListItem> = app
-        .filter_presets
+    // Build tag list display
+    let tag_lines: Vec<Line> = app.workspace_tags
         .iter()
         .enumerate()
-        .map(|(i, preset)| {
-            let is_selected = i == app.preset_list_cursor;
-            let style = if is_selected {
+        .map(|(idx, tag)| {
+            let is_selected = app.task_edit_selected_tags.contains(&tag.id);
+            let is_cursor = app.edit_field == TaskEditField::Tags && idx == app.tag_selector_cursor;
+            let checkbox = if is_selected { "[x]" } else { "[ ]" };
+            let tag_color = tag.color.as_ref()
+                .and_then(|c| parse_hex_color(c))
+                .unwrap_or(Color::Gray);
+
+            let style = if is_cursor {
                 Style::default().bg(Color::DarkGray).fg(Color::White)
             } else {
                 Style::default()
             };
 
-            // Build a description of the preset
-            let mut desc_parts = Vec::new();
-            if preset.filters.priority.is_some() {
-                desc_parts.push("priority");
-            }
-            if preset.filters.assigned_to.is_some() {
-                desc_parts.push("assignee");
-            }
-            if preset.filters.due_before.is_some() || preset.filters.due_after.is_some() {
-                desc_parts.push("due date");
-            }
-            if preset.filters.order_by.is_some() {
-                desc_parts.push("sorted");
-            }
-            let desc = if desc_parts.is_empty() {
-                "no filters".to_string()
-            } else {
-                desc_parts.join(", ")
-            };
-
-            ListItem::new(Line::from(vec![
-                Span::styled("  ", style),
-                Span::styled(&preset.name, style.add_modifier(Modifier::BOLD)),
-                Span::styled(format!(" ({})", desc), style.fg(Color::DarkGray)),
-            ]))
+            Line::from(vec![
+                Span::styled(format!(" {} ", checkbox), style),
+                Span::styled(
+                    format!(" {} ", tag.name),
+                    Style::default().bg(tag_color).fg(Color::Black),
+                ),
+            ])
         })
         .collect();
 
-    let list_title = if app.filter_presets.is_empty() {
-        " No presets - press 'n' to create ".to_string()
+    let tags_widget = if tag_lines.is_empty() {
+        Paragraph::new("No tags. Press T in kanban to manage tags.").block(tag_block)
     } else {
-        format!(" Presets ({}) ", app.filter_presets.len())
+        Paragraph::new(tag_lines).block(tag_block)
     };
+    f.render_widget(tags_widget, chunks[9]);
 
-    let list = List::new(preset_items).block(
-        Block::default()
-            .title(list_title)
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Gray)),
-    );
-    f.render_widget(list, chunks[0]);
-
-    // New preset input (if creating)
-    if app.creating_preset {
-        let input_block = Block::default()
-            .title(" Preset Name ")
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Yellow));
-        let input = Paragraph::new(app.new_preset_name.as_str()).block(input_block);
-        f.render_widget(input, chunks[1]);
-
-        // Set cursor position
-        f.set_cursor_position((
-            chunks[1].x + 1 + app.new_preset_name.len() as u16,
-            chunks[1].y + 1,
-        ));
-    }
-
-    // Hints
-    let hint = if app.creating_preset {
-        Paragraph::new(Line::from(vec![
-            Span::styled("Enter", Style::default().fg(Color::Yellow)),
-            Span::raw(": save | "),
-            Span::styled("Esc", Style::default().fg(Color::Yellow)),
-            Span::raw(": cancel"),
-        ]))
-    } else {
-        Paragraph::new(Line::from(vec![
-            Span::styled("j/k", Style::default().fg(Color::Yellow)),
-            Span::raw(": select | "),
-            Span::styled("Enter", Style::default().fg(Color::Yellow)),
-            Span::raw(": load | "),
-            Span::styled("n", Style::default().fg(Color::Yellow)),
-            Span::raw(": new | "),
-            Span::styled("d", Style::default().fg(Color::Yellow)),
-            Span::raw(": delete | "),
-            Span::styled("Esc", Style::default().fg(Color::Yellow)),
-            Span::raw(": close"),
-        ]))
+    // Set cursor position if in insert mode (not for Tags or Description fields - TextArea handles its own cursor)
+    if app.vim_mode == VimMode::Insert
+        && app.edit_field != TaskEditField::Tags
+        && app.edit_field != TaskEditField::Description
+    {
+        let (cursor_x, cursor_y) = match app.edit_field {
+            TaskEditField::Title => (
+                chunks[0].x + 1 + app.edit_task_title.len() as u16,
+                chunks[0].y + 1,
+            ),
+            TaskEditField::Description => (chunks[1].x + 1, chunks[1].y + 1), // Not used - TextArea handles cursor
+            TaskEditField::Priority => (chunks[2].x + 1, chunks[2].y + 1),
+            TaskEditField::DueDate => (
+                chunks[3].x + 1 + app.edit_task_due_date_str.len() as u16,
+                chunks[3].y + 1,
+            ),
+            TaskEditField::TimeEstimate => (
+                chunks[4].x + 1 + app.edit_task_time_estimate_str.len() as u16,
+                chunks[4].y + 1,
+            ),
+            TaskEditField::Recurrence => (
+                chunks[5].x + 1 + app.edit_task_recurrence_str.len() as u16,
+                chunks[5].y + 1,
+            ),
+            TaskEditField::Assignees => (chunks[6].x + 1, chunks[6].y + 1),
+            TaskEditField::Watchers => (chunks[7].x + 1, chunks[7].y + 1),
+            TaskEditField::CustomFields => {
+                let cursor = app.edit_custom_field_cursor;
+                let name_len = app.workspace_custom_fields.get(cursor).map_or(0, |f| f.name.len());
+                let input_len = app.edit_custom_field_inputs.get(cursor).map_or(0, |i| i.len());
+                (
+                    chunks[8].x + 1 + (name_len + 3 + input_len) as u16,
+                    chunks[8].y + 1 + cursor as u16,
+                )
+            }
+            TaskEditField::Tags => (chunks[9].x + 1, chunks[9].y + 1), // Not actually used
+        };
+        f.set_cursor_position((cursor_x, cursor_y));
     }
-    .alignment(Alignment::Center);
-    f.render_widget(hint, chunks[2]);
 }
 
-fn draw_knowledge_base(f: &mut Frame, app: &App) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Header
-            Constraint::Min(0),    // Main content
-            Constraint::Length(1), // Status bar
-        ])
-        .split(f.area());
+fn draw_task_detail_status_bar(f: &mut Frame, area: Rect, app: &App) {
+    let (mode, mode_color) = if app.editing_task {
+        ("EDIT", Color::Yellow)
+    } else {
+        match app.vim_mode {
+            VimMode::Normal => ("NORMAL", Color::Blue),
+            VimMode::Insert => ("INSERT", Color::Green),
+        }
+    };
 
-    // Header
-    draw_header(f, chunks[0], app);
+    let hints = if app.editing_task {
+        if app.vim_mode == VimMode::Insert {
+            "Type to edit | Esc: normal mode"
+        } else {
+            "j/k: fields | i: edit | h/l: priority | x: clear | Enter: save | q: cancel"
+        }
+    } else if app.adding_comment {
+        "Type comment | Enter: submit | Esc: cancel"
+    } else if app.creating_subtask {
+        "Type title | Enter: create | Esc: cancel"
+    } else if app.adding_checklist_item {
+        "Type item | Enter: add | Esc: done"
+    } else if app.attachment_mode {
+        "j/k: navigate | Enter/o: open | d: download | Esc: back"
+    } else if app.checklist_mode {
+        "j/k: navigate | Space: toggle | J/K: move | o: add | d: delete | Esc: back"
+    } else if app.reaction_picker_visible {
+        "1-6: react | h/l: pick | Enter: toggle | Esc: cancel"
+    } else if app.comment_confirming_delete {
+        "y: move comment to trash | n/Esc: cancel"
+    } else if app.comment_revisions_visible {
+        "j/k: scroll | q/Esc: close"
+    } else if app.adding_blocker_mode || app.removing_blocker_mode {
+        "j/k: navigate | Enter: select | Esc: cancel"
+    } else if app.linking_document_mode {
+        "j/k: navigate | Enter: link | Esc: cancel"
+    } else if app.unlinking_document_mode {
+        "j/k: navigate | Enter: unlink | Esc: cancel"
+    } else {
+        "e: edit | a: comment | j/k: pick comment | r: reply | +: react | E/D: edit/delete comment | v: comment edits | s: subtask | c: checklist | f: files | H: history | b/B: blocker | t: timer | L/U: link/unlink doc | q/Esc: back"
+    };
 
-    // Main content: split into tree and document viewer
-    let content_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(30), // Document tree
-            Constraint::Percentage(70), // Document content
-        ])
-        .split(chunks[1]);
+    let mut spans = vec![
+        Span::styled(
+            format!(" {} ", mode),
+            Style::default().bg(mode_color).fg(Color::White),
+        ),
+        Span::raw(" "),
+    ];
+    spans.extend(timer_span(app));
+    spans.extend(unread_span(app));
+    spans.push(Span::styled(hints, Style::default().fg(Color::DarkGray)));
+    let status = Paragraph::new(Line::from(spans));
 
-    // Draw document tree
-    draw_document_tree(f, content_chunks[0], app);
+    f.render_widget(status, area);
+}
 
-    // Draw document content/editor
-    draw_document_content(f, content_chunks[1], app);
+/// Running work timer badge for status bars
+fn timer_span(app: &App) -> Option<Span<'static>> {
+    app.work_timer.as_ref().map(|timer| {
+        Span::styled(
+            format!(" ⏱ {} {} ", timer.task_key, timer.elapsed_display()),
+            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+        )
+    })
+}
 
-    // Status bar
-    draw_kb_status_bar(f, chunks[2], app);
+/// Badge for unread notifications, hidden when there are none
+fn unread_span(app: &App) -> Option<Span<'static>> {
+    (app.notifications_unread > 0).then(|| {
+        Span::styled(
+            format!(" ✉ {} ", app.notifications_unread),
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        )
+    })
+}
 
-    // Draw create document popup if active
-    if app.kb_creating {
-        draw_kb_create_popup(f, app);
+/// Human-readable file size, e.g. "512 B", "1.5 MB"
+fn format_bytes(bytes: i64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
     }
-
-    // Draw delete confirmation popup if active
-    if app.kb_confirming_delete {
-        draw_kb_delete_confirm_popup(f, app);
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
     }
+    format!("{:.1} {}", size, UNITS[unit])
+}
 
-    // Draw link task popup if active
-    if app.linking_task_mode {
-        draw_link_task_popup(f, app);
+/// Format minutes as "1h 30m" / "45m"
+fn format_minutes(minutes: i64) -> String {
+    let hours = minutes / 60;
+    let mins = minutes % 60;
+    if hours > 0 {
+        format!("{}h {}m", hours, mins)
+    } else {
+        format!("{}m", mins)
     }
 }
 
-fn draw_document_tree(f: &mut Frame, area: Rect, app: &App) {
-    let is_focused = app.kb_focus == KbFocus::Tree;
-    let border_color = if is_focused { Color::Cyan } else { Color::DarkGray };
+fn draw_link_document_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 50, f.area());
 
-    let block = Block::default()
-        .title(" Documents ")
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(border_color));
+    // Clear the background
+    f.render_widget(Clear, area);
 
-    let inner = block.inner(area);
-    f.render_widget(block, area);
+    // Get available documents (not already linked)
+    let linked_ids: std::collections::HashSet<_> = app.task_linked_documents
+        .iter()
+        .map(|d| d.document_id)
+        .collect();
 
-    if app.kb_visible_list.is_empty() {
-        let empty = Paragraph::new("No documents. Press 'n' to create one.")
-            .style(Style::default().fg(Color::DarkGray))
-            .alignment(Alignment::Center);
-        f.render_widget(empty, inner);
-        return;
-    }
+    let available: Vec<_> = app.kb_documents
+        .iter()
+        .filter(|d| !linked_ids.contains(&d.id))
+        .collect();
 
-    // Build list items from visible tree
-    let items: Vec<ListItem> = app
-        .kb_visible_list
+    let items: Vec<ListItem> = available
         .iter()
         .enumerate()
-        .map(|(i, (doc, depth))| {
-            let is_selected = i == app.kb_selected_idx;
-            let style = if is_selected {
+        .map(|(i, doc)| {
+            let style = if i == app.link_document_cursor {
                 Style::default().bg(Color::DarkGray).fg(Color::White)
             } else {
                 Style::default()
             };
+            ListItem::new(Line::from(vec![
+                Span::styled(&doc.title, style),
+                Span::styled(format!(" ({})", doc.path), style.fg(Color::DarkGray)),
+            ]))
+        })
+        .collect();
 
-            // Determine expand/collapse indicator (all docs can be expanded)
-            let indicator = if app.kb_expanded.contains(&doc.id) {
-                "▼ "
-            } else {
-                "▶ "
-            };
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(" Link Document (j/k: navigate, Enter: select, Esc: cancel) ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
 
-            // Build indentation
-            let indent = "  ".repeat(*depth);
+    f.render_widget(list, area);
+}
 
-            // Truncate title if needed
-            let available_width = area.width.saturating_sub(4 + (depth * 2) as u16 + 2) as usize;
-            let title = if doc.title.len() > available_width {
-                format!("{}...", &doc.title[..available_width.saturating_sub(3)])
+fn draw_unlink_document_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 50, f.area());
+
+    // Clear the background
+    f.render_widget(Clear, area);
+
+    let items: Vec<ListItem> = app.task_linked_documents
+        .iter()
+        .enumerate()
+        .map(|(i, doc)| {
+            let style = if i == app.unlink_document_cursor {
+                Style::default().bg(Color::Red).fg(Color::White)
             } else {
-                doc.title.clone()
+                Style::default()
             };
-
             ListItem::new(Line::from(vec![
-                Span::styled(indent, style),
-                Span::styled(indicator, Style::default().fg(Color::Yellow)),
-                Span::styled(title, style),
+                Span::styled(&doc.document_title, style),
+                Span::styled(format!(" ({})", doc.document_path), style.fg(Color::DarkGray)),
             ]))
         })
         .collect();
 
-    let list = List::new(items);
-    f.render_widget(list, inner);
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(" Unlink Document (j/k: navigate, Enter: unlink, Esc: cancel) ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red)),
+        );
+
+    f.render_widget(list, area);
 }
 
-fn draw_document_content(f: &mut Frame, area: Rect, app: &App) {
-    if app.kb_editing {
-        draw_document_editor(f, area, app);
-        return;
-    }
+fn draw_loading(f: &mut Frame, message: &str) {
+    let area = f.area();
 
-    let is_focused = app.kb_focus == KbFocus::Content;
-    let border_color = if is_focused { Color::Cyan } else { Color::DarkGray };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
 
-    match &app.kb_selected_doc {
-        Some(doc) => {
-            // Calculate linked tasks height (header + items or empty message)
-            let linked_tasks_height = if app.kb_linked_tasks.is_empty() {
-                3 // Header + "(none)" + padding
-            } else {
-                (2 + app.kb_linked_tasks.len().min(5)) as u16 // Header + up to 5 tasks
-            };
+    f.render_widget(block, area);
 
-            // Render markdown content to calculate total height
-            let content_width = area.width.saturating_sub(4) as usize;
-            let content_text = doc.content.as_deref().unwrap_or("");
-            let all_content_lines = if content_text.is_empty() {
-                vec![Line::from(Span::styled(
-                    "(No content)",
-                    Style::default().fg(Color::DarkGray),
-                ))]
-            } else {
-                markdown::render_markdown(content_text, content_width)
+    let text = Paragraph::new(message)
+        .style(Style::default().fg(Color::Yellow))
+        .alignment(Alignment::Center);
+
+    let center = centered_rect(50, 20, area);
+    f.render_widget(text, center);
+}
+
+fn draw_loading_overlay(f: &mut Frame, message: &str) {
+    let area = centered_rect(40, 10, f.area());
+
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Loading ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+
+    let text = Paragraph::new(message)
+        .style(Style::default().fg(Color::Yellow))
+        .alignment(Alignment::Center)
+        .block(block);
+
+    f.render_widget(text, area);
+}
+
+fn draw_error_popup(f: &mut Frame, error: &str) {
+    let area = centered_rect(60, 20, f.area());
+
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Error ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red));
+
+    let text = Paragraph::new(error)
+        .style(Style::default().fg(Color::Red))
+        .wrap(Wrap { trim: true })
+        .block(block);
+
+    f.render_widget(text, area);
+}
+
+fn draw_info_popup(f: &mut Frame, info: &str) {
+    let area = centered_rect(60, 20, f.area());
+
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Info ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Green));
+
+    let text = Paragraph::new(info)
+        .style(Style::default().fg(Color::Green))
+        .wrap(Wrap { trim: true })
+        .block(block);
+
+    f.render_widget(text, area);
+}
+
+fn draw_edit_conflict_popup(f: &mut Frame, conflict: &EditConflict) {
+    let area = centered_rect(80, 80, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(" Edit Conflict: {} ", conflict.label))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(1)])
+        .split(inner);
+
+    let heading = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let mut lines = vec![
+        Line::from(format!(
+            "Someone else saved version {} while you were editing.",
+            conflict.server_version
+        )),
+        Line::from(""),
+    ];
+
+    if !conflict.fields.is_empty() {
+        lines.push(Line::from(Span::styled("FIELDS (theirs → yours)", heading)));
+        for field in &conflict.fields {
+            let marker = if field.clash {
+                Span::styled("! ", Style::default().fg(Color::Red))
+            } else {
+                Span::raw("  ")
             };
+            lines.push(Line::from(vec![
+                marker,
+                Span::styled(format!("{:<10}", field.name), Style::default().fg(Color::Cyan)),
+                Span::styled(field.theirs.clone(), Style::default().fg(Color::Red)),
+                Span::raw(" → "),
+                Span::styled(field.mine.clone(), Style::default().fg(Color::Green)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
 
-            let total_lines = all_content_lines.len();
-            let visible_height = area.height.saturating_sub(4 + linked_tasks_height) as usize;
+    let text_changed = conflict.text_diff.iter().any(|l| !matches!(l, DiffLine::Same(_)));
+    if text_changed {
+        lines.push(Line::from(Span::styled("TEXT (- theirs, + yours)", heading)));
+        for line in &conflict.text_diff {
+            lines.push(match line {
+                DiffLine::Same(text) => Line::from(Span::styled(
+                    format!("  {}", text),
+                    Style::default().fg(Color::DarkGray),
+                )),
+                DiffLine::Theirs(text) => Line::from(Span::styled(
+                    format!("- {}", text),
+                    Style::default().fg(Color::Red),
+                )),
+                DiffLine::Mine(text) => Line::from(Span::styled(
+                    format!("+ {}", text),
+                    Style::default().fg(Color::Green),
+                )),
+            });
+        }
+    }
 
-            // Clamp scroll offset to valid range
-            let max_scroll = total_lines.saturating_sub(visible_height);
-            let scroll_offset = app.kb_scroll_offset.min(max_scroll);
+    if conflict.fields.is_empty() && !text_changed {
+        lines.push(Line::from(Span::styled(
+            "Your edit matches what was saved.",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    let body = Paragraph::new(lines).scroll((conflict.scroll, 0));
+    f.render_widget(body, chunks[0]);
+
+    let merge_hint = if conflict.can_merge() {
+        Span::raw("merge (keep their other changes)  ")
+    } else {
+        Span::styled("merge unavailable (same lines changed)  ", Style::default().fg(Color::DarkGray))
+    };
+    let hints = Paragraph::new(Line::from(vec![
+        Span::styled("m", Style::default().fg(Color::Green)),
+        Span::raw(": "),
+        merge_hint,
+        Span::styled("o", Style::default().fg(Color::Green)),
+        Span::raw(": overwrite  "),
+        Span::styled("Esc", Style::default().fg(Color::Green)),
+        Span::raw(": keep editing  "),
+        Span::styled("j/k", Style::default().fg(Color::Green)),
+        Span::raw(": scroll"),
+    ]));
+    f.render_widget(hints, chunks[1]);
+}
+
+fn draw_filter_panel(f: &mut Frame, app: &App) {
+    let area = centered_rect(60, 70, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Filter Tasks ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    // Split into sections
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Priority
+            Constraint::Length(5), // Tags (scrollable)
+            Constraint::Length(3), // Assignee
+            Constraint::Length(3), // Due Date
+            Constraint::Length(3), // Custom Fields
+            Constraint::Length(3), // Order By
+            Constraint::Length(3), // Actions
+            Constraint::Min(0),    // Spacer
+            Constraint::Length(2), // Hints
+        ])
+        .split(inner);
+
+    // Helper for section styling
+    let section_style = |section: FilterPanelSection| -> Style {
+        if app.filter_panel_section == section {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::Gray)
+        }
+    };
+
+    // Priority section
+    let priorities: &[Option<Priority>] = &[
+        None,
+        Some(Priority::Highest),
+        Some(Priority::High),
+        Some(Priority::Medium),
+        Some(Priority::Low),
+        Some(Priority::Lowest),
+    ];
+    let mut priority_spans: Vec<Span> = vec![Span::styled(" ", Style::default())];
+    for (i, p) in priorities.iter().enumerate() {
+        let label = match p {
+            None => "None",
+            Some(Priority::Highest) => "Highest",
+            Some(Priority::High) => "High",
+            Some(Priority::Medium) => "Medium",
+            Some(Priority::Low) => "Low",
+            Some(Priority::Lowest) => "Lowest",
+        };
+        let is_selected = i == app.filter_priority_cursor;
+        let marker = if is_selected { "●" } else { "○" };
+        let style = if is_selected {
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        priority_spans.push(Span::styled(format!("[{} {}] ", marker, label), style));
+    }
+    let priority_block = Block::default()
+        .title(" Priority (h/l) ")
+        .borders(Borders::ALL)
+        .border_style(section_style(FilterPanelSection::Priority));
+    let priority_widget = Paragraph::new(Line::from(priority_spans)).block(priority_block);
+    f.render_widget(priority_widget, chunks[0]);
+
+    // Tags section
+    let tag_lines: Vec<Line> = if app.workspace_tags.is_empty() {
+        vec![Line::from(Span::styled("  No tags available", Style::default().fg(Color::DarkGray)))]
+    } else {
+        app.workspace_tags
+            .iter()
+            .enumerate()
+            .map(|(i, tag)| {
+                let is_selected = app.filter_selected_tags.contains(&tag.id);
+                let is_cursor = app.filter_panel_section == FilterPanelSection::Tags && i == app.filter_tag_cursor;
+                let checkbox = if is_selected { "[x]" } else { "[ ]" };
+                let tag_color = tag.color.as_ref()
+                    .and_then(|c| parse_hex_color(c))
+                    .unwrap_or(Color::Gray);
+
+                let style = if is_cursor {
+                    Style::default().bg(Color::DarkGray).fg(Color::White)
+                } else {
+                    Style::default()
+                };
+
+                Line::from(vec![
+                    Span::styled(format!(" {} ", checkbox), style),
+                    Span::styled(
+                        format!(" {} ", tag.name),
+                        Style::default().bg(tag_color).fg(Color::Black),
+                    ),
+                ])
+            })
+            .collect()
+    };
+    let tag_block = Block::default()
+        .title(" Tags (j/k, Space) ")
+        .borders(Borders::ALL)
+        .border_style(section_style(FilterPanelSection::Tags));
+    let tag_widget = Paragraph::new(tag_lines).block(tag_block);
+    f.render_widget(tag_widget, chunks[1]);
+
+    // Assignee section
+    let assignee_str = if app.filter_assignee_cursor == 0 {
+        "None".to_string()
+    } else {
+        app.workspace_members
+            .get(app.filter_assignee_cursor - 1)
+            .map(|m| m.display_name.clone())
+            .unwrap_or_else(|| "Unknown".to_string())
+    };
+    let assignee_block = Block::default()
+        .title(" Assignee (h/l) ")
+        .borders(Borders::ALL)
+        .border_style(section_style(FilterPanelSection::Assignee));
+    let assignee_widget = Paragraph::new(Line::from(vec![
+        Span::styled(" < ", Style::default().fg(Color::DarkGray)),
+        Span::styled(&assignee_str, Style::default().fg(Color::White)),
+        Span::styled(" > ", Style::default().fg(Color::DarkGray)),
+    ])).block(assignee_block);
+    f.render_widget(assignee_widget, chunks[2]);
+
+    // Due Date section
+    let due_mode_str = match app.filter_due_mode {
+        DueDateMode::Before => "Before",
+        DueDateMode::After => "After",
+    };
+    let due_date_block = Block::default()
+        .title(" Due Date (h/l mode, i edit) ")
+        .borders(Borders::ALL)
+        .border_style(section_style(FilterPanelSection::DueDate));
+    let due_date_widget = Paragraph::new(Line::from(vec![
+        Span::styled(format!(" [{}] ", due_mode_str), Style::default().fg(Color::Cyan)),
+        Span::styled(
+            if app.filter_due_input.is_empty() { "YYYY-MM-DD" } else { &app.filter_due_input },
+            Style::default().fg(if app.filter_due_input.is_empty() { Color::DarkGray } else { Color::White }),
+        ),
+    ])).block(due_date_block);
+    f.render_widget(due_date_widget, chunks[3]);
+
+    // Custom Fields section
+    let custom_field_block = Block::default()
+        .title(" Custom Fields (h/l field, i edit) ")
+        .borders(Borders::ALL)
+        .border_style(section_style(FilterPanelSection::CustomFields));
+    let custom_field_line = match app.workspace_custom_fields.get(app.filter_custom_field_cursor) {
+        Some(field) => {
+            let input = app.filter_custom_field_inputs
+                .get(app.filter_custom_field_cursor)
+                .map(|i| i.as_str())
+                .unwrap_or("");
+            Line::from(vec![
+                Span::styled(" < ", Style::default().fg(Color::DarkGray)),
+                Span::styled(&field.name, Style::default().fg(Color::Cyan)),
+                Span::styled(" > ", Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    if input.is_empty() { "any" } else { input },
+                    Style::default().fg(if input.is_empty() { Color::DarkGray } else { Color::White }),
+                ),
+            ])
+        }
+        None => Line::from(Span::styled("  No custom fields", Style::default().fg(Color::DarkGray))),
+    };
+    let custom_field_widget = Paragraph::new(custom_field_line).block(custom_field_block);
+    f.render_widget(custom_field_widget, chunks[4]);
+
+    // Order By section
+    let (sort_field, sort_label) = SORT_FIELDS.get(app.filter_order_cursor).unwrap_or(&("position", "Position"));
+    let direction = if app.filter_order_desc { "↑" } else { "↓" };
+    let order_block = Block::default()
+        .title(" Order By (h/l field, Space dir) ")
+        .borders(Borders::ALL)
+        .border_style(section_style(FilterPanelSection::OrderBy));
+    let order_widget = Paragraph::new(Line::from(vec![
+        Span::styled(" < ", Style::default().fg(Color::DarkGray)),
+        Span::styled(format!("{} {}", sort_label, direction), Style::default().fg(Color::White)),
+        Span::styled(" > ", Style::default().fg(Color::DarkGray)),
+        Span::styled(format!(" ({})", sort_field), Style::default().fg(Color::DarkGray)),
+    ])).block(order_block);
+    f.render_widget(order_widget, chunks[5]);
+
+    // Actions section
+    let actions_style = section_style(FilterPanelSection::Actions);
+    let actions_block = Block::default()
+        .title(" Actions ")
+        .borders(Borders::ALL)
+        .border_style(actions_style);
+    let actions_widget = Paragraph::new(Line::from(vec![
+        Span::styled(" [Enter: Apply] ", Style::default().fg(Color::Green)),
+        Span::styled(" [c: Clear] ", Style::default().fg(Color::Yellow)),
+        Span::styled(" [s: Save Preset] ", Style::default().fg(Color::Cyan)),
+    ])).block(actions_block);
+    f.render_widget(actions_widget, chunks[6]);
+
+    // Hints
+    let hint = Paragraph::new(Line::from(vec![
+        Span::styled("Tab/j/k", Style::default().fg(Color::Yellow)),
+        Span::raw(": section | "),
+        Span::styled("h/l", Style::default().fg(Color::Yellow)),
+        Span::raw(": value | "),
+        Span::styled("Space", Style::default().fg(Color::Yellow)),
+        Span::raw(": toggle | "),
+        Span::styled("Esc", Style::default().fg(Color::Yellow)),
+        Span::raw(": cancel"),
+    ]))
+    .alignment(Alignment::Center);
+    f.render_widget(hint, chunks[8]);
+}
+
+fn draw_preset_panel(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 50, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Filter Presets ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Magenta));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Min(0),    // Preset list
+            Constraint::Length(3), // New preset input (if creating)
+            Constraint::Length(2), // Hints
+        ])
+        .split(inner);
+
+    // Preset list
+    let preset_items: Vec<ListItem> = app
+        .filter_presets
+        .iter()
+        .enumerate()
+        .map(|(i, preset)| {
+            let is_selected = i == app.preset_list_cursor;
+            let style = if is_selected {
+                Style::default().bg(Color::DarkGray).fg(Color::White)
+            } else {
+                Style::default()
+            };
+
+            // Build a description of the preset
+            let mut desc_parts = Vec::new();
+            if preset.filters.priority.is_some() {
+                desc_parts.push("priority");
+            }
+            if preset.filters.assigned_to.is_some() {
+                desc_parts.push("assignee");
+            }
+            if preset.filters.due_before.is_some() || preset.filters.due_after.is_some() {
+                desc_parts.push("due date");
+            }
+            if preset.filters.order_by.is_some() {
+                desc_parts.push("sorted");
+            }
+            let desc = if desc_parts.is_empty() {
+                "no filters".to_string()
+            } else {
+                desc_parts.join(", ")
+            };
+
+            ListItem::new(Line::from(vec![
+                Span::styled("  ", style),
+                Span::styled(&preset.name, style.add_modifier(Modifier::BOLD)),
+                Span::styled(format!(" ({})", desc), style.fg(Color::DarkGray)),
+            ]))
+        })
+        .collect();
+
+    let list_title = if app.filter_presets.is_empty() {
+        " No presets - press 'n' to create ".to_string()
+    } else {
+        format!(" Presets ({}) ", app.filter_presets.len())
+    };
+
+    let list = List::new(preset_items).block(
+        Block::default()
+            .title(list_title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray)),
+    );
+    f.render_widget(list, chunks[0]);
+
+    // New preset input (if creating)
+    if app.creating_preset {
+        let input_block = Block::default()
+            .title(" Preset Name ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow));
+        let input = Paragraph::new(app.new_preset_name.as_str()).block(input_block);
+        f.render_widget(input, chunks[1]);
+
+        // Set cursor position
+        f.set_cursor_position((
+            chunks[1].x + 1 + app.new_preset_name.len() as u16,
+            chunks[1].y + 1,
+        ));
+    }
+
+    // Hints
+    let hint = if app.creating_preset {
+        Paragraph::new(Line::from(vec![
+            Span::styled("Enter", Style::default().fg(Color::Yellow)),
+            Span::raw(": save | "),
+            Span::styled("Esc", Style::default().fg(Color::Yellow)),
+            Span::raw(": cancel"),
+        ]))
+    } else {
+        Paragraph::new(Line::from(vec![
+            Span::styled("j/k", Style::default().fg(Color::Yellow)),
+            Span::raw(": select | "),
+            Span::styled("Enter", Style::default().fg(Color::Yellow)),
+            Span::raw(": load | "),
+            Span::styled("n", Style::default().fg(Color::Yellow)),
+            Span::raw(": new | "),
+            Span::styled("d", Style::default().fg(Color::Yellow)),
+            Span::raw(": delete | "),
+            Span::styled("Esc", Style::default().fg(Color::Yellow)),
+            Span::raw(": close"),
+        ]))
+    }
+    .alignment(Alignment::Center);
+    f.render_widget(hint, chunks[2]);
+}
+
+fn draw_knowledge_base(f: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Main content
+            Constraint::Length(1), // Status bar
+        ])
+        .split(f.area());
+
+    // Header
+    draw_header(f, chunks[0], app);
+
+    // Main content: split into tree and document viewer
+    let content_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(30), // Document tree
+            Constraint::Percentage(70), // Document content
+        ])
+        .split(chunks[1]);
+
+    // Draw document tree
+    draw_document_tree(f, content_chunks[0], app);
+
+    // Draw document content/editor
+    draw_document_content(f, content_chunks[1], app);
+
+    // Status bar
+    draw_kb_status_bar(f, chunks[2], app);
+
+    // Draw create document popup if active
+    if app.kb_creating {
+        draw_kb_create_popup(f, app);
+    }
+
+    // Draw delete confirmation popup if active
+    if app.kb_confirming_delete {
+        draw_kb_delete_confirm_popup(f, app);
+    }
+
+    // Draw link task popup if active
+    if app.linking_task_mode {
+        draw_link_task_popup(f, app);
+    }
+}
+
+fn draw_document_tree(f: &mut Frame, area: Rect, app: &App) {
+    let is_focused = app.kb_focus == KbFocus::Tree;
+    let border_color = if is_focused { Color::Cyan } else { Color::DarkGray };
+
+    let block = Block::default()
+        .title(" Documents ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    if app.kb_visible_list.is_empty() {
+        let empty = Paragraph::new("No documents. Press 'n' to create one.")
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
+        f.render_widget(empty, inner);
+        return;
+    }
+
+    // Build list items from visible tree
+    let items: Vec<ListItem> = app
+        .kb_visible_list
+        .iter()
+        .enumerate()
+        .map(|(i, (doc, depth))| {
+            let is_selected = i == app.kb_selected_idx;
+            let style = if is_selected {
+                Style::default().bg(Color::DarkGray).fg(Color::White)
+            } else {
+                Style::default()
+            };
+
+            // Determine expand/collapse indicator (all docs can be expanded)
+            let indicator = if app.kb_expanded.contains(&doc.id) {
+                "▼ "
+            } else {
+                "▶ "
+            };
+
+            // Build indentation
+            let indent = "  ".repeat(*depth);
+
+            // Truncate title if needed
+            let available_width = area.width.saturating_sub(4 + (depth * 2) as u16 + 2) as usize;
+            let title = if doc.title.len() > available_width {
+                format!("{}...", &doc.title[..available_width.saturating_sub(3)])
+            } else {
+                doc.title.clone()
+            };
+
+            ListItem::new(Line::from(vec![
+                Span::styled(indent, style),
+                Span::styled(indicator, Style::default().fg(Color::Yellow)),
+                Span::styled(title, style),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items);
+    f.render_widget(list, inner);
+}
+
+fn draw_document_content(f: &mut Frame, area: Rect, app: &App) {
+    if app.kb_editing {
+        draw_document_editor(f, area, app);
+        return;
+    }
+
+    let is_focused = app.kb_focus == KbFocus::Content;
+    let border_color = if is_focused { Color::Cyan } else { Color::DarkGray };
+
+    match &app.kb_selected_doc {
+        Some(doc) => {
+            // Calculate linked tasks height (header + items or empty message)
+            let linked_tasks_height = if app.kb_linked_tasks.is_empty() {
+                3 // Header + "(none)" + padding
+            } else {
+                (2 + app.kb_linked_tasks.len().min(5)) as u16 // Header + up to 5 tasks
+            };
+
+            // Render markdown content to calculate total height
+            let content_width = area.width.saturating_sub(4) as usize;
+            let content_text = doc.content.as_deref().unwrap_or("");
+            let all_content_lines = if content_text.is_empty() {
+                vec![Line::from(Span::styled(
+                    "(No content)",
+                    Style::default().fg(Color::DarkGray),
+                ))]
+            } else {
+                markdown::render_markdown(content_text, content_width)
+            };
+
+            let total_lines = all_content_lines.len();
+            let visible_height = area.height.saturating_sub(4 + linked_tasks_height) as usize;
+
+            // Clamp scroll offset to valid range
+            let max_scroll = total_lines.saturating_sub(visible_height);
+            let scroll_offset = app.kb_scroll_offset.min(max_scroll);
+
+            // Build scroll position indicator
+            let scroll_indicator = if total_lines <= visible_height {
+                String::new()
+            } else if scroll_offset == 0 {
+                " [Top]".to_string()
+            } else if scroll_offset >= max_scroll {
+                " [Bot]".to_string()
+            } else {
+                format!(" [{}%]", scroll_offset * 100 / max_scroll)
+            };
+
+            let title = format!(" {} {}", doc.title, scroll_indicator);
+            let block = Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(border_color));
+
+            let inner = block.inner(area);
+            f.render_widget(block, area);
+
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(3),                       // Content
+                    Constraint::Length(linked_tasks_height),  // Linked Tasks
+                ])
+                .split(inner);
+
+            // Apply scroll offset and render visible lines
+            let visible_lines: Vec<Line> = all_content_lines
+                .into_iter()
+                .skip(scroll_offset)
+                .take(visible_height)
+                .collect();
+
+            let content = Paragraph::new(visible_lines);
+            f.render_widget(content, chunks[0]);
+
+            // Linked Tasks section
+            let mut linked_lines = vec![
+                Line::from(Span::styled(
+                    format!("Linked Tasks ({}):", app.kb_linked_tasks.len()),
+                    Style::default().fg(Color::Cyan),
+                )),
+            ];
+            if app.kb_linked_tasks.is_empty() {
+                linked_lines.push(Line::from(Span::styled(
+                    "  (none)",
+                    Style::default().fg(Color::DarkGray),
+                )));
+            } else {
+                for task in app.kb_linked_tasks.iter().take(5) {
+                    linked_lines.push(Line::from(vec![
+                        Span::raw("  "),
+                        Span::styled(&task.task_title, Style::default().fg(Color::Green)),
+                    ]));
+                }
+                if app.kb_linked_tasks.len() > 5 {
+                    linked_lines.push(Line::from(Span::styled(
+                        format!("  ... and {} more", app.kb_linked_tasks.len() - 5),
+                        Style::default().fg(Color::DarkGray),
+                    )));
+                }
+            }
+            let linked_tasks = Paragraph::new(linked_lines);
+            f.render_widget(linked_tasks, chunks[1]);
+        }
+        None => {
+            let block = Block::default()
+                .title(" Content ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(border_color));
+
+            let inner = block.inner(area);
+            f.render_widget(block, area);
+
+            let empty = Paragraph::new("Select a document to view its content")
+                .style(Style::default().fg(Color::DarkGray))
+                .alignment(Alignment::Center);
+            f.render_widget(empty, inner);
+        }
+    }
+}
+
+fn draw_document_editor(f: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default()
+        .title(" Edit Document ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Title input
+            Constraint::Min(0),    // Content input
+        ])
+        .split(inner);
+
+    // Title input
+    let title_block = Block::default()
+        .title(" Title ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+    let title_input = Paragraph::new(app.kb_edit_title.as_str()).block(title_block);
+    f.render_widget(title_input, chunks[0]);
+
+    // Content input (uses TextArea)
+    if let Some(ref textarea) = app.kb_content_textarea {
+        let content_block = Block::default()
+            .title(" Content (Ctrl+E: external editor) ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = content_block.inner(chunks[1]);
+        f.render_widget(content_block, chunks[1]);
+        f.render_widget(textarea, inner);
+    } else {
+        let content_block = Block::default()
+            .title(" Content ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let content_input = Paragraph::new("").block(content_block);
+        f.render_widget(content_input, chunks[1]);
+    }
+
+    // Don't manually set cursor - TextArea handles it
+}
+
+fn draw_kb_status_bar(f: &mut Frame, area: Rect, app: &App) {
+    let (mode, mode_color) = if app.kb_editing {
+        ("EDIT", Color::Yellow)
+    } else if app.kb_creating {
+        ("CREATE", Color::Green)
+    } else if app.kb_confirming_delete {
+        ("DELETE", Color::Red)
+    } else if app.linking_task_mode {
+        ("LINK", Color::Cyan)
+    } else {
+        ("NORMAL", Color::Blue)
+    };
+
+    let hints = if app.kb_editing {
+        "Ctrl+E: editor | Alt+Enter: save | Esc: cancel"
+    } else if app.kb_creating {
+        "Enter: create | Esc: cancel"
+    } else if app.kb_confirming_delete {
+        "y: confirm | n/Esc: cancel"
+    } else if app.linking_task_mode {
+        "j/k: navigate | Enter: link | Esc: cancel"
+    } else {
+        "j/k: nav | n: new | e: edit | d: del | L: link task | U: unlink | q: close"
+    };
+
+    let status = Paragraph::new(Line::from(vec![
+        Span::styled(
+            format!(" {} ", mode),
+            Style::default().bg(mode_color).fg(Color::White),
+        ),
+        Span::raw(" "),
+        Span::styled(hints, Style::default().fg(Color::DarkGray)),
+    ]));
+
+    f.render_widget(status, area);
+}
+
+fn draw_kb_create_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 30, f.area());
+
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" New Document ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Green));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Title input
+            Constraint::Length(2), // Parent info
+            Constraint::Length(2), // Hint
+            Constraint::Min(0),    // Spacer
+        ])
+        .split(inner);
+
+    // Title input
+    let title_block = Block::default()
+        .title(" Title ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+    let title_input = Paragraph::new(app.kb_create_title.as_str()).block(title_block);
+    f.render_widget(title_input, chunks[0]);
+
+    // Parent info
+    let parent_text = match app.kb_create_parent_id {
+        Some(pid) => {
+            let parent_name = app
+                .kb_documents
+                .iter()
+                .find(|d| d.id == pid)
+                .map(|d| d.title.as_str())
+                .unwrap_or("Unknown");
+            format!("Creating under: {}", parent_name)
+        }
+        None => "Creating at root level".to_string(),
+    };
+    let parent_info = Paragraph::new(parent_text)
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
+    f.render_widget(parent_info, chunks[1]);
+
+    // Hint
+    let hint = Paragraph::new("Enter: create | Esc: cancel")
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
+    f.render_widget(hint, chunks[2]);
+
+    // Set cursor position
+    f.set_cursor_position((
+        chunks[0].x + 1 + app.kb_create_title.len() as u16,
+        chunks[0].y + 1,
+    ));
+}
+
+fn draw_kb_delete_confirm_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 20, f.area());
+
+    f.render_widget(Clear, area);
+
+    let doc_title = app
+        .kb_visible_list
+        .get(app.kb_selected_idx)
+        .map(|(doc, _)| doc.title.as_str())
+        .unwrap_or("Unknown");
+
+    let block = Block::default()
+        .title(" Confirm Delete ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(2), // Message
+            Constraint::Length(2), // Hint
+            Constraint::Min(0),    // Spacer
+        ])
+        .split(inner);
+
+    let message = Paragraph::new(vec![
+        Line::from(Span::raw("Move document and its children to trash:")),
+        Line::from(Span::styled(
+            format!("\"{}\"", doc_title),
+            Style::default().fg(Color::Yellow),
+        )),
+    ])
+    .alignment(Alignment::Center);
+    f.render_widget(message, chunks[0]);
+
+    let hint = Paragraph::new("y: yes, move to trash | n: no, cancel")
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
+    f.render_widget(hint, chunks[1]);
+}
+
+fn draw_trash(f: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Trash list
+            Constraint::Length(1), // Status bar
+        ])
+        .split(f.area());
 
-            // Build scroll position indicator
-            let scroll_indicator = if total_lines <= visible_height {
-                String::new()
-            } else if scroll_offset == 0 {
-                " [Top]".to_string()
-            } else if scroll_offset >= max_scroll {
-                " [Bot]".to_string()
-            } else {
-                format!(" [{}%]", scroll_offset * 100 / max_scroll)
-            };
+    draw_header(f, chunks[0], app);
 
-            let title = format!(" {} {}", doc.title, scroll_indicator);
-            let block = Block::default()
-                .title(title)
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(border_color));
+    let block = Block::default()
+        .title(format!(" Trash ({}) ", app.trash_items.len()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
 
-            let inner = block.inner(area);
-            f.render_widget(block, area);
+    let inner = block.inner(chunks[1]);
+    f.render_widget(block, chunks[1]);
 
-            let chunks = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Min(3),                       // Content
-                    Constraint::Length(linked_tasks_height),  // Linked Tasks
-                ])
-                .split(inner);
+    if app.trash_items.is_empty() {
+        let empty = Paragraph::new("Trash is empty.")
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
+        f.render_widget(empty, inner);
+    } else {
+        // Two lines per item; keep the cursor in view
+        let per_page = (inner.height as usize / 2).max(1);
+        let skip = (app.trash_cursor + 1).saturating_sub(per_page);
 
-            // Apply scroll offset and render visible lines
-            let visible_lines: Vec<Line> = all_content_lines
-                .into_iter()
-                .skip(scroll_offset)
-                .take(visible_height)
-                .collect();
+        let items: Vec<ListItem> = app
+            .trash_items
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .take(per_page)
+            .map(|(i, item)| {
+                let is_selected = i == app.trash_cursor;
+                let style = if is_selected {
+                    Style::default().bg(Color::DarkGray).fg(Color::White)
+                } else {
+                    Style::default()
+                };
 
-            let content = Paragraph::new(visible_lines);
-            f.render_widget(content, chunks[0]);
+                let (label, color) = match item.kind {
+                    todo_shared::TrashItemKind::Task => ("task", Color::Cyan),
+                    todo_shared::TrashItemKind::Document => ("doc", Color::Magenta),
+                    todo_shared::TrashItemKind::Comment => ("comment", Color::Yellow),
+                };
 
-            // Linked Tasks section
-            let mut linked_lines = vec![
-                Line::from(Span::styled(
-                    format!("Linked Tasks ({}):", app.kb_linked_tasks.len()),
-                    Style::default().fg(Color::Cyan),
-                )),
-            ];
-            if app.kb_linked_tasks.is_empty() {
-                linked_lines.push(Line::from(Span::styled(
-                    "  (none)",
-                    Style::default().fg(Color::DarkGray),
-                )));
-            } else {
-                for task in app.kb_linked_tasks.iter().take(5) {
-                    linked_lines.push(Line::from(vec![
-                        Span::raw("  "),
-                        Span::styled(&task.task_title, Style::default().fg(Color::Green)),
-                    ]));
-                }
-                if app.kb_linked_tasks.len() > 5 {
-                    linked_lines.push(Line::from(Span::styled(
-                        format!("  ... and {} more", app.kb_linked_tasks.len() - 5),
+                let mut title_line = vec![
+                    Span::styled(format!("{:<8}", label), Style::default().fg(color)),
+                    Span::styled(item.title.clone(), style),
+                ];
+                if item.nested_count > 0 {
+                    title_line.push(Span::styled(
+                        format!("  +{} nested", item.nested_count),
                         Style::default().fg(Color::DarkGray),
-                    )));
+                    ));
                 }
-            }
-            let linked_tasks = Paragraph::new(linked_lines);
-            f.render_widget(linked_tasks, chunks[1]);
-        }
-        None => {
-            let block = Block::default()
-                .title(" Content ")
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(border_color));
 
-            let inner = block.inner(area);
-            f.render_widget(block, area);
+                let mut details = Vec::new();
+                if let Some(ref context) = item.context {
+                    details.push(format!("in {}", context));
+                }
+                details.push(format!(
+                    "deleted {}{}",
+                    item.deleted_at.format("%Y-%m-%d %H:%M"),
+                    item.deleted_by_username
+                        .as_ref()
+                        .map(|u| format!(" by {}", u))
+                        .unwrap_or_default()
+                ));
+                details.push(format!("purged {}", item.purge_at.format("%Y-%m-%d")));
+
+                ListItem::new(vec![
+                    Line::from(title_line),
+                    Line::from(Span::styled(
+                        format!("        {}", details.join(" · ")),
+                        Style::default().fg(Color::DarkGray),
+                    )),
+                ])
+            })
+            .collect();
 
-            let empty = Paragraph::new("Select a document to view its content")
-                .style(Style::default().fg(Color::DarkGray))
-                .alignment(Alignment::Center);
-            f.render_widget(empty, inner);
-        }
+        f.render_widget(List::new(items), inner);
+    }
+
+    let (mode, mode_color, hints) = if app.trash_confirming_purge {
+        ("DELETE", Color::Red, "y: delete permanently | n/Esc: cancel")
+    } else {
+        (
+            "TRASH",
+            Color::Blue,
+            "j/k: nav | r/Enter: restore | D: delete permanently | R: refresh | q: close",
+        )
+    };
+
+    let status = Paragraph::new(Line::from(vec![
+        Span::styled(
+            format!(" {} ", mode),
+            Style::default().bg(mode_color).fg(Color::White),
+        ),
+        Span::raw(" "),
+        Span::styled(hints, Style::default().fg(Color::DarkGray)),
+    ]));
+    f.render_widget(status, chunks[2]);
+
+    if app.trash_confirming_purge {
+        draw_trash_purge_confirm_popup(f, app);
     }
 }
 
-fn draw_document_editor(f: &mut Frame, area: Rect, app: &App) {
+fn draw_trash_purge_confirm_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 20, f.area());
+
+    f.render_widget(Clear, area);
+
+    let title = app
+        .trash_items
+        .get(app.trash_cursor)
+        .map(|item| item.title.as_str())
+        .unwrap_or("Unknown");
+
     let block = Block::default()
-        .title(" Edit Document ")
+        .title(" Delete Permanently ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow));
+        .border_style(Style::default().fg(Color::Red));
 
     let inner = block.inner(area);
     f.render_widget(block, area);
@@ -3063,63 +5011,164 @@ fn draw_document_editor(f: &mut Frame, area: Rect, app: &App) {
         .direction(Direction::Vertical)
         .margin(1)
         .constraints([
-            Constraint::Length(3), // Title input
-            Constraint::Min(0),    // Content input
+            Constraint::Length(2), // Message
+            Constraint::Length(2), // Hint
+            Constraint::Min(0),    // Spacer
         ])
         .split(inner);
 
-    // Title input
-    let title_block = Block::default()
-        .title(" Title ")
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow));
-    let title_input = Paragraph::new(app.kb_edit_title.as_str()).block(title_block);
-    f.render_widget(title_input, chunks[0]);
+    let message = Paragraph::new(vec![
+        Line::from(Span::raw("This cannot be undone:")),
+        Line::from(Span::styled(
+            format!("\"{}\"", title),
+            Style::default().fg(Color::Yellow),
+        )),
+    ])
+    .alignment(Alignment::Center);
+    f.render_widget(message, chunks[0]);
 
-    // Content input (uses TextArea)
-    if let Some(ref textarea) = app.kb_content_textarea {
-        let content_block = Block::default()
-            .title(" Content (Ctrl+E: external editor) ")
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Gray));
-        let inner = content_block.inner(chunks[1]);
-        f.render_widget(content_block, chunks[1]);
-        f.render_widget(textarea, inner);
-    } else {
-        let content_block = Block::default()
-            .title(" Content ")
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Gray));
-        let content_input = Paragraph::new("").block(content_block);
-        f.render_widget(content_input, chunks[1]);
-    }
+    let hint = Paragraph::new("y: yes, delete | n: no, cancel")
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
+    f.render_widget(hint, chunks[1]);
+}
 
-    // Don't manually set cursor - TextArea handles it
+fn draw_link_task_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 50, f.area());
+
+    // Clear the background
+    f.render_widget(Clear, area);
+
+    // Get available tasks (not already linked)
+    let linked_ids: std::collections::HashSet<_> = app.kb_linked_tasks
+        .iter()
+        .map(|t| t.task_id)
+        .collect();
+
+    // Get all tasks from columns
+    let all_tasks: Vec<_> = app.columns.iter().flat_map(|c| c.tasks.iter()).collect();
+    let available: Vec<_> = all_tasks
+        .iter()
+        .filter(|t| !linked_ids.contains(&t.id))
+        .collect();
+
+    let items: Vec<ListItem> = available
+        .iter()
+        .enumerate()
+        .map(|(i, task)| {
+            let style = if i == app.link_task_cursor {
+                Style::default().bg(Color::DarkGray).fg(Color::White)
+            } else {
+                Style::default()
+            };
+            ListItem::new(Line::from(Span::styled(&task.title, style)))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(" Link Task (j/k: navigate, Enter: select, Esc: cancel) ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
+
+    f.render_widget(list, area);
 }
 
-fn draw_kb_status_bar(f: &mut Frame, area: Rect, app: &App) {
-    let (mode, mode_color) = if app.kb_editing {
-        ("EDIT", Color::Yellow)
-    } else if app.kb_creating {
-        ("CREATE", Color::Green)
-    } else if app.kb_confirming_delete {
-        ("DELETE", Color::Red)
-    } else if app.linking_task_mode {
-        ("LINK", Color::Cyan)
+fn draw_notifications(f: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Notification list
+            Constraint::Length(1), // Status bar
+        ])
+        .split(f.area());
+
+    draw_header(f, chunks[0], app);
+
+    let title = if app.notifications_unread_only {
+        format!(" Notifications ({} unread) ", app.notifications_unread)
     } else {
-        ("NORMAL", Color::Blue)
+        format!(" Notifications ({} unread, all shown) ", app.notifications_unread)
     };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let inner = block.inner(chunks[1]);
+    f.render_widget(block, chunks[1]);
+
+    if app.notifications.is_empty() {
+        let empty = Paragraph::new("Nothing new.")
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
+        f.render_widget(empty, inner);
+    } else {
+        // Two lines per item; keep the cursor in view
+        let per_page = (inner.height as usize / 2).max(1);
+        let skip = (app.notification_cursor + 1).saturating_sub(per_page);
+
+        let items: Vec<ListItem> = app
+            .notifications
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .take(per_page)
+            .map(|(i, notification)| {
+                let is_selected = i == app.notification_cursor;
+                let mut style = if is_selected {
+                    Style::default().bg(Color::DarkGray).fg(Color::White)
+                } else {
+                    Style::default()
+                };
+                if !notification.is_read() {
+                    style = style.add_modifier(Modifier::BOLD);
+                }
+
+                let (label, color) = match notification.kind {
+                    todo_shared::NotificationKind::TaskAssigned => ("assigned", Color::Cyan),
+                    todo_shared::NotificationKind::TaskCommented => ("comment", Color::Yellow),
+                    todo_shared::NotificationKind::Mentioned => ("mention", Color::Magenta),
+                    todo_shared::NotificationKind::StatusChanged => ("moved", Color::Blue),
+                    todo_shared::NotificationKind::DueSoon => ("due", Color::Red),
+                };
+
+                let marker = if notification.is_read() { "  " } else { "● " };
+                let title_line = vec![
+                    Span::styled(marker, Style::default().fg(Color::Yellow)),
+                    Span::styled(format!("{:<9}", label), Style::default().fg(color)),
+                    Span::styled(notification.summary(), style),
+                ];
+
+                let mut details = vec![notification.created_at.format("%Y-%m-%d %H:%M").to_string()];
+                if let Some(ref detail) = notification.detail {
+                    details.push(detail.clone());
+                }
+
+                ListItem::new(vec![
+                    Line::from(title_line),
+                    Line::from(Span::styled(
+                        format!("           {}", details.join(" · ")),
+                        Style::default().fg(Color::DarkGray),
+                    )),
+                ])
+            })
+            .collect();
 
-    let hints = if app.kb_editing {
-        "Ctrl+E: editor | Alt+Enter: save | Esc: cancel"
-    } else if app.kb_creating {
-        "Enter: create | Esc: cancel"
-    } else if app.kb_confirming_delete {
-        "y: confirm | n/Esc: cancel"
-    } else if app.linking_task_mode {
-        "j/k: navigate | Enter: link | Esc: cancel"
+        f.render_widget(List::new(items), inner);
+    }
+
+    let (mode, mode_color, hints) = if app.notification_settings_visible {
+        ("SETTINGS", Color::Magenta, "j/k: nav | Space: toggle | q/Esc: close")
     } else {
-        "j/k: nav | n: new | e: edit | d: del | L: link task | U: unlink | q: close"
+        (
+            "INBOX",
+            Color::Blue,
+            "j/k: nav | Enter: open task | r: read | a: read all | u: unread only | s: settings | R: refresh | q: close",
+        )
     };
 
     let status = Paragraph::new(Line::from(vec![
@@ -3130,86 +5179,198 @@ fn draw_kb_status_bar(f: &mut Frame, area: Rect, app: &App) {
         Span::raw(" "),
         Span::styled(hints, Style::default().fg(Color::DarkGray)),
     ]));
+    f.render_widget(status, chunks[2]);
 
-    f.render_widget(status, area);
+    if app.notification_settings_visible {
+        draw_notification_settings_popup(f, app);
+    }
 }
 
-fn draw_kb_create_popup(f: &mut Frame, app: &App) {
-    let area = centered_rect(50, 30, f.area());
+fn draw_notification_settings_popup(f: &mut Frame, app: &App) {
+    let area = centered_rect(45, 35, f.area());
 
     f.render_widget(Clear, area);
 
     let block = Block::default()
-        .title(" New Document ")
+        .title(" Notify Me When ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Green));
+        .border_style(Style::default().fg(Color::Magenta));
 
     let inner = block.inner(area);
     f.render_widget(block, area);
 
+    let items: Vec<ListItem> = crate::app::notification_setting_rows(&app.notification_settings)
+        .iter()
+        .enumerate()
+        .map(|(i, (label, enabled))| {
+            let style = if i == app.notification_settings_cursor {
+                Style::default().bg(Color::DarkGray).fg(Color::White)
+            } else {
+                Style::default()
+            };
+            let (check, color) = if *enabled {
+                ("[x] ", Color::Green)
+            } else {
+                ("[ ] ", Color::DarkGray)
+            };
+
+            ListItem::new(Line::from(vec![
+                Span::styled(check, Style::default().fg(color)),
+                Span::styled(*label, style),
+            ]))
+        })
+        .collect();
+
+    f.render_widget(List::new(items), inner);
+}
+
+fn draw_profile(f: &mut Frame, app: &App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .margin(1)
         .constraints([
-            Constraint::Length(3), // Title input
-            Constraint::Length(2), // Parent info
-            Constraint::Length(2), // Hint
-            Constraint::Min(0),    // Spacer
+            Constraint::Length(3), // Header
+            Constraint::Length(6), // Account details
+            Constraint::Min(0),    // Sessions
+            Constraint::Length(1), // Status bar
         ])
-        .split(inner);
+        .split(f.area());
 
-    // Title input
-    let title_block = Block::default()
-        .title(" Title ")
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow));
-    let title_input = Paragraph::new(app.kb_create_title.as_str()).block(title_block);
-    f.render_widget(title_input, chunks[0]);
+    draw_header(f, chunks[0], app);
 
-    // Parent info
-    let parent_text = match app.kb_create_parent_id {
-        Some(pid) => {
-            let parent_name = app
-                .kb_documents
-                .iter()
-                .find(|d| d.id == pid)
-                .map(|d| d.title.as_str())
-                .unwrap_or("Unknown");
-            format!("Creating under: {}", parent_name)
-        }
-        None => "Creating at root level".to_string(),
+    let label_style = Style::default().fg(Color::DarkGray);
+    let mut lines = Vec::new();
+    if let Some(ref user) = app.user {
+        lines.push(Line::from(vec![
+            Span::styled("  Username      ", label_style),
+            Span::raw(format!("@{}", user.username)),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("  Display name  ", label_style),
+            Span::raw(user.display_name.clone()),
+        ]));
+        let verified = if user.email_verified {
+            Span::styled(" ✓ verified", Style::default().fg(Color::Green))
+        } else {
+            Span::styled(" not verified", Style::default().fg(Color::Red))
+        };
+        lines.push(Line::from(vec![
+            Span::styled("  Email         ", label_style),
+            Span::raw(user.email.clone()),
+            verified,
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("  Member since  ", label_style),
+            Span::raw(user.created_at.format("%Y-%m-%d").to_string()),
+        ]));
+    }
+
+    let details = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Account ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(details, chunks[1]);
+
+    draw_sessions(f, chunks[2], app);
+
+    let (mode, mode_color, hints) = if app.changing_password {
+        ("PASSWORD", Color::Yellow, "Tab/↑↓: field | Enter: save | Esc: cancel")
+    } else {
+        (
+            "ACCOUNT",
+            Color::Blue,
+            "p: change password | j/k: session | x: sign out session | L: log out everywhere | r: reload | q: back",
+        )
     };
-    let parent_info = Paragraph::new(parent_text)
-        .style(Style::default().fg(Color::DarkGray))
-        .alignment(Alignment::Center);
-    f.render_widget(parent_info, chunks[1]);
 
-    // Hint
-    let hint = Paragraph::new("Enter: create | Esc: cancel")
-        .style(Style::default().fg(Color::DarkGray))
-        .alignment(Alignment::Center);
-    f.render_widget(hint, chunks[2]);
+    let status = Paragraph::new(Line::from(vec![
+        Span::styled(
+            format!(" {} ", mode),
+            Style::default().bg(mode_color).fg(Color::White),
+        ),
+        Span::raw(" "),
+        Span::styled(hints, Style::default().fg(Color::DarkGray)),
+    ]));
+    f.render_widget(status, chunks[3]);
 
-    // Set cursor position
-    f.set_cursor_position((
-        chunks[0].x + 1 + app.kb_create_title.len() as u16,
-        chunks[0].y + 1,
-    ));
+    if app.changing_password {
+        draw_change_password_popup(f, app);
+    }
+
+    if app.session_confirming_revoke || app.sessions_confirming_logout_all {
+        draw_session_revoke_confirm_popup(f, app);
+    }
 }
 
-fn draw_kb_delete_confirm_popup(f: &mut Frame, app: &App) {
+fn draw_sessions(f: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default()
+        .title(format!(" Sessions ({}) ", app.sessions.len()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    if app.sessions.is_empty() {
+        let empty = Paragraph::new("No active sessions.")
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
+        f.render_widget(empty, inner);
+        return;
+    }
+
+    // Two lines per session; keep the cursor in view
+    let per_page = (inner.height as usize / 2).max(1);
+    let skip = (app.session_cursor + 1).saturating_sub(per_page);
+
+    let items: Vec<ListItem> = app
+        .sessions
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .take(per_page)
+        .map(|(i, session)| {
+            let style = if i == app.session_cursor {
+                Style::default().bg(Color::DarkGray).fg(Color::White)
+            } else {
+                Style::default()
+            };
+
+            let mut title_line = vec![Span::styled(
+                format!("  {}", session.user_agent.as_deref().unwrap_or("Unknown device")),
+                style,
+            )];
+            if session.current {
+                title_line.push(Span::styled(" (this device)", Style::default().fg(Color::Green)));
+            }
+
+            let details = [
+                session.ip_address.clone().unwrap_or_else(|| "unknown address".to_string()),
+                format!("last used {}", session.last_used_at.format("%Y-%m-%d %H:%M")),
+                format!("signed in {}", session.created_at.format("%Y-%m-%d")),
+            ];
+
+            ListItem::new(vec![
+                Line::from(title_line),
+                Line::from(Span::styled(
+                    format!("    {}", details.join(" · ")),
+                    Style::default().fg(Color::DarkGray),
+                )),
+            ])
+        })
+        .collect();
+
+    f.render_widget(List::new(items), inner);
+}
+
+fn draw_session_revoke_confirm_popup(f: &mut Frame, app: &App) {
     let area = centered_rect(50, 20, f.area());
 
     f.render_widget(Clear, area);
 
-    let doc_title = app
-        .kb_visible_list
-        .get(app.kb_selected_idx)
-        .map(|(doc, _)| doc.title.as_str())
-        .unwrap_or("Unknown");
-
     let block = Block::default()
-        .title(" Confirm Delete ")
+        .title(" Confirm Sign Out ")
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Red));
 
@@ -3226,63 +5387,95 @@ fn draw_kb_delete_confirm_popup(f: &mut Frame, app: &App) {
         ])
         .split(inner);
 
-    let message = Paragraph::new(vec![
-        Line::from(Span::raw("Delete document:")),
-        Line::from(Span::styled(
-            format!("\"{}\"", doc_title),
-            Style::default().fg(Color::Yellow),
-        )),
-    ])
-    .alignment(Alignment::Center);
+    let lines = if app.sessions_confirming_logout_all {
+        vec![
+            Line::from(Span::raw("Sign out every session?")),
+            Line::from(Span::styled(
+                "This device included",
+                Style::default().fg(Color::Yellow),
+            )),
+        ]
+    } else {
+        let Some(session) = app.sessions.get(app.session_cursor) else { return };
+        let device: String = session
+            .user_agent
+            .as_deref()
+            .unwrap_or("Unknown device")
+            .chars()
+            .take(60)
+            .collect();
+        let prompt = if session.current {
+            "Sign out this device?"
+        } else {
+            "Sign out session:"
+        };
+        vec![
+            Line::from(Span::raw(prompt)),
+            Line::from(Span::styled(device, Style::default().fg(Color::Yellow))),
+        ]
+    };
+    let message = Paragraph::new(lines).alignment(Alignment::Center);
     f.render_widget(message, chunks[0]);
 
-    let hint = Paragraph::new("y: yes, delete | n: no, cancel")
+    let hint = Paragraph::new("y: yes, sign out | n: no, cancel")
         .style(Style::default().fg(Color::DarkGray))
         .alignment(Alignment::Center);
     f.render_widget(hint, chunks[1]);
 }
 
-fn draw_link_task_popup(f: &mut Frame, app: &App) {
+fn draw_change_password_popup(f: &mut Frame, app: &App) {
     let area = centered_rect(50, 50, f.area());
 
-    // Clear the background
     f.render_widget(Clear, area);
 
-    // Get available tasks (not already linked)
-    let linked_ids: std::collections::HashSet<_> = app.kb_linked_tasks
-        .iter()
-        .map(|t| t.task_id)
-        .collect();
+    let block = Block::default()
+        .title(" Change Password ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
 
-    // Get all tasks from columns
-    let all_tasks: Vec<_> = app.columns.iter().flat_map(|c| c.tasks.iter()).collect();
-    let available: Vec<_> = all_tasks
-        .iter()
-        .filter(|t| !linked_ids.contains(&t.id))
-        .collect();
+    let inner = block.inner(area);
+    f.render_widget(block, area);
 
-    let items: Vec<ListItem> = available
-        .iter()
-        .enumerate()
-        .map(|(i, task)| {
-            let style = if i == app.link_task_cursor {
-                Style::default().bg(Color::DarkGray).fg(Color::White)
-            } else {
-                Style::default()
-            };
-            ListItem::new(Line::from(Span::styled(&task.title, style)))
-        })
-        .collect();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Current
+            Constraint::Length(3), // New
+            Constraint::Length(3), // Confirm
+            Constraint::Length(2), // Hint
+            Constraint::Min(0),    // Spacer
+        ])
+        .split(inner);
 
-    let list = List::new(items)
-        .block(
+    let fields = [
+        (PasswordField::Current, " Current Password ", &app.current_password_input),
+        (PasswordField::New, " New Password ", &app.new_password_input),
+        (PasswordField::Confirm, " Confirm New Password ", &app.confirm_password_input),
+    ];
+    for (i, (field, title, value)) in fields.iter().enumerate() {
+        let style = if app.password_field == *field {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        let input = Paragraph::new("*".repeat(value.len())).block(
             Block::default()
-                .title(" Link Task (j/k: navigate, Enter: select, Esc: cancel) ")
+                .title(*title)
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan)),
+                .border_style(style),
         );
+        f.render_widget(input, chunks[i]);
 
-    f.render_widget(list, area);
+        if app.password_field == *field {
+            f.set_cursor_position((chunks[i].x + 1 + value.len() as u16, chunks[i].y + 1));
+        }
+    }
+
+    let hint = Paragraph::new("Other sessions are signed out when the password changes")
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
+    f.render_widget(hint, chunks[3]);
 }
 
 fn draw_help(f: &mut Frame, app: &App) {
@@ -3333,12 +5526,16 @@ fn draw_help(f: &mut Frame, app: &App) {
         ]),
         Line::from(vec![
             Span::styled("  d       ", Style::default().fg(Color::Green)),
-            Span::raw("Delete task"),
+            Span::raw("Move task to trash"),
         ]),
         Line::from(vec![
             Span::styled("  m+h/l   ", Style::default().fg(Color::Green)),
             Span::raw("Move task to column"),
         ]),
+        Line::from(vec![
+            Span::styled("  v       ", Style::default().fg(Color::Green)),
+            Span::raw("Select several tasks (Space/a mark, m move, d trash)"),
+        ]),
         Line::from(vec![
             Span::styled("  e       ", Style::default().fg(Color::Green)),
             Span::raw("Edit (in detail view)"),
@@ -3347,6 +5544,26 @@ fn draw_help(f: &mut Frame, app: &App) {
             Span::styled("  a       ", Style::default().fg(Color::Green)),
             Span::raw("Add comment (in detail view)"),
         ]),
+        Line::from(vec![
+            Span::styled("  r/+     ", Style::default().fg(Color::Green)),
+            Span::raw("Reply to / react to the selected comment (j/k picks)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  E/D     ", Style::default().fg(Color::Green)),
+            Span::raw("Edit / delete the selected comment (yours, or any as admin)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  v       ", Style::default().fg(Color::Green)),
+            Span::raw("Show earlier versions of an (edited) comment"),
+        ]),
+        Line::from(vec![
+            Span::styled("  s       ", Style::default().fg(Color::Green)),
+            Span::raw("Add subtask (in detail view)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  b/B     ", Style::default().fg(Color::Green)),
+            Span::raw("Add/remove blocker (in detail view)"),
+        ]),
         Line::from(vec![
             Span::styled("  L       ", Style::default().fg(Color::Green)),
             Span::raw("Link document (in detail view)"),
@@ -3355,6 +5572,10 @@ fn draw_help(f: &mut Frame, app: &App) {
             Span::styled("  U       ", Style::default().fg(Color::Green)),
             Span::raw("Unlink document (in detail view)"),
         ]),
+        Line::from(vec![
+            Span::styled("  H       ", Style::default().fg(Color::Green)),
+            Span::raw("Toggle comments/history (in detail view)"),
+        ]),
         Line::from(""),
         Line::from(Span::styled(
             "SEARCH & FILTER",
@@ -3397,6 +5618,18 @@ fn draw_help(f: &mut Frame, app: &App) {
             Span::styled("  T       ", Style::default().fg(Color::Green)),
             Span::raw("Tags"),
         ]),
+        Line::from(vec![
+            Span::styled("  C       ", Style::default().fg(Color::Green)),
+            Span::raw("Columns"),
+        ]),
+        Line::from(vec![
+            Span::styled("  z       ", Style::default().fg(Color::Green)),
+            Span::raw("Collapse/expand column"),
+        ]),
+        Line::from(vec![
+            Span::styled("  I       ", Style::default().fg(Color::Green)),
+            Span::raw("Notifications"),
+        ]),
         Line::from(vec![
             Span::styled("  Ctrl+K  ", Style::default().fg(Color::Green)),
             Span::raw("Knowledge Base"),
@@ -3405,6 +5638,10 @@ fn draw_help(f: &mut Frame, app: &App) {
             Span::styled("  Ctrl+W  ", Style::default().fg(Color::Green)),
             Span::raw("Workspace switch"),
         ]),
+        Line::from(vec![
+            Span::styled("  :open   ", Style::default().fg(Color::Green)),
+            Span::raw("Open task by key (e.g. :open TODO-123)"),
+        ]),
         Line::from(""),
         Line::from(Span::styled(
             "KNOWLEDGE BASE",
@@ -3430,7 +5667,7 @@ fn draw_help(f: &mut Frame, app: &App) {
         ]),
         Line::from(vec![
             Span::styled("  d       ", Style::default().fg(Color::Green)),
-            Span::raw("Delete document"),
+            Span::raw("Move document to trash"),
         ]),
         Line::from(vec![
             Span::styled("  L       ", Style::default().fg(Color::Green)),
@@ -3441,6 +5678,25 @@ fn draw_help(f: &mut Frame, app: &App) {
             Span::raw("Unlink task from document"),
         ]),
         Line::from(""),
+        Line::from(Span::styled(
+            "TRASH",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(vec![
+            Span::styled("  r/Enter ", Style::default().fg(Color::Green)),
+            Span::raw("Restore item"),
+        ]),
+        Line::from(vec![
+            Span::styled("  D       ", Style::default().fg(Color::Green)),
+            Span::raw("Delete permanently (admins)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  R       ", Style::default().fg(Color::Green)),
+            Span::raw("Refresh"),
+        ]),
+        Line::from(""),
         Line::from(Span::styled(
             "GENERAL",
             Style::default()
@@ -3516,6 +5772,9 @@ fn draw_menu(f: &mut Frame, app: &App) {
         ("p", "Presets"),
         ("/", "Search"),
         ("w", "Workspaces"),
+        ("x", "Trash"),
+        ("c", "Columns"),
+        ("i", "Notifications"),
     ];
 
     let items: Vec<ListItem> = menu_items
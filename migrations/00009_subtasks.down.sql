@@ -0,0 +1,6 @@
+-- Remove parent/child hierarchy from tasks
+-- Migration: 00009_subtasks
+
+DROP INDEX IF EXISTS idx_tasks_parent;
+ALTER TABLE tasks DROP CONSTRAINT IF EXISTS tasks_parent_not_self;
+ALTER TABLE tasks DROP COLUMN IF EXISTS parent_id;
@@ -0,0 +1,10 @@
+-- Add parent/child hierarchy to tasks
+-- Migration: 00009_subtasks
+
+ALTER TABLE tasks ADD COLUMN parent_id UUID REFERENCES tasks(id) ON DELETE CASCADE;
+
+-- A task cannot be its own parent
+ALTER TABLE tasks ADD CONSTRAINT tasks_parent_not_self CHECK (parent_id IS NULL OR parent_id <> id);
+
+-- Index for fetching subtasks and computing rollups
+CREATE INDEX idx_tasks_parent ON tasks(parent_id) WHERE parent_id IS NOT NULL;
@@ -0,0 +1,4 @@
+-- Remove task dependencies
+-- Migration: 00010_task_dependencies
+
+DROP TABLE IF EXISTS task_dependencies;
@@ -0,0 +1,13 @@
+-- Task dependencies ("blocks / blocked by")
+-- Migration: 00010_task_dependencies
+
+CREATE TABLE task_dependencies (
+    blocking_task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    blocked_task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    created_by UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (blocking_task_id, blocked_task_id),
+    CHECK (blocking_task_id <> blocked_task_id)
+);
+
+CREATE INDEX idx_task_dependencies_blocked ON task_dependencies(blocked_task_id);
@@ -0,0 +1,4 @@
+-- Remove recurrence rules from tasks
+-- Migration: 00011_recurring_tasks
+
+ALTER TABLE tasks DROP COLUMN IF EXISTS recurrence;
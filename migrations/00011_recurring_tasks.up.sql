@@ -0,0 +1,5 @@
+-- Add recurrence rules to tasks
+-- Migration: 00011_recurring_tasks
+
+-- Rule JSON: {"frequency": "weekly", "interval": 1, "weekdays": ["Mon"], "until": "2026-12-31"}
+ALTER TABLE tasks ADD COLUMN recurrence JSONB;
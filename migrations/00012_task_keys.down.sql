@@ -0,0 +1,7 @@
+-- Remove human-readable task keys
+-- Migration: 00012_task_keys
+
+DROP INDEX IF EXISTS idx_tasks_workspace_number;
+ALTER TABLE tasks DROP COLUMN IF EXISTS number;
+ALTER TABLE workspaces DROP COLUMN IF EXISTS task_seq;
+ALTER TABLE workspaces DROP COLUMN IF EXISTS task_key_prefix;
@@ -0,0 +1,25 @@
+-- Human-readable task keys (e.g. TODO-123)
+-- Migration: 00012_task_keys
+
+-- Per-workspace key prefix and last issued task number
+ALTER TABLE workspaces ADD COLUMN task_key_prefix VARCHAR(10) NOT NULL DEFAULT 'TODO';
+ALTER TABLE workspaces ADD COLUMN task_seq INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE tasks ADD COLUMN number INTEGER;
+
+-- Number existing tasks in creation order
+UPDATE tasks t
+SET number = numbered.rn
+FROM (
+    SELECT id, ROW_NUMBER() OVER (PARTITION BY workspace_id ORDER BY created_at, id) AS rn
+    FROM tasks
+) numbered
+WHERE t.id = numbered.id;
+
+UPDATE workspaces w
+SET task_seq = COALESCE((SELECT MAX(number) FROM tasks WHERE workspace_id = w.id), 0);
+
+ALTER TABLE tasks ALTER COLUMN number SET NOT NULL;
+
+-- Keys are unique within a workspace
+CREATE UNIQUE INDEX idx_tasks_workspace_number ON tasks(workspace_id, number);
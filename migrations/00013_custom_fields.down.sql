@@ -0,0 +1,6 @@
+-- Remove custom fields
+-- Migration: 00013_custom_fields
+
+DROP TABLE IF EXISTS task_custom_field_values;
+DROP TABLE IF EXISTS custom_fields;
+DROP TYPE IF EXISTS custom_field_type;
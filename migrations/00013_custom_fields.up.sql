@@ -0,0 +1,30 @@
+-- Workspace-defined custom fields on tasks
+-- Migration: 00013_custom_fields
+
+CREATE TYPE custom_field_type AS ENUM ('text', 'number', 'date', 'select', 'multi_select', 'user');
+
+-- Field definitions
+CREATE TABLE custom_fields (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    field_type custom_field_type NOT NULL,
+    options JSONB NOT NULL DEFAULT '[]'::jsonb,  -- Allowed values for select/multi_select
+    position INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(workspace_id, name)
+);
+
+CREATE INDEX idx_custom_fields_workspace ON custom_fields(workspace_id, position);
+
+-- Per-task values (JSON typed according to the field)
+CREATE TABLE task_custom_field_values (
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    field_id UUID NOT NULL REFERENCES custom_fields(id) ON DELETE CASCADE,
+    value JSONB NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (task_id, field_id)
+);
+
+-- Index for filtering tasks by field value
+CREATE INDEX idx_task_custom_field_values_field ON task_custom_field_values(field_id);
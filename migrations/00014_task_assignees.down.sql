@@ -0,0 +1,18 @@
+-- Restore single assignee column (keeps the earliest assignee)
+-- Migration: 00014_task_assignees
+
+ALTER TABLE tasks ADD COLUMN IF NOT EXISTS assigned_to UUID REFERENCES users(id);
+
+UPDATE tasks t
+SET assigned_to = a.user_id
+FROM (
+    SELECT DISTINCT ON (task_id) task_id, user_id
+    FROM task_assignees
+    ORDER BY task_id, created_at
+) a
+WHERE a.task_id = t.id;
+
+CREATE INDEX IF NOT EXISTS idx_tasks_assigned ON tasks(assigned_to) WHERE assigned_to IS NOT NULL;
+
+DROP TABLE IF EXISTS task_watchers;
+DROP TABLE IF EXISTS task_assignees;
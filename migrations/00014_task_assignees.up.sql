@@ -0,0 +1,28 @@
+-- Multiple assignees and watchers per task
+-- Migration: 00014_task_assignees
+
+CREATE TABLE task_assignees (
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (task_id, user_id)
+);
+
+-- Index for "assigned to me" lookups
+CREATE INDEX idx_task_assignees_user ON task_assignees(user_id);
+
+CREATE TABLE task_watchers (
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (task_id, user_id)
+);
+
+CREATE INDEX idx_task_watchers_user ON task_watchers(user_id);
+
+-- Carry over existing single assignees
+INSERT INTO task_assignees (task_id, user_id, created_at)
+SELECT id, assigned_to, created_at FROM tasks WHERE assigned_to IS NOT NULL;
+
+DROP INDEX IF EXISTS idx_tasks_assigned;
+ALTER TABLE tasks DROP COLUMN assigned_to;
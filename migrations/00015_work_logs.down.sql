@@ -0,0 +1,4 @@
+-- Remove work logs
+-- Migration: 00015_work_logs
+
+DROP TABLE IF EXISTS work_logs;
@@ -0,0 +1,18 @@
+-- Time tracking: work logged against tasks
+-- Migration: 00015_work_logs
+
+CREATE TABLE work_logs (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id),
+    minutes INTEGER NOT NULL CHECK (minutes > 0),
+    work_date DATE NOT NULL,
+    note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_work_logs_task ON work_logs(task_id, work_date);
+
+-- Index for per-user timesheets
+CREATE INDEX idx_work_logs_user_date ON work_logs(user_id, work_date);
@@ -0,0 +1,4 @@
+-- Remove task checklists
+-- Migration: 00016_task_checklists
+
+DROP TABLE IF EXISTS task_checklist_items;
@@ -0,0 +1,4 @@
+-- Remove attachments
+-- Migration: 00017_attachments
+
+DROP TABLE IF EXISTS attachments;
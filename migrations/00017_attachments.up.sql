@@ -0,0 +1,24 @@
+-- File attachments on tasks and documents
+-- Migration: 00017_attachments
+
+CREATE TABLE attachments (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    task_id UUID REFERENCES tasks(id) ON DELETE CASCADE,
+    document_id UUID REFERENCES documents(id) ON DELETE CASCADE,
+    uploaded_by UUID NOT NULL REFERENCES users(id),
+    filename VARCHAR(255) NOT NULL,
+    content_type VARCHAR(255) NOT NULL,
+    size_bytes BIGINT NOT NULL CHECK (size_bytes > 0),
+    -- Key of the contents in the storage backend
+    storage_key TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Attached to exactly one task or document
+    CONSTRAINT attachments_one_owner CHECK ((task_id IS NULL) <> (document_id IS NULL))
+);
+
+CREATE INDEX idx_attachments_task ON attachments(task_id) WHERE task_id IS NOT NULL;
+CREATE INDEX idx_attachments_document ON attachments(document_id) WHERE document_id IS NOT NULL;
+
+-- Index for quota checks
+CREATE INDEX idx_attachments_workspace ON attachments(workspace_id);
@@ -0,0 +1,18 @@
+-- Remove soft deletes
+-- Migration: 00018_soft_delete
+
+-- Anything still in the trash is gone for good
+DELETE FROM task_comments WHERE deleted_at IS NOT NULL;
+DELETE FROM tasks WHERE deleted_at IS NOT NULL;
+DELETE FROM documents WHERE deleted_at IS NOT NULL;
+
+DROP INDEX IF EXISTS idx_documents_live_path;
+ALTER TABLE documents ADD CONSTRAINT documents_workspace_id_path_key UNIQUE (workspace_id, path);
+
+DROP INDEX IF EXISTS idx_task_comments_deleted;
+DROP INDEX IF EXISTS idx_documents_deleted;
+DROP INDEX IF EXISTS idx_tasks_deleted;
+
+ALTER TABLE task_comments DROP COLUMN IF EXISTS deleted_by, DROP COLUMN IF EXISTS deleted_at;
+ALTER TABLE documents DROP COLUMN IF EXISTS deleted_by, DROP COLUMN IF EXISTS deleted_at;
+ALTER TABLE tasks DROP COLUMN IF EXISTS deleted_by, DROP COLUMN IF EXISTS deleted_at;
@@ -0,0 +1,23 @@
+-- Soft deletes: tasks, documents and comments go to a trash before being purged
+-- Migration: 00018_soft_delete
+
+ALTER TABLE tasks
+    ADD COLUMN deleted_at TIMESTAMPTZ,
+    ADD COLUMN deleted_by UUID REFERENCES users(id) ON DELETE SET NULL;
+
+ALTER TABLE documents
+    ADD COLUMN deleted_at TIMESTAMPTZ,
+    ADD COLUMN deleted_by UUID REFERENCES users(id) ON DELETE SET NULL;
+
+ALTER TABLE task_comments
+    ADD COLUMN deleted_at TIMESTAMPTZ,
+    ADD COLUMN deleted_by UUID REFERENCES users(id) ON DELETE SET NULL;
+
+-- Indexes for trash listing and the retention purge
+CREATE INDEX idx_tasks_deleted ON tasks(workspace_id, deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX idx_documents_deleted ON documents(workspace_id, deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX idx_task_comments_deleted ON task_comments(deleted_at) WHERE deleted_at IS NOT NULL;
+
+-- Trashed documents must not block reusing their path
+ALTER TABLE documents DROP CONSTRAINT documents_workspace_id_path_key;
+CREATE UNIQUE INDEX idx_documents_live_path ON documents(workspace_id, path) WHERE deleted_at IS NULL;
@@ -0,0 +1,7 @@
+-- Drop activity entity labels
+-- Migration: 00019_activity_label
+
+DROP INDEX idx_activity_log_entity;
+CREATE INDEX idx_activity_log_entity ON activity_log(entity_type, entity_id);
+
+ALTER TABLE activity_log DROP COLUMN entity_label;
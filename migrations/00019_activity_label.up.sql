@@ -0,0 +1,8 @@
+-- Activity history: keep a readable label so entries survive renames and purges
+-- Migration: 00019_activity_label
+
+ALTER TABLE activity_log ADD COLUMN entity_label VARCHAR(300);
+
+-- Per-entity history is read newest first
+DROP INDEX idx_activity_log_entity;
+CREATE INDEX idx_activity_log_entity ON activity_log(entity_type, entity_id, created_at DESC);
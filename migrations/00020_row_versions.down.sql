@@ -0,0 +1,5 @@
+-- Remove row versions
+-- Migration: 00020_row_versions
+
+ALTER TABLE documents DROP COLUMN version;
+ALTER TABLE tasks DROP COLUMN version;
@@ -0,0 +1,5 @@
+-- Optimistic concurrency: edits name the version they were based on
+-- Migration: 00020_row_versions
+
+ALTER TABLE tasks ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE documents ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,4 @@
+-- Remove work-in-progress limits
+-- Migration: 00021_wip_limits
+
+ALTER TABLE task_statuses DROP COLUMN wip_limit;
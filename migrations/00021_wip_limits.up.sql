@@ -0,0 +1,4 @@
+-- Work-in-progress limits on board columns
+-- Migration: 00021_wip_limits
+
+ALTER TABLE task_statuses ADD COLUMN wip_limit INTEGER CHECK (wip_limit > 0);
@@ -0,0 +1,4 @@
+-- Remove workflow transitions
+-- Migration: 00022_status_transitions
+
+DROP TABLE IF EXISTS status_transitions;
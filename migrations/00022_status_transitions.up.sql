@@ -0,0 +1,17 @@
+-- Workflow: allowed moves between board columns and the rules on them
+-- Migration: 00022_status_transitions
+
+CREATE TABLE status_transitions (
+    id UUID PRIMARY KEY,
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    from_status_id UUID NOT NULL REFERENCES task_statuses(id) ON DELETE CASCADE,
+    to_status_id UUID NOT NULL REFERENCES task_statuses(id) ON DELETE CASCADE,
+    require_assignee BOOLEAN NOT NULL DEFAULT FALSE,
+    require_comment BOOLEAN NOT NULL DEFAULT FALSE,
+    required_role workspace_role,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (from_status_id, to_status_id),
+    CHECK (from_status_id <> to_status_id)
+);
+
+CREATE INDEX idx_status_transitions_workspace ON status_transitions(workspace_id);
@@ -0,0 +1,4 @@
+-- Remove in-app notifications
+-- Migration: 00023_notifications
+
+DROP TABLE IF EXISTS notifications;
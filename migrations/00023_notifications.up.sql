@@ -0,0 +1,19 @@
+-- In-app notifications
+-- Migration: 00023_notifications
+
+CREATE TABLE notifications (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    kind VARCHAR(50) NOT NULL,           -- 'task_assigned', 'task_commented', 'mentioned', 'status_changed', 'due_soon'
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    task_label TEXT NOT NULL,            -- "KEY-1 Title" when it was sent
+    actor_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    detail TEXT,
+    read_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_notifications_user ON notifications(user_id, workspace_id, created_at DESC);
+CREATE INDEX idx_notifications_unread ON notifications(user_id, workspace_id) WHERE read_at IS NULL;
+CREATE INDEX idx_notifications_task ON notifications(task_id, kind);
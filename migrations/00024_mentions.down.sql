@@ -0,0 +1,4 @@
+-- Remove @mentions
+-- Migration: 00024_mentions
+
+DROP TABLE IF EXISTS mentions;
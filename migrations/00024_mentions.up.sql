@@ -0,0 +1,17 @@
+-- @mentions in task descriptions and comments
+-- Migration: 00024_mentions
+
+CREATE TABLE mentions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    comment_id UUID REFERENCES task_comments(id) ON DELETE CASCADE,  -- NULL: the task description
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    mentioned_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- One row per person per comment or description
+CREATE UNIQUE INDEX idx_mentions_comment_user ON mentions(comment_id, user_id) WHERE comment_id IS NOT NULL;
+CREATE UNIQUE INDEX idx_mentions_description_user ON mentions(task_id, user_id) WHERE comment_id IS NULL;
+CREATE INDEX idx_mentions_user ON mentions(user_id, workspace_id, created_at DESC);
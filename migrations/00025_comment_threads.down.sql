@@ -0,0 +1,7 @@
+-- Remove comment threads and reactions
+-- Migration: 00025_comment_threads
+
+DROP TABLE IF EXISTS comment_reactions;
+
+DROP INDEX IF EXISTS idx_task_comments_parent;
+ALTER TABLE task_comments DROP COLUMN IF EXISTS parent_comment_id;
@@ -0,0 +1,16 @@
+-- Threaded comment replies and reactions
+-- Migration: 00025_comment_threads
+
+-- One level of threading: replies always point at a top-level comment
+ALTER TABLE task_comments
+    ADD COLUMN parent_comment_id UUID REFERENCES task_comments(id) ON DELETE CASCADE;
+
+CREATE INDEX idx_task_comments_parent ON task_comments(parent_comment_id) WHERE parent_comment_id IS NOT NULL;
+
+CREATE TABLE comment_reactions (
+    comment_id UUID NOT NULL REFERENCES task_comments(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    emoji VARCHAR(32) NOT NULL,          -- an emoji or a short word
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (comment_id, user_id, emoji)
+);
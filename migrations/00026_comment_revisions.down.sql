@@ -0,0 +1,4 @@
+-- Remove comment edit history
+-- Migration: 00026_comment_revisions
+
+DROP TABLE IF EXISTS comment_revisions;
@@ -0,0 +1,14 @@
+-- Prior versions of edited comments
+-- Migration: 00026_comment_revisions
+
+-- Each row is the text a comment had before an edit
+CREATE TABLE comment_revisions (
+    id UUID PRIMARY KEY,
+    comment_id UUID NOT NULL REFERENCES task_comments(id) ON DELETE CASCADE,
+    content TEXT NOT NULL,
+    written_at TIMESTAMPTZ NOT NULL,     -- when this version was saved
+    edited_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    edited_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_comment_revisions_comment ON comment_revisions(comment_id, edited_at);
@@ -0,0 +1,4 @@
+-- Remove password reset codes
+-- Migration: 00027_password_reset
+
+DROP TABLE IF EXISTS password_reset_codes;
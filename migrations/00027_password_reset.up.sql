@@ -0,0 +1,16 @@
+-- Password reset codes
+-- Migration: 00027_password_reset
+
+-- One-time codes mailed for "forgot password", like email_verification_codes
+CREATE TABLE password_reset_codes (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    code VARCHAR(6) NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,   -- wrong guesses against this code
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    used_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_password_reset_codes_user ON password_reset_codes(user_id);
+CREATE INDEX idx_password_reset_codes_expires ON password_reset_codes(expires_at);
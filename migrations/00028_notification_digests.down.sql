@@ -0,0 +1,5 @@
+-- Remove email digest tracking
+-- Migration: 00028_notification_digests
+
+DROP INDEX IF EXISTS idx_notifications_undigested;
+ALTER TABLE notifications DROP COLUMN IF EXISTS emailed_at;
@@ -0,0 +1,10 @@
+-- Email digests of unread notifications
+-- Migration: 00028_notification_digests
+
+ALTER TABLE notifications ADD COLUMN emailed_at TIMESTAMPTZ;
+
+-- Notifications from before digests existed aren't news anymore
+UPDATE notifications SET emailed_at = NOW();
+
+CREATE INDEX idx_notifications_undigested ON notifications(user_id)
+    WHERE emailed_at IS NULL AND read_at IS NULL;
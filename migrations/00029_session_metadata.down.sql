@@ -0,0 +1,6 @@
+-- Remove session metadata from refresh tokens
+-- Migration: 00029_session_metadata
+
+ALTER TABLE refresh_tokens DROP COLUMN IF EXISTS last_used_at;
+ALTER TABLE refresh_tokens DROP COLUMN IF EXISTS ip_address;
+ALTER TABLE refresh_tokens DROP COLUMN IF EXISTS user_agent;
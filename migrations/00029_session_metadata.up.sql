@@ -0,0 +1,9 @@
+-- Session metadata on refresh tokens
+-- Migration: 00029_session_metadata
+
+-- Each row is now one login session; refreshing rotates its token in place
+ALTER TABLE refresh_tokens ADD COLUMN user_agent TEXT;
+ALTER TABLE refresh_tokens ADD COLUMN ip_address TEXT;
+ALTER TABLE refresh_tokens ADD COLUMN last_used_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+UPDATE refresh_tokens SET last_used_at = created_at;
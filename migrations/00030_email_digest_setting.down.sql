@@ -0,0 +1,4 @@
+-- Remove the digest email setting
+-- Migration: 00030_email_digest_setting
+
+ALTER TABLE user_notification_settings DROP COLUMN IF EXISTS email_digest;
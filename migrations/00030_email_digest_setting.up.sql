@@ -0,0 +1,4 @@
+-- Opt out of notification digest emails
+-- Migration: 00030_email_digest_setting
+
+ALTER TABLE user_notification_settings ADD COLUMN email_digest BOOLEAN DEFAULT TRUE;
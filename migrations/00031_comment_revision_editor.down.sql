@@ -0,0 +1,9 @@
+-- Drop comment history left behind by deleted accounts
+-- Migration: 00031_comment_revision_editor
+
+DELETE FROM comment_revisions WHERE edited_by IS NULL;
+ALTER TABLE comment_revisions DROP CONSTRAINT IF EXISTS comment_revisions_edited_by_fkey;
+ALTER TABLE comment_revisions
+    ADD CONSTRAINT comment_revisions_edited_by_fkey
+    FOREIGN KEY (edited_by) REFERENCES users(id) ON DELETE CASCADE;
+ALTER TABLE comment_revisions ALTER COLUMN edited_by SET NOT NULL;
@@ -0,0 +1,8 @@
+-- Keep comment history when the editor's account is deleted
+-- Migration: 00031_comment_revision_editor
+
+ALTER TABLE comment_revisions ALTER COLUMN edited_by DROP NOT NULL;
+ALTER TABLE comment_revisions DROP CONSTRAINT IF EXISTS comment_revisions_edited_by_fkey;
+ALTER TABLE comment_revisions
+    ADD CONSTRAINT comment_revisions_edited_by_fkey
+    FOREIGN KEY (edited_by) REFERENCES users(id) ON DELETE SET NULL;
@@ -0,0 +1,4 @@
+-- Forget retired task key prefixes
+-- Migration: 00032_previous_task_key_prefixes
+
+DROP TABLE IF EXISTS workspace_previous_key_prefixes;
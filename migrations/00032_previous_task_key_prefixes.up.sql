@@ -0,0 +1,9 @@
+-- Keep resolving task keys after a workspace changes its prefix
+-- Migration: 00032_previous_task_key_prefixes
+
+CREATE TABLE workspace_previous_key_prefixes (
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    prefix VARCHAR(10) NOT NULL,
+    retired_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (workspace_id, prefix)
+);